@@ -27,7 +27,6 @@ from dotenv import load_dotenv
 from graphiti_core import Graphiti
 from graphiti_core.nodes import EpisodeType
 from graphiti_core.utils.bulk_utils import RawEpisode
-from graphiti_core.utils.maintenance.graph_data_operations import clear_data
 
 load_dotenv()
 
@@ -86,7 +85,7 @@ async def add_messages(client: Graphiti):
 async def main():
     setup_logging()
     client = Graphiti(neo4j_uri, neo4j_user, neo4j_password)
-    await clear_data(client.driver)
+    await client.clear()
     await client.build_indices_and_constraints()
     await ingest_products_data(client)
     await add_messages(client)
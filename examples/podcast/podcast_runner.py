@@ -25,7 +25,6 @@ from pydantic import BaseModel, Field
 from transcript_parser import parse_podcast_messages
 
 from graphiti_core import Graphiti
-from graphiti_core.utils.maintenance.graph_data_operations import clear_data
 
 load_dotenv()
 
@@ -70,7 +69,7 @@ class IsPresidentOf(BaseModel):
 async def main():
     setup_logging()
     client = Graphiti(neo4j_uri, neo4j_user, neo4j_password)
-    await clear_data(client.driver)
+    await client.clear()
     await client.build_indices_and_constraints()
     messages = parse_podcast_messages()
     group_id = str(uuid4())
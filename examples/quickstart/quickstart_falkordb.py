@@ -72,7 +72,8 @@ async def main():
 
     # Initialize Graphiti with FalkorDB connection
     falkor_driver = FalkorDriver(host=falkor_host, port=falkor_port, username=falkor_username, password=falkor_password)
-    graphiti = Graphiti(graph_driver=falkor_driver)
+    # The examples below search without group_ids, so searching across groups is allowed
+    graphiti = Graphiti(graph_driver=falkor_driver, allow_cross_group_search=True)
 
     try:
         # Initialize the graph database with graphiti's indices. This only needs to be done once.
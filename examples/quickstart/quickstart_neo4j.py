@@ -64,7 +64,8 @@ async def main():
     #################################################
 
     # Initialize Graphiti with Neo4j connection
-    graphiti = Graphiti(neo4j_uri, neo4j_user, neo4j_password)
+    # The examples below search without group_ids, so searching across groups is allowed
+    graphiti = Graphiti(neo4j_uri, neo4j_user, neo4j_password, allow_cross_group_search=True)
 
     try:
         # Initialize the graph database with graphiti's indices. This only needs to be done once.
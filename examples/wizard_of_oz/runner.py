@@ -26,7 +26,6 @@ from examples.wizard_of_oz.parser import get_wizard_of_oz_messages
 from graphiti_core import Graphiti
 from graphiti_core.llm_client.anthropic_client import AnthropicClient
 from graphiti_core.llm_client.config import LLMConfig
-from graphiti_core.utils.maintenance.graph_data_operations import clear_data
 
 load_dotenv()
 
@@ -75,11 +74,11 @@ async def main():
     #     for i, chapter in enumerate(messages[0:50])
     # ]
 
-    # await clear_data(client.driver)
+    # await client.clear()
     # await client.build_indices_and_constraints()
     # await client.add_episode_bulk(episodes)
 
-    await clear_data(client.driver)
+    await client.clear()
     await client.build_indices_and_constraints()
     for i, chapter in enumerate(messages):
         await client.add_episode(
@@ -15,6 +15,7 @@ limitations under the License.
 """
 
 from abc import ABC, abstractmethod
+from collections import defaultdict
 
 
 class CrossEncoderClient(ABC):
@@ -38,3 +39,30 @@ class CrossEncoderClient(ABC):
                                      sorted in descending order of relevance.
         """
         pass
+
+    async def rank_batch(self, query: str, passages: list[str]) -> list[tuple[int, float]]:
+        """
+        Score all passages against the query together.
+
+        Unlike `rank`, results refer to passages by their index, so duplicate passages are
+        scored separately. The default implementation calls `rank` once; clients that can
+        score a whole batch in fewer requests than `rank` makes should override it.
+
+        Args:
+            query (str): The query string.
+            passages (list[str]): A list of passages to rank.
+
+        Returns:
+            list[tuple[int, float]]: A list of tuples containing the index of the passage in
+                                     passages and its score, sorted in descending order of
+                                     relevance.
+        """
+        positions: dict[str, list[int]] = defaultdict(list)
+        for i, passage in enumerate(passages):
+            positions[passage].append(i)
+
+        results: list[tuple[int, float]] = []
+        for passage, score in await self.rank(query, passages):
+            if positions[passage]:
+                results.append((positions[passage].pop(0), score))
+        return results
@@ -22,6 +22,7 @@ from google.genai import types  # type: ignore
 
 from ..helpers import semaphore_gather
 from ..llm_client import LLMConfig, RateLimitError
+from ..llm_client.client import get_model_override
 from .client import CrossEncoderClient
 
 logger = logging.getLogger(__name__)
@@ -89,7 +90,7 @@ Provide only a number between 0 and 100 (no explanation, just the number):"""
             responses = await semaphore_gather(
                 *[
                     self.client.aio.models.generate_content(
-                        model=self.config.model or DEFAULT_MODEL,
+                        model=get_model_override() or self.config.model or DEFAULT_MODEL,
                         contents=prompt_messages,  # type: ignore
                         config=types.GenerateContentConfig(
                             system_instruction='You are an expert at rating passage relevance. Respond with only a number from 0-100.',
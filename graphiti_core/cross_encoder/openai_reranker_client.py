@@ -14,15 +14,18 @@ See the License for the specific language governing permissions and
 limitations under the License.
 """
 
+import json
 import logging
 from typing import Any
 
 import numpy as np
 import openai
 from openai import AsyncAzureOpenAI, AsyncOpenAI
+from pydantic import BaseModel, ValidationError
 
 from ..helpers import semaphore_gather
 from ..llm_client import LLMConfig, RateLimitError
+from ..llm_client.client import get_model_override
 from ..prompts import Message
 from .client import CrossEncoderClient
 
@@ -31,25 +34,45 @@ logger = logging.getLogger(__name__)
 DEFAULT_MODEL = 'gpt-4.1-nano'
 
 
+class PassageScore(BaseModel):
+    id: int
+    score: float
+
+
+class PassageScores(BaseModel):
+    scores: list[PassageScore]
+
+
 class OpenAIRerankerClient(CrossEncoderClient):
     def __init__(
         self,
         config: LLMConfig | None = None,
         client: AsyncOpenAI | AsyncAzureOpenAI | None = None,
+        max_concurrency: int | None = None,
+        batch_scoring: bool = False,
     ):
         """
         Initialize the OpenAIRerankerClient with the provided configuration and client.
 
         This reranker uses the OpenAI API to run a simple boolean classifier prompt concurrently
-        for each passage. Log-probabilities are used to rank the passages.
+        for each passage. Log-probabilities are used to rank the passages. With batch_scoring,
+        `rank_batch` instead asks the model to score every passage in a single request; those
+        scores are written by the model, so rankings and reranker_min_score thresholds differ
+        from the log-probability ones.
 
         Args:
             config (LLMConfig | None): The configuration for the LLM client, including API key, model, base URL, temperature, and max tokens.
             client (AsyncOpenAI | AsyncAzureOpenAI | None): An optional async client instance to use. If not provided, a new AsyncOpenAI client is created.
+            max_concurrency (int | None): The maximum number of passages `rank` scores at once. Defaults to SEMAPHORE_LIMIT.
+            batch_scoring (bool): Whether `rank_batch` scores all passages in one request instead of ranking them with `rank`. Defaults to False.
         """
+        if max_concurrency is not None and max_concurrency < 1:
+            raise ValueError('max_concurrency must be at least 1')
         if config is None:
             config = LLMConfig()
 
+        self.max_concurrency = max_concurrency
+        self.batch_scoring = batch_scoring
         self.config = config
         if client is None:
             self.client = AsyncOpenAI(api_key=config.api_key, base_url=config.base_url)
@@ -82,7 +105,7 @@ class OpenAIRerankerClient(CrossEncoderClient):
             responses = await semaphore_gather(
                 *[
                     self.client.chat.completions.create(
-                        model=DEFAULT_MODEL,
+                        model=get_model_override() or DEFAULT_MODEL,
                         messages=openai_messages,
                         temperature=0,
                         max_tokens=1,
@@ -91,7 +114,8 @@ class OpenAIRerankerClient(CrossEncoderClient):
                         top_logprobs=2,
                     )
                     for openai_messages in openai_messages_list
-                ]
+                ],
+                max_coroutines=self.max_concurrency,
             )
 
             responses_top_logprobs = [
@@ -101,9 +125,12 @@ class OpenAIRerankerClient(CrossEncoderClient):
                 else []
                 for response in responses
             ]
+            # Responses are in passage order however they complete; passages without logprobs
+            # score 0 so scores stay aligned with their passages
             scores: list[float] = []
             for top_logprobs in responses_top_logprobs:
                 if len(top_logprobs) == 0:
+                    scores.append(0.0)
                     continue
                 norm_logprobs = np.exp(top_logprobs[0].logprob)
                 if top_logprobs[0].token.strip().split(' ')[0].lower() == 'true':
@@ -119,3 +146,59 @@ class OpenAIRerankerClient(CrossEncoderClient):
         except Exception as e:
             logger.error(f'Error in generating LLM response: {e}')
             raise
+
+    async def rank_batch(self, query: str, passages: list[str]) -> list[tuple[int, float]]:
+        if not self.batch_scoring:
+            return await super().rank_batch(query, passages)
+        if len(passages) == 0:
+            return []
+
+        passages_context = '\n'.join(
+            f'<PASSAGE id={i}>\n{passage}\n</PASSAGE>' for i, passage in enumerate(passages)
+        )
+        messages: Any = [
+            {
+                'role': 'system',
+                'content': 'You are an expert tasked with scoring how relevant each passage is to a query',
+            },
+            {
+                'role': 'user',
+                'content': f"""
+                    Score how relevant each PASSAGE is to the QUERY, from 0 (irrelevant) to
+                    1 (highly relevant). Respond with a JSON object of the form
+                    {{"scores": [{{"id": <passage id>, "score": <score>}}, ...]}} with one entry
+                    per passage.
+                    <QUERY>
+                    {query}
+                    </QUERY>
+                    {passages_context}
+                    """,
+            },
+        ]
+        try:
+            response = await self.client.chat.completions.create(
+                model=get_model_override() or DEFAULT_MODEL,
+                messages=messages,
+                temperature=0,
+                response_format={'type': 'json_object'},
+            )
+            content = response.choices[0].message.content or '{}'
+            parsed = PassageScores.model_validate(json.loads(content))
+        except openai.RateLimitError as e:
+            raise RateLimitError from e
+        except (json.JSONDecodeError, ValidationError) as e:
+            logger.error(f'Invalid batch ranking response: {e}')
+            raise
+        except Exception as e:
+            logger.error(f'Error in generating LLM response: {e}')
+            raise
+
+        # Passages the model did not score are ranked last
+        scores = [0.0] * len(passages)
+        for passage_score in parsed.scores:
+            if 0 <= passage_score.id < len(passages):
+                scores[passage_score.id] = min(max(passage_score.score, 0.0), 1.0)
+
+        results = list(enumerate(scores))
+        results.sort(reverse=True, key=lambda x: x[1])
+        return results
@@ -19,6 +19,7 @@ from abc import ABC, abstractmethod
 from collections.abc import Coroutine
 from typing import Any
 
+from graphiti_core.errors import DatabaseConflictError
 from graphiti_core.helpers import DEFAULT_DATABASE
 
 logger = logging.getLogger(__name__)
@@ -64,3 +65,23 @@ class GraphDriver(ABC):
     @abstractmethod
     def delete_all_indexes(self, database_: str = DEFAULT_DATABASE) -> Coroutine:
         raise NotImplementedError()
+
+    async def verify_connectivity(self) -> None:
+        """Raise if the database can't be reached. Drivers with a native check override this."""
+        await self.execute_query('RETURN 1', database_=DEFAULT_DATABASE)
+
+
+async def execute_upsert(driver: GraphDriver, cypher_query_: str, **kwargs: Any) -> Any:
+    """
+    Run a MERGE-based write.
+
+    Two writers merging the same uuid at once can both try to create it. With the uuid uniqueness
+    constraints created by build_indices_and_constraints, the losing write fails with a conflict
+    after the other has committed, so it is retried once and its MERGE then updates the existing
+    record.
+    """
+    try:
+        return await driver.execute_query(cypher_query_, **kwargs)
+    except DatabaseConflictError as e:
+        logger.debug(f'Retrying write after uniqueness conflict: {e.message}')
+        return await driver.execute_query(cypher_query_, **kwargs)
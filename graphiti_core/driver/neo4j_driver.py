@@ -19,13 +19,37 @@ from collections.abc import Coroutine
 from typing import Any
 
 from neo4j import AsyncGraphDatabase, EagerResult
+from neo4j.exceptions import ClientError, ConstraintError, Neo4jError
 from typing_extensions import LiteralString
 
 from graphiti_core.driver.driver import GraphDriver, GraphDriverSession
+from graphiti_core.errors import (
+    DatabaseConflictError,
+    DatabasePoolExhaustedError,
+    DatabaseTimeoutError,
+    TransientDatabaseError,
+)
 from graphiti_core.helpers import DEFAULT_DATABASE
 
 logger = logging.getLogger(__name__)
 
+TIMEOUT_ERROR_CODES = {
+    'Neo.ClientError.Transaction.TransactionTimedOut',
+    'Neo.ClientError.Transaction.TransactionTimedOutClientConfiguration',
+}
+# The driver raises a ClientError without a code when a connection can't be acquired in time
+POOL_EXHAUSTED_MESSAGE = 'failed to obtain a connection from the pool'
+
+
+def transient_database_error(error: Neo4jError) -> TransientDatabaseError | None:
+    """Returns the transient error that a Neo4j error maps to, if it is one."""
+    message = error.message or str(error)
+    if error.code in TIMEOUT_ERROR_CODES:
+        return DatabaseTimeoutError(message)
+    if isinstance(error, ClientError) and POOL_EXHAUSTED_MESSAGE in message:
+        return DatabasePoolExhaustedError(message)
+    return None
+
 
 class Neo4jDriver(GraphDriver):
     provider: str = 'neo4j'
@@ -44,7 +68,15 @@ class Neo4jDriver(GraphDriver):
 
     async def execute_query(self, cypher_query_: LiteralString, **kwargs: Any) -> EagerResult:
         params = kwargs.pop('params', None)
-        result = await self.client.execute_query(cypher_query_, parameters_=params, **kwargs)
+        try:
+            result = await self.client.execute_query(cypher_query_, parameters_=params, **kwargs)
+        except ConstraintError as e:
+            raise DatabaseConflictError(e.message or str(e)) from e
+        except Neo4jError as e:
+            transient_error = transient_database_error(e)
+            if transient_error is None:
+                raise
+            raise transient_error from e
 
         return result
 
@@ -54,6 +86,9 @@ class Neo4jDriver(GraphDriver):
     async def close(self) -> None:
         return await self.client.close()
 
+    async def verify_connectivity(self) -> None:
+        await self.client.verify_connectivity()
+
     def delete_all_indexes(
         self, database_: str = DEFAULT_DATABASE
     ) -> Coroutine[Any, Any, EagerResult]:
@@ -26,6 +26,7 @@ from typing_extensions import LiteralString
 
 from graphiti_core.driver.driver import GraphDriver
 from graphiti_core.embedder import EmbedderClient
+from graphiti_core.encryption import decrypt_field, encrypt_field
 from graphiti_core.errors import EdgeNotFoundError, GroupsEdgesNotFoundError
 from graphiti_core.helpers import DEFAULT_DATABASE, parse_db_date
 from graphiti_core.models.edges.edge_db_queries import (
@@ -37,6 +38,12 @@ from graphiti_core.nodes import Node
 
 logger = logging.getLogger(__name__)
 
+# Relations that mean the same in both directions, so facts between the same two entities are
+# duplicates whichever way round they were extracted
+DEFAULT_SYMMETRIC_RELATIONS = frozenset(
+    {'MARRIED_TO', 'SIBLING_OF', 'FRIENDS_WITH', 'KNOWS', 'RELATED_TO', 'PARTNERS_WITH'}
+)
+
 ENTITY_EDGE_RETURN: LiteralString = """
         RETURN
             e.uuid AS uuid,
@@ -211,6 +218,12 @@ class EntityEdge(Edge):
     invalid_at: datetime | None = Field(
         default=None, description='datetime of when the fact stopped being true'
     )
+    confidence: float | None = Field(
+        default=None,
+        ge=0,
+        le=1,
+        description='confidence between 0 and 1 that the fact holds, as reported at extraction',
+    )
     attributes: dict[str, Any] = Field(
         default={}, description='Additional attributes of the edge. Dependent on edge name'
     )
@@ -247,13 +260,14 @@ class EntityEdge(Edge):
             'uuid': self.uuid,
             'name': self.name,
             'group_id': self.group_id,
-            'fact': self.fact,
+            'fact': encrypt_field(self.group_id, self.fact, driver),
             'fact_embedding': self.fact_embedding,
             'episodes': self.episodes,
             'created_at': self.created_at,
             'expired_at': self.expired_at,
             'valid_at': self.valid_at,
             'invalid_at': self.invalid_at,
+            'confidence': self.confidence,
         }
 
         edge_data.update(self.attributes or {})
@@ -280,7 +294,7 @@ class EntityEdge(Edge):
             routing_='r',
         )
 
-        edges = [get_entity_edge_from_record(record) for record in records]
+        edges = [get_entity_edge_from_record(record, driver) for record in records]
 
         if len(edges) == 0:
             raise EdgeNotFoundError(uuid)
@@ -302,7 +316,7 @@ class EntityEdge(Edge):
             routing_='r',
         )
 
-        edges = [get_entity_edge_from_record(record) for record in records]
+        edges = [get_entity_edge_from_record(record, driver) for record in records]
 
         return edges
 
@@ -335,7 +349,7 @@ class EntityEdge(Edge):
             routing_='r',
         )
 
-        edges = [get_entity_edge_from_record(record) for record in records]
+        edges = [get_entity_edge_from_record(record, driver) for record in records]
 
         if len(edges) == 0:
             raise GroupsEdgesNotFoundError(group_ids)
@@ -353,7 +367,37 @@ class EntityEdge(Edge):
             query, node_uuid=node_uuid, database_=DEFAULT_DATABASE, routing_='r'
         )
 
-        edges = [get_entity_edge_from_record(record) for record in records]
+        edges = [get_entity_edge_from_record(record, driver) for record in records]
+
+        return edges
+
+    @classmethod
+    async def get_between_nodes(
+        cls,
+        driver: GraphDriver,
+        node_uuid_a: str,
+        node_uuid_b: str,
+        include_invalid: bool = False,
+    ):
+        invalid_filter: LiteralString = (
+            '' if include_invalid else 'WHERE e.invalid_at IS NULL AND e.expired_at IS NULL'
+        )
+        records, _, _ = await driver.execute_query(
+            """
+        MATCH (n:Entity {uuid: $node_uuid_a})-[e:RELATES_TO]-(m:Entity {uuid: $node_uuid_b})
+        """
+            + invalid_filter
+            + ENTITY_EDGE_RETURN
+            + """
+        ORDER BY e.created_at
+        """,
+            node_uuid_a=node_uuid_a,
+            node_uuid_b=node_uuid_b,
+            database_=DEFAULT_DATABASE,
+            routing_='r',
+        )
+
+        edges = [get_entity_edge_from_record(record, driver) for record in records]
 
         return edges
 
@@ -457,6 +501,25 @@ class CommunityEdge(Edge):
 
 
 # Edge helpers
+def is_symmetric_relation(name: str, symmetric_relations: frozenset[str]) -> bool:
+    return name.upper() in symmetric_relations
+
+
+def same_direction_or_symmetric(
+    edge: EntityEdge, other: EntityEdge, symmetric_relations: frozenset[str]
+) -> bool:
+    """
+    Whether two facts between the same entities may be duplicates: they point the same way, or
+    the relation is symmetric. "Alice MANAGES Bob" is never a duplicate of "Bob MANAGES Alice".
+    """
+    if (edge.source_node_uuid, edge.target_node_uuid) == (
+        other.source_node_uuid,
+        other.target_node_uuid,
+    ):
+        return True
+    return is_symmetric_relation(edge.name, symmetric_relations)
+
+
 def get_episodic_edge_from_record(record: Any) -> EpisodicEdge:
     return EpisodicEdge(
         uuid=record['uuid'],
@@ -467,12 +530,12 @@ def get_episodic_edge_from_record(record: Any) -> EpisodicEdge:
     )
 
 
-def get_entity_edge_from_record(record: Any) -> EntityEdge:
+def get_entity_edge_from_record(record: Any, driver: GraphDriver | None = None) -> EntityEdge:
     edge = EntityEdge(
         uuid=record['uuid'],
         source_node_uuid=record['source_node_uuid'],
         target_node_uuid=record['target_node_uuid'],
-        fact=record['fact'],
+        fact=decrypt_field(record['group_id'], record['fact'], driver),
         name=record['name'],
         group_id=record['group_id'],
         episodes=record['episodes'],
@@ -480,6 +543,8 @@ def get_entity_edge_from_record(record: Any) -> EntityEdge:
         expired_at=parse_db_date(record['expired_at']),
         valid_at=parse_db_date(record['valid_at']),
         invalid_at=parse_db_date(record['invalid_at']),
+        # Read from the edge properties so that every query returning them picks it up
+        confidence=record['attributes'].get('confidence'),
         attributes=record['attributes'],
     )
 
@@ -494,6 +559,7 @@ def get_entity_edge_from_record(record: Any) -> EntityEdge:
     edge.attributes.pop('expired_at', None)
     edge.attributes.pop('valid_at', None)
     edge.attributes.pop('invalid_at', None)
+    edge.attributes.pop('confidence', None)
 
     return edge
 
@@ -1,8 +1,12 @@
-from .client import EmbedderClient
-from .openai import OpenAIEmbedder, OpenAIEmbedderConfig
+from .cached import CachedEmbedderClient
+from .client import EmbedderClient, EmptyInputPolicy
+from .openai import OpenAIEmbedder, OpenAIEmbedderConfig, TruncationStrategy
 
 __all__ = [
+    'CachedEmbedderClient',
     'EmbedderClient',
+    'EmptyInputPolicy',
     'OpenAIEmbedder',
     'OpenAIEmbedderConfig',
+    'TruncationStrategy',
 ]
@@ -0,0 +1,106 @@
+"""
+Copyright 2024, Zep Software, Inc.
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+"""
+
+import logging
+from collections import OrderedDict
+from collections.abc import Iterable
+
+from .client import EmbedderClient
+
+logger = logging.getLogger(__name__)
+
+DEFAULT_EMBEDDING_CACHE_SIZE = 10000
+
+
+class CachedEmbedderClient(EmbedderClient):
+    """
+    Embedder client that caches text embeddings in memory.
+
+    Wraps another EmbedderClient and caches the embedding of each distinct string. The cache is
+    bounded and evicts the least recently used entries once `max_size` is reached. Token inputs
+    are passed straight through to the wrapped client.
+    """
+
+    def __init__(self, embedder: EmbedderClient, max_size: int = DEFAULT_EMBEDDING_CACHE_SIZE):
+        self.embedder = embedder
+        self.max_size = max_size
+        self._cache: OrderedDict[str, list[float]] = OrderedDict()
+
+    def _get(self, text: str) -> list[float] | None:
+        embedding = self._cache.get(text)
+        if embedding is not None:
+            self._cache.move_to_end(text)
+        return embedding
+
+    def _set(self, text: str, embedding: list[float]):
+        self._cache[text] = embedding
+        self._cache.move_to_end(text)
+        while len(self._cache) > self.max_size:
+            self._cache.popitem(last=False)
+
+    @staticmethod
+    def _cache_key(
+        input_data: str | list[str] | Iterable[int] | Iterable[Iterable[int]],
+    ) -> str | None:
+        if isinstance(input_data, str):
+            return input_data
+        if isinstance(input_data, list) and len(input_data) == 1 and isinstance(input_data[0], str):
+            return input_data[0]
+        return None
+
+    async def create(
+        self, input_data: str | list[str] | Iterable[int] | Iterable[Iterable[int]]
+    ) -> list[float]:
+        key = self._cache_key(input_data)
+        if key is None:
+            return await self.embedder.create(input_data)
+
+        cached = self._get(key)
+        if cached is not None:
+            return cached
+
+        embedding = await self.embedder.create(input_data)
+        self._set(key, embedding)
+        return embedding
+
+    async def create_batch(self, input_data_list: list[str]) -> list[list[float]]:
+        embeddings_by_text: dict[str, list[float]] = {}
+        for text in input_data_list:
+            cached = self._get(text)
+            if cached is not None:
+                embeddings_by_text[text] = cached
+
+        missing = list(dict.fromkeys(t for t in input_data_list if t not in embeddings_by_text))
+        if missing:
+            embeddings = await self.embedder.create_batch(missing)
+            for text, embedding in zip(missing, embeddings, strict=True):
+                embeddings_by_text[text] = embedding
+                self._set(text, embedding)
+
+        return [embeddings_by_text[text] for text in input_data_list]
+
+    async def warmup(self, texts: list[str]) -> int:
+        """
+        Precompute and cache embeddings for the given texts.
+
+        Texts that are already cached are skipped. Returns the number of newly embedded texts.
+        """
+        missing = list(dict.fromkeys(text for text in texts if text not in self._cache))
+        if missing:
+            await self.create_batch(missing)
+
+        logger.debug(f'Warmed embedding cache with {len(missing)} new texts')
+        return len(missing)
@@ -14,16 +14,41 @@ See the License for the specific language governing permissions and
 limitations under the License.
 """
 
+import math
 from abc import ABC, abstractmethod
 from collections.abc import Iterable
+from enum import Enum
 
 from pydantic import BaseModel, Field
 
 EMBEDDING_DIM = 1024
 
 
+class EmptyInputPolicy(str, Enum):
+    """How embedders handle empty or whitespace-only input text."""
+
+    error = 'error'
+    zero_vector = 'zero_vector'
+
+
 class EmbedderConfig(BaseModel):
     embedding_dim: int = Field(default=EMBEDDING_DIM, frozen=True)
+    empty_input_policy: EmptyInputPolicy = EmptyInputPolicy.error
+    # Scale every embedding to unit length, for vector indexes that score by dot product. Stored
+    # and query embeddings come from the same embedder, so they are normalized alike
+    normalize: bool = False
+
+
+def is_empty_input(text: str) -> bool:
+    return not text.strip()
+
+
+def normalize_embedding(embedding: list[float]) -> list[float]:
+    """Scales the embedding to unit L2 norm. Zero vectors are returned as is."""
+    norm = math.sqrt(sum(value * value for value in embedding))
+    if norm == 0:
+        return embedding
+    return [value / norm for value in embedding]
 
 
 class EmbedderClient(ABC):
@@ -20,7 +20,7 @@ from google import genai  # type: ignore
 from google.genai import types  # type: ignore
 from pydantic import Field
 
-from .client import EmbedderClient, EmbedderConfig
+from .client import EmbedderClient, EmbedderConfig, normalize_embedding
 
 DEFAULT_EMBEDDING_MODEL = 'embedding-001'
 
@@ -68,7 +68,8 @@ class GeminiEmbedder(EmbedderClient):
         if not result.embeddings or len(result.embeddings) == 0 or not result.embeddings[0].values:
             raise ValueError('No embeddings returned from Gemini API in create()')
 
-        return result.embeddings[0].values
+        embedding = result.embeddings[0].values
+        return normalize_embedding(embedding) if self.config.normalize else embedding
 
     async def create_batch(self, input_data_list: list[str]) -> list[list[float]]:
         # Generate embeddings
@@ -85,5 +86,7 @@ class GeminiEmbedder(EmbedderClient):
         for embedding in result.embeddings:
             if not embedding.values:
                 raise ValueError('Empty embedding values returned')
-            embeddings.append(embedding.values)
+            embeddings.append(
+                normalize_embedding(embedding.values) if self.config.normalize else embedding.values
+            )
         return embeddings
@@ -14,20 +14,67 @@ See the License for the specific language governing permissions and
 limitations under the License.
 """
 
+import asyncio
+import logging
 from collections.abc import Iterable
+from enum import Enum
 
+import httpx
+import openai
 from openai import AsyncAzureOpenAI, AsyncOpenAI
 from openai.types import EmbeddingModel
 
-from .client import EmbedderClient, EmbedderConfig
+from ..errors import EmptyEmbeddingInputError
+from ..helpers import (
+    DEFAULT_HTTP_CONNECT_TIMEOUT,
+    DEFAULT_HTTP_TIMEOUT,
+    count_tokens,
+    semaphore_gather,
+    split_tokens,
+)
+from .client import (
+    EmbedderClient,
+    EmbedderConfig,
+    EmptyInputPolicy,
+    is_empty_input,
+    normalize_embedding,
+)
+
+logger = logging.getLogger(__name__)
 
 DEFAULT_EMBEDDING_MODEL = 'text-embedding-3-small'
+# Input limit of the OpenAI embedding models
+DEFAULT_MAX_INPUT_TOKENS = 8191
+# Limits of a single embeddings request, across all of its inputs
+DEFAULT_MAX_REQUEST_TOKENS = 300_000
+DEFAULT_MAX_REQUEST_INPUTS = 2048
+DEFAULT_MAX_CONCURRENT_REQUESTS = 4
+DEFAULT_BATCH_RETRIES = 2
+DEFAULT_BATCH_RETRY_DELAY = 1.0
+# Errors after which the same request may succeed
+RETRYABLE_ERRORS = (openai.APIConnectionError, openai.RateLimitError, openai.InternalServerError)
+
+
+class TruncationStrategy(str, Enum):
+    """How texts longer than the model's input limit are embedded."""
+
+    truncate = 'truncate'
+    chunk_mean_pool = 'chunk_mean_pool'
 
 
 class OpenAIEmbedderConfig(EmbedderConfig):
     embedding_model: EmbeddingModel | str = DEFAULT_EMBEDDING_MODEL
     api_key: str | None = None
     base_url: str | None = None
+    timeout: float = DEFAULT_HTTP_TIMEOUT
+    connect_timeout: float = DEFAULT_HTTP_CONNECT_TIMEOUT
+    max_input_tokens: int = DEFAULT_MAX_INPUT_TOKENS
+    truncation_strategy: TruncationStrategy = TruncationStrategy.truncate
+    max_request_tokens: int = DEFAULT_MAX_REQUEST_TOKENS
+    max_request_inputs: int = DEFAULT_MAX_REQUEST_INPUTS
+    max_concurrent_requests: int = DEFAULT_MAX_CONCURRENT_REQUESTS
+    batch_retries: int = DEFAULT_BATCH_RETRIES
+    batch_retry_delay: float = DEFAULT_BATCH_RETRY_DELAY
 
 
 class OpenAIEmbedder(EmbedderClient):
@@ -35,12 +82,29 @@ class OpenAIEmbedder(EmbedderClient):
     OpenAI Embedder Client
 
     This client supports both AsyncOpenAI and AsyncAzureOpenAI clients.
+
+    An httpx.AsyncClient can be passed as http_client to share a connection pool with the
+    LLM client. The timeouts and http_client only apply when no client is provided.
+
+    Empty or whitespace-only texts are never sent to the API. Depending on
+    config.empty_input_policy they either raise EmptyEmbeddingInputError or embed to a zero vector.
+
+    Texts longer than config.max_input_tokens are either cut to the limit or split into chunks
+    whose embeddings are averaged, weighted by chunk length, depending on
+    config.truncation_strategy. Tokens are counted with count_tokens for the embedding model.
+
+    create_batch splits its texts into requests that stay under config.max_request_tokens and
+    config.max_request_inputs, sends up to config.max_concurrent_requests of them at a time,
+    and retries a request that failed with a transient error up to config.batch_retries times.
+
+    With config.normalize, embeddings are L2-normalized after they are cut to embedding_dim.
     """
 
     def __init__(
         self,
         config: OpenAIEmbedderConfig | None = None,
         client: AsyncOpenAI | AsyncAzureOpenAI | None = None,
+        http_client: httpx.AsyncClient | None = None,
     ):
         if config is None:
             config = OpenAIEmbedderConfig()
@@ -49,18 +113,136 @@ class OpenAIEmbedder(EmbedderClient):
         if client is not None:
             self.client = client
         else:
-            self.client = AsyncOpenAI(api_key=config.api_key, base_url=config.base_url)
+            self.client = AsyncOpenAI(
+                api_key=config.api_key,
+                base_url=config.base_url,
+                timeout=httpx.Timeout(config.timeout, connect=config.connect_timeout),
+                http_client=http_client,
+            )
+
+    def _empty_embedding(self) -> list[float]:
+        if self.config.empty_input_policy == EmptyInputPolicy.error:
+            raise EmptyEmbeddingInputError()
+        return [0.0] * self.config.embedding_dim
+
+    def _fit_to_limit(self, text: str) -> list[str]:
+        """Returns the pieces of text to embed, a single one unless the text is chunked."""
+        model = str(self.config.embedding_model)
+        tokens = count_tokens(text, model)
+        if tokens <= self.config.max_input_tokens:
+            return [text]
+
+        chunks = split_tokens(text, self.config.max_input_tokens, model)
+        if self.config.truncation_strategy == TruncationStrategy.truncate:
+            logger.warning(
+                f'Embedding input of {tokens} tokens exceeds the limit of '
+                f'{self.config.max_input_tokens} tokens, truncating it'
+            )
+            return chunks[:1]
+
+        logger.warning(
+            f'Embedding input of {tokens} tokens exceeds the limit of '
+            f'{self.config.max_input_tokens} tokens, embedding it as {len(chunks)} chunks'
+        )
+        return chunks
+
+    def _split_requests(self, texts: list[str]) -> list[list[str]]:
+        """Splits texts, in order, into the inputs of requests that stay under the limits."""
+        requests: list[list[str]] = []
+        request_tokens = 0
+        for text in texts:
+            tokens = count_tokens(text, str(self.config.embedding_model))
+            if (
+                requests
+                and len(requests[-1]) < self.config.max_request_inputs
+                and request_tokens + tokens <= self.config.max_request_tokens
+            ):
+                requests[-1].append(text)
+                request_tokens += tokens
+            else:
+                requests.append([text])
+                request_tokens = tokens
+        return requests
+
+    async def _embed_request(self, texts: list[str]) -> list[list[float]]:
+        attempt = 0
+        while True:
+            try:
+                result = await self.client.embeddings.create(
+                    input=texts, model=self.config.embedding_model
+                )
+                return [embedding.embedding for embedding in result.data]
+            except RETRYABLE_ERRORS as e:
+                if attempt >= self.config.batch_retries:
+                    raise
+                logger.warning(
+                    f'Embedding request of {len(texts)} texts failed, retrying it '
+                    f'({attempt + 1}/{self.config.batch_retries}): {e}'
+                )
+                await asyncio.sleep(self.config.batch_retry_delay * 2**attempt)
+                attempt += 1
+
+    def _finish(self, embedding: list[float]) -> list[float]:
+        embedding = embedding[: self.config.embedding_dim]
+        return normalize_embedding(embedding) if self.config.normalize else embedding
+
+    def _pool(self, chunks: list[str], embeddings: list[list[float]]) -> list[float]:
+        if len(embeddings) == 1:
+            return self._finish(embeddings[0])
+
+        total_chars = sum(len(chunk) for chunk in chunks)
+        pooled = [
+            sum(len(chunk) * embedding[dim] for chunk, embedding in zip(chunks, embeddings))
+            / total_chars
+            for dim in range(len(embeddings[0]))
+        ]
+        return self._finish(pooled)
 
     async def create(
         self, input_data: str | list[str] | Iterable[int] | Iterable[Iterable[int]]
     ) -> list[float]:
+        # Callers embedding one text usually pass it as [text]. Only the first text's embedding is
+        # returned, so it alone is checked for emptiness, fitted to the limit and embedded
+        if isinstance(input_data, list) and input_data and isinstance(input_data[0], str):
+            input_data = input_data[0]
+
+        if not isinstance(input_data, str):
+            result = await self.client.embeddings.create(
+                input=input_data, model=self.config.embedding_model
+            )
+            return self._finish(result.data[0].embedding)
+
+        if is_empty_input(input_data):
+            return self._empty_embedding()
+
+        chunks = self._fit_to_limit(input_data)
         result = await self.client.embeddings.create(
-            input=input_data, model=self.config.embedding_model
+            input=chunks[0] if len(chunks) == 1 else chunks, model=self.config.embedding_model
         )
-        return result.data[0].embedding[: self.config.embedding_dim]
+        return self._pool(chunks, [embedding.embedding for embedding in result.data])
 
     async def create_batch(self, input_data_list: list[str]) -> list[list[float]]:
-        result = await self.client.embeddings.create(
-            input=input_data_list, model=self.config.embedding_model
+        # Only send non-empty texts to the API, then map the results back to their positions
+        valid_indices = [i for i, text in enumerate(input_data_list) if not is_empty_input(text)]
+        if len(valid_indices) < len(input_data_list):
+            self._empty_embedding()
+
+        embeddings = [[0.0] * self.config.embedding_dim for _ in input_data_list]
+        if not valid_indices:
+            return embeddings
+
+        # Over-length texts may be split, so chunks are embedded in order and pooled per text
+        chunks_by_index = {i: self._fit_to_limit(input_data_list[i]) for i in valid_indices}
+        requests = self._split_requests(
+            [chunk for i in valid_indices for chunk in chunks_by_index[i]]
+        )
+        results = await semaphore_gather(
+            *[self._embed_request(request) for request in requests],
+            max_coroutines=self.config.max_concurrent_requests,
         )
-        return [embedding.embedding[: self.config.embedding_dim] for embedding in result.data]
+        chunk_embeddings = iter([embedding for result in results for embedding in result])
+        for i in valid_indices:
+            chunks = chunks_by_index[i]
+            pieces = [next(chunk_embeddings) for _ in chunks]
+            embeddings[i] = self._pool(chunks, pieces)
+        return embeddings
@@ -19,7 +19,7 @@ from collections.abc import Iterable
 import voyageai  # type: ignore
 from pydantic import Field
 
-from .client import EmbedderClient, EmbedderConfig
+from .client import EmbedderClient, EmbedderConfig, normalize_embedding
 
 DEFAULT_EMBEDDING_MODEL = 'voyage-3'
 
@@ -55,11 +55,12 @@ class VoyageAIEmbedder(EmbedderClient):
             return []
 
         result = await self.client.embed(input_list, model=self.config.embedding_model)
-        return [float(x) for x in result.embeddings[0][: self.config.embedding_dim]]
+        return self._finish(result.embeddings[0])
 
     async def create_batch(self, input_data_list: list[str]) -> list[list[float]]:
         result = await self.client.embed(input_data_list, model=self.config.embedding_model)
-        return [
-            [float(x) for x in embedding[: self.config.embedding_dim]]
-            for embedding in result.embeddings
-        ]
+        return [self._finish(embedding) for embedding in result.embeddings]
+
+    def _finish(self, embedding: list[float]) -> list[float]:
+        values = [float(x) for x in embedding[: self.config.embedding_dim]]
+        return normalize_embedding(values) if self.config.normalize else values
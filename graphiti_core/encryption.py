@@ -0,0 +1,125 @@
+"""
+Copyright 2024, Zep Software, Inc.
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+"""
+
+from collections.abc import Iterator, Mapping
+from contextlib import contextmanager
+from contextvars import ContextVar
+from typing import TYPE_CHECKING
+from weakref import WeakKeyDictionary
+
+from graphiti_core.errors import GroupDecryptionError
+
+if TYPE_CHECKING:
+    from graphiti_core.driver.driver import GraphDriver
+
+# Marks stored values as ciphertext, so that plaintext written before a group was encrypted
+# can still be read
+ENCRYPTED_PREFIX = 'enc:v1:'
+
+
+class GroupEncryption:
+    """
+    Field-level encryption at rest for selected groups.
+
+    Episode content, entity and community summaries and edge facts of the groups in `keys` are
+    stored encrypted with Fernet, using that group's key, and decrypted when read back. Names,
+    attributes and embeddings are stored as is; embeddings are computed from the plaintext, so
+    vector search keeps working while fulltext search no longer matches the encrypted fields.
+
+    Requires the `cryptography` package: pip install graphiti-core[encryption]
+    """
+
+    def __init__(self, keys: Mapping[str, str | bytes]):
+        try:
+            from cryptography.fernet import Fernet
+        except ImportError:
+            raise ImportError(
+                'cryptography is required for GroupEncryption. '
+                'Install it with: pip install graphiti-core[encryption]'
+            ) from None
+
+        self._fernets = {group_id: Fernet(key) for group_id, key in keys.items()}
+
+    @staticmethod
+    def generate_key() -> str:
+        from cryptography.fernet import Fernet
+
+        return Fernet.generate_key().decode()
+
+    def encrypt(self, group_id: str, value: str) -> str:
+        fernet = self._fernets.get(group_id)
+        if fernet is None or not value or value.startswith(ENCRYPTED_PREFIX):
+            return value
+        return ENCRYPTED_PREFIX + fernet.encrypt(value.encode()).decode()
+
+    def decrypt(self, group_id: str, value: str) -> str:
+        from cryptography.fernet import InvalidToken
+
+        if not value or not value.startswith(ENCRYPTED_PREFIX):
+            return value
+        fernet = self._fernets.get(group_id)
+        if fernet is None:
+            raise GroupDecryptionError(group_id)
+        try:
+            return fernet.decrypt(value[len(ENCRYPTED_PREFIX) :].encode()).decode()
+        except InvalidToken:
+            raise GroupDecryptionError(group_id) from None
+
+
+_active_encryption: ContextVar[GroupEncryption | None] = ContextVar(
+    'group_encryption', default=None
+)
+# Encryption attached to a driver applies to everything saved and read through that driver
+_driver_encryption: 'WeakKeyDictionary[GraphDriver, GroupEncryption]' = WeakKeyDictionary()
+
+
+def attach_group_encryption(driver: 'GraphDriver', encryption: GroupEncryption | None):
+    """
+    Encrypt and decrypt stored content with the given keys for every save and read made through
+    the driver, including ones outside of a Graphiti operation. None detaches the keys.
+    """
+    if encryption is None:
+        _driver_encryption.pop(driver, None)
+    else:
+        _driver_encryption[driver] = encryption
+
+
+@contextmanager
+def use_group_encryption(encryption: GroupEncryption | None) -> Iterator[None]:
+    """Encrypt and decrypt stored content with the given keys within this context."""
+    token = _active_encryption.set(encryption)
+    try:
+        yield
+    finally:
+        _active_encryption.reset(token)
+
+
+def _encryption_for(driver: 'GraphDriver | None') -> GroupEncryption | None:
+    # Keys set for the current context take precedence over the driver's
+    encryption = _active_encryption.get()
+    if encryption is None and driver is not None:
+        encryption = _driver_encryption.get(driver)
+    return encryption
+
+
+def encrypt_field(group_id: str, value: str, driver: 'GraphDriver | None' = None) -> str:
+    encryption = _encryption_for(driver)
+    return encryption.encrypt(group_id, value) if encryption is not None else value
+
+
+def decrypt_field(group_id: str, value: str, driver: 'GraphDriver | None' = None) -> str:
+    encryption = _encryption_for(driver)
+    return encryption.decrypt(group_id, value) if encryption is not None else value
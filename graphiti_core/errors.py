@@ -81,3 +81,142 @@ class GroupIdValidationError(GraphitiError):
     def __init__(self, group_id: str):
         self.message = f'group_id "{group_id}" must contain only alphanumeric characters, dashes, or underscores'
         super().__init__(self.message)
+
+
+class ReprocessingDisabledError(GraphitiError):
+    """Raised when episode reprocessing is requested but not enabled."""
+
+    def __init__(self, reason: str):
+        self.message = f'episode reprocessing is unavailable: {reason}'
+        super().__init__(self.message)
+
+
+class ImportConflictError(GraphitiError):
+    """Raised when an import contains uuids that already exist in the graph."""
+
+    def __init__(self, uuids: list[str]):
+        self.message = f'import conflicts with {len(uuids)} existing nodes or edges: {uuids[:10]}'
+        super().__init__(self.message)
+
+
+class EmptyEmbeddingInputError(GraphitiError):
+    """Raised when an embedder is asked to embed empty or whitespace-only text."""
+
+    def __init__(self):
+        self.message = 'cannot embed empty or whitespace-only text'
+        super().__init__(self.message)
+
+
+class EpisodeChunkTooLongError(GraphitiError):
+    """Raised when a single chunk of a long episode still exceeds the model's context window."""
+
+    def __init__(self, max_episode_chars: int):
+        self.message = (
+            f'an episode chunk of {max_episode_chars} characters exceeds the model context window; '
+            'lower max_episode_chars'
+        )
+        super().__init__(self.message)
+
+
+class InvalidEpisodeTypeError(GraphitiError):
+    """Raised when an episode source is neither an EpisodeType nor a registered alias."""
+
+    def __init__(self, episode_type: str, accepted: list[str]):
+        self.message = (
+            f'unknown episode type {episode_type!r}; accepted values are: {", ".join(accepted)}'
+        )
+        super().__init__(self.message)
+
+
+class ExtractionError(GraphitiError):
+    """Raised in strict extraction mode when an episode's extraction fails or finds nothing."""
+
+    def __init__(self, episode_uuid: str, reason: str):
+        self.message = f'entity extraction failed for episode {episode_uuid}: {reason}'
+        super().__init__(self.message)
+
+
+class MissingSourceDescriptionError(GraphitiError):
+    """Raised when an episode without a source description is added and one is required."""
+
+    def __init__(self, episode_name: str):
+        self.message = f'episode {episode_name!r} has no source_description, which is required'
+        super().__init__(self.message)
+
+
+class TransientDatabaseError(GraphitiError):
+    """Base class for database errors that may succeed when the query is retried."""
+
+    def __init__(self, message: str):
+        self.message = message
+        super().__init__(self.message)
+
+
+class DatabaseTimeoutError(TransientDatabaseError):
+    """Raised when a query runs past the database's transaction timeout."""
+
+
+class DatabasePoolExhaustedError(TransientDatabaseError):
+    """Raised when no connection could be taken from the driver's connection pool in time."""
+
+
+class DatabaseConflictError(GraphitiError):
+    """Raised when a write violates a uniqueness constraint, e.g. a duplicate uuid."""
+
+    def __init__(self, message: str):
+        self.message = message
+        super().__init__(self.message)
+
+
+class GroupDecryptionError(GraphitiError):
+    """Raised when stored content of an encrypted group can't be decrypted with its key."""
+
+    def __init__(self, group_id: str):
+        self.message = f'could not decrypt stored content for group {group_id!r}; check its key'
+        super().__init__(self.message)
+
+
+class CrossGroupSearchError(GraphitiError):
+    """Raised when a search leaves out group_ids and cross-group search is not allowed."""
+
+    def __init__(self):
+        self.message = (
+            'search requires group_ids; pass allow_cross_group_search=True to Graphiti to search '
+            'across all groups'
+        )
+        super().__init__(self.message)
+
+
+class EmbeddingsUnchangedError(GraphitiError):
+    """Raised when re-embedding a group would not change its stored embeddings."""
+
+    def __init__(self, group_id: str, embedding_dim: int):
+        self.message = (
+            f'the {embedding_dim}-dimensional embeddings of group {group_id!r} already match the '
+            'current embedder; nothing to re-embed'
+        )
+        super().__init__(self.message)
+
+
+class SearchDepthExceededError(GraphitiError):
+    """Raised when a graph traversal is requested deeper than MAX_SEARCH_DEPTH."""
+
+    def __init__(self, depth: int, max_depth: int):
+        self.message = f'search depth {depth} exceeds the maximum of {max_depth}'
+        super().__init__(self.message)
+
+
+class SearchTimeoutError(GraphitiError):
+    """Raised when a search hits its timeout and partial results are not accepted."""
+
+    def __init__(self, timeout: float):
+        self.message = f'search timed out after {timeout} seconds'
+        super().__init__(self.message)
+
+
+class ProviderCheckError(GraphitiError):
+    """Raised when the LLM, embedder, or reranker fails its startup check."""
+
+    def __init__(self, provider: str, reason: str):
+        self.message = f'{provider} check failed: {reason}'
+        super().__init__(self.message)
@@ -31,7 +31,7 @@ def get_range_indices(db_type: str = 'neo4j') -> list[LiteralString]:
             # Entity node
             'CREATE INDEX FOR (n:Entity) ON (n.uuid, n.group_id, n.name, n.created_at)',
             # Episodic node
-            'CREATE INDEX FOR (n:Episodic) ON (n.uuid, n.group_id, n.created_at, n.valid_at)',
+            'CREATE INDEX FOR (n:Episodic) ON (n.uuid, n.group_id, n.created_at, n.valid_at, n.external_id)',
             # Community node
             'CREATE INDEX FOR (n:Community) ON (n.uuid)',
             # RELATES_TO edge
@@ -43,9 +43,6 @@ def get_range_indices(db_type: str = 'neo4j') -> list[LiteralString]:
         ]
     else:
         return [
-            'CREATE INDEX entity_uuid IF NOT EXISTS FOR (n:Entity) ON (n.uuid)',
-            'CREATE INDEX episode_uuid IF NOT EXISTS FOR (n:Episodic) ON (n.uuid)',
-            'CREATE INDEX community_uuid IF NOT EXISTS FOR (n:Community) ON (n.uuid)',
             'CREATE INDEX relation_uuid IF NOT EXISTS FOR ()-[e:RELATES_TO]-() ON (e.uuid)',
             'CREATE INDEX mention_uuid IF NOT EXISTS FOR ()-[e:MENTIONS]-() ON (e.uuid)',
             'CREATE INDEX has_member_uuid IF NOT EXISTS FOR ()-[e:HAS_MEMBER]-() ON (e.uuid)',
@@ -57,6 +54,7 @@ def get_range_indices(db_type: str = 'neo4j') -> list[LiteralString]:
             'CREATE INDEX created_at_entity_index IF NOT EXISTS FOR (n:Entity) ON (n.created_at)',
             'CREATE INDEX created_at_episodic_index IF NOT EXISTS FOR (n:Episodic) ON (n.created_at)',
             'CREATE INDEX valid_at_episodic_index IF NOT EXISTS FOR (n:Episodic) ON (n.valid_at)',
+            'CREATE INDEX external_id_episodic_index IF NOT EXISTS FOR (n:Episodic) ON (n.external_id)',
             'CREATE INDEX name_edge_index IF NOT EXISTS FOR ()-[e:RELATES_TO]-() ON (e.name)',
             'CREATE INDEX created_at_edge_index IF NOT EXISTS FOR ()-[e:RELATES_TO]-() ON (e.created_at)',
             'CREATE INDEX expired_at_edge_index IF NOT EXISTS FOR ()-[e:RELATES_TO]-() ON (e.expired_at)',
@@ -65,6 +63,25 @@ def get_range_indices(db_type: str = 'neo4j') -> list[LiteralString]:
         ]
 
 
+def get_constraints(db_type: str = 'neo4j') -> list[LiteralString]:
+    """
+    Uniqueness constraints on node uuids, run in order. Upserts rely on them to turn concurrent
+    creates of the same node into a conflict that is retried.
+    """
+    if db_type == 'falkordb':
+        return []
+    else:
+        return [
+            # A uniqueness constraint can't be created over an existing index on the same property
+            'DROP INDEX entity_uuid IF EXISTS',
+            'DROP INDEX episode_uuid IF EXISTS',
+            'DROP INDEX community_uuid IF EXISTS',
+            'CREATE CONSTRAINT entity_uuid_unique IF NOT EXISTS FOR (n:Entity) REQUIRE n.uuid IS UNIQUE',
+            'CREATE CONSTRAINT episode_uuid_unique IF NOT EXISTS FOR (n:Episodic) REQUIRE n.uuid IS UNIQUE',
+            'CREATE CONSTRAINT community_uuid_unique IF NOT EXISTS FOR (n:Community) REQUIRE n.uuid IS UNIQUE',
+        ]
+
+
 def get_fulltext_indices(db_type: str = 'neo4j') -> list[LiteralString]:
     if db_type == 'falkordb':
         return [
@@ -14,51 +14,123 @@ See the License for the specific language governing permissions and
 limitations under the License.
 """
 
+import asyncio
+import functools
 import logging
-from datetime import datetime
+from collections import defaultdict
+from collections.abc import Awaitable, Callable
+from contextlib import AbstractAsyncContextManager, nullcontext
+from datetime import datetime, timedelta
 from time import time
+from typing import Concatenate, ParamSpec, TypeVar
 
+import openai
 from dotenv import load_dotenv
 from pydantic import BaseModel
 from typing_extensions import LiteralString
 
 from graphiti_core.cross_encoder.client import CrossEncoderClient
-from graphiti_core.cross_encoder.openai_reranker_client import OpenAIRerankerClient
 from graphiti_core.driver.driver import GraphDriver
 from graphiti_core.driver.neo4j_driver import Neo4jDriver
-from graphiti_core.edges import EntityEdge, EpisodicEdge
-from graphiti_core.embedder import EmbedderClient, OpenAIEmbedder
+from graphiti_core.edges import DEFAULT_SYMMETRIC_RELATIONS, EntityEdge, EpisodicEdge
+from graphiti_core.embedder import EmbedderClient
+from graphiti_core.embedder.cached import CachedEmbedderClient
+from graphiti_core.encryption import (
+    GroupEncryption,
+    attach_group_encryption,
+    use_group_encryption,
+)
+from graphiti_core.errors import (
+    CrossGroupSearchError,
+    EpisodeChunkTooLongError,
+    ImportConflictError,
+    MissingSourceDescriptionError,
+    ProviderCheckError,
+    ReprocessingDisabledError,
+    SearchTimeoutError,
+)
 from graphiti_core.graphiti_types import GraphitiClients
 from graphiti_core.helpers import (
     DEFAULT_DATABASE,
+    DEFAULT_EPISODE_CHUNK_OVERLAP,
+    DEFAULT_MAX_SUMMARY_WORDS,
+    DEFAULT_PAGE_LIMIT,
+    MAX_REFLEXION_ITERATIONS,
+    AttributeConflictPolicy,
+    AttributeValidationPolicy,
+    KeyedLock,
+    NameNormalization,
+    StringSimilarity,
+    chunk_text,
+    count_tokens,
+    detect_script,
+    normalize_name,
     semaphore_gather,
+    truncate_words,
     validate_excluded_entity_types,
     validate_group_id,
 )
-from graphiti_core.llm_client import LLMClient, OpenAIClient
-from graphiti_core.nodes import CommunityNode, EntityNode, EpisodeType, EpisodicNode
-from graphiti_core.search.search import SearchConfig, search
-from graphiti_core.search.search_config import DEFAULT_SEARCH_LIMIT, SearchResults
+from graphiti_core.llm_client import LLMClient, RetryBudget, use_model_override, use_retry_budget
+from graphiti_core.llm_client.client import get_model_override
+from graphiti_core.nodes import (
+    CommunityNode,
+    EntityEmbeddingText,
+    EntityNode,
+    EpisodeType,
+    EpisodicNode,
+)
+from graphiti_core.prompts import (
+    PromptLibraryWrapper,
+    SystemInstructions,
+    use_prompt_library,
+    use_system_instructions,
+)
+from graphiti_core.prompts.models import Message
+from graphiti_core.providers import (
+    EmbedderProvider,
+    LLMProvider,
+    RerankerProvider,
+    create_cross_encoder,
+    create_embedder,
+    create_llm_client,
+)
+from graphiti_core.search.search import SearchConfig, search, search_and_expand
+from graphiti_core.search.search_cache import DEFAULT_SEARCH_CACHE_TTL, SearchResultCache
+from graphiti_core.search.search_config import (
+    DEFAULT_EXPAND_SEED_COUNT,
+    DEFAULT_MAX_TRAVERSAL_NODES,
+    DEFAULT_SEARCH_LIMIT,
+    EdgeReranker,
+    SearchResults,
+)
 from graphiti_core.search.search_config_recipes import (
     COMBINED_HYBRID_SEARCH_CROSS_ENCODER,
-    EDGE_HYBRID_SEARCH_NODE_DISTANCE,
-    EDGE_HYBRID_SEARCH_RRF,
+    NODE_HYBRID_SEARCH_RRF,
 )
 from graphiti_core.search.search_filters import SearchFilters
 from graphiti_core.search.search_utils import (
+    MAX_SEARCH_DEPTH,
     RELEVANT_SCHEMA_LIMIT,
     get_edge_invalidation_candidates,
+    get_embeddings_for_nodes,
     get_mentioned_nodes,
     get_relevant_edges,
 )
 from graphiti_core.telemetry import capture_event
 from graphiti_core.utils.bulk_utils import (
+    DEFAULT_EMBEDDING_CHANNEL_DEPTH,
+    DEFAULT_WRITE_BATCH_SIZE,
+    BatchWriter,
     RawEpisode,
     add_nodes_and_edges_bulk,
+    compress_nodes,
+    compress_uuid_map,
     dedupe_edges_bulk,
     dedupe_nodes_bulk,
     extract_edge_dates_bulk,
     extract_nodes_and_edges_bulk,
+    group_duplicate_candidates,
+    node_name_match,
     resolve_edge_pointers,
     retrieve_previous_episodes_bulk,
 )
@@ -71,31 +143,120 @@ from graphiti_core.utils.maintenance.community_operations import (
 from graphiti_core.utils.maintenance.edge_operations import (
     build_duplicate_of_edges,
     build_episodic_edges,
+    dedupe_extracted_edge,
     extract_edges,
+    filter_extracted_edges,
     resolve_extracted_edge,
     resolve_extracted_edges,
 )
 from graphiti_core.utils.maintenance.graph_data_operations import (
+    DEFAULT_CONNECT_RETRY_SECONDS,
+    DEFAULT_REEMBED_BATCH_SIZE,
     EPISODE_WINDOW_LEN,
+    ConflictPolicy,
+    EpisodePruneResult,
+    GraphExport,
+    GroupStats,
+    Inconsistency,
+    OrphanPolicy,
+    backfill_embeddings,
     build_indices_and_constraints,
+    cleanup_orphans,
+    clear_data,
+    export_group,
+    find_inconsistencies,
+    get_existing_uuids,
+    get_group_stats,
+    move_entity_links,
+    prune_episodes,
+    reembed_group,
     retrieve_episodes,
+    wait_for_database,
 )
 from graphiti_core.utils.maintenance.node_operations import (
     extract_attributes_from_nodes,
     extract_nodes,
+    merge_node_labels,
     resolve_extracted_nodes,
 )
 from graphiti_core.utils.ontology_utils.entity_types_utils import validate_entity_types
 
 logger = logging.getLogger(__name__)
 
+# Text sent to the embedder and reranker by check_providers
+PROVIDER_CHECK_TEXT = 'Graphiti provider check'
+
 load_dotenv()
 
+P = ParamSpec('P')
+R = TypeVar('R')
+
+
+def with_operation_settings(
+    method: Callable[Concatenate['Graphiti', P], Awaitable[R]],
+) -> Callable[Concatenate['Graphiti', P], Awaitable[R]]:
+    # Prompts built while the method runs come from the instance's prompt library and include
+    # its custom system instructions, all of its LLM calls share one retry budget and content of
+    # encrypted groups is encrypted on write and decrypted on read
+    @functools.wraps(method)
+    async def wrapper(self: 'Graphiti', *args: P.args, **kwargs: P.kwargs) -> R:
+        retry_budget = (
+            RetryBudget(self.max_operation_retries)
+            if self.max_operation_retries is not None
+            else None
+        )
+        with (
+            use_prompt_library(self.prompt_library),
+            use_system_instructions(self.system_instructions),
+            use_retry_budget(retry_budget),
+            use_group_encryption(self.encryption),
+        ):
+            return await method(self, *args, **kwargs)
+
+    return wrapper
+
+
+class IngestCounts(BaseModel):
+    """How the entities or facts extracted from an episode were resolved against the graph."""
+
+    # Items that were new to the graph
+    created: int = 0
+    # Existing items that extracted ones were merged into
+    merged: int = 0
+    # Extracted items that weren't kept, e.g. duplicates of another item from the same episode
+    skipped: int = 0
+
+    @classmethod
+    def from_resolutions(cls, resolutions: list[tuple[str, str | None]]) -> 'IngestCounts':
+        """Counts (extracted uuid, resolved uuid) pairs; a resolved uuid of None was dropped."""
+        created = {resolved for extracted, resolved in resolutions if resolved == extracted}
+        merged = {resolved for _, resolved in resolutions if resolved is not None} - created
+        return cls(
+            created=len(created),
+            merged=len(merged),
+            skipped=len(resolutions) - len(created) - len(merged),
+        )
+
 
 class AddEpisodeResults(BaseModel):
     episode: EpisodicNode
     nodes: list[EntityNode]
     edges: list[EntityEdge]
+    # Only set by add_episode
+    node_counts: IngestCounts | None = None
+    edge_counts: IngestCounts | None = None
+
+
+class BulkEpisodeError(BaseModel):
+    # Position of the episode in the bulk input
+    index: int
+    name: str
+    error: str
+
+
+class AddBulkEpisodeResults(BaseModel):
+    results: list[AddEpisodeResults]
+    errors: list[BulkEpisodeError]
 
 
 class Graphiti:
@@ -110,6 +271,48 @@ class Graphiti:
         store_raw_episode_content: bool = True,
         graph_driver: GraphDriver | None = None,
         max_coroutines: int | None = None,
+        allow_episode_reprocessing: bool = False,
+        name_normalization: NameNormalization = NameNormalization.none,
+        search_cache_ttl: float = DEFAULT_SEARCH_CACHE_TTL,
+        max_episode_chars: int | None = None,
+        episode_chunk_overlap: int = DEFAULT_EPISODE_CHUNK_OVERLAP,
+        enable_embeddings: bool = True,
+        episode_type_aliases: dict[str, EpisodeType] | None = None,
+        custom_system_instructions: str | None = None,
+        system_instruction_overrides: dict[str, str] | None = None,
+        max_reflexion_rounds: int = MAX_REFLEXION_ITERATIONS,
+        max_summary_words: int | None = DEFAULT_MAX_SUMMARY_WORDS,
+        auto_merge_threshold: float = 1.0,
+        name_similarity_threshold: float = 1.0,
+        name_similarity: StringSimilarity = StringSimilarity.jaro_winkler,
+        prompt_library: PromptLibraryWrapper | None = None,
+        attribute_validation: AttributeValidationPolicy = AttributeValidationPolicy.keep,
+        llm_provider: LLMProvider | str = LLMProvider.openai,
+        embedder_provider: EmbedderProvider | str = EmbedderProvider.openai,
+        reranker_provider: RerankerProvider | str = RerankerProvider.openai,
+        orphan_cleanup: OrphanPolicy | None = None,
+        max_operation_retries: int | None = None,
+        encryption: GroupEncryption | None = None,
+        max_entities_per_episode: int | None = None,
+        max_edges_per_episode: int | None = None,
+        embedding_channel_depth: int = DEFAULT_EMBEDDING_CHANNEL_DEPTH,
+        min_episode_interval: timedelta | None = None,
+        embedding_fallback: bool = False,
+        require_source_description: bool = False,
+        default_source_description: str | None = None,
+        single_valued_relations: list[str] | None = None,
+        strict_extraction: bool = False,
+        serialize_group_ingestion: bool = True,
+        entity_embedding_text: EntityEmbeddingText = EntityEmbeddingText.name,
+        symmetric_relations: list[str] | None = None,
+        allow_self_loops: bool = False,
+        allow_cross_group_search: bool = False,
+        detect_episode_language: bool = False,
+        max_traversal_nodes: int = DEFAULT_MAX_TRAVERSAL_NODES,
+        search_timeout: float | None = None,
+        raise_on_search_timeout: bool = False,
+        write_batch_size: int = DEFAULT_WRITE_BATCH_SIZE,
+        attribute_conflict_policy: AttributeConflictPolicy = AttributeConflictPolicy.overwrite,
     ):
         """
         Initialize a Graphiti instance.
@@ -127,13 +330,13 @@ class Graphiti:
             The password for authenticating with the Neo4j database.
         llm_client : LLMClient | None, optional
             An instance of LLMClient for natural language processing tasks.
-            If not provided, a default OpenAIClient will be initialized.
+            If not provided, a default client for llm_provider will be initialized.
         embedder : EmbedderClient | None, optional
             An instance of EmbedderClient for embedding tasks.
-            If not provided, a default OpenAIEmbedder will be initialized.
+            If not provided, a default embedder for embedder_provider will be initialized.
         cross_encoder : CrossEncoderClient | None, optional
             An instance of CrossEncoderClient for reranking tasks.
-            If not provided, a default OpenAIRerankerClient will be initialized.
+            If not provided, a default reranker for reranker_provider will be initialized.
         store_raw_episode_content : bool, optional
             Whether to store the raw content of episodes. Defaults to True.
         graph_driver : GraphDriver | None, optional
@@ -142,6 +345,170 @@ class Graphiti:
         max_coroutines : int | None, optional
             The maximum number of concurrent operations allowed. Overrides SEMAPHORE_LIMIT set in the environment.
             If not set, the Graphiti default is used.
+        allow_episode_reprocessing : bool, optional
+            Whether `reprocess_episodes` may be used. Reprocessing re-runs the full LLM pipeline
+            over every stored episode, so it is disabled by default. Defaults to False.
+        name_normalization : NameNormalization, optional
+            How entity names are normalized before exact-name dedup during bulk ingestion.
+            Aggressive normalization can over-merge distinct entities. Defaults to none.
+        search_cache_ttl : float, optional
+            How long, in seconds, search results are cached. Writes made through this instance
+            invalidate the cached results of the affected groups; the TTL bounds staleness from
+            writes made elsewhere. Set to 0 to disable the cache. Defaults to 10 seconds.
+        max_episode_chars : int | None, optional
+            Episodes longer than this many characters are split into chunks that are extracted
+            separately. The results are merged into a single episode. Defaults to None (no limit).
+        episode_chunk_overlap : int, optional
+            How many characters consecutive chunks share, so that relationships spanning a chunk
+            boundary are still extracted. Must be smaller than max_episode_chars. Defaults to 200.
+        enable_embeddings : bool, optional
+            Whether to embed nodes and edges. When False, ingestion makes no embedding calls and
+            search only uses BM25 and graph traversal. Without embeddings, new facts are not
+            deduplicated or invalidated against existing facts. Defaults to True.
+        episode_type_aliases : dict[str, EpisodeType] | None, optional
+            Additional source names accepted by `add_episode`, mapped to the base episode type
+            they are extracted as, e.g. {'email': EpisodeType.message}. Defaults to None.
+        custom_system_instructions : str | None, optional
+            Domain guidance appended to the system message of every prompt, e.g. "This is medical
+            text; preserve drug dosages". Defaults to None.
+        system_instruction_overrides : dict[str, str] | None, optional
+            Per-step replacements for custom_system_instructions, keyed by prompt type
+            (e.g. 'extract_nodes') or prompt type and version (e.g. 'extract_nodes.extract_text').
+            An empty string disables the instructions for that step. Defaults to None.
+        max_reflexion_rounds : int, optional
+            How many times entity and fact extraction asks the LLM what it missed and re-extracts
+            the missed items. Each round costs extra LLM calls; set to 0 to disable reflexion.
+            Defaults to MAX_REFLEXION_ITERATIONS from the environment, or 1.
+        max_summary_words : int | None, optional
+            Entity and community summaries longer than this many words are truncated on a word
+            boundary, in case the model ignores the length the prompts ask for. None disables the
+            limit. Defaults to 250.
+        auto_merge_threshold : float, optional
+            Extracted entities whose name embedding has a cosine similarity above this to an
+            existing entity are merged into it without asking the LLM; only ambiguous entities
+            go to the dedupe prompt. Requires embeddings. Defaults to 1.0, which never
+            auto-merges.
+        name_similarity_threshold : float, optional
+            Entities whose names have at least this string similarity are merged without asking
+            the LLM, so that typos like "Micorsoft" match "Microsoft". Applies to dedup within a
+            batch and against existing entities. Short names score high easily, so values below
+            0.9 risk over-merging. Defaults to 1.0, which only merges exact matches.
+        name_similarity : StringSimilarity, optional
+            The metric used with name_similarity_threshold. Defaults to Jaro-Winkler.
+        prompt_library : PromptLibraryWrapper | None, optional
+            Prompts used by this instance, e.g. DEFAULT_PROMPT_LIBRARY.with_prompt('extract_edges',
+            'edge', my_edge_prompt) to tune fact extraction for a domain. Defaults to None, which
+            uses the built-in prompts.
+        attribute_validation : AttributeValidationPolicy, optional
+            How extracted attributes are checked against the fields of their entity or edge type
+            before they are saved: kept as returned, coerced to the declared types, or dropped
+            when they don't match. Unknown fields are dropped unless they are kept, and every
+            dropped or coerced field is logged as a warning. Defaults to keep.
+        llm_provider : LLMProvider | str, optional
+            The provider of the default LLM client, used when llm_client is not given, e.g.
+            'anthropic'. API keys are read from the provider's environment variable. Defaults
+            to OpenAI.
+        embedder_provider : EmbedderProvider | str, optional
+            The provider of the default embedder, used when embedder is not given. Defaults to
+            OpenAI.
+        reranker_provider : RerankerProvider | str, optional
+            The provider of the default reranker, used when cross_encoder is not given. Defaults
+            to OpenAI.
+        orphan_cleanup : OrphanPolicy | None, optional
+            If set, `cleanup_orphans` runs with this policy after an episode invalidates facts,
+            deleting or flagging the entities left without connections. Defaults to None.
+        max_operation_retries : int | None, optional
+            The total number of LLM retries allowed across all the calls made by one operation,
+            such as one add_episode. Once they are used up, the next failed call raises
+            RetryBudgetExhaustedError instead of retrying. Defaults to None (no budget).
+        encryption : GroupEncryption | None, optional
+            Keys of the groups whose episode content, summaries and facts are stored encrypted.
+            The keys are attached to the graph driver, so content is encrypted and decrypted for
+            every save and read through it. Defaults to None.
+        max_entities_per_episode : int | None, optional
+            The most entities kept from one episode's extraction, as a guardrail against dense
+            or pathological inputs. The first ones extracted are kept. Defaults to None (no cap).
+        max_edges_per_episode : int | None, optional
+            The most facts kept from one episode's extraction. The facts extracted with the
+            highest confidence are kept. Defaults to None (no cap).
+        embedding_channel_depth : int, optional
+            In add_episode_bulk, episodes are embedded while later ones are still being
+            extracted. This many extracted episodes can wait to be embedded before extraction
+            waits for embedding to catch up. Defaults to 8.
+        min_episode_interval : timedelta | None, optional
+            The least time between the previous episodes given as context to extraction.
+            Episodes closer than this to a more recent one are skipped, so that the window of
+            a bursty source spans a longer stretch of time. Defaults to None.
+        embedding_fallback : bool, optional
+            Whether add_episode still saves an episode's nodes and edges when the embedder is
+            failing, without embeddings. They can be embedded later with backfill_embeddings.
+            Defaults to False, in which case the embedder's error is raised.
+        require_source_description : bool, optional
+            Whether adding an episode with an empty source_description raises
+            MissingSourceDescriptionError, for deployments that need the provenance of every
+            fact. Defaults to False.
+        default_source_description : str | None, optional
+            A template for the source_description of episodes added without one, e.g.
+            'imported from {source} at {time}'. {source} is replaced with the episode type and
+            {time} with the time it was added. Episodes given a default satisfy
+            require_source_description. Defaults to None.
+        single_valued_relations : list[str] | None, optional
+            Fact names, e.g. 'WORKS_AT', that an entity should have at most one valid fact of.
+            find_inconsistencies reports entities that have several. Defaults to None.
+        strict_extraction : bool, optional
+            Whether entity extraction raises ExtractionError when the LLM's response can't be
+            parsed or no entities are extracted from non-empty content, instead of adding the
+            episode with nothing extracted from it. Defaults to False.
+        serialize_group_ingestion : bool, optional
+            Whether concurrent add_episode calls for the same group wait for each other to
+            deduplicate and save their entities, so that an entity mentioned by both is created
+            once. Extracting entities and facts still runs concurrently, as do episodes of
+            different groups. Only disable this if episodes are already added one group at a
+            time. Defaults to True.
+        entity_embedding_text : EntityEmbeddingText, optional
+            The text entity embeddings are computed from: the name alone, the name and summary,
+            or the name, summary and most recent episodes mentioning the entity. Entity search
+            compares queries against these embeddings, so keep the same choice for the lifetime
+            of a graph; entities embedded with another choice must be re-embedded to stay
+            comparable. Defaults to name.
+        symmetric_relations : list[str] | None, optional
+            Fact names, e.g. 'MARRIED_TO', that mean the same in both directions. Facts between
+            the same two entities are deduplicated regardless of direction for these relations,
+            and only in the same direction for all others, so that "Alice MANAGES Bob" is never
+            merged into "Bob MANAGES Alice". Defaults to DEFAULT_SYMMETRIC_RELATIONS.
+        allow_self_loops : bool, optional
+            Whether facts connecting an entity to itself, e.g. "Alice PAYS Alice", are kept.
+            They are usually extraction mistakes, or facts between two mentions that turned out
+            to be the same entity. Defaults to False, which drops them.
+        allow_cross_group_search : bool, optional
+            Whether searches may leave out group_ids and so run over every group in the graph.
+            Defaults to False, where such searches raise CrossGroupSearchError, so that one
+            tenant's data can't leak into another's results because a caller forgot its groups.
+        detect_episode_language : bool, optional
+            Whether episodes added without a language_hint get one detected from the writing
+            system of their content, e.g. "Cyrillic", so that extraction keeps names in their
+            original script. Latin-script content gets no hint. Defaults to False.
+        max_traversal_nodes : int, optional
+            The most nodes or facts a graph traversal such as search_and_expand returns, however
+            large the requested limit. Traversals that reach more are cut short and their
+            results marked truncated. Defaults to DEFAULT_MAX_TRAVERSAL_NODES.
+        search_timeout : float | None, optional
+            The most seconds a search may take, from embedding the query to reranking, unless
+            the search passes its own timeout. Searches still running at the deadline are
+            cancelled and the results gathered so far are returned, marked timed_out. Defaults
+            to None, for no timeout.
+        raise_on_search_timeout : bool, optional
+            Whether a search that hits its timeout raises SearchTimeoutError instead of
+            returning partial results. Defaults to False.
+        write_batch_size : int, optional
+            How many nodes and edges are collected before they are written together in one
+            transaction, where ingestion would otherwise save them one query at a time.
+            Defaults to DEFAULT_WRITE_BATCH_SIZE.
+        attribute_conflict_policy : AttributeConflictPolicy, optional
+            How attribute values extracted from a new episode are merged into an existing
+            entity's: overwritten, kept when the stored value was observed at a later episode
+            time, or overwritten with the replaced values kept in the entity's
+            attribute_metadata, apart from its attributes. Defaults to overwrite.
 
         Returns
         -------
@@ -169,32 +536,178 @@ class Graphiti:
                 raise ValueError("uri must be provided when graph_driver is None")
             self.driver = Neo4jDriver(uri, user, password)
 
+        if max_episode_chars is not None and episode_chunk_overlap >= max_episode_chars:
+            raise ValueError('episode_chunk_overlap must be smaller than max_episode_chars')
+        if max_reflexion_rounds < 0:
+            raise ValueError('max_reflexion_rounds must not be negative')
+        if max_operation_retries is not None and max_operation_retries < 0:
+            raise ValueError('max_operation_retries must not be negative')
+        if not 0.0 <= auto_merge_threshold <= 1.0:
+            raise ValueError('auto_merge_threshold must be between 0 and 1')
+        if not 0.0 <= name_similarity_threshold <= 1.0:
+            raise ValueError('name_similarity_threshold must be between 0 and 1')
+        if max_entities_per_episode is not None and max_entities_per_episode < 1:
+            raise ValueError('max_entities_per_episode must be at least 1')
+        if max_edges_per_episode is not None and max_edges_per_episode < 1:
+            raise ValueError('max_edges_per_episode must be at least 1')
+        if embedding_channel_depth < 1:
+            raise ValueError('embedding_channel_depth must be at least 1')
+        if min_episode_interval is not None and min_episode_interval <= timedelta(0):
+            raise ValueError('min_episode_interval must be positive')
+        if max_traversal_nodes < 1:
+            raise ValueError('max_traversal_nodes must be at least 1')
+        if search_timeout is not None and search_timeout <= 0:
+            raise ValueError('search_timeout must be positive')
+        if write_batch_size < 1:
+            raise ValueError('write_batch_size must be at least 1')
+        if default_source_description is not None:
+            try:
+                default_source_description.format(source='', time='')
+            except (KeyError, IndexError, ValueError) as e:
+                raise ValueError(
+                    'default_source_description may only use the {source} and {time} fields'
+                ) from e
+
         self.database = DEFAULT_DATABASE
         self.store_raw_episode_content = store_raw_episode_content
         self.max_coroutines = max_coroutines
+        self.allow_episode_reprocessing = allow_episode_reprocessing
+        self.name_normalization = name_normalization
+        self.name_similarity_threshold = name_similarity_threshold
+        self.name_similarity = name_similarity
+        self.search_cache = SearchResultCache(ttl=search_cache_ttl)
+        self.max_episode_chars = max_episode_chars
+        self.episode_chunk_overlap = episode_chunk_overlap
+        self.enable_embeddings = enable_embeddings
+        self.max_summary_words = max_summary_words
+        self.episode_type_aliases = dict(episode_type_aliases or {})
+        self.prompt_library = prompt_library
+        self.orphan_cleanup = orphan_cleanup
+        self.max_operation_retries = max_operation_retries
+        self.encryption = encryption
+        if encryption is not None:
+            # Saves and reads made through the driver outside of this instance's methods, e.g.
+            # EntityNode.save, use the keys as well
+            attach_group_encryption(self.driver, encryption)
+        self.embedding_channel_depth = embedding_channel_depth
+        self.min_episode_interval = min_episode_interval
+        self.embedding_fallback = embedding_fallback
+        self.require_source_description = require_source_description
+        self.default_source_description = default_source_description
+        self.single_valued_relations = list(single_valued_relations or [])
+        self.serialize_group_ingestion = serialize_group_ingestion
+        self._group_locks = KeyedLock()
+        self.allow_cross_group_search = allow_cross_group_search
+        self.detect_episode_language = detect_episode_language
+        self.search_timeout = search_timeout
+        self.raise_on_search_timeout = raise_on_search_timeout
+        self.write_batch_size = write_batch_size
+        self.system_instructions = (
+            SystemInstructions(
+                default=custom_system_instructions, overrides=system_instruction_overrides or {}
+            )
+            if custom_system_instructions or system_instruction_overrides
+            else None
+        )
         if llm_client:
             self.llm_client = llm_client
         else:
-            self.llm_client = OpenAIClient()
+            self.llm_client = create_llm_client(llm_provider)
         if embedder:
             self.embedder = embedder
         else:
-            self.embedder = OpenAIEmbedder()
+            self.embedder = create_embedder(embedder_provider)
         if cross_encoder:
             self.cross_encoder = cross_encoder
         else:
-            self.cross_encoder = OpenAIRerankerClient()
+            self.cross_encoder = create_cross_encoder(reranker_provider)
 
         self.clients = GraphitiClients(
             driver=self.driver,
             llm_client=self.llm_client,
             embedder=self.embedder,
             cross_encoder=self.cross_encoder,
+            embeddings_enabled=enable_embeddings,
+            max_reflexion_rounds=max_reflexion_rounds,
+            max_summary_words=max_summary_words,
+            auto_merge_threshold=auto_merge_threshold,
+            name_similarity_threshold=name_similarity_threshold,
+            name_similarity=name_similarity,
+            attribute_validation=attribute_validation,
+            max_entities_per_episode=max_entities_per_episode,
+            max_edges_per_episode=max_edges_per_episode,
+            strict_extraction=strict_extraction,
+            entity_embedding_text=entity_embedding_text,
+            symmetric_relations=(
+                frozenset(name.upper() for name in symmetric_relations)
+                if symmetric_relations is not None
+                else DEFAULT_SYMMETRIC_RELATIONS
+            ),
+            allow_self_loops=allow_self_loops,
+            max_traversal_nodes=max_traversal_nodes,
+            attribute_conflict_policy=attribute_conflict_policy,
         )
 
         # Capture telemetry event
         self._capture_initialization_telemetry()
 
+    @property
+    def _ingest_embedder(self) -> EmbedderClient | None:
+        # Embedder used to fill in missing embeddings on write, if embeddings are enabled
+        return self.embedder if self.enable_embeddings else None
+
+    async def _episode_ingest_clients(
+        self, episode: EpisodicNode
+    ) -> tuple[GraphitiClients, EmbedderClient | None]:
+        """
+        Returns the clients and write embedder to ingest an episode with. With embedding_fallback,
+        the episode is embedded up front; if that fails, the episode is ingested without
+        embeddings instead of failing.
+        """
+        if not (self.enable_embeddings and self.embedding_fallback and episode.content):
+            return self.clients, self._ingest_embedder
+
+        try:
+            await episode.generate_content_embedding(self.embedder)
+        except Exception as e:
+            logger.warning(
+                f'Embedding failed, saving episode {episode.uuid} without embeddings. '
+                f'Run backfill_embeddings once the embedder recovers: {e}'
+            )
+            return self.clients.model_copy(update={'embeddings_enabled': False}), None
+
+        return self.clients, self._ingest_embedder
+
+    def _batch_writer(self) -> BatchWriter:
+        return BatchWriter(self.driver, self._ingest_embedder, self.write_batch_size)
+
+    def _group_ingest_lock(self, group_id: str) -> AbstractAsyncContextManager[None]:
+        if not self.serialize_group_ingestion:
+            return nullcontext()
+        return self._group_locks.hold(group_id)
+
+    def _episode_language(self, language_hint: str | None, content: str) -> str | None:
+        if language_hint is None and self.detect_episode_language:
+            return detect_script(content)
+        return language_hint
+
+    def _check_search_groups(self, group_ids: list[str] | None):
+        # search() widens a list of only empty group ids, such as [''], to every group
+        if not any(group_ids or []) and not self.allow_cross_group_search:
+            raise CrossGroupSearchError()
+
+    def _source_description(
+        self, episode_name: str, source_description: str, source: EpisodeType, now: datetime
+    ) -> str:
+        # Applies the default template to a missing source description, or rejects it
+        if source_description.strip():
+            return source_description
+        if self.default_source_description is not None:
+            return self.default_source_description.format(source=source.value, time=now.isoformat())
+        if self.require_source_description:
+            raise MissingSourceDescriptionError(episode_name)
+        return source_description
+
     def _capture_initialization_telemetry(self):
         """Capture telemetry event for Graphiti initialization."""
         try:
@@ -277,8 +790,59 @@ class Graphiti:
             finally:
                 graphiti.close()
         """
+        observer = getattr(self.llm_client, 'observer', None)
+        if observer is not None:
+            await asyncio.to_thread(observer.close)
         await self.driver.close()
 
+    async def wait_for_database(self, retry_seconds: float = DEFAULT_CONNECT_RETRY_SECONDS):
+        """
+        Wait until the graph database accepts connections.
+
+        Call this on startup when the database may still be starting, for example when both are
+        brought up together by docker compose. Connection attempts are retried with backoff for
+        up to retry_seconds before the last error is raised.
+        """
+        await wait_for_database(self.driver, retry_seconds)
+
+    async def check_providers(self, embedding_dim: int | None = None):
+        """
+        Check that the LLM, embedder, and reranker can be reached, so that a misconfigured API key
+        or model fails on startup instead of in the first add_episode.
+
+        Each provider is sent one short request. The embedder must return embeddings of
+        embedding_dim dimensions, which defaults to the dimension in the embedder's config, if
+        it has one. The embedder is skipped when embeddings are disabled. Raises
+        ProviderCheckError naming the first provider that fails.
+        """
+        try:
+            await self.llm_client.generate_response(
+                [Message(role='user', content='Respond with an empty JSON object.')]
+            )
+        except Exception as e:
+            raise ProviderCheckError('LLM', repr(e)) from e
+
+        if self.enable_embeddings:
+            try:
+                embedding = await self.embedder.create(input_data=[PROVIDER_CHECK_TEXT])
+            except Exception as e:
+                raise ProviderCheckError('embedder', repr(e)) from e
+            expected_dim = (
+                embedding_dim
+                if embedding_dim is not None
+                else getattr(getattr(self.embedder, 'config', None), 'embedding_dim', None)
+            )
+            if expected_dim is not None and len(embedding) != expected_dim:
+                raise ProviderCheckError(
+                    'embedder',
+                    f'returned {len(embedding)}-dimensional embeddings, expected {expected_dim}',
+                )
+
+        try:
+            await self.cross_encoder.rank(PROVIDER_CHECK_TEXT, [PROVIDER_CHECK_TEXT])
+        except Exception as e:
+            raise ProviderCheckError('reranker', repr(e)) from e
+
     async def build_indices_and_constraints(self, delete_existing: bool = False):
         """
         Build indices and constraints in the Neo4j database.
@@ -314,12 +878,31 @@ class Graphiti:
         """
         await build_indices_and_constraints(self.driver, delete_existing)
 
+    async def clear(self, group_ids: list[str] | None = None):
+        """
+        Delete the given groups from the graph, or the whole graph if group_ids is None, and
+        invalidate their cached search results.
+
+        Parameters
+        ----------
+        group_ids : list[str] | None, optional
+            The groups to delete. Defaults to None, which deletes everything.
+        """
+        if group_ids is not None:
+            for group_id in group_ids:
+                validate_group_id(group_id)
+
+        await clear_data(self.driver, group_ids)
+        self.search_cache.invalidate(group_ids)
+
+    @with_operation_settings
     async def retrieve_episodes(
         self,
         reference_time: datetime,
         last_n: int = EPISODE_WINDOW_LEN,
         group_ids: list[str] | None = None,
         source: EpisodeType | None = None,
+        min_interval: timedelta | None = None,
     ) -> list[EpisodicNode]:
         """
         Retrieve the last n episodic nodes from the graph.
@@ -335,6 +918,9 @@ class Graphiti:
             The number of episodes to retrieve. Defaults to EPISODE_WINDOW_LEN.
         group_ids : list[str | None], optional
             The group ids to return data from.
+        min_interval : timedelta | None, optional
+            The least time between two retrieved episodes; episodes closer than this to a more
+            recent retrieved one are skipped. Defaults to None.
 
         Returns
         -------
@@ -346,15 +932,18 @@ class Graphiti:
         The actual retrieval is performed by the `retrieve_episodes` function
         from the `graphiti_core.utils` module.
         """
-        return await retrieve_episodes(self.driver, reference_time, last_n, group_ids, source)
+        return await retrieve_episodes(
+            self.driver, reference_time, last_n, group_ids, source, min_interval
+        )
 
+    @with_operation_settings
     async def add_episode(
         self,
         name: str,
         episode_body: str,
         source_description: str,
         reference_time: datetime,
-        source: EpisodeType = EpisodeType.message,
+        source: EpisodeType | str = EpisodeType.message,
         group_id: str = '',
         uuid: str | None = None,
         update_communities: bool = False,
@@ -363,6 +952,9 @@ class Graphiti:
         previous_episode_uuids: list[str] | None = None,
         edge_types: dict[str, BaseModel] | None = None,
         edge_type_map: dict[tuple[str, str], list[str]] | None = None,
+        store_raw: bool | None = None,
+        model_override: str | None = None,
+        language_hint: str | None = None,
     ) -> AddEpisodeResults:
         """
         Process an episode and update the graph.
@@ -380,8 +972,9 @@ class Graphiti:
             A description of the episode's source.
         reference_time : datetime
             The reference time for the episode.
-        source : EpisodeType, optional
-            The type of the episode. Defaults to EpisodeType.message.
+        source : EpisodeType | str, optional
+            The type of the episode, or the name of a base type or registered alias. Raises
+            InvalidEpisodeTypeError for unknown names. Defaults to EpisodeType.message.
         group_id : str | None
             An id for the graph partition the episode is a part of.
         uuid : str | None
@@ -397,6 +990,17 @@ class Graphiti:
         previous_episode_uuids : list[str] | None
             Optional.  list of episode uuids to use as the previous episodes. If this is not provided,
             the most recent episodes by created_at date will be used.
+        store_raw : bool | None
+            Optional. Whether to keep the episode's raw content in the graph. Overrides
+            store_raw_episode_content for this episode when set.
+        model_override : str | None
+            Optional. The model every LLM call made while adding this episode is sent to, e.g. a
+            more capable model for a tricky document, instead of the LLM client's configured
+            models.
+        language_hint : str | None
+            Optional. The language of the episode content, e.g. "Japanese". Extraction is told
+            to keep names as written in it rather than translating or romanizing them, and it is
+            stored as the episode's language.
 
         Returns
         -------
@@ -419,13 +1023,16 @@ class Graphiti:
                 background_tasks.add_task(graphiti.add_episode, **episode_data.dict())
                 return {"message": "Episode processing started"}
         """
-        try:
+        with use_model_override(model_override):
             start = time()
             now = utc_now()
 
+            source = EpisodeType.resolve(source, self.episode_type_aliases)
             validate_entity_types(entity_types)
             validate_excluded_entity_types(excluded_entity_types, entity_types)
             validate_group_id(group_id)
+            if uuid is None:
+                source_description = self._source_description(name, source_description, source, now)
 
             previous_episodes = (
                 await self.retrieve_episodes(
@@ -433,6 +1040,7 @@ class Graphiti:
                     last_n=RELEVANT_SCHEMA_LIMIT,
                     group_ids=[group_id],
                     source=source,
+                    min_interval=self.min_episode_interval,
                 )
                 if previous_episode_uuids is None
                 else await EpisodicNode.get_by_uuids(self.driver, previous_episode_uuids)
@@ -452,6 +1060,9 @@ class Graphiti:
                     valid_at=reference_time,
                 )
             )
+            language = self._episode_language(language_hint, episode.content)
+            if language is not None:
+                episode.language = language
 
             # Create default edge type map
             edge_type_map_default = (
@@ -460,70 +1071,111 @@ class Graphiti:
                 else {('Entity', 'Entity'): []}
             )
 
-            # Extract entities as nodes
+            clients, embedder = await self._episode_ingest_clients(episode)
 
-            extracted_nodes = await extract_nodes(
-                self.clients, episode, previous_episodes, entity_types, excluded_entity_types
+            chunked = (
+                self.max_episode_chars is not None and len(episode.content) > self.max_episode_chars
             )
-
-            # Extract edges and resolve nodes
-            (nodes, uuid_map, node_duplicates), extracted_edges = await semaphore_gather(
-                resolve_extracted_nodes(
-                    self.clients,
-                    extracted_nodes,
+            if chunked:
+                # Extract nodes and edges from each chunk, then resolve them as a whole
+                extracted_nodes, extracted_edges = await self._extract_from_chunks(
+                    clients,
                     episode,
                     previous_episodes,
                     entity_types,
-                ),
-                extract_edges(
-                    self.clients,
+                    excluded_entity_types,
+                    edge_type_map or edge_type_map_default,
+                    edge_types,
+                )
+            else:
+                # Extract entities as nodes, then the facts between them
+                extracted_nodes = await extract_nodes(
+                    clients, episode, previous_episodes, entity_types, excluded_entity_types
+                )
+                extracted_edges = await extract_edges(
+                    clients,
                     episode,
                     extracted_nodes,
                     previous_episodes,
                     edge_type_map or edge_type_map_default,
                     group_id,
                     edge_types,
-                ),
-                max_coroutines=self.max_coroutines,
-            )
-
-            edges = resolve_edge_pointers(extracted_edges, uuid_map)
+                )
 
-            (resolved_edges, invalidated_edges), hydrated_nodes = await semaphore_gather(
-                resolve_extracted_edges(
-                    self.clients,
-                    edges,
+            # Episodes of the same group are deduplicated and saved one at a time, so that two
+            # episodes mentioning the same new entity don't both create it
+            async with self._group_ingest_lock(group_id):
+                nodes, uuid_map, node_duplicates = await resolve_extracted_nodes(
+                    clients,
+                    extracted_nodes,
                     episode,
-                    nodes,
-                    edge_types or {},
-                    edge_type_map or edge_type_map_default,
-                ),
-                extract_attributes_from_nodes(
-                    self.clients, nodes, episode, previous_episodes, entity_types
-                ),
-                max_coroutines=self.max_coroutines,
-            )
+                    previous_episodes,
+                    entity_types,
+                )
 
-            duplicate_of_edges = build_duplicate_of_edges(episode, now, node_duplicates)
+                # Resolving entities can turn a fact into a self-loop or a repeat of another
+                edges = filter_extracted_edges(
+                    resolve_edge_pointers(extracted_edges, uuid_map), clients.allow_self_loops
+                )
+
+                (resolved_edges, invalidated_edges), hydrated_nodes = await semaphore_gather(
+                    resolve_extracted_edges(
+                        clients,
+                        edges,
+                        episode,
+                        nodes,
+                        edge_types or {},
+                        edge_type_map or edge_type_map_default,
+                    ),
+                    extract_attributes_from_nodes(
+                        clients, nodes, episode, previous_episodes, entity_types
+                    ),
+                    max_coroutines=self.max_coroutines,
+                )
 
-            entity_edges = resolved_edges + invalidated_edges + duplicate_of_edges
+                node_counts = IngestCounts.from_resolutions(
+                    [(node.uuid, uuid_map.get(node.uuid)) for node in extracted_nodes]
+                )
+                edge_counts = IngestCounts.from_resolutions(
+                    [(edge.uuid, resolved.uuid) for edge, resolved in zip(edges, resolved_edges)]
+                )
 
-            episodic_edges = build_episodic_edges(nodes, episode, now)
+                duplicate_of_edges = build_duplicate_of_edges(episode, now, node_duplicates)
 
-            episode.entity_edges = [edge.uuid for edge in entity_edges]
+                entity_edges = resolved_edges + invalidated_edges + duplicate_of_edges
 
-            if not self.store_raw_episode_content:
-                episode.content = ''
+                episodic_edges = build_episodic_edges(nodes, episode, now)
 
-            await add_nodes_and_edges_bulk(
-                self.driver, [episode], episodic_edges, hydrated_nodes, entity_edges, self.embedder
-            )
+                episode.entity_edges = [edge.uuid for edge in entity_edges]
+
+                if not (self.store_raw_episode_content if store_raw is None else store_raw):
+                    episode.content = ''
+                    episode.content_embedding = None
+
+                await add_nodes_and_edges_bulk(
+                    self.driver,
+                    [episode],
+                    episodic_edges,
+                    hydrated_nodes,
+                    entity_edges,
+                    embedder,
+                )
+                self.search_cache.invalidate([group_id])
+
+            if self.orphan_cleanup is not None and invalidated_edges:
+                await self.cleanup_orphans(group_id, self.orphan_cleanup)
 
             # Update any communities
             if update_communities:
                 await semaphore_gather(
                     *[
-                        update_community(self.driver, self.llm_client, self.embedder, node)
+                        update_community(
+                            self.driver,
+                            self.llm_client,
+                            embedder,
+                            node,
+                            self.max_summary_words,
+                        )
                         for node in nodes
                     ],
                     max_coroutines=self.max_coroutines,
@@ -531,13 +1183,310 @@ class Graphiti:
             end = time()
             logger.info(f'Completed add_episode in {(end - start) * 1000} ms')
 
-            return AddEpisodeResults(episode=episode, nodes=nodes, edges=entity_edges)
+            logger.debug(
+                f'Episode {episode.uuid} created {node_counts.created} and merged '
+                f'{node_counts.merged} entities, created {edge_counts.created} and merged '
+                f'{edge_counts.merged} facts'
+            )
 
-        except Exception as e:
-            raise e
+            return AddEpisodeResults(
+                episode=episode,
+                nodes=nodes,
+                edges=entity_edges,
+                node_counts=node_counts,
+                edge_counts=edge_counts,
+            )
+
+    @with_operation_settings
+    async def reprocess_episodes(
+        self,
+        group_id: str,
+        since: datetime | None = None,
+        entity_types: dict[str, BaseModel] | None = None,
+        excluded_entity_types: list[str] | None = None,
+        edge_types: dict[str, BaseModel] | None = None,
+        edge_type_map: dict[tuple[str, str], list[str]] | None = None,
+    ) -> list[AddEpisodeResults]:
+        """
+        Re-run extraction over stored episodes and reconcile the graph with the new results.
+
+        This is intended for use after changing extraction prompts or entity/edge types, so that
+        an existing graph can be rebuilt from the raw episode content it already holds.
+
+        Parameters
+        ----------
+        group_id : str
+            The graph partition whose episodes should be reprocessed.
+        since : datetime | None, optional
+            Only reprocess episodes whose valid_at is at or after this time. If not provided,
+            every episode in the group is reprocessed.
+        entity_types, excluded_entity_types, edge_types, edge_type_map
+            Optional. Passed through to `add_episode` for each reprocessed episode.
+
+        Returns
+        -------
+        list[AddEpisodeResults]
+            The results of each reprocessed episode, in chronological order.
+
+        Notes
+        -----
+        Episodes are reprocessed sequentially in valid_at order. New nodes and facts are
+        upserted as in `add_episode`. Facts that were created by an episode but are no longer
+        extracted from it are expired rather than deleted, so their history is preserved.
+
+        This requires the instance to be created with `allow_episode_reprocessing=True` and
+        `store_raw_episode_content=True`. Episodes stored without content are skipped.
+        """
+        if not self.allow_episode_reprocessing:
+            raise ReprocessingDisabledError('allow_episode_reprocessing is not enabled')
+        if not self.store_raw_episode_content:
+            raise ReprocessingDisabledError('raw episode content is not being stored')
+
+        validate_group_id(group_id)
+
+        episodes = await EpisodicNode.get_by_group_ids(self.driver, [group_id])
+        if since is not None:
+            episodes = [episode for episode in episodes if episode.valid_at >= since]
+        episodes.sort(key=lambda episode: (episode.valid_at, episode.uuid))
+
+        results: list[AddEpisodeResults] = []
+        for episode in episodes:
+            if not episode.content:
+                logger.warning(f'Skipping reprocessing of episode {episode.uuid}: no stored content')
+                continue
+
+            results.append(
+                await self._reextract_episode(
+                    episode,
+                    set(episode.entity_edges),
+                    entity_types=entity_types,
+                    excluded_entity_types=excluded_entity_types,
+                    edge_types=edge_types,
+                    edge_type_map=edge_type_map,
+                )
+            )
+
+        return results
+
+    @with_operation_settings
+    async def upsert_episode(
+        self,
+        external_id: str,
+        name: str,
+        episode_body: str,
+        source_description: str,
+        reference_time: datetime,
+        source: EpisodeType | str = EpisodeType.message,
+        group_id: str = '',
+        update_communities: bool = False,
+        entity_types: dict[str, BaseModel] | None = None,
+        excluded_entity_types: list[str] | None = None,
+        edge_types: dict[str, BaseModel] | None = None,
+        edge_type_map: dict[tuple[str, str], list[str]] | None = None,
+    ) -> AddEpisodeResults:
+        """
+        Add an episode, or replace the content of the episode with the same external id.
+
+        This is intended for streaming sources that send corrections to an episode that has
+        already been ingested.
+
+        Parameters
+        ----------
+        external_id : str
+            The id of the episode in its source system. Episodes are matched on this id within
+            the group.
+        name, episode_body, source_description, reference_time, source, group_id
+            As in `add_episode`. When an episode with this external id exists, its name,
+            content, source, source description and valid_at are overwritten with these values.
+        update_communities, entity_types, excluded_entity_types, edge_types, edge_type_map
+            Optional. Passed through to `add_episode`.
+
+        Returns
+        -------
+        AddEpisodeResults
+            The upserted episode with the nodes and edges extracted from its new content.
+
+        Notes
+        -----
+        The episode is re-extracted in place and keeps its uuid. New nodes and facts are added as
+        in `add_episode`. Facts that the episode introduced but that are no longer extracted
+        from the corrected content are expired rather than deleted, so their history is
+        preserved.
+        """
+        source = EpisodeType.resolve(source, self.episode_type_aliases)
+        validate_group_id(group_id)
+        source_description = self._source_description(name, source_description, source, utc_now())
+
+        episode = await EpisodicNode.get_by_external_id(self.driver, group_id, external_id)
+        previous_edge_uuids: set[str] = set()
+        if episode is None:
+            episode = EpisodicNode(
+                name=name,
+                group_id=group_id,
+                labels=[],
+                source=source,
+                content=episode_body,
+                source_description=source_description,
+                created_at=utc_now(),
+                valid_at=reference_time,
+                external_id=external_id,
+            )
+        else:
+            logger.debug(f'Upserting episode {episode.uuid} with external id {external_id}')
+            previous_edge_uuids = set(episode.entity_edges)
+            episode.name = name
+            episode.content = episode_body
+            # The content changed, so its embedding is regenerated on save
+            episode.content_embedding = None
+            episode.source = source
+            episode.source_description = source_description
+            episode.valid_at = reference_time
+
+        # add_episode re-reads the episode by uuid, so the new content is saved first
+        await episode.save(self.driver)
+
+        return await self._reextract_episode(
+            episode,
+            previous_edge_uuids,
+            update_communities=update_communities,
+            entity_types=entity_types,
+            excluded_entity_types=excluded_entity_types,
+            edge_types=edge_types,
+            edge_type_map=edge_type_map,
+        )
+
+    async def _reextract_episode(
+        self,
+        episode: EpisodicNode,
+        previous_edge_uuids: set[str],
+        update_communities: bool = False,
+        entity_types: dict[str, BaseModel] | None = None,
+        excluded_entity_types: list[str] | None = None,
+        edge_types: dict[str, BaseModel] | None = None,
+        edge_type_map: dict[tuple[str, str], list[str]] | None = None,
+    ) -> AddEpisodeResults:
+        # MENTIONS edges are rebuilt from the new extraction
+        await self.driver.execute_query(
+            """
+            MATCH (e:Episodic {uuid: $uuid})-[r:MENTIONS]->(:Entity)
+            DELETE r
+            """,
+            uuid=episode.uuid,
+            database_=DEFAULT_DATABASE,
+        )
+
+        result = await self.add_episode(
+            name=episode.name,
+            episode_body=episode.content,
+            source_description=episode.source_description,
+            reference_time=episode.valid_at,
+            source=episode.source,
+            group_id=episode.group_id,
+            uuid=episode.uuid,
+            update_communities=update_communities,
+            entity_types=entity_types,
+            excluded_entity_types=excluded_entity_types,
+            edge_types=edge_types,
+            edge_type_map=edge_type_map,
+        )
+
+        stale_edge_uuids = previous_edge_uuids - {edge.uuid for edge in result.edges}
+        if stale_edge_uuids:
+            await self._expire_episode_edges(episode.uuid, list(stale_edge_uuids))
+
+        return result
+
+    async def _expire_episode_edges(self, episode_uuid: str, edge_uuids: list[str]):
+        # Only expire edges that this episode introduced; facts first seen elsewhere are kept
+        now = utc_now()
+        stale_edges = await EntityEdge.get_by_uuids(self.driver, edge_uuids)
+        expired_edges: list[EntityEdge] = []
+        for edge in stale_edges:
+            if edge.expired_at is not None or not edge.episodes:
+                continue
+            if edge.episodes[0] != episode_uuid:
+                continue
+            edge.expired_at = now
+            expired_edges.append(edge)
+
+        await semaphore_gather(
+            *[edge.save(self.driver) for edge in expired_edges],
+            max_coroutines=self.max_coroutines,
+        )
+        self.search_cache.invalidate(list({edge.group_id for edge in expired_edges}))
+
+    async def _extract_from_chunks(
+        self,
+        clients: GraphitiClients,
+        episode: EpisodicNode,
+        previous_episodes: list[EpisodicNode],
+        entity_types: dict[str, BaseModel] | None,
+        excluded_entity_types: list[str] | None,
+        edge_type_map: dict[tuple[str, str], list[str]],
+        edge_types: dict[str, BaseModel] | None,
+    ) -> tuple[list[EntityNode], list[EntityEdge]]:
+        """
+        Extract nodes and edges from an episode that exceeds max_episode_chars.
+
+        The content is split into overlapping chunks that are extracted independently. Nodes with
+        matching names are merged across chunks and duplicate facts are dropped.
+        """
+        assert self.max_episode_chars is not None
+        chunks = chunk_text(episode.content, self.max_episode_chars, self.episode_chunk_overlap)
+        logger.debug(f'Extracting episode {episode.uuid} in {len(chunks)} chunks')
+
+        async def extract_chunk(chunk: str) -> tuple[list[EntityNode], list[EntityEdge]]:
+            chunk_episode = episode.model_copy(update={'content': chunk})
+            try:
+                chunk_nodes = await extract_nodes(
+                    clients,
+                    chunk_episode,
+                    previous_episodes,
+                    entity_types,
+                    excluded_entity_types,
+                )
+                chunk_edges = await extract_edges(
+                    clients,
+                    chunk_episode,
+                    chunk_nodes,
+                    previous_episodes,
+                    edge_type_map,
+                    episode.group_id,
+                    edge_types,
+                )
+            except openai.BadRequestError as e:
+                if e.code == 'context_length_exceeded':
+                    raise EpisodeChunkTooLongError(self.max_episode_chars) from e
+                raise
+            return chunk_nodes, chunk_edges
+
+        chunk_results = await semaphore_gather(
+            *[extract_chunk(chunk) for chunk in chunks], max_coroutines=self.max_coroutines
+        )
+
+        nodes, uuid_map = node_name_match(
+            [node for chunk_nodes, _ in chunk_results for node in chunk_nodes],
+            self.name_normalization,
+            self.name_similarity_threshold,
+            self.name_similarity,
+        )
+        edges = resolve_edge_pointers(
+            [edge for _, chunk_edges in chunk_results for edge in chunk_edges], uuid_map
+        )
+
+        # Overlapping chunks can produce the same fact twice
+        unique_edges: dict[tuple[str, str, str], EntityEdge] = {}
+        for edge in edges:
+            key = (edge.source_node_uuid, edge.target_node_uuid, normalize_name(edge.fact))
+            unique_edges.setdefault(key, edge)
+
+        return nodes, list(unique_edges.values())
 
     #### WIP: USE AT YOUR OWN RISK ####
-    async def add_episode_bulk(self, bulk_episodes: list[RawEpisode], group_id: str = ''):
+    @with_operation_settings
+    async def add_episode_bulk(
+        self, bulk_episodes: list[RawEpisode], group_id: str = ''
+    ) -> AddBulkEpisodeResults:
         """
         Process multiple episodes in bulk and update the graph.
 
@@ -553,7 +1502,10 @@ class Graphiti:
 
         Returns
         -------
-        None
+        AddBulkEpisodeResults
+            One result per added episode, in input order, and one error per episode that failed.
+            Each result holds only the nodes and edges extracted from that episode, resolved to
+            their deduplicated versions.
 
         Notes
         -----
@@ -573,6 +1525,10 @@ class Graphiti:
         Important: This method does not perform edge invalidation or date extraction steps.
         If these operations are required, use the `add_episode` method instead for each
         individual episode.
+
+        An episode whose extraction fails is removed again and reported in `errors`, while the
+        rest of the batch is still added. Failures in the batch-wide steps that follow, such as
+        deduplication, still raise.
         """
         try:
             start = time()
@@ -586,84 +1542,140 @@ class Graphiti:
                     labels=[],
                     source=episode.source,
                     content=episode.content,
-                    source_description=episode.source_description,
+                    source_description=self._source_description(
+                        episode.name, episode.source_description, episode.source, now
+                    ),
                     group_id=group_id,
                     created_at=now,
                     valid_at=episode.reference_time,
+                    language=self._episode_language(episode.language, episode.content),
                 )
                 for episode in bulk_episodes
             ]
 
+            if self.enable_embeddings:
+                await semaphore_gather(
+                    *[
+                        episode.generate_content_embedding(self.embedder)
+                        for episode in episodes
+                        if episode.content
+                    ],
+                    max_coroutines=self.max_coroutines,
+                )
+
             # Save all the episodes
-            await semaphore_gather(
-                *[episode.save(self.driver) for episode in episodes],
-                max_coroutines=self.max_coroutines,
-            )
+            writer = self._batch_writer()
+            await writer.add(*episodes)
+            await writer.flush()
 
             # Get previous episode context for each episode
-            episode_pairs = await retrieve_previous_episodes_bulk(self.driver, episodes)
+            episode_pairs = await retrieve_previous_episodes_bulk(
+                self.driver, episodes, self.max_coroutines, self.min_episode_interval
+            )
 
             # Extract all nodes and edges
             (
                 extracted_nodes,
                 extracted_edges,
                 episodic_edges,
-            ) = await extract_nodes_and_edges_bulk(self.clients, episode_pairs, None, None)
+                failures,
+            ) = await extract_nodes_and_edges_bulk(
+                self.clients,
+                episode_pairs,
+                None,
+                None,
+                self.max_coroutines,
+                embedder=self._ingest_embedder,
+                embedding_channel_depth=self.embedding_channel_depth,
+            )
 
-            # Generate embeddings
+            # Failed episodes were already saved to give the others context, so they're removed
             await semaphore_gather(
-                *[node.generate_name_embedding(self.embedder) for node in extracted_nodes],
-                *[edge.generate_embedding(self.embedder) for edge in extracted_edges],
+                *[episode.delete(self.driver) for episode in episodes if episode.uuid in failures],
                 max_coroutines=self.max_coroutines,
             )
 
-            # Dedupe extracted nodes, compress extracted edges
+            # Nodes and edges were embedded while extracting. Edge dates don't depend on node
+            # dedupe, so they're extracted meanwhile
             (nodes, uuid_map), extracted_edges_timestamped = await semaphore_gather(
-                dedupe_nodes_bulk(self.driver, self.llm_client, extracted_nodes),
+                dedupe_nodes_bulk(
+                    self.driver,
+                    self.llm_client,
+                    extracted_nodes,
+                    self.name_normalization,
+                    name_similarity_threshold=self.name_similarity_threshold,
+                    name_similarity=self.name_similarity,
+                ),
                 extract_edge_dates_bulk(self.llm_client, extracted_edges, episode_pairs),
                 max_coroutines=self.max_coroutines,
             )
 
             # save nodes to KG
-            await semaphore_gather(
-                *[node.save(self.driver) for node in nodes],
-                max_coroutines=self.max_coroutines,
-            )
+            await writer.add(*nodes)
 
             # re-map edge pointers so that they don't point to discard dupe nodes
-            extracted_edges_with_resolved_pointers: list[EntityEdge] = resolve_edge_pointers(
-                extracted_edges_timestamped, uuid_map
+            extracted_edges_with_resolved_pointers: list[EntityEdge] = filter_extracted_edges(
+                resolve_edge_pointers(extracted_edges_timestamped, uuid_map),
+                self.clients.allow_self_loops,
             )
             episodic_edges_with_resolved_pointers: list[EpisodicEdge] = resolve_edge_pointers(
                 episodic_edges, uuid_map
             )
 
             # save episodic edges to KG
-            await semaphore_gather(
-                *[edge.save(self.driver) for edge in episodic_edges_with_resolved_pointers],
-                max_coroutines=self.max_coroutines,
-            )
+            await writer.add(*episodic_edges_with_resolved_pointers)
 
             # Dedupe extracted edges
             edges = await dedupe_edges_bulk(
-                self.driver, self.llm_client, extracted_edges_with_resolved_pointers
+                self.driver,
+                self.llm_client,
+                extracted_edges_with_resolved_pointers,
+                self.clients.symmetric_relations,
             )
             logger.debug(f'extracted edge length: {len(edges)}')
 
             # invalidate edges
 
             # save edges to KG
-            await semaphore_gather(
-                *[edge.save(self.driver) for edge in edges],
-                max_coroutines=self.max_coroutines,
-            )
+            await writer.add(*edges)
+            await writer.flush()
+            self.search_cache.invalidate([group_id])
+
+            # Attribute the resolved nodes and edges back to the episode they were extracted from
+            nodes_by_uuid = {node.uuid: node for node in nodes}
+            results: list[AddEpisodeResults] = []
+            errors: list[BulkEpisodeError] = []
+            for i, episode in enumerate(episodes):
+                if episode.uuid in failures:
+                    error = repr(failures[episode.uuid])
+                    errors.append(BulkEpisodeError(index=i, name=episode.name, error=error))
+                    continue
+                episode_node_uuids = dict.fromkeys(
+                    edge.target_node_uuid
+                    for edge in episodic_edges_with_resolved_pointers
+                    if edge.source_node_uuid == episode.uuid
+                )
+                results.append(
+                    AddEpisodeResults(
+                        episode=episode,
+                        nodes=[
+                            nodes_by_uuid[uuid]
+                            for uuid in episode_node_uuids
+                            if uuid in nodes_by_uuid
+                        ],
+                        edges=[edge for edge in edges if episode.uuid in edge.episodes],
+                    )
+                )
 
             end = time()
             logger.info(f'Completed add_episode_bulk in {(end - start) * 1000} ms')
 
+            return AddBulkEpisodeResults(results=results, errors=errors)
+
         except Exception as e:
             raise e
 
+    @with_operation_settings
     async def build_communities(self, group_ids: list[str] | None = None) -> list[CommunityNode]:
         """
         Use a community clustering algorithm to find communities of nodes. Create community nodes summarising
@@ -676,13 +1688,14 @@ class Graphiti:
         await remove_communities(self.driver)
 
         community_nodes, community_edges = await build_communities(
-            self.driver, self.llm_client, group_ids
+            self.driver, self.llm_client, group_ids, self.max_summary_words
         )
 
-        await semaphore_gather(
-            *[node.generate_name_embedding(self.embedder) for node in community_nodes],
-            max_coroutines=self.max_coroutines,
-        )
+        if self.enable_embeddings:
+            await semaphore_gather(
+                *[node.generate_name_embedding(self.embedder) for node in community_nodes],
+                max_coroutines=self.max_coroutines,
+            )
 
         await semaphore_gather(
             *[node.save(self.driver) for node in community_nodes],
@@ -692,9 +1705,11 @@ class Graphiti:
             *[edge.save(self.driver) for edge in community_edges],
             max_coroutines=self.max_coroutines,
         )
+        self.search_cache.invalidate(group_ids)
 
         return community_nodes
 
+    @with_operation_settings
     async def search(
         self,
         query: str,
@@ -702,6 +1717,8 @@ class Graphiti:
         group_ids: list[str] | None = None,
         num_results=DEFAULT_SEARCH_LIMIT,
         search_filter: SearchFilters | None = None,
+        model_override: str | None = None,
+        timeout: float | None = None,
     ) -> list[EntityEdge]:
         """
         Perform a hybrid search on the knowledge graph.
@@ -719,9 +1736,15 @@ class Graphiti:
         center_node_uuid: str, optional
             Facts will be reranked based on proximity to this node
         group_ids : list[str | None] | None, optional
-            The graph partitions to return data from.
+            The graph partitions to return data from. Required unless the instance was created
+            with allow_cross_group_search=True.
         num_results : int, optional
             The maximum number of results to return. Defaults to 10.
+        model_override : str | None, optional
+            The model any LLM call made by this search is sent to, instead of the LLM client's
+            configured models.
+        timeout : float | None, optional
+            The most seconds this search may take. Defaults to the instance's search_timeout.
 
         Returns
         -------
@@ -736,21 +1759,22 @@ class Graphiti:
         The search is performed using the current date and time as the reference
         point for temporal relevance.
         """
-        search_config = (
-            EDGE_HYBRID_SEARCH_RRF if center_node_uuid is None else EDGE_HYBRID_SEARCH_NODE_DISTANCE
+        search_config = SearchConfig.facts_only(
+            limit=num_results,
+            reranker=EdgeReranker.rrf if center_node_uuid is None else EdgeReranker.node_distance,
         )
-        search_config.limit = num_results
 
-        edges = (
-            await search(
-                self.clients,
-                query,
-                group_ids,
-                search_config,
-                search_filter if search_filter is not None else SearchFilters(),
-                center_node_uuid,
-            )
-        ).edges
+        with use_model_override(model_override):
+            edges = (
+                await self._cached_search(
+                    query,
+                    search_config,
+                    group_ids,
+                    search_filter if search_filter is not None else SearchFilters(),
+                    center_node_uuid,
+                    timeout=timeout,
+                )
+            ).edges
 
         return edges
 
@@ -768,6 +1792,7 @@ class Graphiti:
             query, config, group_ids, center_node_uuid, bfs_origin_node_uuids, search_filter
         )
 
+    @with_operation_settings
     async def search_(
         self,
         query: str,
@@ -776,24 +1801,296 @@ class Graphiti:
         center_node_uuid: str | None = None,
         bfs_origin_node_uuids: list[str] | None = None,
         search_filter: SearchFilters | None = None,
+        model_override: str | None = None,
+        timeout: float | None = None,
     ) -> SearchResults:
         """search_ (replaces _search) is our advanced search method that returns Graph objects (nodes and edges) rather
         than a list of facts. This endpoint allows the end user to utilize more advanced features such as filters and
         different search and reranker methodologies across different layers in the graph.
 
-        For different config recipes refer to search/search_config_recipes.
+        For different config recipes refer to search/search_config_recipes. LLM calls made by
+        the search are sent to model_override, when given. The search is cut off after timeout
+        seconds, or the instance's search_timeout if not given.
+        """
+
+        with use_model_override(model_override):
+            return await self._cached_search(
+                query,
+                config,
+                group_ids,
+                search_filter if search_filter is not None else SearchFilters(),
+                center_node_uuid,
+                bfs_origin_node_uuids,
+                timeout,
+            )
+
+    @with_operation_settings
+    async def search_multi_group(
+        self,
+        query: str,
+        group_ids: list[str],
+        config: SearchConfig = COMBINED_HYBRID_SEARCH_CROSS_ENCODER,
+        center_node_uuid: str | None = None,
+        search_filter: SearchFilters | None = None,
+    ) -> SearchResults:
+        """
+        Search each group separately and merge the results into a single ranked result set.
+
+        Unlike passing several group_ids to `search_`, each group gets its own top results, so
+        a large group cannot crowd out a small one, e.g. when searching a user's notes together
+        with shared knowledge. Results are merged with `SearchResults.merge`, truncated to
+        config.limit.
+        """
+        for group_id in group_ids:
+            validate_group_id(group_id)
+
+        results = await semaphore_gather(
+            *[
+                self._cached_search(
+                    query,
+                    config,
+                    [group_id],
+                    search_filter if search_filter is not None else SearchFilters(),
+                    center_node_uuid,
+                )
+                for group_id in group_ids
+            ],
+            max_coroutines=self.max_coroutines,
+        )
+
+        return SearchResults.merge(results, config.limit)
+
+    @with_operation_settings
+    async def search_and_expand(
+        self,
+        query: str,
+        expand_depth: int = MAX_SEARCH_DEPTH,
+        config: SearchConfig = NODE_HYBRID_SEARCH_RRF,
+        search_filter: SearchFilters | None = None,
+        group_ids: list[str] | None = None,
+        seed_count: int = DEFAULT_EXPAND_SEED_COUNT,
+    ) -> SearchResults:
         """
+        Search for the nodes matching the query, then gather what is connected to them.
 
-        return await search(
+        The top `seed_count` nodes found with the node search of `config` are expanded by
+        breadth-first search up to `expand_depth` hops, at most MAX_SEARCH_DEPTH. The results
+        hold the seed nodes followed by the nodes reached from them, and the facts connecting
+        them. Expansions that reach more than max_traversal_nodes are cut short and marked
+        truncated.
+        """
+        self._check_search_groups(group_ids)
+
+        return await search_and_expand(
             self.clients,
             query,
             group_ids,
             config,
             search_filter if search_filter is not None else SearchFilters(),
+            expand_depth,
+            seed_count,
+        )
+
+    async def _cached_search(
+        self,
+        query: str,
+        config: SearchConfig,
+        group_ids: list[str] | None,
+        search_filter: SearchFilters,
+        center_node_uuid: str | None = None,
+        bfs_origin_node_uuids: list[str] | None = None,
+        timeout: float | None = None,
+    ) -> SearchResults:
+        self._check_search_groups(group_ids)
+        timeout = timeout if timeout is not None else self.search_timeout
+
+        cache_key = self.search_cache.key(
+            query,
+            config,
+            group_ids,
+            search_filter,
             center_node_uuid,
             bfs_origin_node_uuids,
+            get_model_override(),
+        )
+        cached_results = self.search_cache.get(cache_key)
+        if cached_results is not None:
+            logger.debug(f'Search cache hit for {cache_key}')
+            return cached_results
+
+        results = await search(
+            self.clients,
+            query,
+            group_ids,
+            config,
+            search_filter,
+            center_node_uuid,
+            bfs_origin_node_uuids,
+            timeout=timeout,
+        )
+        if results.timed_out and timeout is not None:
+            if self.raise_on_search_timeout:
+                raise SearchTimeoutError(timeout)
+            # Partial results are not cached, so the next search gets another chance to finish
+            return results
+        self.search_cache.set(cache_key, results)
+
+        return results
+
+    def invalidate_search_cache(self, group_ids: list[str] | None = None):
+        """
+        Invalidate cached search results for the given groups, or for all groups if None.
+
+        Call this after modifying the graph without going through this instance, e.g. from
+        another process.
+        """
+        self.search_cache.invalidate(group_ids)
+
+    async def warmup_embeddings(self, queries: list[str]) -> int:
+        """
+        Precompute and cache the embeddings of a known set of search queries.
+
+        Subsequent searches for any of these queries will not call the embedding provider to
+        embed the query. If the configured embedder is not already a CachedEmbedderClient, it is
+        wrapped in one.
+
+        Parameters
+        ----------
+        queries : list[str]
+            The search queries to warm up.
+
+        Returns
+        -------
+        int
+            The number of queries that were newly embedded.
+        """
+        if not isinstance(self.embedder, CachedEmbedderClient):
+            self.embedder = CachedEmbedderClient(self.embedder)
+            self.clients.embedder = self.embedder
+
+        # Match the query preprocessing done by search
+        return await self.embedder.warmup([query.replace('\n', ' ') for query in queries])
+
+    def count_tokens(self, text: str, model: str | None = None) -> int:
+        """
+        Count the tokens a text takes up, e.g. to estimate what adding an episode will cost
+        before adding it. Extraction sends an episode's content to the LLM in several prompts,
+        so its total cost is a multiple of this.
+
+        Parameters
+        ----------
+        text : str
+            The text to count the tokens of.
+        model : str | None, optional
+            The model to count tokens for. Defaults to the LLM client's model. Counts are exact
+            for OpenAI models and an estimate for models of other providers.
+
+        Returns
+        -------
+        int
+            The number of tokens.
+        """
+        return count_tokens(text, model or self.llm_client.model)
+
+    @with_operation_settings
+    async def export_group(self, group_id: str, include_embeddings: bool = True) -> GraphExport:
+        """
+        Export a group's episodes, entities, communities, and edges as a portable snapshot.
+
+        The returned GraphExport is a pydantic model and can be serialized with
+        `model_dump_json()` and restored with `GraphExport.model_validate_json()`.
+
+        Parameters
+        ----------
+        group_id : str
+            The group to export.
+        include_embeddings : bool, optional
+            Whether to include embeddings. If False, they are regenerated on import.
+
+        Returns
+        -------
+        GraphExport
+            The exported subgraph.
+        """
+        validate_group_id(group_id)
+
+        return await export_group(self.driver, group_id, include_embeddings)
+
+    @with_operation_settings
+    async def import_group(
+        self, export: GraphExport, conflict_policy: ConflictPolicy = ConflictPolicy.error
+    ):
+        """
+        Re-create a subgraph from a GraphExport, preserving uuids and temporal fields.
+
+        Parameters
+        ----------
+        export : GraphExport
+            The snapshot to import, as produced by `export_group`.
+        conflict_policy : ConflictPolicy, optional
+            What to do with nodes and edges whose uuids already exist: skip them, overwrite
+            them, or raise an ImportConflictError before anything is written.
+            Defaults to ConflictPolicy.error.
+
+        Notes
+        -----
+        Missing embeddings are generated with the configured embedder.
+        """
+        validate_group_id(export.group_id)
+
+        all_uuids = [
+            item.uuid
+            for items in (
+                export.episodes,
+                export.entity_nodes,
+                export.community_nodes,
+                export.entity_edges,
+                export.episodic_edges,
+                export.community_edges,
+            )
+            for item in items
+        ]
+        existing_uuids: set[str] = (
+            set()
+            if conflict_policy == ConflictPolicy.overwrite
+            else await get_existing_uuids(self.driver, all_uuids)
+        )
+
+        if existing_uuids and conflict_policy == ConflictPolicy.error:
+            raise ImportConflictError(sorted(existing_uuids))
+
+        episodes = [e for e in export.episodes if e.uuid not in existing_uuids]
+        entity_nodes = [n for n in export.entity_nodes if n.uuid not in existing_uuids]
+        community_nodes = [n for n in export.community_nodes if n.uuid not in existing_uuids]
+        entity_edges = [e for e in export.entity_edges if e.uuid not in existing_uuids]
+        episodic_edges = [e for e in export.episodic_edges if e.uuid not in existing_uuids]
+        community_edges = [e for e in export.community_edges if e.uuid not in existing_uuids]
+
+        await add_nodes_and_edges_bulk(
+            self.driver, episodes, episodic_edges, entity_nodes, entity_edges, self._ingest_embedder
+        )
+
+        if self.enable_embeddings:
+            await semaphore_gather(
+                *[
+                    node.generate_name_embedding(self.embedder)
+                    for node in community_nodes
+                    if node.name_embedding is None
+                ],
+                max_coroutines=self.max_coroutines,
+            )
+        await semaphore_gather(
+            *[node.save(self.driver) for node in community_nodes],
+            max_coroutines=self.max_coroutines,
+        )
+        await semaphore_gather(
+            *[edge.save(self.driver) for edge in community_edges],
+            max_coroutines=self.max_coroutines,
         )
 
+        self.search_cache.invalidate([export.group_id])
+
+    @with_operation_settings
     async def get_nodes_and_edges_by_episode(self, episode_uuids: list[str]) -> SearchResults:
         episodes = await EpisodicNode.get_by_uuids(self.driver, episode_uuids)
 
@@ -808,13 +2105,152 @@ class Graphiti:
 
         return SearchResults(edges=edges, nodes=nodes, episodes=[], communities=[])
 
+    async def group_stats(self, group_id: str) -> GroupStats:
+        """
+        Count the episodes, entities, facts, and communities in a group.
+
+        Counts are computed in the database. Facts are split into those that are still valid and
+        those that have been invalidated or expired.
+
+        Parameters
+        ----------
+        group_id : str
+            The group to compute statistics for.
+
+        Returns
+        -------
+        GroupStats
+            The counts, and the valid_at of the group's most recent episode.
+        """
+        return await get_group_stats(self.driver, group_id)
+
+    @with_operation_settings
+    async def find_inconsistencies(
+        self, group_id: str, single_valued_relations: list[str] | None = None
+    ) -> list[Inconsistency]:
+        """
+        Find facts that contradict each other because a single-valued relation has several
+        valid targets, e.g. a person with two current WORKS_AT facts to different companies.
+
+        This is an audit tool for human review; nothing is invalidated or deleted.
+
+        Parameters
+        ----------
+        group_id : str
+            The group to check.
+        single_valued_relations : list[str] | None, optional
+            The fact names to check. Defaults to the instance's single_valued_relations.
+
+        Returns
+        -------
+        list[Inconsistency]
+            One entry per entity and relation with more than one valid target, holding the
+            conflicting facts.
+        """
+        validate_group_id(group_id)
+
+        return await find_inconsistencies(
+            self.driver,
+            group_id,
+            single_valued_relations
+            if single_valued_relations is not None
+            else self.single_valued_relations,
+        )
+
+    @with_operation_settings
+    async def list_communities(self, group_id: str) -> list[CommunityNode]:
+        """
+        Retrieve all communities in a group.
+
+        Parameters
+        ----------
+        group_id : str
+            The group to list communities for.
+
+        Returns
+        -------
+        list[CommunityNode]
+            The group's communities, ordered by uuid descending.
+        """
+        return await CommunityNode.get_by_group_ids(self.driver, [group_id])
+
+    @with_operation_settings
+    async def get_community_members(self, community_uuid: str) -> list[EntityNode]:
+        """
+        Retrieve the entities that belong to a community, following its HAS_MEMBER edges.
+
+        Parameters
+        ----------
+        community_uuid : str
+            The uuid of the community node.
+
+        Returns
+        -------
+        list[EntityNode]
+            The community's member entities, ordered by name.
+        """
+        return await EntityNode.get_by_community_uuid(self.driver, community_uuid)
+
+    @with_operation_settings
+    async def get_entity_episodes(
+        self, entity_uuid: str, limit: int = DEFAULT_PAGE_LIMIT
+    ) -> list[EpisodicNode]:
+        """
+        Retrieve the episodes that mention an entity, most recent first.
+
+        This is the provenance view for an entity: the source content and description of each
+        episode it was extracted from.
+
+        Parameters
+        ----------
+        entity_uuid : str
+            The uuid of the entity node.
+        limit : int, optional
+            The maximum number of episodes to return. Defaults to DEFAULT_PAGE_LIMIT.
+
+        Returns
+        -------
+        list[EpisodicNode]
+            The episodes mentioning the entity, ordered by valid_at descending.
+        """
+        return await EpisodicNode.get_by_entity_node_uuid(self.driver, entity_uuid, limit)
+
+    @with_operation_settings
+    async def get_facts_between(
+        self, entity_a_uuid: str, entity_b_uuid: str, include_invalid: bool = False
+    ) -> list[EntityEdge]:
+        """
+        Retrieve the facts directly connecting two entities, in either direction.
+
+        Parameters
+        ----------
+        entity_a_uuid : str
+            The uuid of one entity node.
+        entity_b_uuid : str
+            The uuid of the other entity node.
+        include_invalid : bool, optional
+            Whether to also return facts that have been invalidated or expired, for the history
+            of the relationship. Defaults to False.
+
+        Returns
+        -------
+        list[EntityEdge]
+            The facts between the entities, oldest first.
+        """
+        return await EntityEdge.get_between_nodes(
+            self.driver, entity_a_uuid, entity_b_uuid, include_invalid
+        )
+
+    @with_operation_settings
     async def add_triplet(self, source_node: EntityNode, edge: EntityEdge, target_node: EntityNode):
-        if source_node.name_embedding is None:
-            await source_node.generate_name_embedding(self.embedder)
-        if target_node.name_embedding is None:
-            await target_node.generate_name_embedding(self.embedder)
-        if edge.fact_embedding is None:
-            await edge.generate_embedding(self.embedder)
+        if self.enable_embeddings:
+            composition = self.clients.entity_embedding_text
+            if source_node.name_embedding is None:
+                await source_node.generate_name_embedding(self.embedder, composition)
+            if target_node.name_embedding is None:
+                await target_node.generate_name_embedding(self.embedder, composition)
+            if edge.fact_embedding is None:
+                await edge.generate_embedding(self.embedder)
 
         resolved_nodes, uuid_map, _ = await resolve_extracted_nodes(
             self.clients,
@@ -823,9 +2259,11 @@ class Graphiti:
 
         updated_edge = resolve_edge_pointers([edge], uuid_map)[0]
 
-        related_edges = (await get_relevant_edges(self.driver, [updated_edge], SearchFilters()))[0]
+        # Resolution compares against the full history, including invalidated edges
+        history_filter = SearchFilters(include_invalidated=True)
+        related_edges = (await get_relevant_edges(self.driver, [updated_edge], history_filter))[0]
         existing_edges = (
-            await get_edge_invalidation_candidates(self.driver, [updated_edge], SearchFilters())
+            await get_edge_invalidation_candidates(self.driver, [updated_edge], history_filter)
         )[0]
 
         resolved_edge, invalidated_edges = await resolve_extracted_edge(
@@ -845,8 +2283,281 @@ class Graphiti:
         )
 
         await add_nodes_and_edges_bulk(
-            self.driver, [], [], resolved_nodes, [resolved_edge] + invalidated_edges, self.embedder
+            self.driver,
+            [],
+            [],
+            resolved_nodes,
+            [resolved_edge] + invalidated_edges,
+            self._ingest_embedder,
+        )
+        self.search_cache.invalidate([edge.group_id])
+
+    @with_operation_settings
+    async def merge_entities(self, keep_uuid: str, merge_uuid: str) -> EntityNode:
+        """
+        Merge an entity into another that it duplicates, for manual curation when automatic
+        deduplication missed a match.
+
+        The facts, episode mentions, and community memberships of the merged entity are moved to
+        the kept entity, and the merged entity is deleted. A moved fact is folded into a fact the
+        kept entity already has with the same relation to the same entity if the facts are the
+        same, or the LLM resolves them as duplicates. Facts between the two entities are deleted
+        rather than becoming self-loops. Summaries are combined, up to max_summary_words, and
+        attributes of the kept entity take precedence.
+
+        Parameters
+        ----------
+        keep_uuid : str
+            The uuid of the entity to keep.
+        merge_uuid : str
+            The uuid of the entity to merge into it and delete.
+
+        Returns
+        -------
+        EntityNode
+            The kept entity, after the merge.
+        """
+        if keep_uuid == merge_uuid:
+            raise ValueError('An entity cannot be merged into itself')
+
+        keep_node, merge_node = await semaphore_gather(
+            EntityNode.get_by_uuid(self.driver, keep_uuid),
+            EntityNode.get_by_uuid(self.driver, merge_uuid),
+        )
+        if keep_node.group_id != merge_node.group_id:
+            raise ValueError('Entities from different groups cannot be merged')
+
+        keep_edges, merge_edges = await semaphore_gather(
+            EntityEdge.get_by_node_uuid(self.driver, keep_uuid),
+            EntityEdge.get_by_node_uuid(self.driver, merge_uuid),
+        )
+        # Edges are saved again below, so their embeddings have to be loaded first
+        await semaphore_gather(
+            keep_node.load_name_embedding(self.driver),
+            *[edge.load_fact_embedding(self.driver) for edge in keep_edges + merge_edges],
+            max_coroutines=self.max_coroutines,
+        )
+
+        if merge_node.summary and merge_node.summary not in keep_node.summary:
+            keep_node.summary = truncate_words(
+                f'{keep_node.summary}\n{merge_node.summary}'.strip(), self.max_summary_words
+            )
+        keep_node.attributes = {**merge_node.attributes, **keep_node.attributes}
+        keep_node.attribute_metadata = {
+            **merge_node.attribute_metadata,
+            **keep_node.attribute_metadata,
+        }
+        merge_node_labels(keep_node, merge_node)
+
+        parallel_edges: dict[tuple[str, str, str], list[EntityEdge]] = defaultdict(list)
+        for edge in keep_edges:
+            parallel_edges[(edge.source_node_uuid, edge.target_node_uuid, edge.name)].append(edge)
+        edges_to_save: dict[str, EntityEdge] = {}
+        for edge in resolve_edge_pointers(merge_edges, {merge_uuid: keep_uuid}):
+            if edge.source_node_uuid == edge.target_node_uuid:
+                continue
+            candidates = parallel_edges[(edge.source_node_uuid, edge.target_node_uuid, edge.name)]
+            parallel_edge = next((c for c in candidates if c.fact == edge.fact), None)
+            if parallel_edge is None and candidates:
+                resolved_edge = await dedupe_extracted_edge(self.llm_client, edge, candidates)
+                parallel_edge = None if resolved_edge is edge else resolved_edge
+            if parallel_edge is None:
+                candidates.append(edge)
+                edges_to_save[edge.uuid] = edge
+                continue
+            parallel_edge.episodes = list(dict.fromkeys(parallel_edge.episodes + edge.episodes))
+            edges_to_save[parallel_edge.uuid] = parallel_edge
+
+        async with self._batch_writer() as writer:
+            await writer.add(keep_node, *edges_to_save.values())
+        await move_entity_links(self.driver, merge_uuid, keep_uuid)
+        # Deleting the merged entity also deletes the old copies of its edges and links
+        await merge_node.delete(self.driver)
+
+        self.search_cache.invalidate([keep_node.group_id])
+        return keep_node
+
+    @with_operation_settings
+    async def run_dedup_maintenance(self, group_id: str) -> dict[str, str]:
+        """
+        Merge a group's duplicate entities that deduplication during ingestion missed, e.g.
+        entities extracted in separate batches that only turn out to be the same later. Meant to
+        be run periodically as a cleanup job.
+
+        Entities whose names match, under the instance's name normalization and similarity
+        settings, are merged outright. For the remaining entities, a vector query over their
+        stored name embeddings finds the most similar entities of each, and the LLM picks out
+        the duplicates within each group of similar entities, as in add_episode_bulk. Nothing
+        is re-embedded, and entities saved without embeddings are only merged on their names.
+        Each duplicate is then merged with merge_entities, which repoints its facts to the kept
+        entity.
+
+        Parameters
+        ----------
+        group_id : str
+            The group to deduplicate.
+
+        Returns
+        -------
+        dict[str, str]
+            The uuids of the merged entities, mapped to the uuids of the entities they were
+            merged into.
+        """
+        validate_group_id(group_id)
+
+        async with self._group_ingest_lock(group_id):
+            nodes = await EntityNode.get_by_group_ids(self.driver, [group_id])
+            # The first entity seen for a name is kept, so name matches merge into the oldest
+            nodes.sort(key=lambda node: node.created_at)
+
+            nodes, uuid_map = node_name_match(
+                nodes, self.name_normalization, self.name_similarity_threshold, self.name_similarity
+            )
+            embeddings = await get_embeddings_for_nodes(self.driver, nodes)
+            for node in nodes:
+                node.name_embedding = embeddings.get(node.uuid)
+
+            candidate_groups = await group_duplicate_candidates(self.driver, nodes)
+            results = await semaphore_gather(
+                *[compress_nodes(self.llm_client, group, {}) for group in candidate_groups]
+            )
+            for _, group_uuid_map in results:
+                uuid_map.update(group_uuid_map)
+            uuid_map = compress_uuid_map(uuid_map)
+
+            for merge_uuid, keep_uuid in uuid_map.items():
+                await self.merge_entities(keep_uuid, merge_uuid)
+
+        logger.info(f'Merged {len(uuid_map)} duplicate entities in group {group_id}')
+        return uuid_map
+
+    @with_operation_settings
+    async def backfill_embeddings(self, group_id: str) -> int:
+        """
+        Embed a group's entities, facts, and episodes that were saved without embeddings, e.g.
+        by add_episode with embedding_fallback while the embedder was failing.
+
+        Parameters
+        ----------
+        group_id : str
+            The group to backfill.
+
+        Returns
+        -------
+        int
+            The number of entities, facts, and episodes that were embedded.
+        """
+        if not self.enable_embeddings:
+            raise ValueError('Embeddings are disabled for this Graphiti instance')
+        validate_group_id(group_id)
+
+        backfilled = await backfill_embeddings(
+            self.driver, self.embedder, group_id, self.clients.entity_embedding_text
+        )
+        if backfilled:
+            self.search_cache.invalidate([group_id])
+
+        return backfilled
+
+    @with_operation_settings
+    async def reembed(self, group_id: str, batch_size: int = DEFAULT_REEMBED_BATCH_SIZE) -> int:
+        """
+        Re-embed all of a group's entities, facts, and episodes with the current embedder, e.g.
+        after switching to a new embedding model or dimension, without rebuilding the group.
+
+        Search compares embeddings in its queries rather than through a vector index, so
+        nothing else needs to be rebuilt at the new dimension. Raises EmbeddingsUnchangedError
+        when the group's stored embeddings already match the current embedder.
+
+        Parameters
+        ----------
+        group_id : str
+            The group to re-embed.
+        batch_size : int, optional
+            How many items are embedded and saved at a time. Progress is logged after each
+            batch. Defaults to DEFAULT_REEMBED_BATCH_SIZE.
+
+        Returns
+        -------
+        int
+            The number of entities, facts, and episodes that were re-embedded.
+        """
+        if not self.enable_embeddings:
+            raise ValueError('Embeddings are disabled for this Graphiti instance')
+        validate_group_id(group_id)
+
+        reembedded = await reembed_group(
+            self.driver, self.embedder, group_id, self.clients.entity_embedding_text, batch_size
         )
+        if reembedded:
+            self.search_cache.invalidate([group_id])
+
+        return reembedded
+
+    async def prune_episodes(
+        self, group_id: str, older_than: datetime, delete_orphaned_entities: bool = False
+    ) -> EpisodePruneResult:
+        """
+        Delete a group's episodes created before a cutoff, to keep the graph from growing
+        without bound.
+
+        The episodes' MENTIONS edges are deleted with them. Facts extracted from the episodes are
+        kept, so what was learned from old episodes remains searchable.
+
+        Parameters
+        ----------
+        group_id : str
+            The group to prune.
+        older_than : datetime
+            Episodes whose created_at is before this time are deleted.
+        delete_orphaned_entities : bool, optional
+            Whether to also delete entities that were mentioned by the pruned episodes and are
+            now orphaned: no remaining episode mentions them and none of their facts are still
+            valid. Defaults to False.
+
+        Returns
+        -------
+        EpisodePruneResult
+            The uuids of the deleted episodes and entities.
+        """
+        validate_group_id(group_id)
+
+        result = await prune_episodes(self.driver, group_id, older_than, delete_orphaned_entities)
+        if result.episode_uuids:
+            self.search_cache.invalidate([group_id])
+
+        return result
+
+    async def cleanup_orphans(
+        self, group_id: str, policy: OrphanPolicy = OrphanPolicy.delete
+    ) -> list[str]:
+        """
+        Clean up a group's entities that are no longer connected to anything, e.g. after all of
+        their facts were invalidated or deleted.
+
+        An entity is orphaned when no episode mentions it, none of its facts are still valid,
+        and it is not a member of a community.
+
+        Parameters
+        ----------
+        group_id : str
+            The group to clean up.
+        policy : OrphanPolicy, optional
+            Whether orphaned entities are deleted or flagged with an Orphaned label. Defaults to
+            delete.
+
+        Returns
+        -------
+        list[str]
+            The uuids of the orphaned entities.
+        """
+        validate_group_id(group_id)
+
+        entity_uuids = await cleanup_orphans(self.driver, group_id, policy)
+        if entity_uuids:
+            self.search_cache.invalidate([group_id])
+
+        return entity_uuids
 
     async def remove_episode(self, episode_uuid: str):
         # Find the episode to be deleted
@@ -884,3 +2595,4 @@ class Graphiti:
             max_coroutines=self.max_coroutines,
         )
         await episode.delete(self.driver)
+        self.search_cache.invalidate([episode.group_id])
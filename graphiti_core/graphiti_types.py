@@ -18,8 +18,18 @@ from pydantic import BaseModel, ConfigDict
 
 from graphiti_core.cross_encoder import CrossEncoderClient
 from graphiti_core.driver.driver import GraphDriver
+from graphiti_core.edges import DEFAULT_SYMMETRIC_RELATIONS
 from graphiti_core.embedder import EmbedderClient
+from graphiti_core.helpers import (
+    DEFAULT_MAX_SUMMARY_WORDS,
+    AttributeConflictPolicy,
+    AttributeValidationPolicy,
+    MAX_REFLEXION_ITERATIONS,
+    StringSimilarity,
+)
 from graphiti_core.llm_client import LLMClient
+from graphiti_core.nodes import EntityEmbeddingText
+from graphiti_core.search.search_config import DEFAULT_MAX_TRAVERSAL_NODES
 
 
 class GraphitiClients(BaseModel):
@@ -27,5 +37,20 @@ class GraphitiClients(BaseModel):
     llm_client: LLMClient
     embedder: EmbedderClient
     cross_encoder: CrossEncoderClient
+    embeddings_enabled: bool = True
+    max_reflexion_rounds: int = MAX_REFLEXION_ITERATIONS
+    max_summary_words: int | None = DEFAULT_MAX_SUMMARY_WORDS
+    auto_merge_threshold: float = 1.0
+    name_similarity_threshold: float = 1.0
+    name_similarity: StringSimilarity = StringSimilarity.jaro_winkler
+    attribute_validation: AttributeValidationPolicy = AttributeValidationPolicy.keep
+    attribute_conflict_policy: AttributeConflictPolicy = AttributeConflictPolicy.overwrite
+    max_entities_per_episode: int | None = None
+    max_edges_per_episode: int | None = None
+    strict_extraction: bool = False
+    entity_embedding_text: EntityEmbeddingText = EntityEmbeddingText.name
+    symmetric_relations: frozenset[str] = DEFAULT_SYMMETRIC_RELATIONS
+    allow_self_loops: bool = False
+    max_traversal_nodes: int = DEFAULT_MAX_TRAVERSAL_NODES
 
     model_config = ConfigDict(arbitrary_types_allowed=True)
@@ -15,13 +15,20 @@ limitations under the License.
 """
 
 import asyncio
+import functools
+import logging
+import math
 import os
 import re
-from collections.abc import Coroutine
+import unicodedata
+from collections.abc import AsyncIterator, Coroutine
+from contextlib import asynccontextmanager
 from datetime import datetime
+from enum import Enum
 from typing import Any
 
 import numpy as np
+import tiktoken
 from dotenv import load_dotenv
 from neo4j import time as neo4j_time
 from numpy._typing import NDArray
@@ -32,11 +39,23 @@ from graphiti_core.errors import GroupIdValidationError
 
 load_dotenv()
 
+logger = logging.getLogger(__name__)
+
 DEFAULT_DATABASE = os.getenv('DEFAULT_DATABASE', 'default_db')
 USE_PARALLEL_RUNTIME = bool(os.getenv('USE_PARALLEL_RUNTIME', False))
 SEMAPHORE_LIMIT = int(os.getenv('SEMAPHORE_LIMIT', 20))
-MAX_REFLEXION_ITERATIONS = int(os.getenv('MAX_REFLEXION_ITERATIONS', 0))
+MAX_REFLEXION_ITERATIONS = int(os.getenv('MAX_REFLEXION_ITERATIONS', 1))
 DEFAULT_PAGE_LIMIT = 20
+# Defaults for HTTP calls to model providers, matching the OpenAI SDK defaults. Lower these to
+# fail fast against local proxies; raise the request timeout for slow, long generations.
+DEFAULT_HTTP_TIMEOUT = 600.0
+DEFAULT_HTTP_CONNECT_TIMEOUT = 5.0
+DEFAULT_EPISODE_CHUNK_OVERLAP = 200
+# The summary prompts ask for at most this many words; longer summaries are truncated
+DEFAULT_MAX_SUMMARY_WORDS = 250
+DEFAULT_TOKEN_ENCODING = 'o200k_base'
+# Rough average for English text, used to estimate token counts when no encoding can be loaded
+CHARS_PER_TOKEN = 4
 
 RUNTIME_QUERY: LiteralString = (
     'CYPHER runtime = parallel parallelRuntimeSupport=all\n' if USE_PARALLEL_RUNTIME else ''
@@ -90,12 +109,258 @@ def lucene_sanitize(query: str) -> str:
     return sanitized
 
 
+class NameNormalization(Enum):
+    """How entity names are normalized before they are compared for exact-match dedup."""
+
+    # Compare names exactly as extracted
+    none = 'none'
+    # Lowercase, trim, and collapse internal whitespace
+    basic = 'basic'
+    # Basic normalization, then strip all punctuation and whitespace ("Open AI" == "OpenAI").
+    # This can over-merge distinct entities whose names differ only in punctuation or spacing.
+    aggressive = 'aggressive'
+
+
+def normalize_name(name: str, strip_punctuation: bool = False) -> str:
+    """
+    Normalize an entity name for comparison.
+
+    Lowercases, trims, and collapses runs of whitespace. If strip_punctuation is set, all
+    punctuation and whitespace is also removed.
+    """
+    normalized = ' '.join(name.lower().split())
+    if strip_punctuation:
+        normalized = re.sub(r'[\W_]+', '', normalized)
+    return normalized
+
+
+class AttributeValidationPolicy(Enum):
+    """How extracted attributes are checked against the fields of their entity or edge type."""
+
+    # Store attributes as returned by the LLM
+    keep = 'keep'
+    # Drop unknown fields and coerce mistyped values, e.g. "3" to 3; values that can't be
+    # coerced are dropped
+    coerce = 'coerce'
+    # Drop unknown fields and any value that doesn't already have the declared type
+    drop = 'drop'
+
+
+class AttributeConflictPolicy(Enum):
+    """How newly extracted attribute values are merged into an entity's existing attributes."""
+
+    # The new value replaces the old one
+    overwrite = 'overwrite'
+    # The value observed at the later episode time wins, so older episodes can't revert it
+    keep_newest = 'keep_newest'
+    # The new value replaces the old one, which is kept in the attribute history with its times
+    append_history = 'append_history'
+
+
+class StringSimilarity(Enum):
+    """String similarity metric used to match entity names that differ by a typo."""
+
+    # Favors strings that share a prefix; well suited to short names
+    jaro_winkler = 'jaro_winkler'
+    # One minus the edit distance, divided by the length of the longer string
+    levenshtein = 'levenshtein'
+
+
+def jaro_winkler_similarity(a: str, b: str, prefix_scale: float = 0.1) -> float:
+    if a == b:
+        return 1.0
+    if not a or not b:
+        return 0.0
+
+    match_distance = max(max(len(a), len(b)) // 2 - 1, 0)
+    a_matched = [False] * len(a)
+    b_matched = [False] * len(b)
+    matches = 0
+    for i, char in enumerate(a):
+        for j in range(max(0, i - match_distance), min(i + match_distance + 1, len(b))):
+            if not b_matched[j] and b[j] == char:
+                a_matched[i] = b_matched[j] = True
+                matches += 1
+                break
+    if matches == 0:
+        return 0.0
+
+    a_matches = [char for char, matched in zip(a, a_matched, strict=True) if matched]
+    b_matches = [char for char, matched in zip(b, b_matched, strict=True) if matched]
+    transpositions = sum(x != y for x, y in zip(a_matches, b_matches, strict=True)) / 2
+    jaro = (matches / len(a) + matches / len(b) + (matches - transpositions) / matches) / 3
+
+    prefix = 0
+    for x, y in zip(a[:4], b[:4], strict=False):
+        if x != y:
+            break
+        prefix += 1
+    return jaro + prefix * prefix_scale * (1 - jaro)
+
+
+def levenshtein_similarity(a: str, b: str) -> float:
+    if a == b:
+        return 1.0
+    if not a or not b:
+        return 0.0
+
+    previous = list(range(len(b) + 1))
+    for i, a_char in enumerate(a, start=1):
+        current = [i]
+        for j, b_char in enumerate(b, start=1):
+            current.append(
+                min(previous[j] + 1, current[j - 1] + 1, previous[j - 1] + (a_char != b_char))
+            )
+        previous = current
+    return 1 - previous[-1] / max(len(a), len(b))
+
+
+def string_similarity(a: str, b: str, metric: StringSimilarity) -> float:
+    """Similarity of two strings between 0 and 1, where 1 means they are equal."""
+    if metric == StringSimilarity.levenshtein:
+        return levenshtein_similarity(a, b)
+    return jaro_winkler_similarity(a, b)
+
+
+def chunk_text(text: str, max_chars: int, overlap: int = 0) -> list[str]:
+    """
+    Split text into chunks of at most max_chars characters.
+
+    Consecutive chunks share `overlap` characters so that facts spanning a boundary appear whole
+    in at least one chunk. Chunks end on whitespace where possible.
+    """
+    if overlap >= max_chars:
+        raise ValueError('overlap must be smaller than max_chars')
+    if len(text) <= max_chars:
+        return [text]
+
+    chunks: list[str] = []
+    start = 0
+    while True:
+        end = start + max_chars
+        if end >= len(text):
+            chunks.append(text[start:])
+            return chunks
+
+        # Prefer to break on whitespace, as long as the next chunk still makes progress
+        boundary = max(text.rfind(c, start + overlap + 1, end) for c in ' \n\t')
+        if boundary != -1:
+            end = boundary
+
+        chunks.append(text[start:end])
+
+        # Start the next chunk on a word boundary within the overlap
+        start = end - overlap
+        word_break = re.search(r'\s', text[start:end])
+        if word_break is not None:
+            start += word_break.end()
+
+
+def truncate_words(text: str, max_words: int | None) -> str:
+    """
+    Truncate text to at most max_words words, cutting on a word boundary and appending an
+    ellipsis. Text within the limit, or any text when max_words is None, is returned unchanged.
+    """
+    if max_words is None:
+        return text
+
+    words = list(re.finditer(r'\S+', text))
+    if len(words) <= max_words:
+        return text
+    if max_words <= 0:
+        return ''
+
+    return text[: words[max_words - 1].end()].rstrip(',;:') + '...'
+
+
+def detect_script(text: str) -> str | None:
+    """
+    Return the writing system most of the text's letters are written in, e.g. 'Cyrillic' or
+    'Hangul', or None when it is Latin or the text has no letters.
+    """
+    counts: dict[str, int] = {}
+    for char in text:
+        if char.isalpha():
+            # Unicode names start with the script, e.g. 'CYRILLIC SMALL LETTER A'
+            script = unicodedata.name(char, '').split(' ')[0]
+            counts[script] = counts.get(script, 0) + 1
+    if not counts:
+        return None
+
+    script = max(counts, key=lambda name: counts[name])
+    if script in ('', 'LATIN'):
+        return None
+    return 'Han' if script == 'CJK' else script.title()
+
+
+@functools.lru_cache(maxsize=32)
+def _encoding_for_model(model: str | None) -> tiktoken.Encoding | None:
+    try:
+        if model:
+            try:
+                return tiktoken.encoding_for_model(model)
+            except KeyError:
+                pass
+        # Models tiktoken doesn't know, e.g. those of other providers, are approximated with the
+        # encoding of the current OpenAI models
+        return tiktoken.get_encoding(DEFAULT_TOKEN_ENCODING)
+    except Exception as e:
+        # tiktoken downloads encodings on first use, which fails in offline deployments
+        logger.warning(f'Could not load a token encoding, estimating tokens from text length: {e}')
+        return None
+
+
+def count_tokens(text: str, model: str | None = None) -> int:
+    """
+    Count the tokens text takes up for a model, e.g. to estimate what ingesting an episode will
+    cost. Counts are exact for OpenAI models and an estimate for models of other providers, or
+    when the encoding can't be loaded.
+    """
+    encoding = _encoding_for_model(model)
+    if encoding is None:
+        return math.ceil(len(text) / CHARS_PER_TOKEN)
+    return len(encoding.encode(text, disallowed_special=()))
+
+
+def split_tokens(text: str, max_tokens: int, model: str | None = None) -> list[str]:
+    """
+    Split text into consecutive pieces of at most max_tokens tokens each, as counted by
+    count_tokens.
+    """
+    encoding = _encoding_for_model(model)
+    if encoding is None:
+        max_chars = max_tokens * CHARS_PER_TOKEN
+        return [text[i : i + max_chars] for i in range(0, len(text), max_chars)] or [text]
+
+    tokens = encoding.encode(text, disallowed_special=())
+    pieces = [
+        encoding.decode(tokens[i : i + max_tokens]) for i in range(0, len(tokens), max_tokens)
+    ]
+    return pieces or [text]
+
+
 def normalize_l2(embedding: list[float]) -> NDArray:
     embedding_array = np.array(embedding)
     norm = np.linalg.norm(embedding_array, 2, axis=0, keepdims=True)
     return np.where(norm == 0, embedding_array, embedding_array / norm)
 
 
+def is_finite_embedding(embedding: list[float]) -> bool:
+    return bool(np.all(np.isfinite(embedding)))
+
+
+def cosine_similarity(a: list[float], b: list[float]) -> float:
+    # A NaN or Inf component, e.g. from a corrupt stored embedding, would make the score NaN,
+    # which doesn't compare consistently when results are sorted
+    if not (is_finite_embedding(a) and is_finite_embedding(b)):
+        logger.warning('Embedding with non-finite values found, scoring its similarity as 0')
+        return 0.0
+    norm = np.linalg.norm(a) * np.linalg.norm(b)
+    if norm == 0:
+        return 0.0
+    return float(np.dot(a, b) / norm)
+
+
 # Use this instead of asyncio.gather() to bound coroutines
 async def semaphore_gather(
     *coroutines: Coroutine,
@@ -110,6 +375,57 @@ async def semaphore_gather(
     return await asyncio.gather(*(_wrap_coroutine(coroutine) for coroutine in coroutines))
 
 
+async def gather_until(
+    *coroutines: Coroutine,
+    timeout: float | None,
+    default: Any = None,
+) -> tuple[list[Any], bool]:
+    """
+    Run coroutines concurrently for at most `timeout` seconds. Coroutines still running at the
+    timeout are cancelled and their result is `default`. Returns the results in order and
+    whether the timeout was hit. Cancelling the caller cancels every coroutine.
+    """
+    tasks = [asyncio.ensure_future(coroutine) for coroutine in coroutines]
+    if not tasks:
+        return [], False
+
+    try:
+        done, pending = await asyncio.wait(tasks, timeout=timeout)
+    finally:
+        for task in tasks:
+            if not task.done():
+                task.cancel()
+    if pending:
+        await asyncio.gather(*pending, return_exceptions=True)
+
+    return [task.result() if task in done else default for task in tasks], bool(pending)
+
+
+class KeyedLock:
+    """
+    An asyncio lock per key, e.g. per group_id, so that work on the same key runs one at a time
+    while work on different keys runs concurrently. Locks are dropped once nobody holds or waits
+    for them.
+    """
+
+    def __init__(self):
+        self._locks: dict[str, asyncio.Lock] = {}
+        self._users: dict[str, int] = {}
+
+    @asynccontextmanager
+    async def hold(self, key: str) -> AsyncIterator[None]:
+        lock = self._locks.setdefault(key, asyncio.Lock())
+        self._users[key] = self._users.get(key, 0) + 1
+        try:
+            async with lock:
+                yield
+        finally:
+            self._users[key] -= 1
+            if not self._users[key]:
+                del self._users[key]
+                del self._locks[key]
+
+
 def validate_group_id(group_id: str) -> bool:
     """
     Validate that a group_id contains only ASCII alphanumeric characters, dashes, and underscores.
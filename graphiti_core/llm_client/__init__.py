@@ -14,9 +14,27 @@ See the License for the specific language governing permissions and
 limitations under the License.
 """
 
-from .client import LLMClient
+from .client import LLMClient, RetryBudget, use_model_override, use_retry_budget
 from .config import LLMConfig
-from .errors import RateLimitError
+from .errors import PromptTooLongError, RateLimitError, RetryBudgetExhaustedError
+from .fallback_client import FallbackLLMClient
+from .observer import JSONLFileObserver, LLMObserver, TokenUsage
 from .openai_client import OpenAIClient
+from .tee_client import TeeLLMClient
 
-__all__ = ['LLMClient', 'OpenAIClient', 'LLMConfig', 'RateLimitError']
+__all__ = [
+    'LLMClient',
+    'OpenAIClient',
+    'LLMConfig',
+    'RateLimitError',
+    'PromptTooLongError',
+    'RetryBudget',
+    'RetryBudgetExhaustedError',
+    'use_retry_budget',
+    'use_model_override',
+    'FallbackLLMClient',
+    'TeeLLMClient',
+    'LLMObserver',
+    'JSONLFileObserver',
+    'TokenUsage',
+]
@@ -15,7 +15,6 @@ limitations under the License.
 """
 
 import logging
-from typing import ClassVar
 
 from openai import AsyncAzureOpenAI
 from openai.types.chat import ChatCompletionMessageParam
@@ -30,9 +29,6 @@ logger = logging.getLogger(__name__)
 class AzureOpenAILLMClient(BaseOpenAIClient):
     """Wrapper class for AsyncAzureOpenAI that implements the LLMClient interface."""
 
-    # Class-level constants
-    MAX_RETRIES: ClassVar[int] = 2
-
     def __init__(
         self,
         azure_client: AsyncAzureOpenAI,
@@ -40,7 +36,8 @@ class AzureOpenAILLMClient(BaseOpenAIClient):
         max_tokens: int = DEFAULT_MAX_TOKENS,
     ):
         super().__init__(config, cache=False, max_tokens=max_tokens)
-        self.client = azure_client
+        # Network errors are retried by generate_response, up to config.network_retries
+        self.client = azure_client.with_options(max_retries=0)
 
     async def _create_structured_completion(
         self,
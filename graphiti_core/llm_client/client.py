@@ -19,15 +19,26 @@ import json
 import logging
 import typing
 from abc import ABC, abstractmethod
+from collections.abc import Iterator
+from contextlib import contextmanager
+from contextvars import ContextVar
 
 import httpx
 from diskcache import Cache
 from pydantic import BaseModel
-from tenacity import retry, retry_if_exception, stop_after_attempt, wait_random_exponential
+from tenacity import (
+    RetryCallState,
+    retry,
+    retry_if_exception,
+    stop_after_attempt,
+    wait_random_exponential,
+)
 
+from ..helpers import count_tokens
 from ..prompts.models import Message
 from .config import DEFAULT_MAX_TOKENS, LLMConfig, ModelSize
-from .errors import RateLimitError
+from .errors import PromptTooLongError, RateLimitError, RetryBudgetExhaustedError
+from .observer import LLMObserver, TokenUsage
 
 DEFAULT_TEMPERATURE = 0
 DEFAULT_CACHE_DIR = './llm_cache'
@@ -38,6 +49,73 @@ MULTILINGUAL_EXTRACTION_RESPONSES = (
 
 logger = logging.getLogger(__name__)
 
+# Token usage of the generate_response call currently running in this context
+_token_usage: ContextVar[TokenUsage | None] = ContextVar('llm_token_usage', default=None)
+
+
+class RetryBudget:
+    """A number of LLM retries shared by every call made within one operation."""
+
+    def __init__(self, max_retries: int):
+        self.max_retries = max_retries
+        self.used = 0
+
+    def consume(self, error: BaseException | None = None):
+        if self.used >= self.max_retries:
+            raise RetryBudgetExhaustedError(self.max_retries) from error
+        self.used += 1
+
+
+_retry_budget: ContextVar[RetryBudget | None] = ContextVar('llm_retry_budget', default=None)
+
+
+@contextmanager
+def use_retry_budget(budget: RetryBudget | None) -> Iterator[None]:
+    """Share the given retry budget across LLM calls made within this context."""
+    token = _retry_budget.set(budget)
+    try:
+        yield
+    finally:
+        _retry_budget.reset(token)
+
+
+def consume_retry_budget(error: BaseException | None = None):
+    """
+    Take a retry from the current operation's budget, if there is one. Raises
+    RetryBudgetExhaustedError when the budget is used up, so that the caller stops retrying.
+    """
+    budget = _retry_budget.get()
+    if budget is not None:
+        budget.consume(error)
+
+
+def _consume_retry_budget_before_sleep(retry_state: RetryCallState):
+    consume_retry_budget(retry_state.outcome.exception() if retry_state.outcome else None)
+
+
+_model_override: ContextVar[str | None] = ContextVar('llm_model_override', default=None)
+
+
+@contextmanager
+def use_model_override(model: str | None) -> Iterator[None]:
+    """
+    Send the LLM calls made within this context to the given model, whatever their model size,
+    instead of the client's configured models. None keeps the configured models.
+    """
+    token = _model_override.set(model)
+    try:
+        yield
+    finally:
+        _model_override.reset(token)
+
+
+def get_model_override() -> str | None:
+    return _model_override.get()
+
+
+def estimate_tokens(messages: list[Message], model: str | None = None) -> int:
+    return sum(count_tokens(message.content, model) for message in messages)
+
 
 def is_server_or_retry_error(exception):
     if isinstance(exception, RateLimitError | json.decoder.JSONDecodeError):
@@ -60,11 +138,53 @@ class LLMClient(ABC):
         self.max_tokens = config.max_tokens
         self.cache_enabled = cache
         self.cache_dir = None
+        self.observer: LLMObserver | None = None
 
         # Only create the cache directory if caching is enabled
         if self.cache_enabled:
             self.cache_dir = Cache(DEFAULT_CACHE_DIR)
 
+    @contextmanager
+    def _observe(self, messages: list[Message]) -> Iterator[TokenUsage | None]:
+        """
+        Report the request to the observer, and collect the token usage of the calls made
+        within this context so that it can be reported with the response.
+        """
+        if self.observer is None:
+            yield None
+            return
+
+        usage = TokenUsage()
+        token = _token_usage.set(usage)
+        try:
+            self.observer.on_request(messages)
+            yield usage
+        finally:
+            _token_usage.reset(token)
+
+    def _observe_response(self, response: dict[str, typing.Any], usage: TokenUsage | None):
+        if self.observer is not None:
+            self.observer.on_response(response, usage or TokenUsage())
+
+    def _record_usage(self, input_tokens: int | None, output_tokens: int | None):
+        usage = _token_usage.get()
+        if usage is not None:
+            usage.add(input_tokens, output_tokens)
+
+    def _fit_max_tokens(self, messages: list[Message], max_tokens: int) -> int:
+        """
+        Caps max_tokens at the room the prompt leaves in the context window when auto_max_tokens
+        is enabled. Raises PromptTooLongError if the prompt doesn't fit at all.
+        """
+        if not self.config.auto_max_tokens:
+            return max_tokens
+
+        prompt_tokens = estimate_tokens(messages, get_model_override() or self.model)
+        available = self.config.context_window - prompt_tokens
+        if available <= 0:
+            raise PromptTooLongError(prompt_tokens, self.config.context_window)
+        return min(available, max_tokens)
+
     def _clean_input(self, input: str) -> str:
         """Clean input string of invalid unicode and control characters.
 
@@ -91,6 +211,7 @@ class LLMClient(ABC):
         stop=stop_after_attempt(4),
         wait=wait_random_exponential(multiplier=10, min=5, max=120),
         retry=retry_if_exception(is_server_or_retry_error),
+        before_sleep=_consume_retry_budget_before_sleep,
         after=lambda retry_state: logger.warning(
             f'Retrying {retry_state.fn.__name__ if retry_state.fn else "function"} after {retry_state.attempt_number} attempts...'
         )
@@ -123,7 +244,7 @@ class LLMClient(ABC):
     def _get_cache_key(self, messages: list[Message]) -> str:
         # Create a unique cache key based on the messages and model
         message_str = json.dumps([m.model_dump() for m in messages], sort_keys=True)
-        key_str = f'{self.model}:{message_str}'
+        key_str = f'{get_model_override() or self.model}:{message_str}'
         return hashlib.md5(key_str.encode()).hexdigest()
 
     async def generate_response(
@@ -158,9 +279,12 @@ class LLMClient(ABC):
         for message in messages:
             message.content = self._clean_input(message.content)
 
-        response = await self._generate_response_with_retry(
-            messages, response_model, max_tokens, model_size
-        )
+        max_tokens = self._fit_max_tokens(messages, max_tokens)
+        with self._observe(messages) as usage:
+            response = await self._generate_response_with_retry(
+                messages, response_model, max_tokens, model_size
+            )
+        self._observe_response(response, usage)
 
         if self.cache_enabled and self.cache_dir is not None:
             cache_key = self._get_cache_key(messages)
@@ -16,8 +16,13 @@ limitations under the License.
 
 from enum import Enum
 
+from ..helpers import DEFAULT_HTTP_CONNECT_TIMEOUT, DEFAULT_HTTP_TIMEOUT
+
 DEFAULT_MAX_TOKENS = 8192
+DEFAULT_CONTEXT_WINDOW = 128_000
 DEFAULT_TEMPERATURE = 0
+DEFAULT_NETWORK_RETRIES = 2
+DEFAULT_VALIDATION_RETRIES = 2
 
 
 class ModelSize(Enum):
@@ -42,6 +47,12 @@ class LLMConfig:
         temperature: float = DEFAULT_TEMPERATURE,
         max_tokens: int = DEFAULT_MAX_TOKENS,
         small_model: str | None = None,
+        timeout: float = DEFAULT_HTTP_TIMEOUT,
+        connect_timeout: float = DEFAULT_HTTP_CONNECT_TIMEOUT,
+        auto_max_tokens: bool = False,
+        context_window: int = DEFAULT_CONTEXT_WINDOW,
+        network_retries: int = DEFAULT_NETWORK_RETRIES,
+        validation_retries: int = DEFAULT_VALIDATION_RETRIES,
     ):
         """
         Initialize the LLMConfig with the provided parameters.
@@ -59,6 +70,27 @@ class LLMConfig:
 
                 small_model (str, optional): The specific LLM model to use for generating responses of simpler prompts.
                                                                 Defaults to "gpt-4.1-nano".
+
+                timeout (float, optional): The timeout in seconds for a request to the LLM API.
+                                                                Defaults to 600 seconds.
+
+                connect_timeout (float, optional): The timeout in seconds for establishing a connection to the LLM API.
+                                                                Defaults to 5 seconds.
+
+                auto_max_tokens (bool, optional): Whether to size max_tokens to the room left in the context window
+                                                                after the prompt, counted with count_tokens. Prompts that don't
+                                                                fit raise PromptTooLongError before the request is sent. Defaults to False.
+
+                context_window (int, optional): The model's context window in tokens, used by auto_max_tokens.
+                                                                Defaults to 128000.
+
+                network_retries (int, optional): How many times a request that failed with a connection error, timeout or
+                                                                server error is resent, with exponential backoff and an unchanged prompt.
+                                                                Defaults to 2.
+
+                validation_retries (int, optional): How many times a request is retried after the response failed to parse
+                                                                or validate. The error is appended to the prompt so the model can correct it.
+                                                                Defaults to 2.
         """
         self.base_url = base_url
         self.api_key = api_key
@@ -66,3 +98,9 @@ class LLMConfig:
         self.small_model = small_model
         self.temperature = temperature
         self.max_tokens = max_tokens
+        self.timeout = timeout
+        self.connect_timeout = connect_timeout
+        self.auto_max_tokens = auto_max_tokens
+        self.context_window = context_window
+        self.network_retries = network_retries
+        self.validation_retries = validation_retries
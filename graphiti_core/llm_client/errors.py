@@ -37,3 +37,25 @@ class EmptyResponseError(Exception):
     def __init__(self, message: str):
         self.message = message
         super().__init__(self.message)
+
+
+class PromptTooLongError(Exception):
+    """Exception raised when a prompt doesn't fit in the model's context window."""
+
+    def __init__(self, prompt_tokens: int, context_window: int):
+        self.prompt_tokens = prompt_tokens
+        self.context_window = context_window
+        self.message = (
+            f'Prompt of about {prompt_tokens} tokens does not fit in the context window of '
+            f'{context_window} tokens'
+        )
+        super().__init__(self.message)
+
+
+class RetryBudgetExhaustedError(Exception):
+    """Exception raised when an operation has used up its shared LLM retry budget."""
+
+    def __init__(self, max_retries: int):
+        self.max_retries = max_retries
+        self.message = f'Retry budget of {max_retries} LLM retries for this operation is exhausted'
+        super().__init__(self.message)
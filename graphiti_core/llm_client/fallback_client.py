@@ -0,0 +1,110 @@
+"""
+Copyright 2024, Zep Software, Inc.
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+"""
+
+import logging
+import typing
+
+import httpx
+import openai
+from pydantic import BaseModel
+
+from ..prompts.models import Message
+from .client import LLMClient
+from .config import DEFAULT_MAX_TOKENS, ModelSize
+from .errors import RateLimitError
+
+logger = logging.getLogger(__name__)
+
+
+def is_fallback_error(exception: Exception) -> bool:
+    """Whether an error from one provider should be retried on the next one."""
+    if isinstance(exception, RateLimitError | openai.RateLimitError):
+        return True
+
+    # Network failures: timeouts, refused connections, and provider-side outages
+    if isinstance(exception, httpx.TransportError | openai.APIConnectionError):
+        return True
+    if isinstance(exception, openai.InternalServerError):
+        return True
+
+    return (
+        isinstance(exception, httpx.HTTPStatusError) and 500 <= exception.response.status_code < 600
+    )
+
+
+class FallbackLLMClient(LLMClient):
+    """
+    FallbackLLMClient routes requests across an ordered list of LLM clients.
+
+    Each request is sent to the first client. If it fails with a rate limit or network error,
+    the request is transparently retried on the next client, and so on until one succeeds.
+    Any other error (e.g. a refusal or an invalid response) is raised immediately.
+
+    Each wrapped client keeps its own configuration, retries, and cache, so providers with
+    different models can be mixed freely.
+    """
+
+    def __init__(self, clients: list[LLMClient]):
+        if not clients:
+            raise ValueError('FallbackLLMClient requires at least one LLM client')
+
+        super().__init__(clients[0].config, cache=False)
+        self.clients = clients
+
+    async def _generate_response(
+        self,
+        messages: list[Message],
+        response_model: type[BaseModel] | None = None,
+        max_tokens: int = DEFAULT_MAX_TOKENS,
+        model_size: ModelSize = ModelSize.medium,
+    ) -> dict[str, typing.Any]:
+        return await self.generate_response(messages, response_model, max_tokens, model_size)
+
+    async def generate_response(
+        self,
+        messages: list[Message],
+        response_model: type[BaseModel] | None = None,
+        max_tokens: int | None = None,
+        model_size: ModelSize = ModelSize.medium,
+    ) -> dict[str, typing.Any]:
+        last_error: Exception | None = None
+        with self._observe(messages) as usage:
+            for index, client in enumerate(self.clients):
+                # Clients mutate the messages they are given, so each attempt gets a fresh copy
+                attempt_messages = [message.model_copy() for message in messages]
+                try:
+                    response = await client.generate_response(
+                        attempt_messages, response_model, max_tokens, model_size
+                    )
+                except Exception as e:
+                    if not is_fallback_error(e):
+                        raise
+                    logger.warning(
+                        f'LLM provider {client.__class__.__name__} ({client.model}) failed: {e}. '
+                        f'Trying next provider.'
+                    )
+                    last_error = e
+                    continue
+
+                logger.debug(
+                    f'LLM request served by provider {index} '
+                    f'{client.__class__.__name__} ({client.model})'
+                )
+                self._observe_response(response, usage)
+                return response
+
+            assert last_error is not None
+            raise last_error
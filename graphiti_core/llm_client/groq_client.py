@@ -24,7 +24,7 @@ from groq.types.chat import ChatCompletionMessageParam
 from pydantic import BaseModel
 
 from ..prompts.models import Message
-from .client import LLMClient
+from .client import LLMClient, get_model_override
 from .config import LLMConfig, ModelSize
 from .errors import RateLimitError
 
@@ -59,12 +59,14 @@ class GroqClient(LLMClient):
                 msgs.append({'role': 'system', 'content': m.content})
         try:
             response = await self.client.chat.completions.create(
-                model=self.model or DEFAULT_MODEL,
+                model=get_model_override() or self.model or DEFAULT_MODEL,
                 messages=msgs,
                 temperature=self.temperature,
                 max_tokens=max_tokens or self.max_tokens,
                 response_format={'type': 'json_object'},
             )
+            if response.usage is not None:
+                self._record_usage(response.usage.prompt_tokens, response.usage.completion_tokens)
             result = response.choices[0].message.content or ''
             return json.loads(result)
         except groq.RateLimitError as e:
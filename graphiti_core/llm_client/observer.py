@@ -0,0 +1,150 @@
+"""
+Copyright 2024, Zep Software, Inc.
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+"""
+
+import json
+import logging
+import queue
+import threading
+import typing
+from abc import ABC, abstractmethod
+from pathlib import Path
+
+from pydantic import BaseModel
+
+from ..prompts.models import Message
+from ..utils.datetime_utils import utc_now
+from .config import LLMConfig
+
+logger = logging.getLogger(__name__)
+
+REDACTED = '***'
+
+
+class TokenUsage(BaseModel):
+    """Tokens consumed by one generate_response call, summed over its retries."""
+
+    input_tokens: int = 0
+    output_tokens: int = 0
+
+    def add(self, input_tokens: int | None, output_tokens: int | None):
+        self.input_tokens += input_tokens or 0
+        self.output_tokens += output_tokens or 0
+
+
+class LLMObserver(ABC):
+    """
+    Hook for auditing LLM traffic. Set it as `llm_client.observer` to be notified of the
+    messages sent for each generate_response call and of the response that came back.
+    Observers are called inline, so they should not block.
+    """
+
+    @abstractmethod
+    def on_request(self, messages: list[Message]):
+        pass
+
+    @abstractmethod
+    def on_response(self, response: dict[str, typing.Any], usage: TokenUsage):
+        pass
+
+    def close(self):
+        """Release the observer's resources. Called by Graphiti.close."""
+        pass
+
+
+def redact_config(config: LLMConfig) -> dict[str, typing.Any]:
+    """Return the config as a dict that is safe to log, with the API key redacted."""
+    data = dict(vars(config))
+    if data.get('api_key'):
+        data['api_key'] = REDACTED
+    return data
+
+
+class JSONLFileObserver(LLMObserver):
+    """
+    Appends every request and response to a JSONL file, one record per line. If a config is
+    given, it is written with its API key redacted alongside each request.
+
+    Records are queued and written by a background thread, so LLM calls never wait on the file.
+    Call flush to wait until the records queued so far are written, and close to write the
+    rest and stop the thread. Records made after close are written directly.
+    """
+
+    def __init__(self, path: str | Path, config: LLMConfig | None = None):
+        self.path = Path(path)
+        self.config = redact_config(config) if config is not None else None
+        # None tells the writer thread to stop
+        self._queue: queue.Queue[str | None] = queue.Queue()
+        self._lock = threading.Lock()
+        self._closed = False
+        self._writer = threading.Thread(target=self._write_queued, daemon=True)
+        self._writer.start()
+
+    def _append(self, lines: list[str]):
+        try:
+            with self.path.open('a', encoding='utf-8') as f:
+                f.writelines(line + '\n' for line in lines)
+        except OSError as e:
+            logger.error(f'Failed to write {len(lines)} LLM records to {self.path}: {e}')
+
+    def _write_queued(self):
+        stopped = False
+        while not stopped:
+            queued = [self._queue.get()]
+            # Everything queued in the meantime is written with the same open
+            while not self._queue.empty():
+                queued.append(self._queue.get_nowait())
+            lines = [line for line in queued if line is not None]
+            stopped = len(lines) < len(queued)
+            try:
+                if lines:
+                    self._append(lines)
+            finally:
+                for _ in queued:
+                    self._queue.task_done()
+
+    def _write(self, record: dict[str, typing.Any]):
+        record = {'timestamp': utc_now().isoformat(), **record}
+        line = json.dumps(record, default=str)
+        with self._lock:
+            if not self._closed:
+                self._queue.put(line)
+                return
+        self._append([line])
+
+    def flush(self):
+        """Wait until every record queued so far has been written to the file."""
+        self._queue.join()
+
+    def close(self):
+        """Write the queued records and stop the writer thread."""
+        with self._lock:
+            if self._closed:
+                return
+            self._closed = True
+            self._queue.put(None)
+        self._writer.join()
+
+    def on_request(self, messages: list[Message]):
+        record: dict[str, typing.Any] = {
+            'type': 'request',
+            'messages': [message.model_dump() for message in messages],
+        }
+        if self.config is not None:
+            record['config'] = self.config
+        self._write(record)
+
+    def on_response(self, response: dict[str, typing.Any], usage: TokenUsage):
+        self._write({'type': 'response', 'response': response, 'usage': usage.model_dump()})
@@ -14,6 +14,7 @@ See the License for the specific language governing permissions and
 limitations under the License.
 """
 
+import asyncio
 import json
 import logging
 import typing
@@ -25,7 +26,12 @@ from openai.types.chat import ChatCompletionMessageParam
 from pydantic import BaseModel
 
 from ..prompts.models import Message
-from .client import MULTILINGUAL_EXTRACTION_RESPONSES, LLMClient
+from .client import (
+    MULTILINGUAL_EXTRACTION_RESPONSES,
+    LLMClient,
+    consume_retry_budget,
+    get_model_override,
+)
 from .config import DEFAULT_MAX_TOKENS, LLMConfig, ModelSize
 from .errors import RateLimitError, RefusalError
 
@@ -43,8 +49,9 @@ class BaseOpenAIClient(LLMClient):
     reducing code duplication while allowing for implementation-specific differences.
     """
 
-    # Class-level constants
-    MAX_RETRIES: ClassVar[int] = 2
+    # Seconds before the first resend of a request that failed with a network error; the delay
+    # doubles with each further attempt
+    NETWORK_RETRY_BASE_DELAY: ClassVar[float] = 0.5
 
     def __init__(
         self,
@@ -100,11 +107,19 @@ class BaseOpenAIClient(LLMClient):
 
     def _get_model_for_size(self, model_size: ModelSize) -> str:
         """Get the appropriate model name based on the requested size."""
+        model_override = get_model_override()
+        if model_override is not None:
+            return model_override
         if model_size == ModelSize.small:
             return self.small_model or DEFAULT_SMALL_MODEL
         else:
             return self.model or DEFAULT_MODEL
 
+    def _record_response_usage(self, response: Any):
+        usage = getattr(response, 'usage', None)
+        if usage is not None:
+            self._record_usage(usage.prompt_tokens, usage.completion_tokens)
+
     def _handle_structured_response(self, response: Any) -> dict[str, Any]:
         """Handle structured response parsing and validation."""
         response_object = response.choices[0].message
@@ -141,6 +156,7 @@ class BaseOpenAIClient(LLMClient):
                     max_tokens=max_tokens or self.max_tokens,
                     response_model=response_model,
                 )
+                self._record_response_usage(response)
                 return self._handle_structured_response(response)
             else:
                 response = await self._create_completion(
@@ -149,6 +165,7 @@ class BaseOpenAIClient(LLMClient):
                     temperature=self.temperature,
                     max_tokens=max_tokens or self.max_tokens,
                 )
+                self._record_response_usage(response)
                 return self._handle_json_response(response)
 
         except openai.LengthFinishReasonError as e:
@@ -166,52 +183,78 @@ class BaseOpenAIClient(LLMClient):
         max_tokens: int | None = None,
         model_size: ModelSize = ModelSize.medium,
     ) -> dict[str, typing.Any]:
-        """Generate a response with retry logic and error handling."""
+        """
+        Generate a response with retry logic and error handling.
+
+        Network errors and invalid responses are retried separately, up to the config's
+        network_retries and validation_retries. A request that failed on the network is resent
+        unchanged after a backoff, while after an invalid response the error is added to the
+        prompt so that the model can correct it.
+        """
         if max_tokens is None:
             max_tokens = self.max_tokens
 
-        retry_count = 0
-        last_error = None
+        network_retries = 0
+        validation_retries = 0
 
         # Add multilingual extraction instructions
         messages[0].content += MULTILINGUAL_EXTRACTION_RESPONSES
 
-        while retry_count <= self.MAX_RETRIES:
-            try:
-                response = await self._generate_response(
-                    messages, response_model, max_tokens, model_size
-                )
-                return response
-            except (RateLimitError, RefusalError):
-                # These errors should not trigger retries
-                raise
-            except (openai.APITimeoutError, openai.APIConnectionError, openai.InternalServerError):
-                # Let OpenAI's client handle these retries
-                raise
-            except Exception as e:
-                last_error = e
-
-                # Don't retry if we've hit the max retries
-                if retry_count >= self.MAX_RETRIES:
-                    logger.error(f'Max retries ({self.MAX_RETRIES}) exceeded. Last error: {e}')
+        max_tokens = self._fit_max_tokens(messages, max_tokens)
+        with self._observe(messages) as usage:
+            while True:
+                try:
+                    response = await self._generate_response(
+                        messages, response_model, max_tokens, model_size
+                    )
+                    self._observe_response(response, usage)
+                    return response
+                except (RateLimitError, RefusalError):
+                    # These errors should not trigger retries
                     raise
-
-                retry_count += 1
-
-                # Construct a detailed error message for the LLM
-                error_context = (
-                    f'The previous response attempt was invalid. '
-                    f'Error type: {e.__class__.__name__}. '
-                    f'Error details: {str(e)}. '
-                    f'Please try again with a valid response, ensuring the output matches '
-                    f'the expected format and constraints.'
-                )
-
-                error_message = Message(role='user', content=error_context)
-                messages.append(error_message)
-                logger.warning(
-                    f'Retrying after application error (attempt {retry_count}/{self.MAX_RETRIES}): {e}'
-                )
-
-        # If we somehow get here, raise the last error
-        raise last_error or Exception('Max retries exceeded with no specific error')
+                except (
+                    openai.APITimeoutError,
+                    openai.APIConnectionError,
+                    openai.InternalServerError,
+                ) as e:
+                    if network_retries >= self.config.network_retries:
+                        logger.error(
+                            f'Max network retries ({self.config.network_retries}) exceeded. '
+                            f'Last error: {e}'
+                        )
+                        raise
+
+                    consume_retry_budget(e)
+                    network_retries += 1
+                    delay = self.NETWORK_RETRY_BASE_DELAY * 2 ** (network_retries - 1)
+                    logger.warning(
+                        f'Retrying after network error in {delay}s '
+                        f'(attempt {network_retries}/{self.config.network_retries}): {e}'
+                    )
+                    await asyncio.sleep(delay)
+                except Exception as e:
+                    if validation_retries >= self.config.validation_retries:
+                        logger.error(
+                            f'Max validation retries ({self.config.validation_retries}) exceeded. '
+                            f'Last error: {e}'
+                        )
+                        raise
+
+                    consume_retry_budget(e)
+                    validation_retries += 1
+
+                    # Construct a detailed error message for the LLM
+                    error_context = (
+                        f'The previous response attempt was invalid. '
+                        f'Error type: {e.__class__.__name__}. '
+                        f'Error details: {str(e)}. '
+                        f'Please try again with a valid response, ensuring the output matches '
+                        f'the expected format and constraints.'
+                    )
+
+                    error_message = Message(role='user', content=error_context)
+                    messages.append(error_message)
+                    logger.warning(
+                        f'Retrying after invalid response '
+                        f'(attempt {validation_retries}/{self.config.validation_retries}): {e}'
+                    )
@@ -16,6 +16,7 @@ limitations under the License.
 
 import typing
 
+import httpx
 from openai import AsyncOpenAI
 from openai.types.chat import ChatCompletionMessageParam
 from pydantic import BaseModel
@@ -41,14 +42,19 @@ class OpenAIClient(BaseOpenAIClient):
         cache: bool = False,
         client: typing.Any = None,
         max_tokens: int = DEFAULT_MAX_TOKENS,
+        http_client: httpx.AsyncClient | None = None,
     ):
         """
         Initialize the OpenAIClient with the provided configuration, cache setting, and client.
 
         Args:
-            config (LLMConfig | None): The configuration for the LLM client, including API key, model, base URL, temperature, and max tokens.
+            config (LLMConfig | None): The configuration for the LLM client, including API key, model, base URL, temperature, max tokens, and timeouts.
             cache (bool): Whether to use caching for responses. Defaults to False.
             client (Any | None): An optional async client instance to use. If not provided, a new AsyncOpenAI client is created.
+                An AsyncOpenAI client is copied with its own retries disabled, as network errors
+                are retried up to config.network_retries.
+            http_client (httpx.AsyncClient | None): An optional HTTP client for the new AsyncOpenAI client.
+                Pass the same client to OpenAIEmbedder to share a single connection pool. Ignored if client is provided.
         """
         super().__init__(config, cache, max_tokens)
 
@@ -56,7 +62,16 @@ class OpenAIClient(BaseOpenAIClient):
             config = LLMConfig()
 
         if client is None:
-            self.client = AsyncOpenAI(api_key=config.api_key, base_url=config.base_url)
+            self.client = AsyncOpenAI(
+                api_key=config.api_key,
+                base_url=config.base_url,
+                timeout=httpx.Timeout(config.timeout, connect=config.connect_timeout),
+                http_client=http_client,
+                # Network errors are retried by generate_response, up to config.network_retries
+                max_retries=0,
+            )
+        elif isinstance(client, AsyncOpenAI):
+            self.client = client.with_options(max_retries=0)
         else:
             self.client = client
 
@@ -0,0 +1,80 @@
+"""
+Copyright 2024, Zep Software, Inc.
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+"""
+
+import json
+import threading
+import typing
+from pathlib import Path
+
+from pydantic import BaseModel
+
+from ..prompts.models import Message
+from ..utils.datetime_utils import utc_now
+from .client import LLMClient
+from .config import DEFAULT_MAX_TOKENS, ModelSize
+
+
+class TeeLLMClient(LLMClient):
+    """
+    TeeLLMClient forwards every request to a wrapped LLM client unchanged and appends each
+    request and its response to a JSONL file, one record per call. Recordings can be replayed
+    with MockLLMClient.from_recording to build deterministic regression tests from a real run.
+
+    Failed calls are not recorded. The wrapped client keeps its own configuration, retries,
+    and cache.
+    """
+
+    def __init__(self, client: LLMClient, path: str | Path):
+        super().__init__(client.config, cache=False)
+        self.client = client
+        self.path = Path(path)
+        self._lock = threading.Lock()
+
+    async def _generate_response(
+        self,
+        messages: list[Message],
+        response_model: type[BaseModel] | None = None,
+        max_tokens: int = DEFAULT_MAX_TOKENS,
+        model_size: ModelSize = ModelSize.medium,
+    ) -> dict[str, typing.Any]:
+        return await self.generate_response(messages, response_model, max_tokens, model_size)
+
+    async def generate_response(
+        self,
+        messages: list[Message],
+        response_model: type[BaseModel] | None = None,
+        max_tokens: int | None = None,
+        model_size: ModelSize = ModelSize.medium,
+    ) -> dict[str, typing.Any]:
+        # The wrapped client may mutate the messages, so the ones it was given are recorded
+        recorded_messages = [message.model_dump() for message in messages]
+        response = await self.client.generate_response(
+            messages, response_model, max_tokens, model_size
+        )
+        self._write(
+            {
+                'timestamp': utc_now().isoformat(),
+                'messages': recorded_messages,
+                'response_model': response_model.__name__ if response_model else None,
+                'response': response,
+            }
+        )
+        return response
+
+    def _write(self, record: dict[str, typing.Any]):
+        line = json.dumps(record, default=str)
+        with self._lock, self.path.open('a', encoding='utf-8') as f:
+            f.write(line + '\n')
@@ -14,19 +14,20 @@ See the License for the specific language governing permissions and
 limitations under the License.
 """
 
+# Episodic edges are merged on the (episode, entity) pair, so reprocessing never duplicates them
 EPISODIC_EDGE_SAVE = """
         MATCH (episode:Episodic {uuid: $episode_uuid}) 
         MATCH (node:Entity {uuid: $entity_uuid}) 
-        MERGE (episode)-[r:MENTIONS {uuid: $uuid}]->(node)
-        SET r = {uuid: $uuid, group_id: $group_id, created_at: $created_at}
+        MERGE (episode)-[r:MENTIONS]->(node)
+        ON CREATE SET r.uuid = $uuid, r.group_id = $group_id, r.created_at = $created_at
         RETURN r.uuid AS uuid"""
 
 EPISODIC_EDGE_SAVE_BULK = """
     UNWIND $episodic_edges AS edge
     MATCH (episode:Episodic {uuid: edge.source_node_uuid}) 
     MATCH (node:Entity {uuid: edge.target_node_uuid}) 
-    MERGE (episode)-[r:MENTIONS {uuid: edge.uuid}]->(node)
-    SET r = {uuid: edge.uuid, group_id: edge.group_id, created_at: edge.created_at}
+    MERGE (episode)-[r:MENTIONS]->(node)
+    ON CREATE SET r.uuid = edge.uuid, r.group_id = edge.group_id, r.created_at = edge.created_at
     RETURN r.uuid AS uuid
 """
 
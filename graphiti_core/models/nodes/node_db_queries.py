@@ -17,7 +17,8 @@ limitations under the License.
 EPISODIC_NODE_SAVE = """
         MERGE (n:Episodic {uuid: $uuid})
         SET n = {uuid: $uuid, name: $name, group_id: $group_id, source_description: $source_description, source: $source, content: $content, 
-        entity_edges: $entity_edges, created_at: $created_at, valid_at: $valid_at}
+        entity_edges: $entity_edges, created_at: $created_at, valid_at: $valid_at, external_id: $external_id,
+        language: $language, content_embedding: $content_embedding}
         RETURN n.uuid AS uuid"""
 
 EPISODIC_NODE_SAVE_BULK = """
@@ -25,7 +26,9 @@ EPISODIC_NODE_SAVE_BULK = """
     MERGE (n:Episodic {uuid: episode.uuid})
     SET n = {uuid: episode.uuid, name: episode.name, group_id: episode.group_id, source_description: episode.source_description, 
         source: episode.source, content: episode.content, 
-    entity_edges: episode.entity_edges, created_at: episode.created_at, valid_at: episode.valid_at}
+    entity_edges: episode.entity_edges, created_at: episode.created_at, valid_at: episode.valid_at,
+    external_id: episode.external_id, language: episode.language,
+    content_embedding: episode.content_embedding}
     RETURN n.uuid AS uuid
 """
 
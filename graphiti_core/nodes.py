@@ -25,10 +25,11 @@ from uuid import uuid4
 from pydantic import BaseModel, Field
 from typing_extensions import LiteralString
 
-from graphiti_core.driver.driver import GraphDriver
+from graphiti_core.driver.driver import GraphDriver, execute_upsert
 from graphiti_core.embedder import EmbedderClient
-from graphiti_core.errors import NodeNotFoundError
-from graphiti_core.helpers import DEFAULT_DATABASE, parse_db_date
+from graphiti_core.encryption import decrypt_field, encrypt_field
+from graphiti_core.errors import InvalidEpisodeTypeError, NodeNotFoundError
+from graphiti_core.helpers import DEFAULT_DATABASE, parse_db_date, semaphore_gather
 from graphiti_core.models.nodes.node_db_queries import (
     COMMUNITY_NODE_SAVE,
     ENTITY_NODE_SAVE,
@@ -38,6 +39,16 @@ from graphiti_core.utils.datetime_utils import utc_now
 
 logger = logging.getLogger(__name__)
 
+# How many of an entity's most recent mentions are embedded with it, and how much of each
+MAX_EMBEDDING_MENTIONS = 3
+MAX_EMBEDDING_MENTION_CHARS = 1000
+
+# Node properties holding, as JSON, when each attribute's current value was observed and the
+# values it replaced. They are read into EntityNode.attribute_metadata rather than attributes
+ATTRIBUTE_OBSERVED_AT_KEY = 'attribute_observed_at'
+ATTRIBUTE_HISTORY_KEY = 'attribute_history'
+ATTRIBUTE_METADATA_KEYS = frozenset({ATTRIBUTE_OBSERVED_AT_KEY, ATTRIBUTE_HISTORY_KEY})
+
 ENTITY_NODE_RETURN: LiteralString = """
         RETURN
             n.uuid As uuid, 
@@ -85,6 +96,27 @@ class EpisodeType(Enum):
         logger.error(f'Episode type: {episode_type} not implemented')
         raise NotImplementedError
 
+    @staticmethod
+    def resolve(
+        episode_type: 'EpisodeType | str', aliases: dict[str, 'EpisodeType'] | None = None
+    ) -> 'EpisodeType':
+        """
+        Resolve an episode source to an EpisodeType, consulting the alias map before the base
+        types. Alias names are matched case-insensitively.
+        """
+        if isinstance(episode_type, EpisodeType):
+            return episode_type
+
+        aliases = {name.lower(): value for name, value in (aliases or {}).items()}
+        key = episode_type.strip().lower()
+        if key in aliases:
+            return aliases[key]
+        if key in EpisodeType._value2member_map_:
+            return EpisodeType(key)
+
+        accepted = [member.value for member in EpisodeType] + sorted(aliases)
+        raise InvalidEpisodeTypeError(episode_type, accepted)
+
 
 class Node(BaseModel, ABC):
     uuid: str = Field(default_factory=lambda: str(uuid4()))
@@ -149,19 +181,44 @@ class EpisodicNode(Node):
         description='list of entity edges referenced in this episode',
         default_factory=list,
     )
+    external_id: str | None = Field(
+        default=None,
+        description='caller-supplied id of the episode in its source system, used for upserts',
+    )
+    language: str | None = Field(
+        default=None,
+        description='language of the episode content, e.g. "Japanese", given to extraction so '
+        'that names are kept in their original script',
+    )
+    content_embedding: list[float] | None = Field(
+        default=None, description='embedding of the episode content'
+    )
+
+    async def generate_content_embedding(self, embedder: EmbedderClient):
+        start = time()
+        text = self.content.replace('\n', ' ')
+        self.content_embedding = await embedder.create(input_data=[text])
+        end = time()
+        logger.debug(f'embedded episode {self.uuid} in {end - start} ms')
+
+        return self.content_embedding
 
     async def save(self, driver: GraphDriver):
-        result = await driver.execute_query(
+        result = await execute_upsert(
+            driver,
             EPISODIC_NODE_SAVE,
             uuid=self.uuid,
             name=self.name,
             group_id=self.group_id,
             source_description=self.source_description,
-            content=self.content,
+            content=encrypt_field(self.group_id, self.content, driver),
             entity_edges=self.entity_edges,
             created_at=self.created_at,
             valid_at=self.valid_at,
             source=self.source.value,
+            external_id=self.external_id,
+            language=self.language,
+            content_embedding=self.content_embedding,
             database_=DEFAULT_DATABASE,
         )
 
@@ -182,14 +239,16 @@ class EpisodicNode(Node):
             e.group_id AS group_id,
             e.source_description AS source_description,
             e.source AS source,
-            e.entity_edges AS entity_edges
+            e.entity_edges AS entity_edges,
+            e.external_id AS external_id,
+            e.language AS language
         """,
             uuid=uuid,
             database_=DEFAULT_DATABASE,
             routing_='r',
         )
 
-        episodes = [get_episodic_node_from_record(record) for record in records]
+        episodes = [get_episodic_node_from_record(record, driver) for record in records]
 
         if len(episodes) == 0:
             raise NodeNotFoundError(uuid)
@@ -210,14 +269,16 @@ class EpisodicNode(Node):
             e.group_id AS group_id,
             e.source_description AS source_description,
             e.source AS source,
-            e.entity_edges AS entity_edges
+            e.entity_edges AS entity_edges,
+            e.external_id AS external_id,
+            e.language AS language
         """,
             uuids=uuids,
             database_=DEFAULT_DATABASE,
             routing_='r',
         )
 
-        episodes = [get_episodic_node_from_record(record) for record in records]
+        episodes = [get_episodic_node_from_record(record, driver) for record in records]
 
         return episodes
 
@@ -247,7 +308,9 @@ class EpisodicNode(Node):
             e.group_id AS group_id,
             e.source_description AS source_description,
             e.source AS source,
-            e.entity_edges AS entity_edges
+            e.entity_edges AS entity_edges,
+            e.external_id AS external_id,
+            e.language AS language
         ORDER BY e.uuid DESC
         """
             + limit_query,
@@ -258,12 +321,45 @@ class EpisodicNode(Node):
             routing_='r',
         )
 
-        episodes = [get_episodic_node_from_record(record) for record in records]
+        episodes = [get_episodic_node_from_record(record, driver) for record in records]
 
         return episodes
 
     @classmethod
-    async def get_by_entity_node_uuid(cls, driver: GraphDriver, entity_node_uuid: str):
+    async def get_by_external_id(cls, driver: GraphDriver, group_id: str, external_id: str):
+        records, _, _ = await driver.execute_query(
+            """
+        MATCH (e:Episodic {group_id: $group_id, external_id: $external_id})
+            RETURN e.content AS content,
+            e.created_at AS created_at,
+            e.valid_at AS valid_at,
+            e.uuid AS uuid,
+            e.name AS name,
+            e.group_id AS group_id,
+            e.source_description AS source_description,
+            e.source AS source,
+            e.entity_edges AS entity_edges,
+            e.external_id AS external_id,
+            e.language AS language
+        ORDER BY e.created_at
+        LIMIT 1
+        """,
+            group_id=group_id,
+            external_id=external_id,
+            database_=DEFAULT_DATABASE,
+            routing_='r',
+        )
+
+        episodes = [get_episodic_node_from_record(record, driver) for record in records]
+
+        return episodes[0] if episodes else None
+
+    @classmethod
+    async def get_by_entity_node_uuid(
+        cls, driver: GraphDriver, entity_node_uuid: str, limit: int | None = None
+    ):
+        limit_query: LiteralString = 'LIMIT $limit' if limit is not None else ''
+
         records, _, _ = await driver.execute_query(
             """
         MATCH (e:Episodic)-[r:MENTIONS]->(n:Entity {uuid: $entity_node_uuid})
@@ -276,28 +372,70 @@ class EpisodicNode(Node):
             e.group_id AS group_id,
             e.source_description AS source_description,
             e.source AS source,
-            e.entity_edges AS entity_edges
-        """,
+            e.entity_edges AS entity_edges,
+            e.external_id AS external_id,
+            e.language AS language
+        ORDER BY e.valid_at DESC
+        """
+            + limit_query,
             entity_node_uuid=entity_node_uuid,
+            limit=limit,
             database_=DEFAULT_DATABASE,
             routing_='r',
         )
 
-        episodes = [get_episodic_node_from_record(record) for record in records]
+        episodes = [get_episodic_node_from_record(record, driver) for record in records]
 
         return episodes
 
 
+class EntityEmbeddingText(Enum):
+    """
+    The text an entity's name_embedding is computed from. Richer text can help retrieval when
+    names alone are ambiguous, but every entity of a graph should use the same choice so that
+    their embeddings are comparable.
+    """
+
+    name = 'name'
+    name_and_summary = 'name_and_summary'
+    name_summary_and_mentions = 'name_summary_and_mentions'
+
+
 class EntityNode(Node):
     name_embedding: list[float] | None = Field(default=None, description='embedding of the name')
     summary: str = Field(description='regional summary of surrounding edges', default_factory=str)
     attributes: dict[str, Any] = Field(
         default={}, description='Additional attributes of the node. Dependent on node labels'
     )
+    attribute_metadata: dict[str, str] = Field(
+        default={},
+        description='When each attribute value was observed and the values it replaced, kept by '
+        'the keep_newest and append_history attribute conflict policies',
+    )
 
-    async def generate_name_embedding(self, embedder: EmbedderClient):
+    def embedding_text(
+        self,
+        composition: EntityEmbeddingText = EntityEmbeddingText.name,
+        mentions: list[str] | None = None,
+    ) -> str:
+        parts = [self.name]
+        if composition != EntityEmbeddingText.name and self.summary:
+            parts.append(self.summary)
+        if composition == EntityEmbeddingText.name_summary_and_mentions:
+            parts.extend(
+                mention[:MAX_EMBEDDING_MENTION_CHARS]
+                for mention in (mentions or [])[:MAX_EMBEDDING_MENTIONS]
+            )
+        return '\n'.join(parts).replace('\n', ' ')
+
+    async def generate_name_embedding(
+        self,
+        embedder: EmbedderClient,
+        composition: EntityEmbeddingText = EntityEmbeddingText.name,
+        mentions: list[str] | None = None,
+    ):
         start = time()
-        text = self.name.replace('\n', ' ')
+        text = self.embedding_text(composition, mentions)
         self.name_embedding = await embedder.create(input_data=[text])
         end = time()
         logger.debug(f'embedded {text} in {end - start} ms')
@@ -324,15 +462,17 @@ class EntityNode(Node):
             'name': self.name,
             'name_embedding': self.name_embedding,
             'group_id': self.group_id,
-            'summary': self.summary,
+            'summary': encrypt_field(self.group_id, self.summary, driver),
             'created_at': self.created_at,
         }
 
         entity_data.update(self.attributes or {})
+        entity_data.update(self.attribute_metadata)
 
-        result = await driver.execute_query(
+        result = await execute_upsert(
+            driver,
             ENTITY_NODE_SAVE,
-            labels=self.labels + ['Entity'],
+            labels=list(dict.fromkeys(self.labels + ['Entity'])),
             entity_data=entity_data,
             database_=DEFAULT_DATABASE,
         )
@@ -356,7 +496,7 @@ class EntityNode(Node):
             routing_='r',
         )
 
-        nodes = [get_entity_node_from_record(record) for record in records]
+        nodes = [get_entity_node_from_record(record, driver) for record in records]
 
         if len(nodes) == 0:
             raise NodeNotFoundError(uuid)
@@ -375,7 +515,7 @@ class EntityNode(Node):
             routing_='r',
         )
 
-        nodes = [get_entity_node_from_record(record) for record in records]
+        nodes = [get_entity_node_from_record(record, driver) for record in records]
 
         return nodes
 
@@ -407,7 +547,26 @@ class EntityNode(Node):
             routing_='r',
         )
 
-        nodes = [get_entity_node_from_record(record) for record in records]
+        nodes = [get_entity_node_from_record(record, driver) for record in records]
+
+        return nodes
+
+    @classmethod
+    async def get_by_community_uuid(cls, driver: GraphDriver, community_uuid: str):
+        records, _, _ = await driver.execute_query(
+            """
+        MATCH (c:Community {uuid: $community_uuid})-[:HAS_MEMBER]->(n:Entity)
+        """
+            + ENTITY_NODE_RETURN
+            + """
+        ORDER BY n.name, n.uuid
+        """,
+            community_uuid=community_uuid,
+            database_=DEFAULT_DATABASE,
+            routing_='r',
+        )
+
+        nodes = [get_entity_node_from_record(record, driver) for record in records]
 
         return nodes
 
@@ -417,12 +576,13 @@ class CommunityNode(Node):
     summary: str = Field(description='region summary of member nodes', default_factory=str)
 
     async def save(self, driver: GraphDriver):
-        result = await driver.execute_query(
+        result = await execute_upsert(
+            driver,
             COMMUNITY_NODE_SAVE,
             uuid=self.uuid,
             name=self.name,
             group_id=self.group_id,
-            summary=self.summary,
+            summary=encrypt_field(self.group_id, self.summary, driver),
             name_embedding=self.name_embedding,
             created_at=self.created_at,
             database_=DEFAULT_DATABASE,
@@ -472,7 +632,7 @@ class CommunityNode(Node):
             routing_='r',
         )
 
-        nodes = [get_community_node_from_record(record) for record in records]
+        nodes = [get_community_node_from_record(record, driver) for record in records]
 
         if len(nodes) == 0:
             raise NodeNotFoundError(uuid)
@@ -496,7 +656,7 @@ class CommunityNode(Node):
             routing_='r',
         )
 
-        communities = [get_community_node_from_record(record) for record in records]
+        communities = [get_community_node_from_record(record, driver) for record in records]
 
         return communities
 
@@ -533,13 +693,15 @@ class CommunityNode(Node):
             routing_='r',
         )
 
-        communities = [get_community_node_from_record(record) for record in records]
+        communities = [get_community_node_from_record(record, driver) for record in records]
 
         return communities
 
 
 # Node helpers
-def get_episodic_node_from_record(record: Any) -> EpisodicNode:
+def get_episodic_node_from_record(
+    record: Any, driver: GraphDriver | None = None
+) -> EpisodicNode:
     created_at = parse_db_date(record['created_at'])
     valid_at = parse_db_date(record['valid_at'])
     
@@ -549,7 +711,7 @@ def get_episodic_node_from_record(record: Any) -> EpisodicNode:
         raise ValueError(f"valid_at cannot be None for episode {record.get('uuid', 'unknown')}")
     
     return EpisodicNode(
-        content=record['content'],
+        content=decrypt_field(record['group_id'], record['content'], driver),
         created_at=created_at,
         valid_at=valid_at,
         uuid=record['uuid'],
@@ -558,17 +720,19 @@ def get_episodic_node_from_record(record: Any) -> EpisodicNode:
         name=record['name'],
         source_description=record['source_description'],
         entity_edges=record['entity_edges'],
+        external_id=record.get('external_id'),
+        language=record.get('language'),
     )
 
 
-def get_entity_node_from_record(record: Any) -> EntityNode:
+def get_entity_node_from_record(record: Any, driver: GraphDriver | None = None) -> EntityNode:
     entity_node = EntityNode(
         uuid=record['uuid'],
         name=record['name'],
         group_id=record['group_id'],
         labels=record['labels'],
         created_at=parse_db_date(record['created_at']),  # type: ignore
-        summary=record['summary'],
+        summary=decrypt_field(record['group_id'], record['summary'], driver),
         attributes=record['attributes'],
     )
 
@@ -578,24 +742,59 @@ def get_entity_node_from_record(record: Any) -> EntityNode:
     entity_node.attributes.pop('name_embedding', None)
     entity_node.attributes.pop('summary', None)
     entity_node.attributes.pop('created_at', None)
+    for key in ATTRIBUTE_METADATA_KEYS:
+        if key in entity_node.attributes:
+            entity_node.attribute_metadata[key] = entity_node.attributes.pop(key)
 
     return entity_node
 
 
-def get_community_node_from_record(record: Any) -> CommunityNode:
+def get_community_node_from_record(
+    record: Any, driver: GraphDriver | None = None
+) -> CommunityNode:
     return CommunityNode(
         uuid=record['uuid'],
         name=record['name'],
         group_id=record['group_id'],
         name_embedding=record['name_embedding'],
         created_at=parse_db_date(record['created_at']),  # type: ignore
-        summary=record['summary'],
+        summary=decrypt_field(record['group_id'], record['summary'], driver),
     )
 
 
-async def create_entity_node_embeddings(embedder: EmbedderClient, nodes: list[EntityNode]):
+async def create_entity_node_embeddings(
+    embedder: EmbedderClient,
+    nodes: list[EntityNode],
+    composition: EntityEmbeddingText = EntityEmbeddingText.name,
+    mentions: dict[str, list[str]] | None = None,
+):
     if not nodes:  # Handle empty list case
         return
-    name_embeddings = await embedder.create_batch([node.name for node in nodes])
+    mentions = mentions or {}
+    name_embeddings = await embedder.create_batch(
+        [node.embedding_text(composition, mentions.get(node.uuid)) for node in nodes]
+    )
     for node, name_embedding in zip(nodes, name_embeddings, strict=True):
         node.name_embedding = name_embedding
+
+
+async def get_entity_mentions(
+    driver: GraphDriver, nodes: list[EntityNode], episode: EpisodicNode | None = None
+) -> dict[str, list[str]]:
+    """
+    Returns the content of the most recent episodes mentioning each entity, keyed by entity
+    uuid. The given episode, e.g. one that is being added, counts as the most recent mention.
+    """
+    stored_mentions = await semaphore_gather(
+        *[
+            EpisodicNode.get_by_entity_node_uuid(driver, node.uuid, MAX_EMBEDDING_MENTIONS)
+            for node in nodes
+        ]
+    )
+
+    mentions: dict[str, list[str]] = {}
+    for node, episodes in zip(nodes, stored_mentions, strict=True):
+        if episode is not None:
+            episodes = [episode] + [e for e in episodes if e.uuid != episode.uuid]
+        mentions[node.uuid] = [e.content for e in episodes if e.content][:MAX_EMBEDDING_MENTIONS]
+    return mentions
@@ -1,4 +1,19 @@
-from .lib import prompt_library
+from .lib import (
+    DEFAULT_PROMPT_LIBRARY,
+    PromptLibraryWrapper,
+    SystemInstructions,
+    prompt_library,
+    use_prompt_library,
+    use_system_instructions,
+)
 from .models import Message
 
-__all__ = ['prompt_library', 'Message']
+__all__ = [
+    'prompt_library',
+    'Message',
+    'SystemInstructions',
+    'use_system_instructions',
+    'PromptLibraryWrapper',
+    'DEFAULT_PROMPT_LIBRARY',
+    'use_prompt_library',
+]
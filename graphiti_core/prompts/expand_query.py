@@ -0,0 +1,68 @@
+"""
+Copyright 2024, Zep Software, Inc.
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+"""
+
+from typing import Any, Protocol, TypedDict
+
+from pydantic import BaseModel, Field
+
+from .models import Message, PromptFunction, PromptVersion
+
+
+class ExpandedQuery(BaseModel):
+    expanded_query: str = Field(
+        ...,
+        description='The query rewritten as a fuller search query, with synonyms and related terms',
+    )
+    sub_queries: list[str] = Field(
+        default_factory=list,
+        description='Up to three shorter queries for distinct aspects of the query, if it has any',
+    )
+
+
+class Prompt(Protocol):
+    v1: PromptVersion
+
+
+class Versions(TypedDict):
+    v1: PromptFunction
+
+
+def v1(context: dict[str, Any]) -> list[Message]:
+    return [
+        Message(
+            role='system',
+            content='You are an AI assistant that rewrites short search queries over a knowledge graph of entities and facts so that they retrieve more of the relevant results.',
+        ),
+        Message(
+            role='user',
+            content=f"""
+            <QUERY>
+            {context['query']}
+            </QUERY>
+
+            Rewrite the QUERY as an expanded search query that keeps its meaning and adds synonyms and closely related terms.
+            If the QUERY asks about several distinct things, also write one short sub-query for each, at most three.
+
+            Guidelines:
+            1. Keep every name in the QUERY exactly as written.
+            2. Do not add facts or assumptions that the QUERY does not imply.
+            3. Leave sub_queries empty for a query about a single thing.
+            """,
+        ),
+    ]
+
+
+versions: Versions = {'v1': v1}
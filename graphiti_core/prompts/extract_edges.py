@@ -20,6 +20,7 @@ from typing import Any, Protocol, TypedDict
 from pydantic import BaseModel, Field
 
 from .models import Message, PromptFunction, PromptVersion
+from .prompt_helpers import preserve_names
 
 
 class Edge(BaseModel):
@@ -35,6 +36,10 @@ class Edge(BaseModel):
         None,
         description='The date and time when the relationship described by the edge fact stopped being true or ended. Use ISO 8601 format (YYYY-MM-DDTHH:MM:SS.SSSSSSZ)',
     )
+    confidence: float | None = Field(
+        None,
+        description='How confident you are that the fact holds, from 0.0 (speculative) to 1.0 (explicitly stated)',
+    )
 
 
 class ExtractedEdges(BaseModel):
@@ -103,6 +108,7 @@ You may use information from the PREVIOUS MESSAGES only to disambiguate referenc
 
 
 {context['custom_prompt']}
+{preserve_names(context.get('language'))}
 
 # EXTRACTION RULES
 
@@ -113,6 +119,7 @@ You may use information from the PREVIOUS MESSAGES only to disambiguate referenc
 5. The `fact_text` should quote or closely paraphrase the original source sentence(s).
 6. Use `REFERENCE_TIME` to resolve vague or relative temporal expressions (e.g., "last week").
 7. Do **not** hallucinate or infer temporal bounds from unrelated events.
+8. Set `confidence` lower for facts that are hedged, speculative, or only implied.
 
 # DATETIME RULES
 
@@ -20,6 +20,7 @@ from typing import Any, Protocol, TypedDict
 from pydantic import BaseModel, Field
 
 from .models import Message, PromptFunction, PromptVersion
+from .prompt_helpers import preserve_names
 
 
 class ExtractedEntity(BaseModel):
@@ -112,6 +113,7 @@ reference entities.
    - Be **explicit and unambiguous** in naming entities (e.g., use full names when available).
 
 {context['custom_prompt']}
+{preserve_names(context.get('language'))}
 """
     return [
         Message(role='system', content=sys_prompt),
@@ -135,6 +137,7 @@ def extract_json(context: dict[str, Any]) -> list[Message]:
 </ENTITY TYPES>
 
 {context['custom_prompt']}
+{preserve_names(context.get('language'))}
 
 Given the above source description and JSON, extract relevant entities from the provided JSON.
 For each entity extracted, also determine its entity type based on the provided ENTITY TYPES and their descriptions.
@@ -167,6 +170,7 @@ For each entity extracted, also determine its entity type based on the provided
 Indicate the classified entity type by providing its entity_type_id.
 
 {context['custom_prompt']}
+{preserve_names(context.get('language'))}
 
 Guidelines:
 1. Extract significant entities, concepts, or actors mentioned in the conversation.
@@ -14,8 +14,13 @@ See the License for the specific language governing permissions and
 limitations under the License.
 """
 
+from collections.abc import Iterator
+from contextlib import contextmanager
+from contextvars import ContextVar
 from typing import Any, Protocol, TypedDict
 
+from pydantic import BaseModel, Field
+
 from .dedupe_edges import Prompt as DedupeEdgesPrompt
 from .dedupe_edges import Versions as DedupeEdgesVersions
 from .dedupe_edges import versions as dedupe_edges_versions
@@ -25,6 +30,9 @@ from .dedupe_nodes import versions as dedupe_nodes_versions
 from .eval import Prompt as EvalPrompt
 from .eval import Versions as EvalVersions
 from .eval import versions as eval_versions
+from .expand_query import Prompt as ExpandQueryPrompt
+from .expand_query import Versions as ExpandQueryVersions
+from .expand_query import versions as expand_query_versions
 from .extract_edge_dates import Prompt as ExtractEdgeDatesPrompt
 from .extract_edge_dates import Versions as ExtractEdgeDatesVersions
 from .extract_edge_dates import versions as extract_edge_dates_versions
@@ -52,6 +60,7 @@ class PromptLibrary(Protocol):
     invalidate_edges: InvalidateEdgesPrompt
     extract_edge_dates: ExtractEdgeDatesPrompt
     summarize_nodes: SummarizeNodesPrompt
+    expand_query: ExpandQueryPrompt
     eval: EvalPrompt
 
 
@@ -63,30 +72,116 @@ class PromptLibraryImpl(TypedDict):
     invalidate_edges: InvalidateEdgesVersions
     extract_edge_dates: ExtractEdgeDatesVersions
     summarize_nodes: SummarizeNodesVersions
+    expand_query: ExpandQueryVersions
     eval: EvalVersions
 
 
+class SystemInstructions(BaseModel):
+    """
+    Domain guidance appended to the system message of every prompt, e.g. "This is medical text;
+    preserve drug dosages".
+
+    Overrides are keyed by prompt type (e.g. 'extract_nodes') or by prompt type and version
+    (e.g. 'extract_nodes.extract_text') and replace the default for those prompts. An empty
+    override disables the instructions for that prompt.
+    """
+
+    default: str | None = None
+    overrides: dict[str, str] = Field(default_factory=dict)
+
+    def for_prompt(self, prompt_type: str, version: str) -> str | None:
+        for key in (f'{prompt_type}.{version}', prompt_type):
+            if key in self.overrides:
+                return self.overrides[key]
+        return self.default
+
+
+_system_instructions: ContextVar[SystemInstructions | None] = ContextVar(
+    'system_instructions', default=None
+)
+
+
+@contextmanager
+def use_system_instructions(instructions: SystemInstructions | None) -> Iterator[None]:
+    """Apply the given system instructions to prompts built within this context."""
+    token = _system_instructions.set(instructions)
+    try:
+        yield
+    finally:
+        _system_instructions.reset(token)
+
+
 class VersionWrapper:
-    def __init__(self, func: PromptFunction):
+    def __init__(self, func: PromptFunction, prompt_type: str = '', version: str = ''):
         self.func = func
+        self.prompt_type = prompt_type
+        self.version = version
 
     def __call__(self, context: dict[str, Any]) -> list[Message]:
         messages = self.func(context)
+        system_instructions = _system_instructions.get()
+        custom_instructions = (
+            system_instructions.for_prompt(self.prompt_type, self.version)
+            if system_instructions is not None
+            else None
+        )
         for message in messages:
-            message.content += DO_NOT_ESCAPE_UNICODE if message.role == 'system' else ''
+            if message.role != 'system':
+                continue
+            if custom_instructions:
+                message.content += f'\n\n{custom_instructions}\n'
+            message.content += DO_NOT_ESCAPE_UNICODE
         return messages
 
 
 class PromptTypeWrapper:
-    def __init__(self, versions: dict[str, PromptFunction]):
+    def __init__(self, prompt_type: str, versions: dict[str, PromptFunction]):
         for version, func in versions.items():
-            setattr(self, version, VersionWrapper(func))
+            setattr(self, version, VersionWrapper(func, prompt_type, version))
 
 
 class PromptLibraryWrapper:
     def __init__(self, library: PromptLibraryImpl):
+        self.library = library
         for prompt_type, versions in library.items():
-            setattr(self, prompt_type, PromptTypeWrapper(versions))  # type: ignore[arg-type]
+            setattr(self, prompt_type, PromptTypeWrapper(prompt_type, versions))  # type: ignore[arg-type]
+
+    def with_prompt(
+        self, prompt_type: str, version: str, func: PromptFunction
+    ) -> 'PromptLibraryWrapper':
+        """
+        Returns a copy of this library with one prompt version replaced, e.g.
+        `with_prompt('extract_edges', 'edge', my_edge_prompt)`. The replacement gets the same
+        system instructions and unicode handling as the built-in prompts.
+        """
+        versions = self.library.get(prompt_type)
+        if versions is None or version not in versions:
+            raise ValueError(f'Unknown prompt: {prompt_type}.{version}')
+
+        library = {name: dict(funcs) for name, funcs in self.library.items()}
+        library[prompt_type][version] = func
+        return PromptLibraryWrapper(library)  # type: ignore[arg-type]
+
+
+_active_prompt_library: ContextVar[PromptLibraryWrapper | None] = ContextVar(
+    'prompt_library', default=None
+)
+
+
+@contextmanager
+def use_prompt_library(library: PromptLibraryWrapper | None) -> Iterator[None]:
+    """Build prompts from the given library within this context, instead of the default one."""
+    token = _active_prompt_library.set(library)
+    try:
+        yield
+    finally:
+        _active_prompt_library.reset(token)
+
+
+class ActivePromptLibrary:
+    # Resolves prompts from the library set with use_prompt_library, falling back to the default
+    def __getattr__(self, prompt_type: str) -> PromptTypeWrapper:
+        return getattr(_active_prompt_library.get() or DEFAULT_PROMPT_LIBRARY, prompt_type)
 
 
 PROMPT_LIBRARY_IMPL: PromptLibraryImpl = {
@@ -97,6 +192,8 @@ PROMPT_LIBRARY_IMPL: PromptLibraryImpl = {
     'invalidate_edges': invalidate_edges_versions,
     'extract_edge_dates': extract_edge_dates_versions,
     'summarize_nodes': summarize_nodes_versions,
+    'expand_query': expand_query_versions,
     'eval': eval_versions,
 }
-prompt_library: PromptLibrary = PromptLibraryWrapper(PROMPT_LIBRARY_IMPL)  # type: ignore[assignment]
+DEFAULT_PROMPT_LIBRARY = PromptLibraryWrapper(PROMPT_LIBRARY_IMPL)
+prompt_library: PromptLibrary = ActivePromptLibrary()  # type: ignore[assignment]
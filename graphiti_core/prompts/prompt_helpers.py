@@ -1 +1,11 @@
 DO_NOT_ESCAPE_UNICODE = '\nDo not escape unicode characters.\n'
+
+
+def preserve_names(language: str | None) -> str:
+    """Instructions to keep names as written, for content whose language is known."""
+    if not language:
+        return ''
+    return (
+        f'\nThe content is written in {language}. Keep names exactly as they are written in the '
+        'content, in their original script: do not translate, transliterate, or romanize them.\n'
+    )
@@ -0,0 +1,123 @@
+"""
+Copyright 2024, Zep Software, Inc.
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+"""
+
+
+from enum import Enum
+
+from graphiti_core.cross_encoder.client import CrossEncoderClient
+from graphiti_core.embedder.client import EmbedderClient
+from graphiti_core.llm_client.client import LLMClient
+from graphiti_core.llm_client.config import LLMConfig
+
+# Provider clients are imported when they are created, so that the optional dependencies of the
+# providers that aren't used don't need to be installed
+
+
+class LLMProvider(Enum):
+    openai = 'openai'
+    # Any OpenAI-compatible server, such as Ollama or a local model, selected with base_url
+    openai_generic = 'openai_generic'
+    anthropic = 'anthropic'
+    gemini = 'gemini'
+    groq = 'groq'
+
+
+class EmbedderProvider(Enum):
+    # Also used for OpenAI-compatible servers, such as Ollama, selected with base_url
+    openai = 'openai'
+    gemini = 'gemini'
+    voyage = 'voyage'
+
+
+class RerankerProvider(Enum):
+    openai = 'openai'
+    gemini = 'gemini'
+    # Runs the BAAI/bge-reranker-v2-m3 model locally
+    bge = 'bge'
+
+
+def create_llm_client(provider: LLMProvider | str, config: LLMConfig | None = None) -> LLMClient:
+    """Create the LLM client for a provider, e.g. `create_llm_client('anthropic', config)`."""
+    provider = LLMProvider(provider)
+    if provider == LLMProvider.openai:
+        from graphiti_core.llm_client.openai_client import OpenAIClient
+
+        if config is None:
+            return OpenAIClient()
+        return OpenAIClient(config, max_tokens=config.max_tokens)
+    if provider == LLMProvider.openai_generic:
+        from graphiti_core.llm_client.openai_generic_client import OpenAIGenericClient
+
+        return OpenAIGenericClient(config)
+    if provider == LLMProvider.anthropic:
+        from graphiti_core.llm_client.anthropic_client import AnthropicClient
+
+        return AnthropicClient(config)
+    if provider == LLMProvider.gemini:
+        from graphiti_core.llm_client.gemini_client import GeminiClient
+
+        if config is None:
+            return GeminiClient()
+        return GeminiClient(config, max_tokens=config.max_tokens)
+
+    from graphiti_core.llm_client.groq_client import GroqClient
+
+    return GroqClient(config)
+
+
+def create_embedder(
+    provider: EmbedderProvider | str,
+    api_key: str | None = None,
+    model: str | None = None,
+    base_url: str | None = None,
+) -> EmbedderClient:
+    """
+    Create the embedder for a provider. The provider's default model is used unless one is given,
+    and base_url is only used by the OpenAI provider.
+    """
+    provider = EmbedderProvider(provider)
+    settings = {'api_key': api_key, **({'embedding_model': model} if model else {})}
+    if provider == EmbedderProvider.openai:
+        from graphiti_core.embedder.openai import OpenAIEmbedder, OpenAIEmbedderConfig
+
+        return OpenAIEmbedder(OpenAIEmbedderConfig(base_url=base_url, **settings))
+    if provider == EmbedderProvider.gemini:
+        from graphiti_core.embedder.gemini import GeminiEmbedder, GeminiEmbedderConfig
+
+        return GeminiEmbedder(GeminiEmbedderConfig(**settings))
+
+    from graphiti_core.embedder.voyage import VoyageAIEmbedder, VoyageAIEmbedderConfig
+
+    return VoyageAIEmbedder(VoyageAIEmbedderConfig(**settings))
+
+
+def create_cross_encoder(
+    provider: RerankerProvider | str, config: LLMConfig | None = None
+) -> CrossEncoderClient:
+    """Create the reranker for a provider. The BGE reranker runs locally and ignores config."""
+    provider = RerankerProvider(provider)
+    if provider == RerankerProvider.openai:
+        from graphiti_core.cross_encoder.openai_reranker_client import OpenAIRerankerClient
+
+        return OpenAIRerankerClient(config)
+    if provider == RerankerProvider.gemini:
+        from graphiti_core.cross_encoder.gemini_reranker_client import GeminiRerankerClient
+
+        return GeminiRerankerClient(config)
+
+    from graphiti_core.cross_encoder.bge_reranker_client import BGERerankerClient
+
+    return BGERerankerClient()
@@ -14,34 +14,50 @@ See the License for the specific language governing permissions and
 limitations under the License.
 """
 
+import asyncio
 import logging
 from collections import defaultdict
+from datetime import datetime, timezone
 from time import time
+from typing import Any, TypeVar
 
 from graphiti_core.cross_encoder.client import CrossEncoderClient
 from graphiti_core.driver.driver import GraphDriver
 from graphiti_core.edges import EntityEdge
 from graphiti_core.errors import SearchRerankerError
 from graphiti_core.graphiti_types import GraphitiClients
-from graphiti_core.helpers import semaphore_gather
+from graphiti_core.helpers import gather_until, semaphore_gather
+from graphiti_core.llm_client import LLMClient
+from graphiti_core.llm_client.config import ModelSize
 from graphiti_core.nodes import CommunityNode, EntityNode, EpisodicNode
+from graphiti_core.prompts import prompt_library
+from graphiti_core.prompts.expand_query import ExpandedQuery
 from graphiti_core.search.search_config import (
+    DEFAULT_EXPAND_SEED_COUNT,
+    DEFAULT_OVERFETCH_FACTOR,
+    DEFAULT_RERANK_TOP_K,
     DEFAULT_SEARCH_LIMIT,
     CommunityReranker,
     CommunitySearchConfig,
+    CommunitySearchMethod,
     EdgeReranker,
     EdgeSearchConfig,
     EdgeSearchMethod,
     EpisodeReranker,
     EpisodeSearchConfig,
+    EpisodeSearchMethod,
     NodeReranker,
     NodeSearchConfig,
     NodeSearchMethod,
     SearchConfig,
     SearchResults,
+    SortBy,
+    without_embeddings,
 )
 from graphiti_core.search.search_filters import SearchFilters
 from graphiti_core.search.search_utils import (
+    MAX_SEARCH_DEPTH,
+    FulltextMatchMode,
     community_fulltext_search,
     community_similarity_search,
     edge_bfs_search,
@@ -49,6 +65,7 @@ from graphiti_core.search.search_utils import (
     edge_similarity_search,
     episode_fulltext_search,
     episode_mentions_reranker,
+    episode_similarity_search,
     get_embeddings_for_communities,
     get_embeddings_for_edges,
     get_embeddings_for_nodes,
@@ -58,10 +75,18 @@ from graphiti_core.search.search_utils import (
     node_fulltext_search,
     node_similarity_search,
     rrf,
+    validate_search_depth,
 )
+from graphiti_core.utils.datetime_utils import utc_now
 
 logger = logging.getLogger(__name__)
 
+T = TypeVar('T', EntityEdge, EntityNode, EpisodicNode, CommunityNode)
+# Most queries searched for one expanded query: the original, its expansion and sub-queries
+MAX_QUERY_VARIANTS = 5
+# Per-method scores of each search result, keyed by result uuid
+ScoreBreakdown = dict[str, dict[str, float]]
+
 
 async def search(
     clients: GraphitiClients,
@@ -72,8 +97,17 @@ async def search(
     center_node_uuid: str | None = None,
     bfs_origin_node_uuids: list[str] | None = None,
     query_vector: list[float] | None = None,
+    timeout: float | None = None,
 ) -> SearchResults:
+    """
+    Search the graph for edges, nodes, episodes, and communities matching the query.
+
+    With a timeout, the whole search, from embedding the query to reranking, must finish within
+    that many seconds. Searches still running at the deadline are cancelled, and the results
+    gathered so far are returned marked timed_out.
+    """
     start = time()
+    deadline = asyncio.get_running_loop().time() + timeout if timeout is not None else None
 
     driver = clients.driver
     embedder = clients.embedder
@@ -86,15 +120,37 @@ async def search(
             episodes=[],
             communities=[],
         )
-    query_vector = (
-        query_vector
-        if query_vector is not None
-        else await embedder.create(input_data=[query.replace('\n', ' ')])
-    )
+
+    if config.query_expansion:
+        return await expanded_search(
+            clients,
+            query,
+            group_ids,
+            config,
+            search_filter,
+            center_node_uuid,
+            bfs_origin_node_uuids,
+            query_vector,
+            deadline,
+        )
+
+    if not clients.embeddings_enabled:
+        config = without_embeddings(config)
+        query_vector = []
+
+    if query_vector is None:
+        try:
+            query_vector = await asyncio.wait_for(
+                embedder.create(input_data=[query.replace('\n', ' ')]), _remaining(deadline)
+            )
+        except asyncio.TimeoutError:
+            logger.warning(f'Search timed out after {timeout} seconds while embedding the query')
+            return SearchResults(edges=[], nodes=[], episodes=[], communities=[], timed_out=True)
 
     # if group_ids is empty, set it to None
     group_ids = group_ids if group_ids and group_ids != [''] else None
-    edges, nodes, episodes, communities = await semaphore_gather(
+    score_breakdown: ScoreBreakdown | None = {} if config.explain else None
+    scope_results, timed_out = await gather_until(
         edge_search(
             driver,
             cross_encoder,
@@ -107,6 +163,10 @@ async def search(
             bfs_origin_node_uuids,
             config.limit,
             config.reranker_min_score,
+            config.rerank_top_k,
+            score_breakdown,
+            config.overfetch_factor,
+            config.fulltext_match_mode,
         ),
         node_search(
             driver,
@@ -120,6 +180,10 @@ async def search(
             bfs_origin_node_uuids,
             config.limit,
             config.reranker_min_score,
+            config.rerank_top_k,
+            score_breakdown,
+            config.overfetch_factor,
+            config.fulltext_match_mode,
         ),
         episode_search(
             driver,
@@ -131,6 +195,10 @@ async def search(
             search_filter,
             config.limit,
             config.reranker_min_score,
+            config.rerank_top_k,
+            score_breakdown,
+            config.overfetch_factor,
+            config.fulltext_match_mode,
         ),
         community_search(
             driver,
@@ -141,14 +209,22 @@ async def search(
             config.community_config,
             config.limit,
             config.reranker_min_score,
+            config.rerank_top_k,
+            score_breakdown,
+            config.overfetch_factor,
+            config.fulltext_match_mode,
         ),
+        timeout=_remaining(deadline),
+    )
+    # Searches cut off by the timeout contribute no results
+    edges, nodes, episodes, communities = (
+        result if result is not None else [] for result in scope_results
     )
+    if timed_out:
+        logger.warning(f'Search timed out after {timeout} seconds; returning partial results')
 
-    results = SearchResults(
-        edges=edges,
-        nodes=nodes,
-        episodes=episodes,
-        communities=communities,
+    results = finish_results(
+        config, edges, nodes, episodes, communities, score_breakdown, timed_out
     )
 
     latency = (time() - start) * 1000
@@ -158,6 +234,448 @@ async def search(
     return results
 
 
+def finish_results(
+    config: SearchConfig,
+    edges: list[EntityEdge],
+    nodes: list[EntityNode],
+    episodes: list[EpisodicNode],
+    communities: list[CommunityNode],
+    score_breakdown: ScoreBreakdown | None,
+    timed_out: bool,
+    truncated: bool = False,
+) -> SearchResults:
+    """Apply the config's recency weighting and date sorting to the reranked results."""
+    if config.recency_weight > 0:
+        weight, half_life, now = config.recency_weight, config.recency_half_life_days, utc_now()
+        edges = rerank_by_recency(edges, weight, half_life, now, score_breakdown)
+        nodes = rerank_by_recency(nodes, weight, half_life, now, score_breakdown)
+        episodes = rerank_by_recency(episodes, weight, half_life, now, score_breakdown)
+        communities = rerank_by_recency(communities, weight, half_life, now, score_breakdown)
+
+    return SearchResults(
+        edges=sort_results(edges, config.sort_by),
+        nodes=sort_results(nodes, config.sort_by),
+        episodes=sort_results(episodes, config.sort_by),
+        communities=sort_results(communities, config.sort_by),
+        score_breakdowns=score_breakdown,
+        truncated=truncated,
+        timed_out=timed_out,
+    )
+
+
+async def search_and_expand(
+    clients: GraphitiClients,
+    query: str,
+    group_ids: list[str] | None,
+    config: SearchConfig,
+    search_filter: SearchFilters,
+    expand_depth: int = MAX_SEARCH_DEPTH,
+    seed_count: int = DEFAULT_EXPAND_SEED_COUNT,
+) -> SearchResults:
+    """
+    Search for seed nodes, then expand from the top `seed_count` of them by breadth-first
+    search up to `expand_depth` hops.
+
+    Only the node search of `config` is used, to find the seeds. The results hold the seeds
+    followed by the nodes reached from them, and the facts along the way, each capped at
+    config.limit and at clients.max_traversal_nodes. When the traversal reaches more than that,
+    it is cut short and the results are marked truncated. Depths above MAX_SEARCH_DEPTH raise
+    SearchDepthExceededError.
+    """
+    validate_search_depth(expand_depth)
+
+    seed_config = config.model_copy(
+        update={'edge_config': None, 'episode_config': None, 'community_config': None}
+    )
+    seed_results = await search(clients, query, group_ids, seed_config, search_filter)
+    seeds = seed_results.nodes[:seed_count]
+    if not seeds:
+        return SearchResults(edges=[], nodes=[], episodes=[], communities=[])
+
+    limit = min(config.limit, clients.max_traversal_nodes)
+    seed_uuids = [node.uuid for node in seeds]
+    # One result past the limit is fetched to tell whether the traversal was cut short
+    expanded_nodes, edges = await semaphore_gather(
+        node_bfs_search(clients.driver, seed_uuids, search_filter, expand_depth, limit + 1),
+        edge_bfs_search(clients.driver, seed_uuids, expand_depth, search_filter, limit + 1),
+    )
+
+    nodes: list[EntityNode] = list(seeds)
+    seen_uuids = set(seed_uuids)
+    for node in expanded_nodes:
+        if node.uuid not in seen_uuids:
+            seen_uuids.add(node.uuid)
+            nodes.append(node)
+
+    truncated = len(nodes) > limit or len(edges) > limit
+    if truncated:
+        logger.info(f'Expansion from {len(seeds)} seed nodes was truncated at {limit} results')
+
+    return SearchResults(
+        edges=edges[:limit],
+        nodes=nodes[:limit],
+        episodes=[],
+        communities=[],
+        truncated=truncated,
+    )
+
+
+async def expand_query(llm_client: LLMClient, query: str) -> list[str]:
+    """
+    The query followed by the LLM's expanded query and sub-queries, without duplicates and
+    capped at MAX_QUERY_VARIANTS.
+    """
+    llm_response = await llm_client.generate_response(
+        prompt_library.expand_query.v1({'query': query}),
+        response_model=ExpandedQuery,
+        model_size=ModelSize.small,
+    )
+    variants = [query, llm_response.get('expanded_query', ''), *llm_response.get('sub_queries', [])]
+    unique_variants = dict.fromkeys(variant.strip() for variant in variants if variant.strip())
+
+    return list(unique_variants)[:MAX_QUERY_VARIANTS]
+
+
+async def expanded_search(
+    clients: GraphitiClients,
+    query: str,
+    group_ids: list[str] | None,
+    config: SearchConfig,
+    search_filter: SearchFilters,
+    center_node_uuid: str | None,
+    bfs_origin_node_uuids: list[str] | None,
+    query_vector: list[float] | None,
+    deadline: float | None,
+) -> SearchResults:
+    """
+    Search for each variant of the query from expand_query, merge the candidates with
+    SearchResults.merge and rerank them once against the original query. If the expansion fails
+    or takes up the timeout, only the original query is searched.
+    """
+    try:
+        variants = await asyncio.wait_for(
+            expand_query(clients.llm_client, query), _remaining(deadline)
+        )
+    except Exception as e:
+        logger.warning(f'Query expansion failed, searching the original query only: {e!r}')
+        variants = [query]
+    logger.debug(f'Expanded query {query!r} to {variants}')
+
+    variant_config = candidate_config(config)
+    results: list[SearchResults] = await semaphore_gather(
+        *[
+            search(
+                clients,
+                variant,
+                group_ids,
+                variant_config,
+                search_filter,
+                center_node_uuid,
+                bfs_origin_node_uuids,
+                query_vector if variant == query else None,
+                _remaining(deadline),
+            )
+            for variant in variants
+        ]
+    )
+
+    candidates = SearchResults.merge(results)
+    timed_out = any(result.timed_out for result in results)
+    score_breakdown = (
+        merge_score_breakdowns([result.score_breakdowns for result in results])
+        if config.explain
+        else None
+    )
+    try:
+        candidates = await asyncio.wait_for(
+            rerank_candidates(clients, query, query_vector, config, candidates),
+            _remaining(deadline),
+        )
+    except asyncio.TimeoutError:
+        logger.warning('Search timed out while reranking expanded results; returning them unranked')
+        timed_out = True
+
+    limit = config.limit
+    edges, nodes = candidates.edges[:limit], candidates.nodes[:limit]
+    episodes, communities = candidates.episodes[:limit], candidates.communities[:limit]
+    record_rerank_scores(score_breakdown, edges)
+    record_rerank_scores(score_breakdown, nodes)
+    record_rerank_scores(score_breakdown, episodes)
+    record_rerank_scores(score_breakdown, communities)
+
+    return finish_results(
+        config,
+        edges,
+        nodes,
+        episodes,
+        communities,
+        score_breakdown,
+        timed_out,
+        any(result.truncated for result in results),
+    )
+
+
+def candidate_config(config: SearchConfig) -> SearchConfig:
+    """
+    The config each variant of an expanded query is searched with. Variants only gather
+    candidates, so the cross-encoder and MMR rerankers, recency and date sorting, which are
+    applied once to the merged candidates, are left out.
+    """
+    update: dict[str, Any] = {
+        'query_expansion': False,
+        'sort_by': SortBy.relevance,
+        'recency_weight': 0.0,
+    }
+    if config.edge_config is not None and config.edge_config.reranker in (
+        EdgeReranker.cross_encoder,
+        EdgeReranker.mmr,
+    ):
+        update['edge_config'] = config.edge_config.model_copy(update={'reranker': EdgeReranker.rrf})
+    if config.node_config is not None and config.node_config.reranker in (
+        NodeReranker.cross_encoder,
+        NodeReranker.mmr,
+    ):
+        update['node_config'] = config.node_config.model_copy(update={'reranker': NodeReranker.rrf})
+    if (
+        config.episode_config is not None
+        and config.episode_config.reranker == EpisodeReranker.cross_encoder
+    ):
+        update['episode_config'] = config.episode_config.model_copy(
+            update={'reranker': EpisodeReranker.rrf}
+        )
+    if config.community_config is not None and config.community_config.reranker in (
+        CommunityReranker.cross_encoder,
+        CommunityReranker.mmr,
+    ):
+        update['community_config'] = config.community_config.model_copy(
+            update={'reranker': CommunityReranker.rrf}
+        )
+    return config.model_copy(update=update)
+
+
+def merge_score_breakdowns(breakdowns: list[ScoreBreakdown | None]) -> ScoreBreakdown:
+    """Combine the breakdowns of several searches, keeping each method's best score."""
+    merged: ScoreBreakdown = {}
+    for breakdown in breakdowns:
+        for uuid, scores in (breakdown or {}).items():
+            merged_scores = merged.setdefault(uuid, {})
+            for method, score in scores.items():
+                merged_scores[method] = max(merged_scores.get(method, score), score)
+    return merged
+
+
+async def rerank_candidates(
+    clients: GraphitiClients,
+    query: str,
+    query_vector: list[float] | None,
+    config: SearchConfig,
+    candidates: SearchResults,
+) -> SearchResults:
+    """
+    Rerank merged candidates against the query with the config's cross-encoder and MMR rerankers.
+    Candidates of layers with other rerankers keep their merged order.
+    """
+    if not clients.embeddings_enabled:
+        config = without_embeddings(config)
+    driver, cross_encoder = clients.driver, clients.cross_encoder
+    top_k, min_score = config.rerank_top_k, config.reranker_min_score
+    edge_config, node_config = config.edge_config, config.node_config
+    episode_config, community_config = config.episode_config, config.community_config
+
+    uses_mmr = (
+        (edge_config is not None and edge_config.reranker == EdgeReranker.mmr)
+        or (node_config is not None and node_config.reranker == NodeReranker.mmr)
+        or (community_config is not None and community_config.reranker == CommunityReranker.mmr)
+    )
+    if uses_mmr and query_vector is None:
+        query_vector = await clients.embedder.create(input_data=[query.replace('\n', ' ')])
+
+    async def by_cross_encoder(items: list[T], passages: list[str]) -> list[T]:
+        uuids = await cross_encoder_rerank(
+            cross_encoder,
+            query,
+            [(item.uuid, passage) for item, passage in zip(items, passages, strict=True)],
+            top_k,
+            min_score,
+        )
+        return in_uuid_order(items, uuids)
+
+    def by_mmr(items: list[T], embeddings: dict[str, list[float]], mmr_lambda: float) -> list[T]:
+        uuids = maximal_marginal_relevance(query_vector or [], embeddings, mmr_lambda, min_score)
+        return in_uuid_order(items, uuids)
+
+    async def rerank_edges(edges: list[EntityEdge]) -> list[EntityEdge]:
+        if edge_config is None:
+            return edges
+        if edge_config.reranker == EdgeReranker.cross_encoder:
+            return await by_cross_encoder(edges, [edge.fact for edge in edges])
+        if edge_config.reranker == EdgeReranker.mmr:
+            embeddings = await get_embeddings_for_edges(driver, edges)
+            return by_mmr(edges, embeddings, edge_config.mmr_lambda)
+        return edges
+
+    async def rerank_nodes(nodes: list[EntityNode]) -> list[EntityNode]:
+        if node_config is None:
+            return nodes
+        if node_config.reranker == NodeReranker.cross_encoder:
+            return await by_cross_encoder(nodes, [node.name for node in nodes])
+        if node_config.reranker == NodeReranker.mmr:
+            embeddings = await get_embeddings_for_nodes(driver, nodes)
+            return by_mmr(nodes, embeddings, node_config.mmr_lambda)
+        return nodes
+
+    async def rerank_episodes(episodes: list[EpisodicNode]) -> list[EpisodicNode]:
+        if episode_config is None or episode_config.reranker != EpisodeReranker.cross_encoder:
+            return episodes
+        return await by_cross_encoder(episodes, [episode.content for episode in episodes])
+
+    async def rerank_communities(communities: list[CommunityNode]) -> list[CommunityNode]:
+        if community_config is None:
+            return communities
+        if community_config.reranker == CommunityReranker.cross_encoder:
+            names = [community.name for community in communities]
+            return await by_cross_encoder(communities, names)
+        if community_config.reranker == CommunityReranker.mmr:
+            embeddings = await get_embeddings_for_communities(driver, communities)
+            return by_mmr(communities, embeddings, community_config.mmr_lambda)
+        return communities
+
+    edges, nodes, episodes, communities = await semaphore_gather(
+        rerank_edges(candidates.edges),
+        rerank_nodes(candidates.nodes),
+        rerank_episodes(candidates.episodes),
+        rerank_communities(candidates.communities),
+    )
+    return SearchResults(edges=edges, nodes=nodes, episodes=episodes, communities=communities)
+
+
+def in_uuid_order(items: list[T], uuids: list[str]) -> list[T]:
+    items_by_uuid = {item.uuid: item for item in items}
+    return [items_by_uuid[uuid] for uuid in uuids]
+
+
+def _remaining(deadline: float | None) -> float | None:
+    if deadline is None:
+        return None
+    return max(deadline - asyncio.get_running_loop().time(), 0)
+
+
+def sort_results(results: list[T], sort_by: SortBy) -> list[T]:
+    """
+    Sort the most relevant results by date, most recent first.
+
+    Results are still selected by relevance, so date sorting only reorders the returned page.
+    Nodes without a valid_at field are sorted by created_at, and edges or episodes whose
+    valid_at is unknown are placed last.
+    """
+    if sort_by == SortBy.relevance:
+        return results
+
+    def sort_key(item: T) -> tuple[bool, datetime]:
+        if sort_by == SortBy.valid_at_desc and isinstance(item, EntityEdge | EpisodicNode):
+            return (
+                item.valid_at is not None,
+                item.valid_at or datetime.min.replace(tzinfo=timezone.utc),
+            )
+        return True, item.created_at
+
+    return sorted(results, key=sort_key, reverse=True)
+
+
+def rerank_by_recency(
+    results: list[T],
+    recency_weight: float,
+    half_life_days: float,
+    now: datetime,
+    score_breakdown: ScoreBreakdown | None = None,
+) -> list[T]:
+    """
+    Blend each result's relevance with how recent it is.
+
+    Results carry no comparable scores, so relevance is the reciprocal of the result's rank.
+    Recency decays by half every half_life_days, measured from valid_at for edges and episodes
+    and from created_at otherwise. The final score is
+    (1 - recency_weight) * relevance + recency_weight * recency.
+
+    Like sort_by, this only reorders the results that were selected by relevance.
+    """
+    if recency_weight <= 0 or len(results) == 0:
+        return results
+
+    def recency(item: T) -> float:
+        timestamp = item.created_at
+        if isinstance(item, EntityEdge | EpisodicNode) and item.valid_at is not None:
+            timestamp = item.valid_at
+        age_days = max((now - timestamp).total_seconds(), 0) / 86400
+        return 0.5 ** (age_days / half_life_days)
+
+    scores: dict[str, float] = {}
+    for i, item in enumerate(results):
+        recency_factor = recency(item)
+        scores[item.uuid] = (1 - recency_weight) / (i + 1) + recency_weight * recency_factor
+        if score_breakdown is not None:
+            score_breakdown.setdefault(item.uuid, {})['recency'] = recency_factor
+    return sorted(results, key=lambda item: scores[item.uuid], reverse=True)
+
+
+def record_method_scores(
+    score_breakdown: ScoreBreakdown | None,
+    methods: list[str],
+    method_scores: list[dict[str, float]],
+    search_results: list[list[T]],
+):
+    """
+    Record the score each search method gave each result for explain.
+
+    BM25 and cosine similarity results are recorded with the score the database gave them.
+    Traversals have no score, so bfs results are recorded with the reciprocal of their rank.
+    """
+    if score_breakdown is None:
+        return
+    for method, raw_scores, results in zip(methods, method_scores, search_results, strict=True):
+        for i, item in enumerate(results):
+            scores = score_breakdown.setdefault(item.uuid, {})
+            score = raw_scores.get(item.uuid, 1 / (i + 1))
+            scores[method] = max(scores.get(method, 0.0), score)
+
+
+def record_rerank_scores(score_breakdown: ScoreBreakdown | None, results: list[T]):
+    """Record the reciprocal rank each result ended up with after reranking, for explain."""
+    if score_breakdown is None:
+        return
+    for i, item in enumerate(results):
+        score_breakdown.setdefault(item.uuid, {})['rerank'] = 1 / (i + 1)
+
+
+def candidate_limit(limit: int, overfetch_factor: int | None, reranked: bool) -> int:
+    """How many results each search method fetches for a layer returning `limit` results."""
+    if overfetch_factor is None:
+        overfetch_factor = DEFAULT_OVERFETCH_FACTOR if reranked else 1
+    return overfetch_factor * limit
+
+
+async def cross_encoder_rerank(
+    cross_encoder: CrossEncoderClient,
+    query: str,
+    candidates: list[tuple[str, str]],
+    top_k: int,
+    min_score: float,
+) -> list[str]:
+    """
+    Rerank the first top_k (uuid, passage) candidates with the cross-encoder.
+
+    Candidates past top_k are not sent to the cross-encoder and keep their preliminary order
+    below the reranked block.
+    """
+    top_candidates = candidates[:top_k]
+    ranked_indices = await cross_encoder.rank_batch(
+        query, [passage for _, passage in top_candidates]
+    )
+    reranked_uuids = [top_candidates[i][0] for i, score in ranked_indices if score >= min_score]
+
+    return reranked_uuids + [uuid for uuid, _ in candidates[top_k:]]
+
+
 async def edge_search(
     driver: GraphDriver,
     cross_encoder: CrossEncoderClient,
@@ -170,38 +688,74 @@ async def edge_search(
     bfs_origin_node_uuids: list[str] | None = None,
     limit=DEFAULT_SEARCH_LIMIT,
     reranker_min_score: float = 0,
+    rerank_top_k: int = DEFAULT_RERANK_TOP_K,
+    score_breakdown: ScoreBreakdown | None = None,
+    overfetch_factor: int | None = None,
+    fulltext_match_mode: FulltextMatchMode = FulltextMatchMode.any_terms,
 ) -> list[EntityEdge]:
     if config is None:
         return []
-    search_results: list[list[EntityEdge]] = list(
-        await semaphore_gather(
-            *[
-                edge_fulltext_search(driver, query, search_filter, group_ids, 2 * limit),
-                edge_similarity_search(
-                    driver,
-                    query_vector,
-                    None,
-                    None,
-                    search_filter,
-                    group_ids,
-                    2 * limit,
-                    config.sim_min_score,
-                ),
-                edge_bfs_search(
-                    driver, bfs_origin_node_uuids, config.bfs_max_depth, search_filter, 2 * limit
-                ),
-            ]
+
+    reranked = (
+        config.reranker != EdgeReranker.rrf
+        or len(config.search_methods) > 1
+        or bfs_origin_node_uuids is not None
+    )
+    fetch_limit = candidate_limit(limit, overfetch_factor, reranked)
+    search_tasks = []
+    methods: list[str] = []
+    method_scores: list[dict[str, float]] = []
+    if EdgeSearchMethod.bm25 in config.search_methods:
+        methods.append(EdgeSearchMethod.bm25.value)
+        method_scores.append({})
+        search_tasks.append(
+            edge_fulltext_search(
+                driver,
+                query,
+                search_filter,
+                group_ids,
+                fetch_limit,
+                fulltext_match_mode,
+                scores=method_scores[-1],
+            )
+        )
+    if EdgeSearchMethod.cosine_similarity in config.search_methods:
+        methods.append(EdgeSearchMethod.cosine_similarity.value)
+        method_scores.append({})
+        search_tasks.append(
+            edge_similarity_search(
+                driver,
+                query_vector,
+                None,
+                None,
+                search_filter,
+                group_ids,
+                fetch_limit,
+                config.sim_min_score,
+                scores=method_scores[-1],
+            )
+        )
+    methods.append(EdgeSearchMethod.bfs.value)
+    method_scores.append({})
+    search_tasks.append(
+        edge_bfs_search(
+            driver, bfs_origin_node_uuids, config.bfs_max_depth, search_filter, fetch_limit
         )
     )
+    search_results: list[list[EntityEdge]] = list(await semaphore_gather(*search_tasks))
 
     if EdgeSearchMethod.bfs in config.search_methods and bfs_origin_node_uuids is None:
         source_node_uuids = [edge.source_node_uuid for result in search_results for edge in result]
+        methods.append(EdgeSearchMethod.bfs.value)
+        method_scores.append({})
         search_results.append(
             await edge_bfs_search(
-                driver, source_node_uuids, config.bfs_max_depth, search_filter, 2 * limit
+                driver, source_node_uuids, config.bfs_max_depth, search_filter, fetch_limit
             )
         )
 
+    record_method_scores(score_breakdown, methods, method_scores, search_results)
+
     edge_uuid_map = {edge.uuid: edge for result in search_results for edge in result}
 
     reranked_uuids: list[str] = []
@@ -220,11 +774,18 @@ async def edge_search(
             reranker_min_score,
         )
     elif config.reranker == EdgeReranker.cross_encoder:
-        fact_to_uuid_map = {edge.fact: edge.uuid for edge in list(edge_uuid_map.values())[:limit]}
-        reranked_facts = await cross_encoder.rank(query, list(fact_to_uuid_map.keys()))
-        reranked_uuids = [
-            fact_to_uuid_map[fact] for fact, score in reranked_facts if score >= reranker_min_score
-        ]
+        # use rrf as a preliminary sort
+        rrf_result_uuids = rrf(
+            [[edge.uuid for edge in result] for result in search_results],
+            min_score=reranker_min_score,
+        )
+        reranked_uuids = await cross_encoder_rerank(
+            cross_encoder,
+            query,
+            [(uuid, edge_uuid_map[uuid].fact) for uuid in rrf_result_uuids],
+            rerank_top_k,
+            reranker_min_score,
+        )
     elif config.reranker == EdgeReranker.node_distance:
         if center_node_uuid is None:
             raise SearchRerankerError('No center node provided for Node Distance reranker')
@@ -255,6 +816,7 @@ async def edge_search(
     if config.reranker == EdgeReranker.episode_mentions:
         reranked_edges.sort(reverse=True, key=lambda edge: len(edge.episodes))
 
+    record_rerank_scores(score_breakdown, reranked_edges[:limit])
     return reranked_edges[:limit]
 
 
@@ -270,31 +832,74 @@ async def node_search(
     bfs_origin_node_uuids: list[str] | None = None,
     limit=DEFAULT_SEARCH_LIMIT,
     reranker_min_score: float = 0,
+    rerank_top_k: int = DEFAULT_RERANK_TOP_K,
+    score_breakdown: ScoreBreakdown | None = None,
+    overfetch_factor: int | None = None,
+    fulltext_match_mode: FulltextMatchMode = FulltextMatchMode.any_terms,
 ) -> list[EntityNode]:
     if config is None:
         return []
-    search_results: list[list[EntityNode]] = list(
-        await semaphore_gather(
-            *[
-                node_fulltext_search(driver, query, search_filter, group_ids, 2 * limit),
-                node_similarity_search(
-                    driver, query_vector, search_filter, group_ids, 2 * limit, config.sim_min_score
-                ),
-                node_bfs_search(
-                    driver, bfs_origin_node_uuids, search_filter, config.bfs_max_depth, 2 * limit
-                ),
-            ]
+
+    reranked = (
+        config.reranker != NodeReranker.rrf
+        or len(config.search_methods) > 1
+        or bfs_origin_node_uuids is not None
+    )
+    fetch_limit = candidate_limit(limit, overfetch_factor, reranked)
+    search_tasks = []
+    methods: list[str] = []
+    method_scores: list[dict[str, float]] = []
+    if NodeSearchMethod.bm25 in config.search_methods:
+        methods.append(NodeSearchMethod.bm25.value)
+        method_scores.append({})
+        search_tasks.append(
+            node_fulltext_search(
+                driver,
+                query,
+                search_filter,
+                group_ids,
+                fetch_limit,
+                fulltext_match_mode,
+                name_boost=config.name_boost,
+                summary_boost=config.summary_boost,
+                scores=method_scores[-1],
+            )
+        )
+    if NodeSearchMethod.cosine_similarity in config.search_methods:
+        methods.append(NodeSearchMethod.cosine_similarity.value)
+        method_scores.append({})
+        search_tasks.append(
+            node_similarity_search(
+                driver,
+                query_vector,
+                search_filter,
+                group_ids,
+                fetch_limit,
+                config.sim_min_score,
+                scores=method_scores[-1],
+            )
+        )
+    methods.append(NodeSearchMethod.bfs.value)
+    method_scores.append({})
+    search_tasks.append(
+        node_bfs_search(
+            driver, bfs_origin_node_uuids, search_filter, config.bfs_max_depth, fetch_limit
         )
     )
+    search_results: list[list[EntityNode]] = list(await semaphore_gather(*search_tasks))
 
     if NodeSearchMethod.bfs in config.search_methods and bfs_origin_node_uuids is None:
         origin_node_uuids = [node.uuid for result in search_results for node in result]
+        methods.append(NodeSearchMethod.bfs.value)
+        method_scores.append({})
         search_results.append(
             await node_bfs_search(
-                driver, origin_node_uuids, search_filter, config.bfs_max_depth, 2 * limit
+                driver, origin_node_uuids, search_filter, config.bfs_max_depth, fetch_limit
             )
         )
 
+    record_method_scores(score_breakdown, methods, method_scores, search_results)
+
     search_result_uuids = [[node.uuid for node in result] for result in search_results]
     node_uuid_map = {node.uuid: node for result in search_results for node in result}
 
@@ -313,14 +918,15 @@ async def node_search(
             reranker_min_score,
         )
     elif config.reranker == NodeReranker.cross_encoder:
-        name_to_uuid_map = {node.name: node.uuid for node in list(node_uuid_map.values())}
-
-        reranked_node_names = await cross_encoder.rank(query, list(name_to_uuid_map.keys()))
-        reranked_uuids = [
-            name_to_uuid_map[name]
-            for name, score in reranked_node_names
-            if score >= reranker_min_score
-        ]
+        # use rrf as a preliminary sort
+        rrf_result_uuids = rrf(search_result_uuids, min_score=reranker_min_score)
+        reranked_uuids = await cross_encoder_rerank(
+            cross_encoder,
+            query,
+            [(uuid, node_uuid_map[uuid].name) for uuid in rrf_result_uuids],
+            rerank_top_k,
+            reranker_min_score,
+        )
     elif config.reranker == NodeReranker.episode_mentions:
         reranked_uuids = await episode_mentions_reranker(
             driver, search_result_uuids, min_score=reranker_min_score
@@ -337,6 +943,7 @@ async def node_search(
 
     reranked_nodes = [node_uuid_map[uuid] for uuid in reranked_uuids]
 
+    record_rerank_scores(score_breakdown, reranked_nodes[:limit])
     return reranked_nodes[:limit]
 
 
@@ -344,22 +951,55 @@ async def episode_search(
     driver: GraphDriver,
     cross_encoder: CrossEncoderClient,
     query: str,
-    _query_vector: list[float],
+    query_vector: list[float],
     group_ids: list[str] | None,
     config: EpisodeSearchConfig | None,
     search_filter: SearchFilters,
     limit=DEFAULT_SEARCH_LIMIT,
     reranker_min_score: float = 0,
+    rerank_top_k: int = DEFAULT_RERANK_TOP_K,
+    score_breakdown: ScoreBreakdown | None = None,
+    overfetch_factor: int | None = None,
+    fulltext_match_mode: FulltextMatchMode = FulltextMatchMode.any_terms,
 ) -> list[EpisodicNode]:
     if config is None:
         return []
-    search_results: list[list[EpisodicNode]] = list(
-        await semaphore_gather(
-            *[
-                episode_fulltext_search(driver, query, search_filter, group_ids, 2 * limit),
-            ]
+
+    reranked = config.reranker != EpisodeReranker.rrf or len(config.search_methods) > 1
+    fetch_limit = candidate_limit(limit, overfetch_factor, reranked)
+
+    search_tasks = []
+    methods: list[str] = []
+    method_scores: list[dict[str, float]] = []
+    if EpisodeSearchMethod.bm25 in config.search_methods:
+        methods.append(EpisodeSearchMethod.bm25.value)
+        method_scores.append({})
+        search_tasks.append(
+            episode_fulltext_search(
+                driver,
+                query,
+                search_filter,
+                group_ids,
+                fetch_limit,
+                fulltext_match_mode,
+                scores=method_scores[-1],
+            )
         )
-    )
+    if EpisodeSearchMethod.cosine_similarity in config.search_methods:
+        methods.append(EpisodeSearchMethod.cosine_similarity.value)
+        method_scores.append({})
+        search_tasks.append(
+            episode_similarity_search(
+                driver,
+                query_vector,
+                group_ids,
+                fetch_limit,
+                config.sim_min_score,
+                scores=method_scores[-1],
+            )
+        )
+    search_results: list[list[EpisodicNode]] = list(await semaphore_gather(*search_tasks))
+    record_method_scores(score_breakdown, methods, method_scores, search_results)
 
     search_result_uuids = [[episode.uuid for episode in result] for result in search_results]
     episode_uuid_map = {episode.uuid: episode for result in search_results for episode in result}
@@ -371,19 +1011,17 @@ async def episode_search(
     elif config.reranker == EpisodeReranker.cross_encoder:
         # use rrf as a preliminary reranker
         rrf_result_uuids = rrf(search_result_uuids, min_score=reranker_min_score)
-        rrf_results = [episode_uuid_map[uuid] for uuid in rrf_result_uuids][:limit]
-
-        content_to_uuid_map = {episode.content: episode.uuid for episode in rrf_results}
-
-        reranked_contents = await cross_encoder.rank(query, list(content_to_uuid_map.keys()))
-        reranked_uuids = [
-            content_to_uuid_map[content]
-            for content, score in reranked_contents
-            if score >= reranker_min_score
-        ]
+        reranked_uuids = await cross_encoder_rerank(
+            cross_encoder,
+            query,
+            [(uuid, episode_uuid_map[uuid].content) for uuid in rrf_result_uuids],
+            rerank_top_k,
+            reranker_min_score,
+        )
 
     reranked_episodes = [episode_uuid_map[uuid] for uuid in reranked_uuids]
 
+    record_rerank_scores(score_breakdown, reranked_episodes[:limit])
     return reranked_episodes[:limit]
 
 
@@ -396,20 +1034,48 @@ async def community_search(
     config: CommunitySearchConfig | None,
     limit=DEFAULT_SEARCH_LIMIT,
     reranker_min_score: float = 0,
+    rerank_top_k: int = DEFAULT_RERANK_TOP_K,
+    score_breakdown: ScoreBreakdown | None = None,
+    overfetch_factor: int | None = None,
+    fulltext_match_mode: FulltextMatchMode = FulltextMatchMode.any_terms,
 ) -> list[CommunityNode]:
     if config is None:
         return []
 
-    search_results: list[list[CommunityNode]] = list(
-        await semaphore_gather(
-            *[
-                community_fulltext_search(driver, query, group_ids, 2 * limit),
-                community_similarity_search(
-                    driver, query_vector, group_ids, 2 * limit, config.sim_min_score
-                ),
-            ]
+    reranked = config.reranker != CommunityReranker.rrf or len(config.search_methods) > 1
+    fetch_limit = candidate_limit(limit, overfetch_factor, reranked)
+
+    search_tasks = []
+    methods: list[str] = []
+    method_scores: list[dict[str, float]] = []
+    if CommunitySearchMethod.bm25 in config.search_methods:
+        methods.append(CommunitySearchMethod.bm25.value)
+        method_scores.append({})
+        search_tasks.append(
+            community_fulltext_search(
+                driver,
+                query,
+                group_ids,
+                fetch_limit,
+                fulltext_match_mode,
+                scores=method_scores[-1],
+            )
         )
-    )
+    if CommunitySearchMethod.cosine_similarity in config.search_methods:
+        methods.append(CommunitySearchMethod.cosine_similarity.value)
+        method_scores.append({})
+        search_tasks.append(
+            community_similarity_search(
+                driver,
+                query_vector,
+                group_ids,
+                fetch_limit,
+                config.sim_min_score,
+                scores=method_scores[-1],
+            )
+        )
+    search_results: list[list[CommunityNode]] = list(await semaphore_gather(*search_tasks))
+    record_method_scores(score_breakdown, methods, method_scores, search_results)
 
     search_result_uuids = [[community.uuid for community in result] for result in search_results]
     community_uuid_map = {
@@ -428,12 +1094,17 @@ async def community_search(
             query_vector, search_result_uuids_and_vectors, config.mmr_lambda, reranker_min_score
         )
     elif config.reranker == CommunityReranker.cross_encoder:
-        name_to_uuid_map = {node.name: node.uuid for result in search_results for node in result}
-        reranked_nodes = await cross_encoder.rank(query, list(name_to_uuid_map.keys()))
-        reranked_uuids = [
-            name_to_uuid_map[name] for name, score in reranked_nodes if score >= reranker_min_score
-        ]
+        # use rrf as a preliminary sort
+        rrf_result_uuids = rrf(search_result_uuids, min_score=reranker_min_score)
+        reranked_uuids = await cross_encoder_rerank(
+            cross_encoder,
+            query,
+            [(uuid, community_uuid_map[uuid].name) for uuid in rrf_result_uuids],
+            rerank_top_k,
+            reranker_min_score,
+        )
 
     reranked_communities = [community_uuid_map[uuid] for uuid in reranked_uuids]
 
+    record_rerank_scores(score_breakdown, reranked_communities[:limit])
     return reranked_communities[:limit]
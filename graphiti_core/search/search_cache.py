@@ -0,0 +1,121 @@
+"""
+Copyright 2024, Zep Software, Inc.
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+"""
+
+import hashlib
+import json
+import logging
+from collections import OrderedDict
+from time import monotonic
+
+from graphiti_core.search.search_config import SearchConfig, SearchResults
+from graphiti_core.search.search_filters import SearchFilters
+
+logger = logging.getLogger(__name__)
+
+DEFAULT_SEARCH_CACHE_TTL = 10.0
+DEFAULT_SEARCH_CACHE_SIZE = 1000
+
+
+class SearchResultCache:
+    """
+    In-memory cache of search results with a TTL and per-group invalidation.
+
+    Each group has a version number that is folded into the cache key of any search scoped to
+    that group. Invalidating a group bumps its version, so stale entries are never returned and
+    age out of the cache. Searches across all groups are keyed on a version that is bumped by
+    every invalidation.
+
+    The cache only sees writes made through the Graphiti instance that owns it. The TTL bounds
+    how stale results can be when the graph is modified elsewhere, so it should be kept short.
+    Results are copied in and out of the cache, so callers may modify the results they get.
+    """
+
+    def __init__(
+        self, ttl: float = DEFAULT_SEARCH_CACHE_TTL, max_size: int = DEFAULT_SEARCH_CACHE_SIZE
+    ):
+        self.ttl = ttl
+        self.max_size = max_size
+        self._entries: OrderedDict[str, tuple[float, SearchResults]] = OrderedDict()
+        self._group_versions: dict[str, int] = {}
+        self._all_groups_version = 0
+        self._epoch = 0
+
+    def key(
+        self,
+        query: str,
+        config: SearchConfig,
+        group_ids: list[str] | None,
+        search_filter: SearchFilters,
+        center_node_uuid: str | None = None,
+        bfs_origin_node_uuids: list[str] | None = None,
+        model_override: str | None = None,
+    ) -> str:
+        versions = (
+            [
+                (group_id, self._group_versions.get(group_id, 0))
+                for group_id in sorted(set(group_ids))
+            ]
+            if group_ids
+            else self._all_groups_version
+        )
+
+        key_data = json.dumps(
+            [
+                query,
+                config.model_dump(mode='json'),
+                search_filter.model_dump(mode='json'),
+                center_node_uuid,
+                bfs_origin_node_uuids,
+                model_override,
+                versions,
+                self._epoch,
+            ],
+            sort_keys=True,
+        )
+        return hashlib.md5(key_data.encode()).hexdigest()
+
+    def get(self, key: str) -> SearchResults | None:
+        entry = self._entries.get(key)
+        if entry is None:
+            return None
+
+        expires_at, results = entry
+        if monotonic() >= expires_at:
+            del self._entries[key]
+            return None
+
+        self._entries.move_to_end(key)
+        return results.model_copy(deep=True)
+
+    def set(self, key: str, results: SearchResults):
+        if self.ttl <= 0:
+            return
+
+        self._entries[key] = (monotonic() + self.ttl, results.model_copy(deep=True))
+        self._entries.move_to_end(key)
+        while len(self._entries) > self.max_size:
+            self._entries.popitem(last=False)
+
+    def invalidate(self, group_ids: list[str] | None = None):
+        """Invalidate cached results for the given groups, or for every group if None."""
+        self._all_groups_version += 1
+        if group_ids is None:
+            self._epoch += 1
+            self._entries.clear()
+            return
+
+        for group_id in group_ids:
+            self._group_versions[group_id] = self._group_versions.get(group_id, 0) + 1
@@ -15,18 +15,35 @@ limitations under the License.
 """
 
 from enum import Enum
+from typing import TypeVar
 
-from pydantic import BaseModel, Field
+from pydantic import BaseModel, Field, SerializerFunctionWrapHandler, model_serializer
 
 from graphiti_core.edges import EntityEdge
 from graphiti_core.nodes import CommunityNode, EntityNode, EpisodicNode
 from graphiti_core.search.search_utils import (
     DEFAULT_MIN_SCORE,
     DEFAULT_MMR_LAMBDA,
+    DEFAULT_NAME_BOOST,
+    DEFAULT_SUMMARY_BOOST,
     MAX_SEARCH_DEPTH,
+    FulltextMatchMode,
+    rrf,
 )
 
 DEFAULT_SEARCH_LIMIT = 10
+# Cross-encoder rerankers score every passage they are given, and the OpenAI reranker makes one
+# API call per passage. Only the top candidates by the cheaper first-stage ranking are reranked;
+# a larger K can surface relevant results ranked low by that first stage, at a higher cost.
+DEFAULT_RERANK_TOP_K = 20
+# Rerankers can promote results that a single search method ranks below the limit, so each method
+# fetches this many times the limit when its results are reranked
+DEFAULT_OVERFETCH_FACTOR = 2
+DEFAULT_RECENCY_HALF_LIFE_DAYS = 30.0
+# How many of the top nodes found by search_and_expand are expanded from
+DEFAULT_EXPAND_SEED_COUNT = 5
+# The most nodes or facts a graph traversal returns, however large the requested limit
+DEFAULT_MAX_TRAVERSAL_NODES = 1000
 
 
 class EdgeSearchMethod(Enum):
@@ -42,6 +59,7 @@ class NodeSearchMethod(Enum):
 
 
 class EpisodeSearchMethod(Enum):
+    cosine_similarity = 'cosine_similarity'
     bm25 = 'bm25'
 
 
@@ -77,6 +95,13 @@ class CommunityReranker(Enum):
     cross_encoder = 'cross_encoder'
 
 
+class SortBy(Enum):
+    relevance = 'relevance'
+    # Items without a valid_at date, such as entity and community nodes, use created_at
+    valid_at_desc = 'valid_at_desc'
+    created_at_desc = 'created_at_desc'
+
+
 class EdgeSearchConfig(BaseModel):
     search_methods: list[EdgeSearchMethod]
     reranker: EdgeReranker = Field(default=EdgeReranker.rrf)
@@ -91,6 +116,9 @@ class NodeSearchConfig(BaseModel):
     sim_min_score: float = Field(default=DEFAULT_MIN_SCORE)
     mmr_lambda: float = Field(default=DEFAULT_MMR_LAMBDA)
     bfs_max_depth: int = Field(default=MAX_SEARCH_DEPTH)
+    # BM25 weights of a query match on the node's name and on its summary
+    name_boost: float = Field(default=DEFAULT_NAME_BOOST, gt=0)
+    summary_boost: float = Field(default=DEFAULT_SUMMARY_BOOST, gt=0)
 
 
 class EpisodeSearchConfig(BaseModel):
@@ -116,6 +144,79 @@ class SearchConfig(BaseModel):
     community_config: CommunitySearchConfig | None = Field(default=None)
     limit: int = Field(default=DEFAULT_SEARCH_LIMIT)
     reranker_min_score: float = Field(default=0)
+    rerank_top_k: int = Field(default=DEFAULT_RERANK_TOP_K)
+    # Candidates fetched per search method, as a multiple of limit. None fetches
+    # DEFAULT_OVERFETCH_FACTOR times the limit when results are reranked, and just the limit when
+    # a single method's ranking is returned as is
+    overfetch_factor: int | None = Field(default=None, ge=1)
+    # How BM25 search matches the query terms. Matching all terms or the phrase keeps multi-word
+    # queries from returning everything that mentions any one of their words
+    fulltext_match_mode: FulltextMatchMode = Field(default=FulltextMatchMode.any_terms)
+    sort_by: SortBy = Field(default=SortBy.relevance)
+    # Blends relevance with how recent each result is; 0 ranks by relevance alone
+    recency_weight: float = Field(default=0, ge=0, le=1)
+    recency_half_life_days: float = Field(default=DEFAULT_RECENCY_HALF_LIFE_DAYS, gt=0)
+    # Attaches a per-result score breakdown to the results, for debugging relevance
+    explain: bool = Field(default=False)
+    # Rewrites the query with the LLM into an expanded query and sub-queries, searches for each
+    # and merges the results. Improves recall of short queries at the cost of an LLM call
+    query_expansion: bool = Field(default=False)
+
+    @classmethod
+    def nodes_only(
+        cls, limit: int = DEFAULT_SEARCH_LIMIT, reranker: NodeReranker = NodeReranker.rrf
+    ) -> 'SearchConfig':
+        """Hybrid BM25 and similarity search over entity nodes only."""
+        return cls(
+            node_config=NodeSearchConfig(
+                search_methods=[NodeSearchMethod.bm25, NodeSearchMethod.cosine_similarity],
+                reranker=reranker,
+            ),
+            limit=limit,
+        )
+
+    @classmethod
+    def facts_only(
+        cls, limit: int = DEFAULT_SEARCH_LIMIT, reranker: EdgeReranker = EdgeReranker.rrf
+    ) -> 'SearchConfig':
+        """Hybrid BM25 and similarity search over facts (entity edges) only."""
+        return cls(
+            edge_config=EdgeSearchConfig(
+                search_methods=[EdgeSearchMethod.bm25, EdgeSearchMethod.cosine_similarity],
+                reranker=reranker,
+            ),
+            limit=limit,
+        )
+
+    @classmethod
+    def episodes_only(
+        cls, limit: int = DEFAULT_SEARCH_LIMIT, reranker: EpisodeReranker = EpisodeReranker.rrf
+    ) -> 'SearchConfig':
+        """BM25 search over episodes only."""
+        return cls(
+            episode_config=EpisodeSearchConfig(
+                search_methods=[EpisodeSearchMethod.bm25],
+                reranker=reranker,
+            ),
+            limit=limit,
+        )
+
+    @classmethod
+    def comprehensive(cls, limit: int = DEFAULT_SEARCH_LIMIT) -> 'SearchConfig':
+        """Hybrid search with rrf reranking over facts, nodes, episodes, and communities."""
+        return cls(
+            edge_config=cls.facts_only().edge_config,
+            node_config=cls.nodes_only().node_config,
+            episode_config=cls.episodes_only().episode_config,
+            community_config=CommunitySearchConfig(
+                search_methods=[
+                    CommunitySearchMethod.bm25,
+                    CommunitySearchMethod.cosine_similarity,
+                ],
+                reranker=CommunityReranker.rrf,
+            ),
+            limit=limit,
+        )
 
 
 class SearchResults(BaseModel):
@@ -123,3 +224,125 @@ class SearchResults(BaseModel):
     nodes: list[EntityNode]
     episodes: list[EpisodicNode]
     communities: list[CommunityNode]
+    score_breakdowns: dict[str, dict[str, float]] | None = Field(
+        default=None,
+        description='Scores that ranked each result, keyed by result uuid. Only set by explain',
+    )
+    truncated: bool = Field(
+        default=False,
+        description='Whether a graph traversal was cut short at its node budget, so that more '
+        'results were reachable than returned',
+    )
+    timed_out: bool = Field(
+        default=False,
+        description='Whether the search hit its timeout, so that only the results gathered '
+        'before the deadline were returned',
+    )
+
+    @model_serializer(mode='wrap')
+    def _omit_empty_breakdowns(self, handler: SerializerFunctionWrapHandler) -> dict:
+        data = handler(self)
+        if self.score_breakdowns is None:
+            data.pop('score_breakdowns', None)
+        if not self.truncated:
+            data.pop('truncated', None)
+        if not self.timed_out:
+            data.pop('timed_out', None)
+        return data
+
+    @classmethod
+    def merge(cls, results: list['SearchResults'], limit: int | None = None) -> 'SearchResults':
+        """
+        Combine several result sets, e.g. from searches over different groups, into one.
+
+        Each collection is re-ranked with reciprocal rank fusion over its position in each result
+        set, deduplicated by uuid, and truncated to limit. Results found by several searches are
+        kept once and rank higher.
+        """
+        return cls(
+            edges=merge_ranked([result.edges for result in results], limit),
+            nodes=merge_ranked([result.nodes for result in results], limit),
+            episodes=merge_ranked([result.episodes for result in results], limit),
+            communities=merge_ranked([result.communities for result in results], limit),
+        )
+
+
+T = TypeVar('T', EntityEdge, EntityNode, EpisodicNode, CommunityNode)
+
+
+def merge_ranked(rankings: list[list[T]], limit: int | None = None) -> list[T]:
+    items_by_uuid: dict[str, T] = {}
+    for ranking in rankings:
+        for item in ranking:
+            items_by_uuid.setdefault(item.uuid, item)
+
+    merged_uuids = rrf([[item.uuid for item in ranking] for ranking in rankings])
+    return [items_by_uuid[uuid] for uuid in merged_uuids[:limit]]
+
+
+def without_embeddings(config: SearchConfig) -> SearchConfig:
+    """
+    Return a copy of the config that only uses keyword and graph search methods.
+
+    Cosine similarity search methods are dropped and MMR rerankers, which compare embeddings,
+    fall back to RRF.
+    """
+    edge_config = node_config = episode_config = community_config = None
+    if config.edge_config is not None:
+        edge_config = config.edge_config.model_copy(
+            update={
+                'search_methods': [
+                    method
+                    for method in config.edge_config.search_methods
+                    if method != EdgeSearchMethod.cosine_similarity
+                ],
+                'reranker': EdgeReranker.rrf
+                if config.edge_config.reranker == EdgeReranker.mmr
+                else config.edge_config.reranker,
+            }
+        )
+    if config.node_config is not None:
+        node_config = config.node_config.model_copy(
+            update={
+                'search_methods': [
+                    method
+                    for method in config.node_config.search_methods
+                    if method != NodeSearchMethod.cosine_similarity
+                ],
+                'reranker': NodeReranker.rrf
+                if config.node_config.reranker == NodeReranker.mmr
+                else config.node_config.reranker,
+            }
+        )
+    if config.episode_config is not None:
+        episode_config = config.episode_config.model_copy(
+            update={
+                'search_methods': [
+                    method
+                    for method in config.episode_config.search_methods
+                    if method != EpisodeSearchMethod.cosine_similarity
+                ]
+            }
+        )
+    if config.community_config is not None:
+        community_config = config.community_config.model_copy(
+            update={
+                'search_methods': [
+                    method
+                    for method in config.community_config.search_methods
+                    if method != CommunitySearchMethod.cosine_similarity
+                ],
+                'reranker': CommunityReranker.rrf
+                if config.community_config.reranker == CommunityReranker.mmr
+                else config.community_config.reranker,
+            }
+        )
+
+    return config.model_copy(
+        update={
+            'edge_config': edge_config,
+            'node_config': node_config,
+            'episode_config': episode_config,
+            'community_config': community_config,
+        }
+    )
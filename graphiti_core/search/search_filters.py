@@ -14,12 +14,18 @@ See the License for the specific language governing permissions and
 limitations under the License.
 """
 
+import re
 from datetime import datetime
 from enum import Enum
 from typing import Any
 
 from pydantic import BaseModel, Field
 
+from graphiti_core.utils.datetime_utils import utc_now
+
+# Property names can't be query parameters, so attribute names are checked before being inlined
+ATTRIBUTE_NAME_PATTERN = re.compile(r'[A-Za-z_][A-Za-z0-9_]*')
+
 
 class ComparisonOperator(Enum):
     equals = '='
@@ -37,6 +43,14 @@ class DateFilter(BaseModel):
     )
 
 
+class AttributeFilter(BaseModel):
+    name: str = Field(description='Name of the entity attribute to filter on, e.g. "age"')
+    value: str | int | float | bool = Field(description='Value to compare the attribute with')
+    comparison_operator: ComparisonOperator = Field(
+        default=ComparisonOperator.equals, description='Comparison operator for attribute filter'
+    )
+
+
 class SearchFilters(BaseModel):
     node_labels: list[str] | None = Field(
         default=None, description='List of node labels to filter on'
@@ -48,6 +62,19 @@ class SearchFilters(BaseModel):
     invalid_at: list[list[DateFilter]] | None = Field(default=None)
     created_at: list[list[DateFilter]] | None = Field(default=None)
     expired_at: list[list[DateFilter]] | None = Field(default=None)
+    min_confidence: float | None = Field(
+        default=None,
+        description='Minimum edge confidence. Edges without a confidence are excluded',
+    )
+    include_invalidated: bool = Field(
+        default=False,
+        description='Include edges that have been invalidated or expired, e.g. for history queries',
+    )
+    node_attributes: list[AttributeFilter] | None = Field(
+        default=None,
+        description='Conditions on entity attributes, e.g. age > 30, that returned nodes must all '
+        'meet. Nodes without the attribute are excluded',
+    )
 
 
 def node_search_filter_query_constructor(
@@ -61,6 +88,14 @@ def node_search_filter_query_constructor(
         node_label_filter = ' AND n:' + node_labels
         filter_query += node_label_filter
 
+    if filters.node_attributes is not None:
+        for i, attribute_filter in enumerate(filters.node_attributes):
+            if not ATTRIBUTE_NAME_PATTERN.fullmatch(attribute_filter.name):
+                raise ValueError(f'Invalid attribute name: {attribute_filter.name!r}')
+            operator = attribute_filter.comparison_operator.value
+            filter_query += f' AND n.{attribute_filter.name} {operator} $node_attribute_{i}'
+            filter_params[f'node_attribute_{i}'] = attribute_filter.value
+
     return filter_query, filter_params
 
 
@@ -70,6 +105,13 @@ def edge_search_filter_query_constructor(
     filter_query: str = ''
     filter_params: dict[str, Any] = {}
 
+    if not filters.include_invalidated:
+        filter_query += (
+            '\nAND (r.invalid_at IS NULL OR r.invalid_at > $now)'
+            '\nAND (r.expired_at IS NULL OR r.expired_at > $now)'
+        )
+        filter_params['now'] = utc_now()
+
     if filters.edge_types is not None:
         edge_types = filters.edge_types
         edge_types_filter = '\nAND r.name in $edge_types'
@@ -181,4 +223,8 @@ def edge_search_filter_query_constructor(
 
         filter_query += expired_at_filter
 
+    if filters.min_confidence is not None:
+        filter_query += '\nAND r.confidence >= $min_confidence'
+        filter_params['min_confidence'] = filters.min_confidence
+
     return filter_query, filter_params
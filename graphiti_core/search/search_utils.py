@@ -16,6 +16,7 @@ limitations under the License.
 
 import logging
 from collections import defaultdict
+from enum import Enum
 from time import time
 from typing import Any
 
@@ -25,6 +26,7 @@ from typing_extensions import LiteralString
 
 from graphiti_core.driver.driver import GraphDriver
 from graphiti_core.edges import EntityEdge, get_entity_edge_from_record
+from graphiti_core.errors import SearchDepthExceededError
 from graphiti_core.graph_queries import (
     get_nodes_query,
     get_relationships_query,
@@ -33,6 +35,7 @@ from graphiti_core.graph_queries import (
 from graphiti_core.helpers import (
     DEFAULT_DATABASE,
     RUNTIME_QUERY,
+    is_finite_embedding,
     lucene_sanitize,
     normalize_l2,
     semaphore_gather,
@@ -59,9 +62,31 @@ DEFAULT_MIN_SCORE = 0.6
 DEFAULT_MMR_LAMBDA = 0.5
 MAX_SEARCH_DEPTH = 3
 MAX_QUERY_LENGTH = 32
+# BM25 weights of the entity node fields, so that a match on a name outranks one in a summary
+DEFAULT_NAME_BOOST = 3.0
+DEFAULT_SUMMARY_BOOST = 1.0
 
 
-def fulltext_query(query: str, group_ids: list[str] | None = None):
+class FulltextMatchMode(Enum):
+    # Lucene's default: documents matching any of the query terms
+    any_terms = 'any_terms'
+    all_terms = 'all_terms'
+    # The query terms in order, next to each other
+    phrase = 'phrase'
+
+
+def fulltext_query(
+    query: str,
+    group_ids: list[str] | None = None,
+    match_mode: FulltextMatchMode = FulltextMatchMode.any_terms,
+    field_boosts: dict[str, float] | None = None,
+):
+    """
+    Lucene query for the terms of the query within the given groups. With field_boosts, each term,
+    or the phrase, is matched against each field separately and a match is weighted by the field's
+    boost. Terms are boosted one at a time, so with all_terms they can still match in different
+    fields.
+    """
     group_ids_filter_list = (
         [f'group_id:"{lucene_sanitize(g)}"' for g in group_ids] if group_ids is not None else []
     )
@@ -76,6 +101,22 @@ def fulltext_query(query: str, group_ids: list[str] | None = None):
     if len(lucene_query.split(' ')) + len(group_ids or '') >= MAX_QUERY_LENGTH:
         return ''
 
+    terms = lucene_query.split()
+    if match_mode == FulltextMatchMode.phrase and terms:
+        terms = ['"' + ' '.join(terms) + '"']
+    if field_boosts:
+        terms = [
+            '('
+            + ' OR '.join(f'{field}:{term}^{boost:g}' for field, boost in field_boosts.items())
+            + ')'
+            for term in terms
+        ]
+
+    if match_mode == FulltextMatchMode.all_terms and terms:
+        lucene_query = ' AND '.join(terms)
+    elif (match_mode == FulltextMatchMode.phrase or field_boosts) and terms:
+        lucene_query = ' '.join(terms)
+
     full_query = group_ids_filter + '(' + lucene_query + ')'
 
     return full_query
@@ -120,7 +161,7 @@ async def get_mentioned_nodes(
         routing_='r',
     )
 
-    nodes = [get_entity_node_from_record(record) for record in records]
+    nodes = [get_entity_node_from_record(record, driver) for record in records]
 
     return nodes
 
@@ -147,20 +188,28 @@ async def get_communities_by_nodes(
         routing_='r',
     )
 
-    communities = [get_community_node_from_record(record) for record in records]
+    communities = [get_community_node_from_record(record, driver) for record in records]
 
     return communities
 
 
+def _record_scores(scores: dict[str, float] | None, records: list[Any]):
+    """Collect the score the database gave each returned record, keyed by uuid."""
+    if scores is not None:
+        scores.update({record['uuid']: record['score'] for record in records})
+
+
 async def edge_fulltext_search(
     driver: GraphDriver,
     query: str,
     search_filter: SearchFilters,
     group_ids: list[str] | None = None,
     limit=RELEVANT_SCHEMA_LIMIT,
+    match_mode: FulltextMatchMode = FulltextMatchMode.any_terms,
+    scores: dict[str, float] | None = None,
 ) -> list[EntityEdge]:
     # fulltext search over facts
-    fuzzy_query = fulltext_query(query, group_ids)
+    fuzzy_query = fulltext_query(query, group_ids, match_mode)
     if fuzzy_query == '':
         return []
 
@@ -187,8 +236,9 @@ async def edge_fulltext_search(
             r.expired_at AS expired_at,
             r.valid_at AS valid_at,
             r.invalid_at AS invalid_at,
-            properties(r) AS attributes
-        ORDER BY score DESC LIMIT $limit
+            properties(r) AS attributes,
+            score
+        ORDER BY score DESC, uuid LIMIT $limit
         """
     )
 
@@ -202,7 +252,8 @@ async def edge_fulltext_search(
         routing_='r',
     )
 
-    edges = [get_entity_edge_from_record(record) for record in records]
+    _record_scores(scores, records)
+    edges = [get_entity_edge_from_record(record, driver) for record in records]
 
     return edges
 
@@ -216,6 +267,7 @@ async def edge_similarity_search(
     group_ids: list[str] | None = None,
     limit: int = RELEVANT_SCHEMA_LIMIT,
     min_score: float = DEFAULT_MIN_SCORE,
+    scores: dict[str, float] | None = None,
 ) -> list[EntityEdge]:
     # vector similarity search over embedded facts
     query_params: dict[str, Any] = {}
@@ -260,8 +312,9 @@ async def edge_similarity_search(
             r.expired_at AS expired_at,
             r.valid_at AS valid_at,
             r.invalid_at AS invalid_at,
-            properties(r) AS attributes
-        ORDER BY score DESC
+            properties(r) AS attributes,
+            score
+        ORDER BY score DESC, uuid
         LIMIT $limit
         """
     )
@@ -278,11 +331,31 @@ async def edge_similarity_search(
         routing_='r',
     )
 
-    edges = [get_entity_edge_from_record(record) for record in records]
+    _record_scores(scores, records)
+    edges = [get_entity_edge_from_record(record, driver) for record in records]
 
     return edges
 
 
+def validate_search_depth(depth: int) -> int:
+    """
+    Check a traversal depth, raising SearchDepthExceededError above MAX_SEARCH_DEPTH so that a
+    traversal can't run over a whole dense graph.
+    """
+    depth = int(depth)
+    if depth < 1:
+        raise ValueError('bfs_max_depth must be at least 1')
+    if depth > MAX_SEARCH_DEPTH:
+        raise SearchDepthExceededError(depth, MAX_SEARCH_DEPTH)
+    return depth
+
+
+def _bfs_quantifier(bfs_max_depth: int) -> LiteralString:
+    # Path quantifiers can't be query parameters, so the depth is validated and inlined
+    depth = validate_search_depth(bfs_max_depth)
+    return '{1,' + str(depth) + '}'  # type: ignore
+
+
 async def edge_bfs_search(
     driver: GraphDriver,
     bfs_origin_node_uuids: list[str] | None,
@@ -299,7 +372,9 @@ async def edge_bfs_search(
     query = (
         """
                                     UNWIND $bfs_origin_node_uuids AS origin_uuid
-                                    MATCH path = (origin:Entity|Episodic {uuid: origin_uuid})-[:RELATES_TO|MENTIONS]->{1,3}(n:Entity)
+                                    MATCH path = (origin:Entity|Episodic {uuid: origin_uuid})-[:RELATES_TO|MENTIONS]->"""
+        + _bfs_quantifier(bfs_max_depth)
+        + """(n:Entity)
                                     UNWIND relationships(path) AS rel
                                     MATCH (n:Entity)-[r:RELATES_TO]-(m:Entity)
                                     WHERE r.uuid = rel.uuid
@@ -327,13 +402,12 @@ async def edge_bfs_search(
         query,
         params=filter_params,
         bfs_origin_node_uuids=bfs_origin_node_uuids,
-        depth=bfs_max_depth,
         limit=limit,
         database_=DEFAULT_DATABASE,
         routing_='r',
     )
 
-    edges = [get_entity_edge_from_record(record) for record in records]
+    edges = [get_entity_edge_from_record(record, driver) for record in records]
 
     return edges
 
@@ -344,9 +418,17 @@ async def node_fulltext_search(
     search_filter: SearchFilters,
     group_ids: list[str] | None = None,
     limit=RELEVANT_SCHEMA_LIMIT,
+    match_mode: FulltextMatchMode = FulltextMatchMode.any_terms,
+    name_boost: float = DEFAULT_NAME_BOOST,
+    summary_boost: float = DEFAULT_SUMMARY_BOOST,
+    scores: dict[str, float] | None = None,
 ) -> list[EntityNode]:
-    # BM25 search to get top nodes
-    fuzzy_query = fulltext_query(query, group_ids)
+    # BM25 search to get top nodes, weighting name matches over summary matches. FalkorDB
+    # fulltext queries don't support Lucene field boosts, so its fields are weighted equally
+    field_boosts = (
+        {'name': name_boost, 'summary': summary_boost} if driver.provider != 'falkordb' else None
+    )
+    fuzzy_query = fulltext_query(query, group_ids, match_mode, field_boosts)
     if fuzzy_query == '':
         return []
     filter_query, filter_params = node_search_filter_query_constructor(search_filter)
@@ -361,8 +443,9 @@ async def node_fulltext_search(
         """
         + filter_query
         + ENTITY_NODE_RETURN
-        + """
-        ORDER BY score DESC
+        + """,
+            score
+        ORDER BY score DESC, uuid
         """
     )
     records, header, _ = await driver.execute_query(
@@ -375,7 +458,8 @@ async def node_fulltext_search(
         routing_='r',
     )
 
-    nodes = [get_entity_node_from_record(record) for record in records]
+    _record_scores(scores, records)
+    nodes = [get_entity_node_from_record(record, driver) for record in records]
 
     return nodes
 
@@ -387,6 +471,7 @@ async def node_similarity_search(
     group_ids: list[str] | None = None,
     limit=RELEVANT_SCHEMA_LIMIT,
     min_score: float = DEFAULT_MIN_SCORE,
+    scores: dict[str, float] | None = None,
 ) -> list[EntityNode]:
     # vector similarity search over entity names
     query_params: dict[str, Any] = {}
@@ -412,8 +497,9 @@ async def node_similarity_search(
         + """ AS score
         WHERE score > $min_score"""
         + ENTITY_NODE_RETURN
-        + """
-        ORDER BY score DESC
+        + """,
+            score
+        ORDER BY score DESC, uuid
         LIMIT $limit
             """
     )
@@ -429,7 +515,8 @@ async def node_similarity_search(
         routing_='r',
     )
 
-    nodes = [get_entity_node_from_record(record) for record in records]
+    _record_scores(scores, records)
+    nodes = [get_entity_node_from_record(record, driver) for record in records]
 
     return nodes
 
@@ -450,7 +537,9 @@ async def node_bfs_search(
     query = (
         """
                             UNWIND $bfs_origin_node_uuids AS origin_uuid
-                            MATCH (origin:Entity|Episodic {uuid: origin_uuid})-[:RELATES_TO|MENTIONS]->{1,3}(n:Entity)
+                            MATCH (origin:Entity|Episodic {uuid: origin_uuid})-[:RELATES_TO|MENTIONS]->"""
+        + _bfs_quantifier(bfs_max_depth)
+        + """(n:Entity)
                             WHERE n.group_id = origin.group_id
                             """
         + filter_query
@@ -463,12 +552,11 @@ async def node_bfs_search(
         query,
         params=filter_params,
         bfs_origin_node_uuids=bfs_origin_node_uuids,
-        depth=bfs_max_depth,
         limit=limit,
         database_=DEFAULT_DATABASE,
         routing_='r',
     )
-    nodes = [get_entity_node_from_record(record) for record in records]
+    nodes = [get_entity_node_from_record(record, driver) for record in records]
 
     return nodes
 
@@ -479,9 +567,11 @@ async def episode_fulltext_search(
     _search_filter: SearchFilters,
     group_ids: list[str] | None = None,
     limit=RELEVANT_SCHEMA_LIMIT,
+    match_mode: FulltextMatchMode = FulltextMatchMode.any_terms,
+    scores: dict[str, float] | None = None,
 ) -> list[EpisodicNode]:
     # BM25 search to get top episodes
-    fuzzy_query = fulltext_query(query, group_ids)
+    fuzzy_query = fulltext_query(query, group_ids, match_mode)
     if fuzzy_query == '':
         return []
 
@@ -500,8 +590,11 @@ async def episode_fulltext_search(
             e.group_id AS group_id,
             e.source_description AS source_description,
             e.source AS source,
-            e.entity_edges AS entity_edges
-        ORDER BY score DESC
+            e.entity_edges AS entity_edges,
+            e.external_id AS external_id,
+            e.language AS language,
+            score
+        ORDER BY score DESC, uuid
         LIMIT $limit
         """
     )
@@ -514,7 +607,69 @@ async def episode_fulltext_search(
         database_=DEFAULT_DATABASE,
         routing_='r',
     )
-    episodes = [get_episodic_node_from_record(record) for record in records]
+    _record_scores(scores, records)
+    episodes = [get_episodic_node_from_record(record, driver) for record in records]
+
+    return episodes
+
+
+async def episode_similarity_search(
+    driver: GraphDriver,
+    search_vector: list[float],
+    group_ids: list[str] | None = None,
+    limit=RELEVANT_SCHEMA_LIMIT,
+    min_score=DEFAULT_MIN_SCORE,
+    scores: dict[str, float] | None = None,
+) -> list[EpisodicNode]:
+    # vector similarity search over episode content
+    query_params: dict[str, Any] = {}
+
+    group_filter_query: LiteralString = ''
+    if group_ids is not None:
+        group_filter_query += 'AND e.group_id IN $group_ids'
+        query_params['group_ids'] = group_ids
+
+    query = (
+        RUNTIME_QUERY
+        + """
+        MATCH (e:Episodic)
+        WHERE e.content_embedding IS NOT NULL
+        """
+        + group_filter_query
+        + """
+        WITH e, """
+        + get_vector_cosine_func_query('e.content_embedding', '$search_vector', driver.provider)
+        + """ AS score
+        WHERE score > $min_score
+        RETURN
+            e.content AS content,
+            e.created_at AS created_at,
+            e.valid_at AS valid_at,
+            e.uuid AS uuid,
+            e.name AS name,
+            e.group_id AS group_id,
+            e.source_description AS source_description,
+            e.source AS source,
+            e.entity_edges AS entity_edges,
+            e.external_id AS external_id,
+            e.language AS language,
+            score
+        ORDER BY score DESC, uuid
+        LIMIT $limit
+        """
+    )
+
+    records, _, _ = await driver.execute_query(
+        query,
+        search_vector=search_vector,
+        limit=limit,
+        min_score=min_score,
+        database_=DEFAULT_DATABASE,
+        routing_='r',
+        **query_params,
+    )
+    _record_scores(scores, records)
+    episodes = [get_episodic_node_from_record(record, driver) for record in records]
 
     return episodes
 
@@ -524,9 +679,11 @@ async def community_fulltext_search(
     query: str,
     group_ids: list[str] | None = None,
     limit=RELEVANT_SCHEMA_LIMIT,
+    match_mode: FulltextMatchMode = FulltextMatchMode.any_terms,
+    scores: dict[str, float] | None = None,
 ) -> list[CommunityNode]:
     # BM25 search to get top communities
-    fuzzy_query = fulltext_query(query, group_ids)
+    fuzzy_query = fulltext_query(query, group_ids, match_mode)
     if fuzzy_query == '':
         return []
 
@@ -539,8 +696,9 @@ async def community_fulltext_search(
             comm.group_id AS group_id, 
             comm.name AS name, 
             comm.created_at AS created_at, 
-            comm.summary AS summary
-        ORDER BY score DESC
+            comm.summary AS summary,
+            score
+        ORDER BY score DESC, uuid
         LIMIT $limit
         """
     )
@@ -553,7 +711,8 @@ async def community_fulltext_search(
         database_=DEFAULT_DATABASE,
         routing_='r',
     )
-    communities = [get_community_node_from_record(record) for record in records]
+    _record_scores(scores, records)
+    communities = [get_community_node_from_record(record, driver) for record in records]
 
     return communities
 
@@ -564,6 +723,7 @@ async def community_similarity_search(
     group_ids: list[str] | None = None,
     limit=RELEVANT_SCHEMA_LIMIT,
     min_score=DEFAULT_MIN_SCORE,
+    scores: dict[str, float] | None = None,
 ) -> list[CommunityNode]:
     # vector similarity search over entity names
     query_params: dict[str, Any] = {}
@@ -589,8 +749,9 @@ async def community_similarity_search(
                comm.group_id AS group_id,
                comm.name AS name, 
                comm.created_at AS created_at, 
-               comm.summary AS summary
-           ORDER BY score DESC
+               comm.summary AS summary,
+               score
+           ORDER BY score DESC, uuid
            LIMIT $limit
         """
     )
@@ -604,7 +765,8 @@ async def community_similarity_search(
         database_=DEFAULT_DATABASE,
         routing_='r',
     )
-    communities = [get_community_node_from_record(record) for record in records]
+    _record_scores(scores, records)
+    communities = [get_community_node_from_record(record, driver) for record in records]
 
     return communities
 
@@ -768,7 +930,7 @@ async def get_relevant_nodes(
 
     relevant_nodes_dict: dict[str, list[EntityNode]] = {
         result['search_node_uuid']: [
-            get_entity_node_from_record(record) for record in result['matches']
+            get_entity_node_from_record(record, driver) for record in result['matches']
         ]
         for result in results
     }
@@ -806,7 +968,7 @@ async def get_relevant_edges(
         + """ AS score
         WHERE score > $min_score
         WITH edge, e, score
-        ORDER BY score DESC
+        ORDER BY score DESC, e.uuid
         RETURN edge.uuid AS search_edge_uuid,
             collect({
                 uuid: e.uuid,
@@ -838,7 +1000,7 @@ async def get_relevant_edges(
 
     relevant_edges_dict: dict[str, list[EntityEdge]] = {
         result['search_edge_uuid']: [
-            get_entity_edge_from_record(record) for record in result['matches']
+            get_entity_edge_from_record(record, driver) for record in result['matches']
         ]
         for result in results
     }
@@ -877,7 +1039,7 @@ async def get_edge_invalidation_candidates(
         + """ AS score
         WHERE score > $min_score
         WITH edge, e, score
-        ORDER BY score DESC
+        ORDER BY score DESC, e.uuid
         RETURN edge.uuid AS search_edge_uuid,
             collect({
                 uuid: e.uuid,
@@ -908,7 +1070,7 @@ async def get_edge_invalidation_candidates(
     )
     invalidation_edges_dict: dict[str, list[EntityEdge]] = {
         result['search_edge_uuid']: [
-            get_entity_edge_from_record(record) for record in result['matches']
+            get_entity_edge_from_record(record, driver) for record in result['matches']
         ]
         for result in results
     }
@@ -925,8 +1087,9 @@ def rrf(results: list[list[str]], rank_const=1, min_score: float = 0) -> list[st
         for i, uuid in enumerate(result):
             scores[uuid] += 1 / (i + rank_const)
 
+    # Ties are broken by uuid so equal scores don't depend on the order results came back in
     scored_uuids = [term for term in scores.items()]
-    scored_uuids.sort(reverse=True, key=lambda term: term[1])
+    scored_uuids.sort(key=lambda term: (-term[1], term[0]))
 
     sorted_uuids = [term[0] for term in scored_uuids]
 
@@ -949,15 +1112,14 @@ async def node_distance_reranker(
         MATCH (center:Entity {uuid: $center_uuid})-[:RELATES_TO]-(n:Entity {uuid: node_uuid})
         RETURN 1 AS score, node_uuid AS uuid
         """
-    results, header, _ = await driver.execute_query(
+    # Every driver returns records that can be indexed by column name
+    results, _, _ = await driver.execute_query(
         query,
         node_uuids=filtered_uuids,
         center_uuid=center_node_uuid,
         database_=DEFAULT_DATABASE,
         routing_='r',
     )
-    if driver.provider == 'falkordb':
-        results = [dict(zip(header, row, strict=True)) for row in results]
 
     for result in results:
         uuid = result['uuid']
@@ -1018,6 +1180,9 @@ def maximal_marginal_relevance(
     query_array = np.array(query_vector)
     candidate_arrays: dict[str, NDArray] = {}
     for uuid, embedding in candidates.items():
+        if not is_finite_embedding(embedding):
+            logger.warning(f'Skipping {uuid} in MMR reranking: its embedding has non-finite values')
+            continue
         candidate_arrays[uuid] = normalize_l2(embedding)
 
     uuids: list[str] = list(candidate_arrays.keys())
@@ -1039,7 +1204,7 @@ def maximal_marginal_relevance(
         mmr = mmr_lambda * np.dot(query_array, candidate_arrays[uuid]) + (mmr_lambda - 1) * max_sim
         mmr_scores[uuid] = mmr
 
-    uuids.sort(reverse=True, key=lambda c: mmr_scores[c])
+    uuids.sort(key=lambda c: (-mmr_scores[c], c))
 
     end = time()
     logger.debug(f'Completed MMR reranking in {(end - start) * 1000} ms')
@@ -0,0 +1,218 @@
+"""
+Copyright 2024, Zep Software, Inc.
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+"""
+
+
+import hashlib
+import json
+import math
+import typing
+from collections.abc import Callable, Iterable
+from dataclasses import dataclass
+from pathlib import Path
+
+from pydantic import BaseModel
+
+from ..cross_encoder.client import CrossEncoderClient
+from ..embedder.client import EMBEDDING_DIM, EmbedderClient
+from ..llm_client.client import LLMClient
+from ..llm_client.config import DEFAULT_MAX_TOKENS, LLMConfig, ModelSize
+from ..prompts.models import Message
+
+# A matcher selects the LLM calls a scripted response applies to. Strings match any message
+# containing them, model classes match calls made with that response_model, and callables
+# receive the messages and response_model.
+LLMMatcher = str | type[BaseModel] | Callable[[list[Message], type[BaseModel] | None], bool]
+# A scripted response is returned as is, raised if it is an exception, or called with the
+# messages and response_model to build the response.
+LLMResponse = (
+    dict[str, typing.Any]
+    | Exception
+    | Callable[[list[Message], type[BaseModel] | None], dict[str, typing.Any]]
+)
+
+
+@dataclass
+class ScriptedResponse:
+    matcher: LLMMatcher
+    response: LLMResponse
+    # Number of calls this response is used for; None means unlimited
+    times: int | None = None
+
+    def matches(self, messages: list[Message], response_model: type[BaseModel] | None) -> bool:
+        if self.times is not None and self.times <= 0:
+            return False
+        if isinstance(self.matcher, str):
+            return any(self.matcher in message.content for message in messages)
+        if isinstance(self.matcher, type):
+            return response_model is self.matcher
+        return self.matcher(messages, response_model)
+
+
+def _recorded_call(record: dict[str, typing.Any]) -> LLMMatcher:
+    # Clients append format instructions to the prompt, so the recorded one is a prefix
+    prompt = record['messages'][-1]['content']
+    model_name = record['response_model']
+
+    def matches(messages: list[Message], response_model: type[BaseModel] | None) -> bool:
+        name = response_model.__name__ if response_model is not None else None
+        return name == model_name and messages[-1].content.startswith(prompt)
+
+    return matches
+
+
+class MockLLMClient(LLMClient):
+    """
+    LLM client that returns scripted responses, for deterministic tests.
+
+    Responses are registered with `add_response` and the first registered response that matches
+    a call is used. Calls that match nothing get `default_response`, or raise a ValueError if it
+    is None. Every call is recorded in `calls`.
+
+    Scripted exceptions go through the usual LLMClient retry handling, so a RateLimitError
+    with unlimited uses is retried with backoff before being raised.
+    """
+
+    def __init__(self, default_response: dict[str, typing.Any] | None = None):
+        super().__init__(LLMConfig(api_key='mock'), cache=False)
+        self.default_response = default_response
+        self.responses: list[ScriptedResponse] = []
+        self.calls: list[tuple[list[Message], type[BaseModel] | None]] = []
+
+    def add_response(
+        self, matcher: LLMMatcher, response: LLMResponse, times: int | None = None
+    ) -> 'MockLLMClient':
+        self.responses.append(ScriptedResponse(matcher, response, times))
+        return self
+
+    @classmethod
+    def from_recording(
+        cls, path: str | Path, default_response: dict[str, typing.Any] | None = None
+    ) -> 'MockLLMClient':
+        """
+        Script the responses recorded by a TeeLLMClient. Each recorded response is returned once,
+        for a call with the same response model whose prompt starts with the recorded prompt.
+        """
+        client = cls(default_response)
+        with Path(path).open(encoding='utf-8') as f:
+            for line in f:
+                if line.strip():
+                    record = json.loads(line)
+                    client.add_response(_recorded_call(record), record['response'], times=1)
+        return client
+
+    @property
+    def call_count(self) -> int:
+        return len(self.calls)
+
+    def count_calls(self, matcher: LLMMatcher) -> int:
+        scripted = ScriptedResponse(matcher, {})
+        return sum(scripted.matches(messages, model) for messages, model in self.calls)
+
+    async def _generate_response(
+        self,
+        messages: list[Message],
+        response_model: type[BaseModel] | None = None,
+        max_tokens: int = DEFAULT_MAX_TOKENS,
+        model_size: ModelSize = ModelSize.medium,
+    ) -> dict[str, typing.Any]:
+        self.calls.append(([message.model_copy() for message in messages], response_model))
+
+        scripted = next(
+            (r for r in self.responses if r.matches(messages, response_model)),
+            None,
+        )
+        if scripted is None:
+            if self.default_response is None:
+                raise ValueError(f'No scripted response for call: {messages[-1].content[:200]}')
+            return dict(self.default_response)
+
+        if scripted.times is not None:
+            scripted.times -= 1
+
+        response = scripted.response
+        if isinstance(response, Exception):
+            raise response
+        if callable(response):
+            return response(messages, response_model)
+        return dict(response)
+
+
+class MockEmbedderClient(EmbedderClient):
+    """
+    Embedder client that returns deterministic unit vectors derived from a hash of the text.
+
+    Equal texts always get equal embeddings. Texts are recorded in `inputs`.
+    """
+
+    def __init__(self, embedding_dim: int = EMBEDDING_DIM):
+        self.embedding_dim = embedding_dim
+        self.inputs: list[str] = []
+
+    def embed(self, text: str) -> list[float]:
+        values: list[float] = []
+        counter = 0
+        while len(values) < self.embedding_dim:
+            digest = hashlib.sha256(f'{counter}:{text}'.encode()).digest()
+            values.extend((byte - 127.5) / 127.5 for byte in digest)
+            counter += 1
+
+        vector = values[: self.embedding_dim]
+        norm = math.sqrt(sum(value * value for value in vector))
+        return [value / norm for value in vector]
+
+    async def create(
+        self, input_data: str | list[str] | Iterable[int] | Iterable[Iterable[int]]
+    ) -> list[float]:
+        if isinstance(input_data, str):
+            text = input_data
+        elif isinstance(input_data, list) and input_data and isinstance(input_data[0], str):
+            text = input_data[0]
+        else:
+            text = str(list(input_data))
+
+        self.inputs.append(text)
+        return self.embed(text)
+
+    async def create_batch(self, input_data_list: list[str]) -> list[list[float]]:
+        self.inputs.extend(input_data_list)
+        return [self.embed(text) for text in input_data_list]
+
+
+class MockCrossEncoderClient(CrossEncoderClient):
+    """
+    Cross-encoder client that scores passages by the fraction of query words they contain.
+
+    Scores can be fixed per passage with `scores`. Calls are recorded in `calls`.
+    """
+
+    def __init__(self, scores: dict[str, float] | None = None):
+        self.scores = scores or {}
+        self.calls: list[tuple[str, list[str]]] = []
+
+    def score(self, query: str, passage: str) -> float:
+        if passage in self.scores:
+            return self.scores[passage]
+
+        query_words = set(query.lower().split())
+        if not query_words:
+            return 0.0
+        return len(query_words & set(passage.lower().split())) / len(query_words)
+
+    async def rank(self, query: str, passages: list[str]) -> list[tuple[str, float]]:
+        self.calls.append((query, list(passages)))
+        ranked = [(passage, self.score(query, passage)) for passage in passages]
+        ranked.sort(key=lambda item: item[1], reverse=True)
+        return ranked
@@ -14,25 +14,42 @@ See the License for the specific language governing permissions and
 limitations under the License.
 """
 
+import asyncio
 import logging
 import typing
 from collections import defaultdict
-from datetime import datetime
+from datetime import datetime, timedelta
 from math import ceil
 
-from numpy import dot, sqrt
+from numpy import sqrt
 from pydantic import BaseModel
 from typing_extensions import Any
 
 from graphiti_core.driver.driver import GraphDriver, GraphDriverSession
-from graphiti_core.edges import Edge, EntityEdge, EpisodicEdge
+from graphiti_core.edges import (
+    DEFAULT_SYMMETRIC_RELATIONS,
+    Edge,
+    EntityEdge,
+    EpisodicEdge,
+    is_symmetric_relation,
+    same_direction_or_symmetric,
+)
 from graphiti_core.embedder import EmbedderClient
+from graphiti_core.encryption import encrypt_field
 from graphiti_core.graph_queries import (
     get_entity_edge_save_bulk_query,
     get_entity_node_save_bulk_query,
 )
 from graphiti_core.graphiti_types import GraphitiClients
-from graphiti_core.helpers import DEFAULT_DATABASE, semaphore_gather
+from graphiti_core.helpers import (
+    DEFAULT_DATABASE,
+    NameNormalization,
+    StringSimilarity,
+    cosine_similarity,
+    normalize_name,
+    semaphore_gather,
+    string_similarity,
+)
 from graphiti_core.llm_client import LLMClient
 from graphiti_core.models.edges.edge_db_queries import (
     EPISODIC_EDGE_SAVE_BULK,
@@ -42,7 +59,11 @@ from graphiti_core.models.nodes.node_db_queries import (
 )
 from graphiti_core.nodes import EntityNode, EpisodeType, EpisodicNode
 from graphiti_core.search.search_filters import SearchFilters
-from graphiti_core.search.search_utils import get_relevant_edges, get_relevant_nodes
+from graphiti_core.search.search_utils import (
+    get_relevant_edges,
+    get_relevant_nodes,
+    node_similarity_search,
+)
 from graphiti_core.utils.datetime_utils import utc_now
 from graphiti_core.utils.maintenance.edge_operations import (
     build_episodic_edges,
@@ -64,6 +85,13 @@ from graphiti_core.utils.maintenance.temporal_operations import extract_edge_dat
 logger = logging.getLogger(__name__)
 
 CHUNK_SIZE = 10
+# How many extracted episodes can wait to be embedded before extraction waits for embedding
+DEFAULT_EMBEDDING_CHANNEL_DEPTH = 8
+# How many pending saves a BatchWriter collects before writing them
+DEFAULT_WRITE_BATCH_SIZE = 100
+# How many similar entities each entity is compared with when looking for missed duplicates
+DEDUP_CANDIDATE_LIMIT = 10
+DEDUP_CANDIDATE_MIN_SCORE = 0.8
 
 
 class RawEpisode(BaseModel):
@@ -72,18 +100,27 @@ class RawEpisode(BaseModel):
     source_description: str
     source: EpisodeType
     reference_time: datetime
+    language: str | None = None
 
 
 async def retrieve_previous_episodes_bulk(
-    driver: GraphDriver, episodes: list[EpisodicNode]
+    driver: GraphDriver,
+    episodes: list[EpisodicNode],
+    max_coroutines: int | None = None,
+    min_interval: timedelta | None = None,
 ) -> list[tuple[EpisodicNode, list[EpisodicNode]]]:
     previous_episodes_list = await semaphore_gather(
         *[
             retrieve_episodes(
-                driver, episode.valid_at, last_n=EPISODE_WINDOW_LEN, group_ids=[episode.group_id]
+                driver,
+                episode.valid_at,
+                last_n=EPISODE_WINDOW_LEN,
+                group_ids=[episode.group_id],
+                min_interval=min_interval,
             )
             for episode in episodes
-        ]
+        ],
+        max_coroutines=max_coroutines,
     )
     episode_tuples: list[tuple[EpisodicNode, list[EpisodicNode]]] = [
         (episode, previous_episodes_list[i]) for i, episode in enumerate(episodes)
@@ -98,7 +135,7 @@ async def add_nodes_and_edges_bulk(
     episodic_edges: list[EpisodicEdge],
     entity_nodes: list[EntityNode],
     entity_edges: list[EntityEdge],
-    embedder: EmbedderClient,
+    embedder: EmbedderClient | None,
 ):
     session = driver.session(database=DEFAULT_DATABASE)
     try:
@@ -121,39 +158,49 @@ async def add_nodes_and_edges_bulk_tx(
     episodic_edges: list[EpisodicEdge],
     entity_nodes: list[EntityNode],
     entity_edges: list[EntityEdge],
-    embedder: EmbedderClient,
+    embedder: EmbedderClient | None,
     driver: GraphDriver,
 ):
+    for episodic_node in episodic_nodes:
+        # Episodes stored without their content have nothing to embed
+        if (
+            episodic_node.content_embedding is None
+            and embedder is not None
+            and episodic_node.content
+        ):
+            await episodic_node.generate_content_embedding(embedder)
     episodes = [dict(episode) for episode in episodic_nodes]
     for episode in episodes:
         episode['source'] = str(episode['source'].value)
+        episode['content'] = encrypt_field(episode['group_id'], episode['content'], driver)
     nodes: list[dict[str, Any]] = []
     for node in entity_nodes:
-        if node.name_embedding is None:
+        if node.name_embedding is None and embedder is not None:
             await node.generate_name_embedding(embedder)
         entity_data: dict[str, Any] = {
             'uuid': node.uuid,
             'name': node.name,
             'name_embedding': node.name_embedding,
             'group_id': node.group_id,
-            'summary': node.summary,
+            'summary': encrypt_field(node.group_id, node.summary, driver),
             'created_at': node.created_at,
         }
 
         entity_data.update(node.attributes or {})
+        entity_data.update(node.attribute_metadata)
         entity_data['labels'] = list(set(node.labels + ['Entity']))
         nodes.append(entity_data)
 
     edges: list[dict[str, Any]] = []
     for edge in entity_edges:
-        if edge.fact_embedding is None:
+        if edge.fact_embedding is None and embedder is not None:
             await edge.generate_embedding(embedder)
         edge_data: dict[str, Any] = {
             'uuid': edge.uuid,
             'source_node_uuid': edge.source_node_uuid,
             'target_node_uuid': edge.target_node_uuid,
             'name': edge.name,
-            'fact': edge.fact,
+            'fact': encrypt_field(edge.group_id, edge.fact, driver),
             'fact_embedding': edge.fact_embedding,
             'group_id': edge.group_id,
             'episodes': edge.episodes,
@@ -161,6 +208,7 @@ async def add_nodes_and_edges_bulk_tx(
             'expired_at': edge.expired_at,
             'valid_at': edge.valid_at,
             'invalid_at': edge.invalid_at,
+            'confidence': edge.confidence,
         }
 
         edge_data.update(edge.attributes or {})
@@ -176,37 +224,162 @@ async def add_nodes_and_edges_bulk_tx(
     await tx.run(entity_edge_save_bulk, entity_edges=edges)
 
 
+class BatchWriter:
+    """
+    Collects saves of episodes, entities, and the edges between them, and writes them together
+    with the UNWIND queries of add_nodes_and_edges_bulk, so that saving n items takes one
+    transaction of at most four queries rather than n queries.
+
+    Pending saves are written on flush, and as soon as batch_size of them have been collected.
+    Each write saves nodes before edges, so edges only need to be added after the nodes they
+    connect. Used as an async context manager, the writer flushes on a clean exit.
+    """
+
+    def __init__(
+        self,
+        driver: GraphDriver,
+        embedder: EmbedderClient | None = None,
+        batch_size: int = DEFAULT_WRITE_BATCH_SIZE,
+    ):
+        if batch_size < 1:
+            raise ValueError('batch_size must be at least 1')
+        self.driver = driver
+        self.embedder = embedder
+        self.batch_size = batch_size
+        self.episodic_nodes: list[EpisodicNode] = []
+        self.episodic_edges: list[EpisodicEdge] = []
+        self.entity_nodes: list[EntityNode] = []
+        self.entity_edges: list[EntityEdge] = []
+
+    @property
+    def pending(self) -> int:
+        return (
+            len(self.episodic_nodes)
+            + len(self.episodic_edges)
+            + len(self.entity_nodes)
+            + len(self.entity_edges)
+        )
+
+    async def add(self, *items: EpisodicNode | EntityNode | EpisodicEdge | EntityEdge):
+        for item in items:
+            if isinstance(item, EpisodicNode):
+                self.episodic_nodes.append(item)
+            elif isinstance(item, EntityNode):
+                self.entity_nodes.append(item)
+            elif isinstance(item, EpisodicEdge):
+                self.episodic_edges.append(item)
+            else:
+                self.entity_edges.append(item)
+
+            if self.pending >= self.batch_size:
+                await self.flush()
+
+    async def flush(self):
+        if self.pending == 0:
+            return
+
+        episodic_nodes, self.episodic_nodes = self.episodic_nodes, []
+        episodic_edges, self.episodic_edges = self.episodic_edges, []
+        entity_nodes, self.entity_nodes = self.entity_nodes, []
+        entity_edges, self.entity_edges = self.entity_edges, []
+        await add_nodes_and_edges_bulk(
+            self.driver, episodic_nodes, episodic_edges, entity_nodes, entity_edges, self.embedder
+        )
+        logger.debug(
+            f'Wrote {len(episodic_nodes) + len(entity_nodes)} nodes and '
+            f'{len(episodic_edges) + len(entity_edges)} edges'
+        )
+
+    async def __aenter__(self) -> 'BatchWriter':
+        return self
+
+    async def __aexit__(self, exc_type, exc, tb):
+        if exc_type is None:
+            await self.flush()
+
+
 async def extract_nodes_and_edges_bulk(
     clients: GraphitiClients,
     episode_tuples: list[tuple[EpisodicNode, list[EpisodicNode]]],
     entity_types: dict[str, BaseModel] | None = None,
     excluded_entity_types: list[str] | None = None,
-) -> tuple[list[EntityNode], list[EntityEdge], list[EpisodicEdge]]:
-    extracted_nodes_bulk = await semaphore_gather(
-        *[
-            extract_nodes(clients, episode, previous_episodes, entity_types, excluded_entity_types)
-            for episode, previous_episodes in episode_tuples
-        ]
-    )
-
-    episodes, previous_episodes_list = (
-        [episode[0] for episode in episode_tuples],
-        [episode[1] for episode in episode_tuples],
+    max_coroutines: int | None = None,
+    embedder: EmbedderClient | None = None,
+    embedding_channel_depth: int = DEFAULT_EMBEDDING_CHANNEL_DEPTH,
+) -> tuple[list[EntityNode], list[EntityEdge], list[EpisodicEdge], dict[str, Exception]]:
+    """
+    Extract nodes and edges from each episode.
+
+    An episode whose extraction fails contributes nothing; its error is returned keyed by the
+    episode uuid so that the other episodes can still be added.
+
+    With an embedder, extraction and embedding run as a pipeline: each episode's nodes and edges
+    are embedded as soon as its extraction finishes, while later episodes are still being
+    extracted. At most `embedding_channel_depth` extracted episodes wait to be embedded; once
+    that many are queued, extraction waits for embedding to catch up.
+    """
+    failures: dict[str, Exception] = {}
+    channel: asyncio.Queue[tuple[list[EntityNode], list[EntityEdge]] | None] = asyncio.Queue(
+        maxsize=embedding_channel_depth
     )
-
-    extracted_edges_bulk = await semaphore_gather(
-        *[
-            extract_edges(
-                clients,
-                episode,
-                extracted_nodes_bulk[i],
-                previous_episodes_list[i],
-                {},
-                episode.group_id,
+    embedding_errors: list[Exception] = []
+
+    async def extract_episode(
+        episode: EpisodicNode, previous_episodes: list[EpisodicNode]
+    ) -> tuple[list[EntityNode], list[EntityEdge]]:
+        try:
+            # Edges reference the episode's nodes, so only this episode's edges wait on its nodes
+            extracted_nodes = await extract_nodes(
+                clients, episode, previous_episodes, entity_types, excluded_entity_types
             )
-            for i, episode in enumerate(episodes)
-        ]
+            extracted_edges = await extract_edges(
+                clients, episode, extracted_nodes, previous_episodes, {}, episode.group_id
+            )
+        except Exception as e:
+            logger.error(f'Extraction failed for episode {episode.uuid}: {e}')
+            failures[episode.uuid] = e
+            return [], []
+        if embedder is not None:
+            await channel.put((extracted_nodes, extracted_edges))
+        return extracted_nodes, extracted_edges
+
+    async def embed_extracted(embedder: EmbedderClient):
+        while (extracted := await channel.get()) is not None:
+            # After a failure the channel is still drained, so that extraction isn't blocked
+            if embedding_errors:
+                continue
+            extracted_nodes, extracted_edges = extracted
+            try:
+                await semaphore_gather(
+                    *[node.generate_name_embedding(embedder) for node in extracted_nodes],
+                    *[edge.generate_embedding(embedder) for edge in extracted_edges],
+                    max_coroutines=max_coroutines,
+                )
+            except Exception as e:
+                embedding_errors.append(e)
+
+    extraction = semaphore_gather(
+        *[
+            extract_episode(episode, previous_episodes)
+            for episode, previous_episodes in episode_tuples
+        ],
+        max_coroutines=max_coroutines,
     )
+    if embedder is None:
+        extraction_results = await extraction
+    else:
+        embedding = asyncio.create_task(embed_extracted(embedder))
+        try:
+            extraction_results = await extraction
+            await channel.put(None)
+            await embedding
+        finally:
+            embedding.cancel()
+        if embedding_errors:
+            raise embedding_errors[0]
+    extracted_nodes_bulk = [extracted_nodes for extracted_nodes, _ in extraction_results]
+    extracted_edges_bulk = [extracted_edges for _, extracted_edges in extraction_results]
+    episodes = [episode for episode, _ in episode_tuples]
 
     episodic_edges: list[EpisodicEdge] = []
     for i, episode in enumerate(episodes):
@@ -220,18 +393,24 @@ async def extract_nodes_and_edges_bulk(
     for extracted_edges in extracted_edges_bulk:
         edges += extracted_edges
 
-    return nodes, edges, episodic_edges
+    return nodes, edges, episodic_edges, failures
 
 
 async def dedupe_nodes_bulk(
     driver: GraphDriver,
     llm_client: LLMClient,
     extracted_nodes: list[EntityNode],
+    name_normalization: NameNormalization = NameNormalization.none,
+    embedder: EmbedderClient | None = None,
+    name_similarity_threshold: float = 1.0,
+    name_similarity: StringSimilarity = StringSimilarity.jaro_winkler,
 ) -> tuple[list[EntityNode], dict[str, str]]:
     # Compress nodes
-    nodes, uuid_map = node_name_match(extracted_nodes)
+    nodes, uuid_map = node_name_match(
+        extracted_nodes, name_normalization, name_similarity_threshold, name_similarity
+    )
 
-    compressed_nodes, compressed_map = await compress_nodes(llm_client, nodes, uuid_map)
+    compressed_nodes, compressed_map = await compress_nodes(llm_client, nodes, uuid_map, embedder)
 
     node_chunks = [nodes[i : i + CHUNK_SIZE] for i in range(0, len(nodes), CHUNK_SIZE)]
 
@@ -260,25 +439,42 @@ async def dedupe_nodes_bulk(
 
 
 async def dedupe_edges_bulk(
-    driver: GraphDriver, llm_client: LLMClient, extracted_edges: list[EntityEdge]
+    driver: GraphDriver,
+    llm_client: LLMClient,
+    extracted_edges: list[EntityEdge],
+    symmetric_relations: frozenset[str] = DEFAULT_SYMMETRIC_RELATIONS,
 ) -> list[EntityEdge]:
     # First compress edges
-    compressed_edges = await compress_edges(llm_client, extracted_edges)
+    compressed_edges = await compress_edges(llm_client, extracted_edges, symmetric_relations)
 
     edge_chunks = [
         compressed_edges[i : i + CHUNK_SIZE] for i in range(0, len(compressed_edges), CHUNK_SIZE)
     ]
 
-    relevant_edges_chunks: list[list[EntityEdge]] = list(
+    relevant_edges_chunks: list[list[list[EntityEdge]]] = list(
         await semaphore_gather(
-            *[get_relevant_edges(driver, edge_chunk, SearchFilters()) for edge_chunk in edge_chunks]
+            *[
+                get_relevant_edges(driver, edge_chunk, SearchFilters(include_invalidated=True))
+                for edge_chunk in edge_chunks
+            ]
         )
     )
 
+    # Facts pointing the other way are only duplicate candidates for symmetric relations
+    existing_edges_chunks: list[list[EntityEdge]] = [
+        [
+            relevant_edge
+            for edge, relevant_edges in zip(edge_chunk, relevant_edges_chunk, strict=True)
+            for relevant_edge in relevant_edges
+            if same_direction_or_symmetric(edge, relevant_edge, symmetric_relations)
+        ]
+        for edge_chunk, relevant_edges_chunk in zip(edge_chunks, relevant_edges_chunks, strict=True)
+    ]
+
     resolved_edge_chunks: list[list[EntityEdge]] = list(
         await semaphore_gather(
             *[
-                dedupe_extracted_edges(llm_client, edge_chunk, relevant_edges_chunks[i])
+                dedupe_extracted_edges(llm_client, edge_chunk, existing_edges_chunks[i])
                 for i, edge_chunk in enumerate(edge_chunks)
             ]
         )
@@ -288,99 +484,238 @@ async def dedupe_edges_bulk(
     return edges
 
 
-def node_name_match(nodes: list[EntityNode]) -> tuple[list[EntityNode], dict[str, str]]:
+def node_name_match(
+    nodes: list[EntityNode],
+    name_normalization: NameNormalization = NameNormalization.none,
+    name_similarity_threshold: float = 1.0,
+    name_similarity: StringSimilarity = StringSimilarity.jaro_winkler,
+) -> tuple[list[EntityNode], dict[str, str]]:
+    """
+    Merge nodes whose normalized names match. If `name_similarity_threshold` is below 1, a name
+    without an exact match is also merged into the most similar name seen so far, as long as the
+    similarity is at least the threshold.
+    """
     uuid_map: dict[str, str] = {}
     name_map: dict[str, EntityNode] = {}
     for node in nodes:
-        if node.name in name_map:
-            uuid_map[node.uuid] = name_map[node.name].uuid
+        # The first node seen for a normalized name is kept, with its original display name
+        key = (
+            node.name
+            if name_normalization == NameNormalization.none
+            else normalize_name(
+                node.name, strip_punctuation=name_normalization == NameNormalization.aggressive
+            )
+        )
+        if key in name_map:
+            uuid_map[node.uuid] = name_map[key].uuid
             continue
 
-        name_map[node.name] = node
+        if name_similarity_threshold < 1.0 and name_map:
+            best_score, best_key = max(
+                (string_similarity(key, other, name_similarity), other) for other in name_map
+            )
+            if best_score >= name_similarity_threshold:
+                match = name_map[best_key]
+                logger.debug(f'Merged {node.name} into {match.name} ({best_score:.3f})')
+                uuid_map[node.uuid] = match.uuid
+                continue
+
+        name_map[key] = node
 
     return [node for node in name_map.values()], uuid_map
 
 
-async def compress_nodes(
-    llm_client: LLMClient, nodes: list[EntityNode], uuid_map: dict[str, str]
-) -> tuple[list[EntityNode], dict[str, str]]:
-    # We want to first compress the nodes by deduplicating nodes across each of the episodes added in bulk
+def node_similarity_text(node: EntityNode) -> str:
+    return f'{node.name}: {node.summary}' if node.summary else node.name
+
+
+async def get_node_similarity_embeddings(
+    nodes: list[EntityNode], embedder: EmbedderClient | None = None
+) -> dict[str, list[float]] | None:
+    """
+    Embeddings used to group likely duplicates for dedup. Node summaries are embedded with the
+    embedder if one is given, otherwise existing name embeddings are used. Returns None if
+    neither is available.
+    """
     if len(nodes) == 0:
-        return nodes, uuid_map
+        return {}
+
+    if embedder is not None:
+        embeddings = await embedder.create_batch([node_similarity_text(node) for node in nodes])
+        return {node.uuid: embedding for node, embedding in zip(nodes, embeddings, strict=True)}
 
-    # Our approach involves us deduplicating chunks of nodes in parallel.
-    # We want n chunks of size n so that n ** 2 == len(nodes).
-    # We want chunk sizes to be at least 10 for optimizing LLM processing time
-    chunk_size = max(int(sqrt(len(nodes))), CHUNK_SIZE)
+    if all(node.name_embedding for node in nodes):
+        return {node.uuid: node.name_embedding or [] for node in nodes}
+
+    return None
+
+
+def chunk_nodes_by_similarity(
+    nodes: list[EntityNode], embeddings: dict[str, list[float]], chunk_size: int
+) -> list[list[EntityNode]]:
+    """
+    Split nodes into at most ceil(len(nodes) / chunk_size) chunks of up to chunk_size nodes,
+    drafting the most similar pairs of nodes into the same chunk first.
+    """
+    max_chunks = ceil(len(nodes) / chunk_size)
 
-    # First calculate similarity scores between nodes
     similarity_scores: list[tuple[int, int, float]] = [
-        (i, j, dot(n.name_embedding or [], m.name_embedding or []))
+        (i, j, cosine_similarity(embeddings[n.uuid], embeddings[m.uuid]))
         for i, n in enumerate(nodes)
         for j, m in enumerate(nodes[:i])
     ]
+    similarity_scores.sort(key=lambda score_tuple: score_tuple[2], reverse=True)
 
-    # We now sort by semantic similarity
-    similarity_scores.sort(key=lambda score_tuple: score_tuple[2])
+    chunks: list[list[int]] = []
+    chunk_of: dict[int, int] = {}
 
-    # initialize our chunks based on chunk size
-    node_chunks: list[list[EntityNode]] = [[] for _ in range(ceil(len(nodes) / chunk_size))]
+    def add_to_chunk(index: int, chunk_index: int):
+        chunks[chunk_index].append(index)
+        chunk_of[index] = chunk_index
 
-    # Draft the most similar nodes into the same chunk
-    while len(similarity_scores) > 0:
-        i, j, _ = similarity_scores.pop()
-        # determine if any of the nodes have already been drafted into a chunk
-        n = nodes[i]
-        m = nodes[j]
-        # make sure the shortest chunks get preference
-        node_chunks.sort(reverse=True, key=lambda chunk: len(chunk))
+    def chunk_with_room(needed: int) -> int | None:
+        open_chunks = [k for k, chunk in enumerate(chunks) if len(chunk) + needed <= chunk_size]
+        if len(open_chunks) == 0:
+            return None
+        return min(open_chunks, key=lambda k: len(chunks[k]))
 
-        n_chunk = max([i if n in chunk else -1 for i, chunk in enumerate(node_chunks)])
-        m_chunk = max([i if m in chunk else -1 for i, chunk in enumerate(node_chunks)])
+    for i, j, _ in similarity_scores:
+        if i in chunk_of and j in chunk_of:
+            continue
 
-        # both nodes already in a chunk
-        if n_chunk > -1 and m_chunk > -1:
+        if i in chunk_of or j in chunk_of:
+            placed, unplaced = (i, j) if i in chunk_of else (j, i)
+            # If the chunk is full, the node is drafted by a later, less similar pair
+            if len(chunks[chunk_of[placed]]) < chunk_size:
+                add_to_chunk(unplaced, chunk_of[placed])
             continue
 
-        # n has a chunk and that chunk is not full
-        elif n_chunk > -1 and len(node_chunks[n_chunk]) < chunk_size:
-            # put m in the same chunk as n
-            node_chunks[n_chunk].append(m)
+        if len(chunks) < max_chunks:
+            chunks.append([])
+            chunk_index: int | None = len(chunks) - 1
+        else:
+            chunk_index = chunk_with_room(2)
+        if chunk_index is not None:
+            add_to_chunk(i, chunk_index)
+            add_to_chunk(j, chunk_index)
+
+    # Nodes that were never drafted go wherever there is room left
+    for i in range(len(nodes)):
+        if i in chunk_of:
+            continue
+        chunk_index = chunk_with_room(1)
+        if chunk_index is None:
+            chunks.append([])
+            chunk_index = len(chunks) - 1
+        add_to_chunk(i, chunk_index)
 
-        # m has a chunk and that chunk is not full
-        elif m_chunk > -1 and len(node_chunks[m_chunk]) < chunk_size:
-            # put n in the same chunk as m
-            node_chunks[m_chunk].append(n)
+    return [[nodes[i] for i in chunk] for chunk in chunks]
 
-        # neither node has a chunk or the chunk is full
-        else:
-            # add both nodes to the shortest chunk
-            node_chunks[-1].extend([n, m])
 
-    results = await semaphore_gather(
-        *[dedupe_node_list(llm_client, chunk) for chunk in node_chunks]
+async def group_duplicate_candidates(
+    driver: GraphDriver,
+    nodes: list[EntityNode],
+    limit: int = DEDUP_CANDIDATE_LIMIT,
+    min_score: float = DEDUP_CANDIDATE_MIN_SCORE,
+) -> list[list[EntityNode]]:
+    """
+    Group nodes that may be duplicates of each other. Each node's stored name embedding is
+    looked up with a vector query for its `limit` most similar nodes, and nodes linked by these
+    matches end up in the same group, so pairs are never compared exhaustively. Nodes without a
+    name embedding or without a match are left out.
+    """
+    node_map = {node.uuid: node for node in nodes}
+    queried = [node for node in nodes if node.name_embedding]
+    similar_nodes = await semaphore_gather(
+        *[
+            node_similarity_search(
+                driver,
+                node.name_embedding or [],
+                SearchFilters(),
+                [node.group_id],
+                limit + 1,
+                min_score,
+            )
+            for node in queried
+        ]
     )
 
+    parent = {uuid: uuid for uuid in node_map}
+
+    def find(uuid: str) -> str:
+        while parent[uuid] != uuid:
+            parent[uuid] = parent[parent[uuid]]
+            uuid = parent[uuid]
+        return uuid
+
+    for node, similar in zip(queried, similar_nodes, strict=True):
+        for other in similar:
+            if other.uuid != node.uuid and other.uuid in node_map:
+                parent[find(other.uuid)] = find(node.uuid)
+
+    groups: dict[str, list[EntityNode]] = defaultdict(list)
+    for node in nodes:
+        groups[find(node.uuid)].append(node)
+
+    return [group for group in groups.values() if len(group) > 1]
+
+
+async def compress_nodes(
+    llm_client: LLMClient,
+    nodes: list[EntityNode],
+    uuid_map: dict[str, str],
+    embedder: EmbedderClient | None = None,
+) -> tuple[list[EntityNode], dict[str, str]]:
+    """
+    Deduplicate nodes across the episodes added in bulk.
+
+    Nodes are deduplicated in chunks in parallel. Chunks are built by semantic similarity so that
+    likely duplicates are compared in the same LLM call, using the embeddings from
+    `get_node_similarity_embeddings`. If no embeddings are available, nodes are chunked in order.
+    """
+    embeddings = await get_node_similarity_embeddings(nodes, embedder)
     extended_map = dict(uuid_map)
-    compressed_nodes: list[EntityNode] = []
-    for node_chunk, uuid_map_chunk in results:
-        compressed_nodes += node_chunk
-        extended_map.update(uuid_map_chunk)
 
-    # Check if we have removed all duplicates
-    if len(compressed_nodes) == len(nodes):
-        compressed_uuid_map = compress_uuid_map(extended_map)
-        return compressed_nodes, compressed_uuid_map
+    while len(nodes) > 0:
+        # Our approach involves us deduplicating chunks of nodes in parallel.
+        # We want n chunks of size n so that n ** 2 == len(nodes).
+        # We want chunk sizes to be at least 10 for optimizing LLM processing time
+        chunk_size = max(int(sqrt(len(nodes))), CHUNK_SIZE)
 
-    return await compress_nodes(llm_client, compressed_nodes, extended_map)
+        node_chunks = (
+            chunk_nodes_by_similarity(nodes, embeddings, chunk_size)
+            if embeddings is not None
+            else [nodes[i : i + chunk_size] for i in range(0, len(nodes), chunk_size)]
+        )
+
+        results = await semaphore_gather(
+            *[dedupe_node_list(llm_client, chunk) for chunk in node_chunks]
+        )
+
+        compressed_nodes: list[EntityNode] = []
+        for node_chunk, uuid_map_chunk in results:
+            compressed_nodes += node_chunk
+            extended_map.update(uuid_map_chunk)
+
+        # Check if we have removed all duplicates
+        if len(compressed_nodes) == len(nodes):
+            return compressed_nodes, compress_uuid_map(extended_map)
 
+        nodes = compressed_nodes
 
-async def compress_edges(llm_client: LLMClient, edges: list[EntityEdge]) -> list[EntityEdge]:
+    return nodes, compress_uuid_map(extended_map)
+
+
+async def compress_edges(
+    llm_client: LLMClient,
+    edges: list[EntityEdge],
+    symmetric_relations: frozenset[str] = DEFAULT_SYMMETRIC_RELATIONS,
+) -> list[EntityEdge]:
     if len(edges) == 0:
         return edges
     # We only want to dedupe edges that are between the same pair of nodes
     # We build a map of the edges based on their source and target nodes.
-    edge_chunks = chunk_edges_by_nodes(edges)
+    edge_chunks = chunk_edges_by_nodes(edges, symmetric_relations)
 
     results = await semaphore_gather(
         *[dedupe_edge_list(llm_client, chunk) for chunk in edge_chunks]
@@ -394,7 +729,7 @@ async def compress_edges(llm_client: LLMClient, edges: list[EntityEdge]) -> list
     if len(compressed_edges) == len(edges):
         return compressed_edges
 
-    return await compress_edges(llm_client, compressed_edges)
+    return await compress_edges(llm_client, compressed_edges, symmetric_relations)
 
 
 def compress_uuid_map(uuid_map: dict[str, str]) -> dict[str, str]:
@@ -462,20 +797,25 @@ async def extract_edge_dates_bulk(
     return edges
 
 
-def chunk_edges_by_nodes(edges: list[EntityEdge]) -> list[list[EntityEdge]]:
+def chunk_edges_by_nodes(
+    edges: list[EntityEdge], symmetric_relations: frozenset[str] = DEFAULT_SYMMETRIC_RELATIONS
+) -> list[list[EntityEdge]]:
     # We only want to dedupe edges that are between the same pair of nodes
     # We build a map of the edges based on their source and target nodes.
-    edge_chunk_map: dict[str, list[EntityEdge]] = defaultdict(list)
+    edge_chunk_map: dict[tuple[str, str], list[EntityEdge]] = defaultdict(list)
     for edge in edges:
         # We drop loop edges
         if edge.source_node_uuid == edge.target_node_uuid:
             continue
 
-        # Keep the order of the two nodes consistent, we want to be direction agnostic during edge resolution
+        # Symmetric relations are direction agnostic, so their node order is made consistent.
+        # Other relations keep their direction, so that "Alice MANAGES Bob" and "Bob MANAGES
+        # Alice" are never merged.
         pointers = [edge.source_node_uuid, edge.target_node_uuid]
-        pointers.sort()
+        if is_symmetric_relation(edge.name, symmetric_relations):
+            pointers.sort()
 
-        edge_chunk_map[pointers[0] + pointers[1]].append(edge)
+        edge_chunk_map[(pointers[0], pointers[1])].append(edge)
 
     edge_chunks = [chunk for chunk in edge_chunk_map.values()]
 
@@ -14,12 +14,57 @@ See the License for the specific language governing permissions and
 limitations under the License.
 """
 
-from datetime import datetime, timezone
+from collections.abc import Iterator
+from contextlib import contextmanager
+from contextvars import ContextVar
+from datetime import datetime, timedelta, timezone
+from typing import Protocol
+
+
+class Clock(Protocol):
+    def now(self) -> datetime: ...
+
+
+class SystemClock:
+    """The wall clock, in UTC."""
+
+    def now(self) -> datetime:
+        return datetime.now(timezone.utc)
+
+
+class FixedClock:
+    """A clock that stays at the given time until it is advanced, for deterministic tests."""
+
+    def __init__(self, now: datetime):
+        self._now = now if now.tzinfo is not None else now.replace(tzinfo=timezone.utc)
+
+    def now(self) -> datetime:
+        return self._now
+
+    def advance(self, delta: timedelta):
+        self._now += delta
+
+
+_clock: ContextVar[Clock] = ContextVar('graphiti_clock', default=SystemClock())
 
 
 def utc_now() -> datetime:
     """Returns the current UTC datetime with timezone information."""
-    return datetime.now(timezone.utc)
+    return _clock.get().now()
+
+
+@contextmanager
+def use_clock(clock: Clock) -> Iterator[Clock]:
+    """
+    Makes utc_now, and so the created_at of new nodes and edges, read from the given clock
+    within the block. The override is scoped to the current context, so concurrent tasks that
+    were started outside the block keep their own clock.
+    """
+    token = _clock.set(clock)
+    try:
+        yield clock
+    finally:
+        _clock.reset(token)
 
 
 def ensure_utc(dt: datetime | None) -> datetime | None:
@@ -7,7 +7,12 @@ from pydantic import BaseModel
 from graphiti_core.driver.driver import GraphDriver
 from graphiti_core.edges import CommunityEdge
 from graphiti_core.embedder import EmbedderClient
-from graphiti_core.helpers import DEFAULT_DATABASE, semaphore_gather
+from graphiti_core.helpers import (
+    DEFAULT_DATABASE,
+    DEFAULT_MAX_SUMMARY_WORDS,
+    semaphore_gather,
+    truncate_words,
+)
 from graphiti_core.llm_client import LLMClient
 from graphiti_core.nodes import CommunityNode, EntityNode, get_community_node_from_record
 from graphiti_core.prompts import prompt_library
@@ -124,7 +129,11 @@ def label_propagation(projection: dict[str, list[Neighbor]]) -> list[list[str]]:
     return clusters
 
 
-async def summarize_pair(llm_client: LLMClient, summary_pair: tuple[str, str]) -> str:
+async def summarize_pair(
+    llm_client: LLMClient,
+    summary_pair: tuple[str, str],
+    max_summary_words: int | None = DEFAULT_MAX_SUMMARY_WORDS,
+) -> str:
     # Prepare context for LLM
     context = {'node_summaries': [{'summary': summary} for summary in summary_pair]}
 
@@ -132,7 +141,7 @@ async def summarize_pair(llm_client: LLMClient, summary_pair: tuple[str, str]) -
         prompt_library.summarize_nodes.summarize_pair(context), response_model=Summary
     )
 
-    pair_summary = llm_response.get('summary', '')
+    pair_summary = truncate_words(llm_response.get('summary', ''), max_summary_words)
 
     return pair_summary
 
@@ -151,7 +160,9 @@ async def generate_summary_description(llm_client: LLMClient, summary: str) -> s
 
 
 async def build_community(
-    llm_client: LLMClient, community_cluster: list[EntityNode]
+    llm_client: LLMClient,
+    community_cluster: list[EntityNode],
+    max_summary_words: int | None = DEFAULT_MAX_SUMMARY_WORDS,
 ) -> tuple[CommunityNode, list[CommunityEdge]]:
     summaries = [entity.summary for entity in community_cluster]
     length = len(summaries)
@@ -163,7 +174,9 @@ async def build_community(
         new_summaries: list[str] = list(
             await semaphore_gather(
                 *[
-                    summarize_pair(llm_client, (str(left_summary), str(right_summary)))
+                    summarize_pair(
+                        llm_client, (str(left_summary), str(right_summary)), max_summary_words
+                    )
                     for left_summary, right_summary in zip(
                         summaries[: int(length / 2)], summaries[int(length / 2) :], strict=False
                     )
@@ -193,7 +206,10 @@ async def build_community(
 
 
 async def build_communities(
-    driver: GraphDriver, llm_client: LLMClient, group_ids: list[str] | None
+    driver: GraphDriver,
+    llm_client: LLMClient,
+    group_ids: list[str] | None,
+    max_summary_words: int | None = DEFAULT_MAX_SUMMARY_WORDS,
 ) -> tuple[list[CommunityNode], list[CommunityEdge]]:
     community_clusters = await get_community_clusters(driver, group_ids)
 
@@ -201,7 +217,7 @@ async def build_communities(
 
     async def limited_build_community(cluster):
         async with semaphore:
-            return await build_community(llm_client, cluster)
+            return await build_community(llm_client, cluster, max_summary_words)
 
     communities: list[tuple[CommunityNode, list[CommunityEdge]]] = list(
         await semaphore_gather(
@@ -247,7 +263,7 @@ async def determine_entity_community(
     )
 
     if len(records) > 0:
-        return get_community_node_from_record(records[0]), False
+        return get_community_node_from_record(records[0], driver), False
 
     # If the node has no community, add it to the mode community of surrounding entities
     records, _, _ = await driver.execute_query(
@@ -265,7 +281,7 @@ async def determine_entity_community(
     )
 
     communities: list[CommunityNode] = [
-        get_community_node_from_record(record) for record in records
+        get_community_node_from_record(record, driver) for record in records
     ]
 
     community_map: dict[str, int] = defaultdict(int)
@@ -290,14 +306,20 @@ async def determine_entity_community(
 
 
 async def update_community(
-    driver: GraphDriver, llm_client: LLMClient, embedder: EmbedderClient, entity: EntityNode
+    driver: GraphDriver,
+    llm_client: LLMClient,
+    embedder: EmbedderClient | None,
+    entity: EntityNode,
+    max_summary_words: int | None = DEFAULT_MAX_SUMMARY_WORDS,
 ):
     community, is_new = await determine_entity_community(driver, entity)
 
     if community is None:
         return
 
-    new_summary = await summarize_pair(llm_client, (entity.summary, community.summary))
+    new_summary = await summarize_pair(
+        llm_client, (entity.summary, community.summary), max_summary_words
+    )
     new_name = await generate_summary_description(llm_client, new_summary)
 
     community.summary = new_summary
@@ -307,6 +329,7 @@ async def update_community(
         community_edge = (build_community_edges([entity], community, utc_now()))[0]
         await community_edge.save(driver)
 
-    await community.generate_name_embedding(embedder)
+    if embedder is not None:
+        await community.generate_name_embedding(embedder)
 
     await community.save(driver)
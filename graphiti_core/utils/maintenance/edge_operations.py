@@ -17,6 +17,7 @@ limitations under the License.
 import logging
 from datetime import datetime
 from time import time
+from typing import Any
 
 from pydantic import BaseModel
 from typing_extensions import LiteralString
@@ -27,9 +28,10 @@ from graphiti_core.edges import (
     EntityEdge,
     EpisodicEdge,
     create_entity_edge_embeddings,
+    same_direction_or_symmetric,
 )
 from graphiti_core.graphiti_types import GraphitiClients
-from graphiti_core.helpers import DEFAULT_DATABASE, MAX_REFLEXION_ITERATIONS, semaphore_gather
+from graphiti_core.helpers import DEFAULT_DATABASE, AttributeValidationPolicy, semaphore_gather
 from graphiti_core.llm_client import LLMClient
 from graphiti_core.llm_client.config import ModelSize
 from graphiti_core.nodes import CommunityNode, EntityNode, EpisodicNode
@@ -39,6 +41,7 @@ from graphiti_core.prompts.extract_edges import ExtractedEdges, MissingFacts
 from graphiti_core.search.search_filters import SearchFilters
 from graphiti_core.search.search_utils import get_edge_invalidation_candidates, get_relevant_edges
 from graphiti_core.utils.datetime_utils import ensure_utc, utc_now
+from graphiti_core.utils.ontology_utils.entity_types_utils import validate_attributes
 
 logger = logging.getLogger(__name__)
 
@@ -48,14 +51,16 @@ def build_episodic_edges(
     episode: EpisodicNode,
     created_at: datetime,
 ) -> list[EpisodicEdge]:
+    # An entity mentioned more than once still gets a single edge to the episode
+    unique_node_uuids = dict.fromkeys(node.uuid for node in entity_nodes)
     episodic_edges: list[EpisodicEdge] = [
         EpisodicEdge(
             source_node_uuid=episode.uuid,
-            target_node_uuid=node.uuid,
+            target_node_uuid=node_uuid,
             created_at=created_at,
             group_id=episode.group_id,
         )
-        for node in entity_nodes
+        for node_uuid in unique_node_uuids
     ]
 
     logger.debug(f'Built episodic edges: {episodic_edges}')
@@ -103,6 +108,25 @@ def build_community_edges(
     return edges
 
 
+def _edge_data_key(edge_data: dict[str, Any]) -> tuple[Any, Any, str]:
+    return (
+        edge_data.get('source_entity_id'),
+        edge_data.get('target_entity_id'),
+        str(edge_data.get('fact', '')).strip().lower(),
+    )
+
+
+def _parse_confidence(confidence: Any) -> float | None:
+    # Models don't always respect the 0-1 range, so out of range values are clamped
+    if confidence is None:
+        return None
+    try:
+        return min(max(float(confidence), 0.0), 1.0)
+    except (TypeError, ValueError):
+        logger.warning(f'Ignoring invalid fact confidence: {confidence}')
+        return None
+
+
 async def extract_edges(
     clients: GraphitiClients,
     episode: EpisodicNode,
@@ -147,37 +171,47 @@ async def extract_edges(
         'reference_time': episode.valid_at,
         'edge_types': edge_types_context,
         'custom_prompt': '',
+        'language': episode.language,
     }
 
-    facts_missed = True
-    reflexion_iterations = 0
-    while facts_missed and reflexion_iterations <= MAX_REFLEXION_ITERATIONS:
-        llm_response = await llm_client.generate_response(
-            prompt_library.extract_edges.edge(context),
-            response_model=ExtractedEdges,
-            max_tokens=extract_edges_max_tokens,
-        )
-        edges_data = llm_response.get('edges', [])
+    llm_response = await llm_client.generate_response(
+        prompt_library.extract_edges.edge(context),
+        response_model=ExtractedEdges,
+        max_tokens=extract_edges_max_tokens,
+    )
+    edges_data = list(llm_response.get('edges', []))
 
+    for _ in range(clients.max_reflexion_rounds):
         context['extracted_facts'] = [edge_data.get('fact', '') for edge_data in edges_data]
+        reflexion_response = await llm_client.generate_response(
+            prompt_library.extract_edges.reflexion(context),
+            response_model=MissingFacts,
+            max_tokens=extract_edges_max_tokens,
+        )
 
-        reflexion_iterations += 1
-        if reflexion_iterations < MAX_REFLEXION_ITERATIONS:
-            reflexion_response = await llm_client.generate_response(
-                prompt_library.extract_edges.reflexion(context),
-                response_model=MissingFacts,
-                max_tokens=extract_edges_max_tokens,
-            )
+        missing_facts = reflexion_response.get('missing_facts', [])
+        if len(missing_facts) == 0:
+            break
 
-            missing_facts = reflexion_response.get('missing_facts', [])
+        custom_prompt = 'The following facts were missed in a previous extraction: '
+        for fact in missing_facts:
+            custom_prompt += f'\n{fact},'
 
-            custom_prompt = 'The following facts were missed in a previous extraction: '
-            for fact in missing_facts:
-                custom_prompt += f'\n{fact},'
+        context['custom_prompt'] = custom_prompt
 
-            context['custom_prompt'] = custom_prompt
+        llm_response = await llm_client.generate_response(
+            prompt_library.extract_edges.edge(context),
+            response_model=ExtractedEdges,
+            max_tokens=extract_edges_max_tokens,
+        )
 
-            facts_missed = len(missing_facts) != 0
+        # Merge rather than replace so that facts found in earlier rounds are kept
+        extracted_keys = {_edge_data_key(edge_data) for edge_data in edges_data}
+        for edge_data in llm_response.get('edges', []):
+            key = _edge_data_key(edge_data)
+            if key not in extracted_keys:
+                extracted_keys.add(key)
+                edges_data.append(edge_data)
 
     end = time()
     logger.debug(f'Extracted new edges: {edges_data} in {(end - start) * 1000} ms')
@@ -229,17 +263,67 @@ async def extract_edges(
             created_at=utc_now(),
             valid_at=valid_at_datetime,
             invalid_at=invalid_at_datetime,
+            confidence=_parse_confidence(edge_data.get('confidence')),
         )
         edges.append(edge)
         logger.debug(
             f'Created new edge: {edge.name} from (UUID: {edge.source_node_uuid}) to (UUID: {edge.target_node_uuid})'
         )
 
+    edges = filter_extracted_edges(edges, clients.allow_self_loops)
+
+    max_edges = clients.max_edges_per_episode
+    if max_edges is not None and len(edges) > max_edges:
+        logger.warning(
+            f'Episode {episode.uuid} extracted {len(edges)} facts; '
+            f'keeping the {max_edges} most confident'
+        )
+        edges = _most_confident_edges(edges, max_edges)
+
     logger.debug(f'Extracted edges: {[(e.name, e.uuid) for e in edges]}')
 
     return edges
 
 
+def filter_extracted_edges(
+    edges: list[EntityEdge], allow_self_loops: bool = False
+) -> list[EntityEdge]:
+    """
+    Drop facts that connect an entity to itself, unless allow_self_loops is set, and collapse
+    facts that repeat another one's source, target, relation and fact. The first of a set of
+    repeated facts is kept. Self-loops appear when the model ignores the prompt, or when both
+    ends of a fact resolve to the same entity.
+    """
+    kept: list[EntityEdge] = []
+    seen: set[tuple[str, str, str, str]] = set()
+    self_loops = 0
+    for edge in edges:
+        if edge.source_node_uuid == edge.target_node_uuid and not allow_self_loops:
+            self_loops += 1
+            continue
+        key = (edge.source_node_uuid, edge.target_node_uuid, edge.name, edge.fact.strip().lower())
+        if key in seen:
+            continue
+        seen.add(key)
+        kept.append(edge)
+
+    duplicates = len(edges) - len(kept) - self_loops
+    if self_loops or duplicates:
+        logger.info(f'Dropped {self_loops} self-referential and {duplicates} duplicate facts')
+    return kept
+
+
+def _most_confident_edges(edges: list[EntityEdge], limit: int) -> list[EntityEdge]:
+    # Facts without a confidence rank last; ties and the result keep the extraction order
+    ranked = sorted(
+        edges,
+        key=lambda edge: edge.confidence if edge.confidence is not None else -1.0,
+        reverse=True,
+    )
+    kept = {edge.uuid for edge in ranked[:limit]}
+    return [edge for edge in edges if edge.uuid in kept]
+
+
 async def dedupe_extracted_edges(
     llm_client: LLMClient,
     extracted_edges: list[EntityEdge],
@@ -295,14 +379,34 @@ async def resolve_extracted_edges(
     driver = clients.driver
     llm_client = clients.llm_client
     embedder = clients.embedder
-    await create_entity_edge_embeddings(embedder, extracted_edges)
 
-    search_results = await semaphore_gather(
-        get_relevant_edges(driver, extracted_edges, SearchFilters()),
-        get_edge_invalidation_candidates(driver, extracted_edges, SearchFilters(), 0.2),
-    )
+    # Related edges and invalidation candidates are found by fact similarity, over the full history
+    if clients.embeddings_enabled:
+        await create_entity_edge_embeddings(embedder, extracted_edges)
+
+        history_filter = SearchFilters(include_invalidated=True)
+        search_results = await semaphore_gather(
+            get_relevant_edges(driver, extracted_edges, history_filter),
+            get_edge_invalidation_candidates(driver, extracted_edges, history_filter, 0.2),
+        )
 
-    related_edges_lists, edge_invalidation_candidates = search_results
+        related_edges_lists, edge_invalidation_candidates = search_results
+        # Facts pointing the other way are only duplicate candidates for symmetric relations
+        related_edges_lists = [
+            [
+                related_edge
+                for related_edge in related_edges
+                if same_direction_or_symmetric(
+                    extracted_edge, related_edge, clients.symmetric_relations
+                )
+            ]
+            for extracted_edge, related_edges in zip(
+                extracted_edges, related_edges_lists, strict=True
+            )
+        ]
+    else:
+        related_edges_lists = [[] for _ in extracted_edges]
+        edge_invalidation_candidates = [[] for _ in extracted_edges]
 
     logger.debug(
         f'Related edges lists: {[(e.name, e.uuid) for edges_lst in related_edges_lists for e in edges_lst]}'
@@ -345,6 +449,7 @@ async def resolve_extracted_edges(
                     existing_edges,
                     episode,
                     extracted_edge_types,
+                    clients.attribute_validation,
                 )
                 for extracted_edge, related_edges, existing_edges, extracted_edge_types in zip(
                     extracted_edges,
@@ -368,10 +473,11 @@ async def resolve_extracted_edges(
 
     logger.debug(f'Resolved edges: {[(e.name, e.uuid) for e in resolved_edges]}')
 
-    await semaphore_gather(
-        create_entity_edge_embeddings(embedder, resolved_edges),
-        create_entity_edge_embeddings(embedder, invalidated_edges),
-    )
+    if clients.embeddings_enabled:
+        await semaphore_gather(
+            create_entity_edge_embeddings(embedder, resolved_edges),
+            create_entity_edge_embeddings(embedder, invalidated_edges),
+        )
 
     return resolved_edges, invalidated_edges
 
@@ -416,6 +522,7 @@ async def resolve_extracted_edge(
     existing_edges: list[EntityEdge],
     episode: EpisodicNode,
     edge_types: dict[str, BaseModel] | None = None,
+    attribute_validation: AttributeValidationPolicy = AttributeValidationPolicy.keep,
 ) -> tuple[EntityEdge, list[EntityEdge]]:
     if len(related_edges) == 0 and len(existing_edges) == 0:
         return extracted_edge, []
@@ -490,7 +597,15 @@ async def resolve_extracted_edge(
             model_size=ModelSize.small,
         )
 
-        resolved_edge.attributes = edge_attributes_response
+        resolved_edge.attributes = (
+            validate_attributes(
+                edge_attributes_response,
+                edge_model,  # type: ignore[arg-type]
+                attribute_validation,
+            )[0]
+            if edge_model is not None
+            else edge_attributes_response
+        )
 
     end = time()
     logger.debug(
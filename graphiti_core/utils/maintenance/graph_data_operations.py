@@ -14,23 +14,114 @@ See the License for the specific language governing permissions and
 limitations under the License.
 """
 
+import asyncio
 import logging
-from datetime import datetime, timezone
+import math
+from collections.abc import Awaitable, Callable
+from datetime import datetime, timedelta, timezone
+from enum import Enum
+from time import monotonic
+from typing import TypeVar
 
+from pydantic import BaseModel, Field
 from typing_extensions import LiteralString
 
 from graphiti_core.driver.driver import GraphDriver
-from graphiti_core.graph_queries import get_fulltext_indices, get_range_indices
+from graphiti_core.edges import (
+    ENTITY_EDGE_RETURN,
+    CommunityEdge,
+    EntityEdge,
+    EpisodicEdge,
+    create_entity_edge_embeddings,
+    get_entity_edge_from_record,
+)
+from graphiti_core.embedder import EmbedderClient
+from graphiti_core.encryption import decrypt_field
+from graphiti_core.errors import (
+    EmbeddingsUnchangedError,
+    GroupsEdgesNotFoundError,
+    ImportConflictError,
+)
+from graphiti_core.graph_queries import get_constraints, get_fulltext_indices, get_range_indices
 from graphiti_core.helpers import DEFAULT_DATABASE, parse_db_date, semaphore_gather
-from graphiti_core.nodes import EpisodeType, EpisodicNode
+from graphiti_core.nodes import (
+    ENTITY_NODE_RETURN,
+    CommunityNode,
+    EntityEmbeddingText,
+    EntityNode,
+    EpisodeType,
+    EpisodicNode,
+    create_entity_node_embeddings,
+    get_entity_mentions,
+    get_entity_node_from_record,
+)
+from graphiti_core.utils.datetime_utils import utc_now
 
 EPISODE_WINDOW_LEN = 3
+DEFAULT_CONNECT_RETRY_SECONDS = 60.0
+DEFAULT_REEMBED_BATCH_SIZE = 100
+
+ReembeddedItem = TypeVar('ReembeddedItem', EntityNode, EntityEdge, EpisodicNode)
 
 logger = logging.getLogger(__name__)
 
 
+async def wait_for_database(
+    driver: GraphDriver,
+    retry_seconds: float = DEFAULT_CONNECT_RETRY_SECONDS,
+    initial_delay: float = 1.0,
+    max_delay: float = 10.0,
+):
+    """
+    Wait until the database accepts queries.
+
+    Failed connection attempts are retried with exponential backoff until retry_seconds have
+    passed, after which the last error is raised.
+    """
+    deadline = monotonic() + retry_seconds
+    delay = initial_delay
+    attempt = 1
+    while True:
+        try:
+            await driver.execute_query('RETURN 1', database_=DEFAULT_DATABASE)
+        except Exception as e:
+            remaining = deadline - monotonic()
+            if remaining <= 0:
+                logger.error(f'Could not connect to the database after {attempt} attempts: {e}')
+                raise
+
+            logger.warning(
+                f'Database connection attempt {attempt} failed: {e}. Retrying in {delay:.1f}s'
+            )
+            await asyncio.sleep(min(delay, remaining))
+            delay = min(delay * 2, max_delay)
+            attempt += 1
+            continue
+
+        if attempt > 1:
+            logger.info(f'Connected to the database after {attempt} attempts')
+        return
+
+
 async def build_indices_and_constraints(driver: GraphDriver, delete_existing: bool = False):
     if delete_existing:
+        records, _, _ = await driver.execute_query(
+            """
+        SHOW CONSTRAINTS YIELD name
+        """,
+            database_=DEFAULT_DATABASE,
+        )
+        constraint_names = [record['name'] for record in records]
+        await semaphore_gather(
+            *[
+                driver.execute_query(
+                    """DROP CONSTRAINT $name""",
+                    name=name,
+                    database_=DEFAULT_DATABASE,
+                )
+                for name in constraint_names
+            ]
+        )
         records, _, _ = await driver.execute_query(
             """
         SHOW INDEXES YIELD name
@@ -48,6 +139,9 @@ async def build_indices_and_constraints(driver: GraphDriver, delete_existing: bo
                 for name in index_names
             ]
         )
+    for query in get_constraints(driver.provider):
+        await driver.execute_query(query, database_=DEFAULT_DATABASE)
+
     range_indices: list[LiteralString] = get_range_indices(driver.provider)
 
     fulltext_indices: list[LiteralString] = get_fulltext_indices(driver.provider)
@@ -89,6 +183,7 @@ async def retrieve_episodes(
     last_n: int = EPISODE_WINDOW_LEN,
     group_ids: list[str] | None = None,
     source: EpisodeType | None = None,
+    min_interval: timedelta | None = None,
 ) -> list[EpisodicNode]:
     """
     Retrieve the last n episodic nodes from the graph.
@@ -100,6 +195,9 @@ async def retrieve_episodes(
                                    querying the graph's state at a specific point in time.
         last_n (int, optional): The number of most recent episodes to retrieve, relative to the reference_time.
         group_ids (list[str], optional): The list of group ids to return data from.
+        min_interval (timedelta, optional): The least time between two retrieved episodes. Episodes closer than
+                                   this to a more recent retrieved one are skipped, so that a burst of episodes
+                                   doesn't fill the whole window.
 
     Returns:
         list[EpisodicNode]: A list of EpisodicNode objects representing the retrieved episodes.
@@ -128,26 +226,644 @@ async def retrieve_episodes(
         LIMIT $num_episodes
         """
     )
-    result, _, _ = await driver.execute_query(
-        query,
-        reference_time=reference_time,
-        source=source.name if source is not None else None,
-        num_episodes=last_n,
-        group_ids=group_ids,
+
+    async def latest_episodes(before: datetime, limit: int) -> list[EpisodicNode]:
+        result, _, _ = await driver.execute_query(
+            query,
+            reference_time=before,
+            source=source.name if source is not None else None,
+            num_episodes=limit,
+            group_ids=group_ids,
+            database_=DEFAULT_DATABASE,
+        )
+        return [
+            EpisodicNode(
+                content=decrypt_field(record['group_id'], record['content'], driver),
+                created_at=parse_db_date(record['created_at']) or datetime.min.replace(tzinfo=timezone.utc),
+                valid_at=parse_db_date(record['valid_at']) or datetime.min.replace(tzinfo=timezone.utc),
+                uuid=record['uuid'],
+                group_id=record['group_id'],
+                source=EpisodeType.from_str(record['source']),
+                name=record['name'],
+                source_description=record['source_description'],
+            )
+            for record in result
+        ]
+
+    if not min_interval:
+        episodes = await latest_episodes(reference_time, last_n)
+    else:
+        # Each next episode is looked up at least min_interval before the last one retrieved
+        episodes = []
+        before = reference_time
+        while len(episodes) < last_n:
+            latest = await latest_episodes(before, 1)
+            if not latest:
+                break
+            episodes += latest
+            before = latest[0].valid_at - min_interval
+
+    return list(reversed(episodes))  # Return in chronological order
+
+
+class ConflictPolicy(Enum):
+    """How import_group handles nodes and edges whose uuids already exist in the graph."""
+
+    skip = 'skip'
+    overwrite = 'overwrite'
+    error = 'error'
+
+
+class GraphExport(BaseModel):
+    """A portable snapshot of a single group's subgraph."""
+
+    group_id: str
+    exported_at: datetime
+    episodes: list[EpisodicNode] = Field(default_factory=list)
+    entity_nodes: list[EntityNode] = Field(default_factory=list)
+    community_nodes: list[CommunityNode] = Field(default_factory=list)
+    entity_edges: list[EntityEdge] = Field(default_factory=list)
+    episodic_edges: list[EpisodicEdge] = Field(default_factory=list)
+    community_edges: list[CommunityEdge] = Field(default_factory=list)
+
+
+async def export_group(
+    driver: GraphDriver, group_id: str, include_embeddings: bool = True
+) -> GraphExport:
+    """
+    Export every episode, entity, community, and edge in a group.
+
+    Args:
+        driver (Driver): The graph driver instance.
+        group_id (str): The group to export.
+        include_embeddings (bool, optional): Whether to include name and fact embeddings. Without
+            them the export is much smaller, and embeddings are regenerated on import.
+
+    Returns:
+        GraphExport: The exported subgraph.
+    """
+    episodes, entity_nodes, community_nodes, community_edges = await semaphore_gather(
+        EpisodicNode.get_by_group_ids(driver, [group_id]),
+        EntityNode.get_by_group_ids(driver, [group_id]),
+        CommunityNode.get_by_group_ids(driver, [group_id]),
+        CommunityEdge.get_by_group_ids(driver, [group_id]),
+    )
+
+    try:
+        entity_edges = await EntityEdge.get_by_group_ids(driver, [group_id])
+    except GroupsEdgesNotFoundError:
+        entity_edges = []
+    try:
+        episodic_edges = await EpisodicEdge.get_by_group_ids(driver, [group_id])
+    except GroupsEdgesNotFoundError:
+        episodic_edges = []
+
+    # properties(e) includes the fact embedding, so it is moved out of the attributes
+    for edge in entity_edges:
+        fact_embedding = edge.attributes.pop('fact_embedding', None)
+        edge.fact_embedding = fact_embedding if include_embeddings else None
+
+    if include_embeddings:
+        await semaphore_gather(*[node.load_name_embedding(driver) for node in entity_nodes])
+    else:
+        for community in community_nodes:
+            community.name_embedding = None
+
+    return GraphExport(
+        group_id=group_id,
+        exported_at=utc_now(),
+        episodes=episodes,
+        entity_nodes=entity_nodes,
+        community_nodes=community_nodes,
+        entity_edges=entity_edges,
+        episodic_edges=episodic_edges,
+        community_edges=community_edges,
+    )
+
+
+async def get_existing_uuids(driver: GraphDriver, uuids: list[str]) -> set[str]:
+    if not uuids:
+        return set()
+
+    node_records, edge_records = await semaphore_gather(
+        driver.execute_query(
+            """
+            MATCH (n:Entity|Episodic|Community) WHERE n.uuid IN $uuids
+            RETURN n.uuid AS uuid
+            """,
+            uuids=uuids,
+            database_=DEFAULT_DATABASE,
+            routing_='r',
+        ),
+        driver.execute_query(
+            """
+            MATCH ()-[e:RELATES_TO|MENTIONS|HAS_MEMBER]->() WHERE e.uuid IN $uuids
+            RETURN e.uuid AS uuid
+            """,
+            uuids=uuids,
+            database_=DEFAULT_DATABASE,
+            routing_='r',
+        ),
+    )
+
+    return {record['uuid'] for record in node_records[0] + edge_records[0]}
+
+
+class GroupStats(BaseModel):
+    """Size and freshness of a single group's subgraph."""
+
+    group_id: str
+    episode_count: int
+    entity_node_count: int
+    entity_edge_count: int
+    valid_entity_edge_count: int
+    invalidated_entity_edge_count: int
+    community_count: int
+    latest_valid_at: datetime | None = None
+
+
+async def get_group_stats(driver: GraphDriver, group_id: str) -> GroupStats:
+    episode_records, node_records, edge_records, community_records = await semaphore_gather(
+        driver.execute_query(
+            """
+            MATCH (e:Episodic {group_id: $group_id})
+            RETURN count(e) AS count, max(e.valid_at) AS latest_valid_at
+            """,
+            group_id=group_id,
+            database_=DEFAULT_DATABASE,
+            routing_='r',
+        ),
+        driver.execute_query(
+            """
+            MATCH (n:Entity {group_id: $group_id})
+            RETURN count(n) AS count
+            """,
+            group_id=group_id,
+            database_=DEFAULT_DATABASE,
+            routing_='r',
+        ),
+        driver.execute_query(
+            """
+            MATCH (:Entity)-[e:RELATES_TO {group_id: $group_id}]->(:Entity)
+            RETURN count(e) AS count,
+                count(CASE WHEN e.invalid_at IS NOT NULL OR e.expired_at IS NOT NULL THEN 1 END)
+                    AS invalidated_count
+            """,
+            group_id=group_id,
+            database_=DEFAULT_DATABASE,
+            routing_='r',
+        ),
+        driver.execute_query(
+            """
+            MATCH (c:Community {group_id: $group_id})
+            RETURN count(c) AS count
+            """,
+            group_id=group_id,
+            database_=DEFAULT_DATABASE,
+            routing_='r',
+        ),
+    )
+
+    episode_record = episode_records[0][0]
+    edge_record = edge_records[0][0]
+
+    return GroupStats(
+        group_id=group_id,
+        episode_count=episode_record['count'],
+        entity_node_count=node_records[0][0]['count'],
+        entity_edge_count=edge_record['count'],
+        valid_entity_edge_count=edge_record['count'] - edge_record['invalidated_count'],
+        invalidated_entity_edge_count=edge_record['invalidated_count'],
+        community_count=community_records[0][0]['count'],
+        latest_valid_at=parse_db_date(episode_record['latest_valid_at']),
+    )
+
+
+class Inconsistency(BaseModel):
+    """Valid facts of a single-valued relation that point from one entity to several targets."""
+
+    source_node_uuid: str
+    relation: str
+    edges: list[EntityEdge]
+
+
+async def find_inconsistencies(
+    driver: GraphDriver, group_id: str, single_valued_relations: list[str]
+) -> list[Inconsistency]:
+    """
+    Find entities with more than one valid fact of a relation that should have a single target,
+    such as two current WORKS_AT facts pointing to different companies. Nothing is changed.
+    """
+    if not single_valued_relations:
+        return []
+
+    records, _, _ = await driver.execute_query(
+        """
+        MATCH (n:Entity)-[e:RELATES_TO {group_id: $group_id}]->(m:Entity)
+        WHERE e.name IN $relations
+            AND (e.invalid_at IS NULL OR e.invalid_at > $now)
+            AND (e.expired_at IS NULL OR e.expired_at > $now)
+        """
+        + ENTITY_EDGE_RETURN
+        + """
+        ORDER BY source_node_uuid, name, uuid
+        """,
+        group_id=group_id,
+        relations=single_valued_relations,
+        now=utc_now(),
         database_=DEFAULT_DATABASE,
+        routing_='r',
     )
 
-    episodes = [
-        EpisodicNode(
-            content=record['content'],
-            created_at=parse_db_date(record['created_at']) or datetime.min.replace(tzinfo=timezone.utc),
-            valid_at=parse_db_date(record['valid_at']) or datetime.min.replace(tzinfo=timezone.utc),
-            uuid=record['uuid'],
-            group_id=record['group_id'],
-            source=EpisodeType.from_str(record['source']),
-            name=record['name'],
-            source_description=record['source_description'],
-        )
-        for record in result
+    edges_by_relation: dict[tuple[str, str], list[EntityEdge]] = {}
+    for record in records:
+        edge = get_entity_edge_from_record(record, driver)
+        edges_by_relation.setdefault((edge.source_node_uuid, edge.name), []).append(edge)
+
+    return [
+        Inconsistency(source_node_uuid=source_node_uuid, relation=relation, edges=edges)
+        for (source_node_uuid, relation), edges in edges_by_relation.items()
+        if len({edge.target_node_uuid for edge in edges}) > 1
     ]
-    return list(reversed(episodes))  # Return in chronological order
+
+
+class EpisodePruneResult(BaseModel):
+    """What `prune_episodes` deleted."""
+
+    episode_uuids: list[str] = Field(default_factory=list)
+    entity_uuids: list[str] = Field(default_factory=list)
+
+
+class OrphanPolicy(Enum):
+    """What cleanup_orphans does with entities that are no longer connected to anything."""
+
+    delete = 'delete'
+    # Add an Orphaned label to the entity and keep it
+    flag = 'flag'
+
+
+async def cleanup_orphans(
+    driver: GraphDriver, group_id: str, policy: OrphanPolicy = OrphanPolicy.delete
+) -> list[str]:
+    """
+    Delete or flag the group's entities that no episode mentions, that have no fact which is
+    still valid, and that don't belong to a community. Returns the uuids of those entities.
+    """
+    records, _, _ = await driver.execute_query(
+        """
+        MATCH (n:Entity {group_id: $group_id})
+        WHERE NOT (:Episodic)-[:MENTIONS]->(n) AND NOT (:Community)-[:HAS_MEMBER]->(n)
+        OPTIONAL MATCH (n)-[r:RELATES_TO]-(:Entity)
+        WHERE r.invalid_at IS NULL AND r.expired_at IS NULL
+        WITH n, count(r) AS valid_edge_count
+        WHERE valid_edge_count = 0
+        RETURN n.uuid AS uuid
+        ORDER BY uuid
+        """,
+        group_id=group_id,
+        database_=DEFAULT_DATABASE,
+        routing_='r',
+    )
+    entity_uuids = [record['uuid'] for record in records]
+    if len(entity_uuids) == 0:
+        return []
+
+    if policy == OrphanPolicy.delete:
+        await driver.execute_query(
+            """
+            MATCH (n:Entity)
+            WHERE n.uuid IN $uuids
+            DETACH DELETE n
+            """,
+            uuids=entity_uuids,
+            database_=DEFAULT_DATABASE,
+        )
+    else:
+        await driver.execute_query(
+            """
+            MATCH (n:Entity)
+            WHERE n.uuid IN $uuids
+            SET n:Orphaned
+            """,
+            uuids=entity_uuids,
+            database_=DEFAULT_DATABASE,
+        )
+
+    logger.info(f'Cleaned up {len(entity_uuids)} orphaned entities in group {group_id}')
+    return entity_uuids
+
+
+async def move_entity_links(driver: GraphDriver, from_uuid: str, to_uuid: str):
+    """
+    Point the episode mentions and community memberships of one entity at another. The links
+    of `from_uuid` are left in place and go away when that entity is deleted.
+    """
+    await driver.execute_query(
+        """
+        MATCH (e:Episodic)-[r:MENTIONS]->(:Entity {uuid: $from_uuid})
+        MATCH (to:Entity {uuid: $to_uuid})
+        MERGE (e)-[m:MENTIONS]->(to)
+        ON CREATE SET m.uuid = r.uuid, m.group_id = r.group_id, m.created_at = r.created_at
+        """,
+        from_uuid=from_uuid,
+        to_uuid=to_uuid,
+        database_=DEFAULT_DATABASE,
+    )
+    await driver.execute_query(
+        """
+        MATCH (c:Community)-[r:HAS_MEMBER]->(:Entity {uuid: $from_uuid})
+        MATCH (to:Entity {uuid: $to_uuid})
+        MERGE (c)-[m:HAS_MEMBER]->(to)
+        ON CREATE SET m.uuid = r.uuid, m.group_id = r.group_id, m.created_at = r.created_at
+        """,
+        from_uuid=from_uuid,
+        to_uuid=to_uuid,
+        database_=DEFAULT_DATABASE,
+    )
+
+
+async def backfill_embeddings(
+    driver: GraphDriver,
+    embedder: EmbedderClient,
+    group_id: str,
+    entity_embedding_text: EntityEmbeddingText = EntityEmbeddingText.name,
+) -> int:
+    """
+    Embed the group's entities, facts, and episodes with content that have no embedding, and
+    save them. Entities are embedded from the text chosen by entity_embedding_text. Returns how
+    many were embedded.
+    """
+    node_records, edge_records, episode_records = await semaphore_gather(
+        driver.execute_query(
+            """
+            MATCH (n:Entity {group_id: $group_id})
+            WHERE n.name_embedding IS NULL
+            """
+            + ENTITY_NODE_RETURN,
+            group_id=group_id,
+            database_=DEFAULT_DATABASE,
+            routing_='r',
+        ),
+        driver.execute_query(
+            """
+            MATCH (n:Entity)-[e:RELATES_TO {group_id: $group_id}]->(m:Entity)
+            WHERE e.fact_embedding IS NULL
+            """
+            + ENTITY_EDGE_RETURN,
+            group_id=group_id,
+            database_=DEFAULT_DATABASE,
+            routing_='r',
+        ),
+        driver.execute_query(
+            """
+            MATCH (e:Episodic {group_id: $group_id})
+            WHERE e.content_embedding IS NULL AND e.content <> ''
+            RETURN e.uuid AS uuid
+            """,
+            group_id=group_id,
+            database_=DEFAULT_DATABASE,
+            routing_='r',
+        ),
+    )
+    nodes = [get_entity_node_from_record(record, driver) for record in node_records[0]]
+    edges = [get_entity_edge_from_record(record, driver) for record in edge_records[0]]
+    episodes = await EpisodicNode.get_by_uuids(
+        driver, [record['uuid'] for record in episode_records[0]]
+    )
+
+    mentions = (
+        await get_entity_mentions(driver, nodes)
+        if entity_embedding_text == EntityEmbeddingText.name_summary_and_mentions
+        else None
+    )
+
+    await semaphore_gather(
+        create_entity_node_embeddings(embedder, nodes, entity_embedding_text, mentions),
+        create_entity_edge_embeddings(embedder, edges),
+        *[episode.generate_content_embedding(embedder) for episode in episodes],
+    )
+    await semaphore_gather(
+        *[node.save(driver) for node in nodes],
+        *[edge.save(driver) for edge in edges],
+        *[episode.save(driver) for episode in episodes],
+    )
+
+    backfilled = len(nodes) + len(edges) + len(episodes)
+    logger.info(f'Backfilled embeddings of {backfilled} items in group {group_id}')
+    return backfilled
+
+
+async def reembed_group(
+    driver: GraphDriver,
+    embedder: EmbedderClient,
+    group_id: str,
+    entity_embedding_text: EntityEmbeddingText = EntityEmbeddingText.name,
+    batch_size: int = DEFAULT_REEMBED_BATCH_SIZE,
+) -> int:
+    """
+    Replace the embeddings of all of the group's entities, facts, and episodes with ones from
+    the given embedder, e.g. after switching embedding models. Items are embedded and saved in
+    batches of batch_size, with progress logged after each batch.
+
+    Raises EmbeddingsUnchangedError without saving anything when a stored entity embedding is
+    already what the embedder produces, since the run would not change anything. Returns how
+    many items were re-embedded.
+    """
+    await _check_embeddings_changed(driver, embedder, group_id, entity_embedding_text)
+
+    async def get_nodes(limit: int, uuid_cursor: str | None) -> list[EntityNode]:
+        return await EntityNode.get_by_group_ids(driver, [group_id], limit, uuid_cursor)
+
+    async def reembed_nodes(nodes: list[EntityNode]):
+        mentions = (
+            await get_entity_mentions(driver, nodes)
+            if entity_embedding_text == EntityEmbeddingText.name_summary_and_mentions
+            else None
+        )
+        await create_entity_node_embeddings(embedder, nodes, entity_embedding_text, mentions)
+
+    async def get_edges(limit: int, uuid_cursor: str | None) -> list[EntityEdge]:
+        try:
+            return await EntityEdge.get_by_group_ids(driver, [group_id], limit, uuid_cursor)
+        except GroupsEdgesNotFoundError:
+            return []
+
+    async def reembed_edges(edges: list[EntityEdge]):
+        await create_entity_edge_embeddings(embedder, edges)
+
+    async def get_episodes(limit: int, uuid_cursor: str | None) -> list[EpisodicNode]:
+        return await EpisodicNode.get_by_group_ids(driver, [group_id], limit, uuid_cursor)
+
+    async def reembed_episodes(episodes: list[EpisodicNode]):
+        await semaphore_gather(
+            *[
+                episode.generate_content_embedding(embedder)
+                for episode in episodes
+                if episode.content
+            ]
+        )
+
+    entities = await _reembed_in_batches(
+        driver, group_id, 'entities', batch_size, get_nodes, reembed_nodes
+    )
+    facts = await _reembed_in_batches(
+        driver, group_id, 'facts', batch_size, get_edges, reembed_edges
+    )
+    episodes = await _reembed_in_batches(
+        driver, group_id, 'episodes', batch_size, get_episodes, reembed_episodes
+    )
+
+    reembedded = entities + facts + episodes
+    logger.info(f'Re-embedded {reembedded} items in group {group_id}')
+    return reembedded
+
+
+async def _reembed_in_batches(
+    driver: GraphDriver,
+    group_id: str,
+    kind: str,
+    batch_size: int,
+    get_batch: Callable[[int, str | None], Awaitable[list[ReembeddedItem]]],
+    reembed_batch: Callable[[list[ReembeddedItem]], Awaitable[None]],
+) -> int:
+    count = 0
+    uuid_cursor: str | None = None
+    while True:
+        batch = await get_batch(batch_size, uuid_cursor)
+        if not batch:
+            break
+        await reembed_batch(batch)
+        await semaphore_gather(*[item.save(driver) for item in batch])
+
+        count += len(batch)
+        uuid_cursor = batch[-1].uuid
+        logger.info(f'Re-embedded {count} {kind} in group {group_id}')
+        if len(batch) < batch_size:
+            break
+
+    return count
+
+
+async def _check_embeddings_changed(
+    driver: GraphDriver,
+    embedder: EmbedderClient,
+    group_id: str,
+    entity_embedding_text: EntityEmbeddingText,
+):
+    # Re-embeds one stored entity to compare the embedder's output with what is stored
+    records, _, _ = await driver.execute_query(
+        """
+        MATCH (n:Entity {group_id: $group_id})
+        WHERE n.name_embedding IS NOT NULL
+        RETURN n.uuid AS uuid, n.name_embedding AS name_embedding
+        LIMIT 1
+        """,
+        group_id=group_id,
+        database_=DEFAULT_DATABASE,
+        routing_='r',
+    )
+    if not records:
+        return
+
+    stored_embedding = records[0]['name_embedding']
+    node = await EntityNode.get_by_uuid(driver, records[0]['uuid'])
+    mentions = (
+        await get_entity_mentions(driver, [node])
+        if entity_embedding_text == EntityEmbeddingText.name_summary_and_mentions
+        else None
+    )
+    await create_entity_node_embeddings(embedder, [node], entity_embedding_text, mentions)
+    embedding = node.name_embedding or []
+
+    logger.info(
+        f'Group {group_id} has {len(stored_embedding)}-dimensional embeddings, '
+        f'the embedder produces {len(embedding)} dimensions'
+    )
+    if len(embedding) == len(stored_embedding) and all(
+        math.isclose(new, old, abs_tol=1e-6)
+        for new, old in zip(embedding, stored_embedding, strict=True)
+    ):
+        raise EmbeddingsUnchangedError(group_id, len(embedding))
+
+
+async def get_episode_group_ids(driver: GraphDriver) -> list[str]:
+    records, _, _ = await driver.execute_query(
+        """
+        MATCH (e:Episodic)
+        RETURN DISTINCT e.group_id AS group_id
+        """,
+        database_=DEFAULT_DATABASE,
+        routing_='r',
+    )
+
+    return [record['group_id'] for record in records]
+
+
+async def prune_episodes(
+    driver: GraphDriver,
+    group_id: str,
+    older_than: datetime,
+    delete_orphaned_entities: bool = False,
+) -> EpisodePruneResult:
+    episode_records, _, _ = await driver.execute_query(
+        """
+        MATCH (e:Episodic {group_id: $group_id})
+        WHERE e.created_at < $older_than
+        OPTIONAL MATCH (e)-[:MENTIONS]->(n:Entity)
+        RETURN e.uuid AS uuid, collect(n.uuid) AS entity_uuids
+        """,
+        group_id=group_id,
+        older_than=older_than,
+        database_=DEFAULT_DATABASE,
+        routing_='r',
+    )
+    if len(episode_records) == 0:
+        return EpisodePruneResult()
+
+    episode_uuids = [record['uuid'] for record in episode_records]
+    # Deleting the episodes also deletes their MENTIONS edges
+    await driver.execute_query(
+        """
+        MATCH (e:Episodic)
+        WHERE e.uuid IN $uuids
+        DETACH DELETE e
+        """,
+        uuids=episode_uuids,
+        database_=DEFAULT_DATABASE,
+    )
+
+    if not delete_orphaned_entities:
+        return EpisodePruneResult(episode_uuids=episode_uuids)
+
+    # Only entities mentioned by the pruned episodes are candidates. They are kept while another
+    # episode still mentions them or while they have a fact that is still valid.
+    candidate_uuids = list(
+        {uuid for record in episode_records for uuid in record['entity_uuids'] if uuid}
+    )
+    orphan_records, _, _ = await driver.execute_query(
+        """
+        MATCH (n:Entity)
+        WHERE n.uuid IN $uuids AND NOT (:Episodic)-[:MENTIONS]->(n)
+        OPTIONAL MATCH (n)-[r:RELATES_TO]-(:Entity)
+        WHERE r.invalid_at IS NULL AND r.expired_at IS NULL
+        WITH n, count(r) AS valid_edge_count
+        WHERE valid_edge_count = 0
+        RETURN n.uuid AS uuid
+        """,
+        uuids=candidate_uuids,
+        database_=DEFAULT_DATABASE,
+        routing_='r',
+    )
+    entity_uuids = [record['uuid'] for record in orphan_records]
+    if len(entity_uuids) > 0:
+        await driver.execute_query(
+            """
+            MATCH (n:Entity)
+            WHERE n.uuid IN $uuids
+            DETACH DELETE n
+            """,
+            uuids=entity_uuids,
+            database_=DEFAULT_DATABASE,
+        )
+
+    return EpisodePruneResult(episode_uuids=episode_uuids, entity_uuids=entity_uuids)
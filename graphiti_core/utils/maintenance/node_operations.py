@@ -14,20 +14,41 @@ See the License for the specific language governing permissions and
 limitations under the License.
 """
 
+import json
 import logging
 from contextlib import suppress
+from datetime import datetime
 from time import time
 from typing import Any
 from uuid import uuid4
 
 import pydantic
-from pydantic import BaseModel, Field
+from pydantic import BaseModel, Field, ValidationError
 
+from graphiti_core.errors import ExtractionError
 from graphiti_core.graphiti_types import GraphitiClients
-from graphiti_core.helpers import MAX_REFLEXION_ITERATIONS, semaphore_gather
+from graphiti_core.helpers import (
+    DEFAULT_MAX_SUMMARY_WORDS,
+    AttributeConflictPolicy,
+    AttributeValidationPolicy,
+    cosine_similarity,
+    normalize_name,
+    semaphore_gather,
+    string_similarity,
+    truncate_words,
+)
 from graphiti_core.llm_client import LLMClient
 from graphiti_core.llm_client.config import ModelSize
-from graphiti_core.nodes import EntityNode, EpisodeType, EpisodicNode, create_entity_node_embeddings
+from graphiti_core.nodes import (
+    ATTRIBUTE_HISTORY_KEY,
+    ATTRIBUTE_OBSERVED_AT_KEY,
+    EntityEmbeddingText,
+    EntityNode,
+    EpisodeType,
+    EpisodicNode,
+    create_entity_node_embeddings,
+    get_entity_mentions,
+)
 from graphiti_core.prompts import prompt_library
 from graphiti_core.prompts.dedupe_nodes import NodeResolutions
 from graphiti_core.prompts.extract_nodes import (
@@ -39,8 +60,9 @@ from graphiti_core.search.search import search
 from graphiti_core.search.search_config import SearchResults
 from graphiti_core.search.search_config_recipes import NODE_HYBRID_SEARCH_RRF
 from graphiti_core.search.search_filters import SearchFilters
-from graphiti_core.utils.datetime_utils import utc_now
+from graphiti_core.utils.datetime_utils import ensure_utc, utc_now
 from graphiti_core.utils.maintenance.edge_operations import filter_existing_duplicate_of_edges
+from graphiti_core.utils.ontology_utils.entity_types_utils import validate_attributes
 
 logger = logging.getLogger(__name__)
 
@@ -66,6 +88,34 @@ async def extract_nodes_reflexion(
     return missed_entities
 
 
+async def _extract_entities(
+    llm_client: LLMClient, episode: EpisodicNode, context: dict[str, Any], strict: bool = False
+) -> list[ExtractedEntity]:
+    llm_response = {}
+    if episode.source == EpisodeType.message:
+        llm_response = await llm_client.generate_response(
+            prompt_library.extract_nodes.extract_message(context),
+            response_model=ExtractedEntities,
+        )
+    elif episode.source == EpisodeType.text:
+        llm_response = await llm_client.generate_response(
+            prompt_library.extract_nodes.extract_text(context), response_model=ExtractedEntities
+        )
+    elif episode.source == EpisodeType.json:
+        llm_response = await llm_client.generate_response(
+            prompt_library.extract_nodes.extract_json(context), response_model=ExtractedEntities
+        )
+
+    if strict:
+        # Without this, a response missing the entity list reads as an episode without entities
+        try:
+            return ExtractedEntities.model_validate(llm_response).extracted_entities
+        except ValidationError as e:
+            raise ExtractionError(episode.uuid, f'the response could not be parsed: {e}') from e
+
+    return [ExtractedEntity(**entity) for entity in llm_response.get('extracted_entities', [])]
+
+
 async def extract_nodes(
     clients: GraphitiClients,
     episode: EpisodicNode,
@@ -75,10 +125,7 @@ async def extract_nodes(
 ) -> list[EntityNode]:
     start = time()
     llm_client = clients.llm_client
-    llm_response = {}
     custom_prompt = ''
-    entities_missed = True
-    reflexion_iterations = 0
 
     entity_types_context = [
         {
@@ -108,44 +155,38 @@ async def extract_nodes(
         'custom_prompt': custom_prompt,
         'entity_types': entity_types_context,
         'source_description': episode.source_description,
+        'language': episode.language,
     }
 
-    while entities_missed and reflexion_iterations <= MAX_REFLEXION_ITERATIONS:
-        if episode.source == EpisodeType.message:
-            llm_response = await llm_client.generate_response(
-                prompt_library.extract_nodes.extract_message(context),
-                response_model=ExtractedEntities,
-            )
-        elif episode.source == EpisodeType.text:
-            llm_response = await llm_client.generate_response(
-                prompt_library.extract_nodes.extract_text(context), response_model=ExtractedEntities
-            )
-        elif episode.source == EpisodeType.json:
-            llm_response = await llm_client.generate_response(
-                prompt_library.extract_nodes.extract_json(context), response_model=ExtractedEntities
-            )
-
-        extracted_entities: list[ExtractedEntity] = [
-            ExtractedEntity(**entity_types_context)
-            for entity_types_context in llm_response.get('extracted_entities', [])
-        ]
-
-        reflexion_iterations += 1
-        if reflexion_iterations < MAX_REFLEXION_ITERATIONS:
-            missing_entities = await extract_nodes_reflexion(
-                llm_client,
-                episode,
-                previous_episodes,
-                [entity.name for entity in extracted_entities],
-            )
-
-            entities_missed = len(missing_entities) != 0
+    strict = clients.strict_extraction
+    extracted_entities = await _extract_entities(llm_client, episode, context, strict)
 
-            custom_prompt = 'Make sure that the following entities are extracted: '
-            for entity in missing_entities:
-                custom_prompt += f'\n{entity},'
+    for _ in range(clients.max_reflexion_rounds):
+        missing_entities = await extract_nodes_reflexion(
+            llm_client,
+            episode,
+            previous_episodes,
+            [entity.name for entity in extracted_entities],
+        )
+        if len(missing_entities) == 0:
+            break
+
+        custom_prompt = 'Make sure that the following entities are extracted: '
+        for entity in missing_entities:
+            custom_prompt += f'\n{entity},'
+        context['custom_prompt'] = custom_prompt
+
+        # Merge the re-extracted entities into what we already have rather than replacing it,
+        # so a re-extraction focused on the missed entities can't drop earlier results
+        extracted_names = {entity.name.strip().lower() for entity in extracted_entities}
+        for entity in await _extract_entities(llm_client, episode, context, strict):
+            if entity.name.strip().lower() not in extracted_names:
+                extracted_names.add(entity.name.strip().lower())
+                extracted_entities.append(entity)
 
     filtered_extracted_entities = [entity for entity in extracted_entities if entity.name.strip()]
+    if strict and not filtered_extracted_entities and episode.content.strip():
+        raise ExtractionError(episode.uuid, 'no entities were extracted from non-empty content')
     end = time()
     logger.debug(f'Extracted new nodes: {filtered_extracted_entities} in {(end - start) * 1000} ms')
     # Convert the extracted data into EntityNode objects
@@ -172,6 +213,14 @@ async def extract_nodes(
         extracted_nodes.append(new_node)
         logger.debug(f'Created new node: {new_node.name} (UUID: {new_node.uuid})')
 
+    max_entities = clients.max_entities_per_episode
+    if max_entities is not None and len(extracted_nodes) > max_entities:
+        logger.warning(
+            f'Episode {episode.uuid} extracted {len(extracted_nodes)} entities; '
+            f'keeping the first {max_entities}'
+        )
+        extracted_nodes = extracted_nodes[:max_entities]
+
     logger.debug(f'Extracted nodes: {[(n.name, n.uuid) for n in extracted_nodes]}')
     return extracted_nodes
 
@@ -219,6 +268,7 @@ async def dedupe_extracted_nodes(
         if node.uuid in uuid_map:
             existing_uuid = uuid_map[node.uuid]
             existing_node = node_map[existing_uuid]
+            merge_node_labels(existing_node, node)
             nodes.append(existing_node)
         else:
             nodes.append(node)
@@ -226,6 +276,96 @@ async def dedupe_extracted_nodes(
     return nodes, uuid_map
 
 
+def merge_node_labels(node: EntityNode, duplicate: EntityNode):
+    """
+    Give an untyped node the entity type label of its duplicate.
+
+    Nodes that already have an entity type keep it, so a node never carries conflicting types.
+    """
+    if any(label != 'Entity' for label in node.labels):
+        return
+    node.labels = list(dict.fromkeys(node.labels + duplicate.labels))
+
+
+async def find_auto_merge_matches(
+    clients: GraphitiClients,
+    extracted_nodes: list[EntityNode],
+    existing_nodes: list[EntityNode],
+) -> dict[str, EntityNode]:
+    """
+    Match extracted nodes to existing nodes in the same group whose name embedding has a cosine
+    similarity above `clients.auto_merge_threshold`. Names are compared because extracted nodes
+    have no summary yet. Returns the matches keyed by extracted node uuid.
+    """
+    threshold = clients.auto_merge_threshold
+    if (
+        threshold >= 1.0
+        or not clients.embeddings_enabled
+        or len(extracted_nodes) == 0
+        or len(existing_nodes) == 0
+    ):
+        return {}
+
+    embeddings = await clients.embedder.create_batch(
+        [node.name.replace('\n', ' ') for node in extracted_nodes + existing_nodes]
+    )
+    extracted_embeddings = embeddings[: len(extracted_nodes)]
+    existing_embeddings = embeddings[len(extracted_nodes) :]
+
+    matches: dict[str, EntityNode] = {}
+    for node, embedding in zip(extracted_nodes, extracted_embeddings, strict=True):
+        best_score, best_match = max(
+            (
+                (cosine_similarity(embedding, existing_embedding), existing_node)
+                for existing_node, existing_embedding in zip(
+                    existing_nodes, existing_embeddings, strict=True
+                )
+                if existing_node.group_id == node.group_id
+            ),
+            key=lambda scored: scored[0],
+            default=(0.0, None),
+        )
+        if best_match is not None and best_score > threshold:
+            logger.debug(f'Auto-merged {node.name} into {best_match.name} ({best_score:.3f})')
+            matches[node.uuid] = best_match
+
+    return matches
+
+
+def find_similar_name_matches(
+    clients: GraphitiClients,
+    extracted_nodes: list[EntityNode],
+    existing_nodes: list[EntityNode],
+) -> dict[str, EntityNode]:
+    """
+    Match extracted nodes to existing nodes in the same group whose normalized name has a string
+    similarity of at least `clients.name_similarity_threshold`, so that typos are merged without
+    asking the LLM. Returns the matches keyed by extracted node uuid.
+    """
+    threshold = clients.name_similarity_threshold
+    if threshold >= 1.0 or len(extracted_nodes) == 0 or len(existing_nodes) == 0:
+        return {}
+
+    existing_names = [normalize_name(existing_node.name) for existing_node in existing_nodes]
+    matches: dict[str, EntityNode] = {}
+    for node in extracted_nodes:
+        name = normalize_name(node.name)
+        best_score, best_match = max(
+            (
+                (string_similarity(name, existing_name, clients.name_similarity), existing_node)
+                for existing_node, existing_name in zip(existing_nodes, existing_names, strict=True)
+                if existing_node.group_id == node.group_id
+            ),
+            key=lambda scored: scored[0],
+            default=(0.0, None),
+        )
+        if best_match is not None and best_score >= threshold:
+            logger.debug(f'Name-matched {node.name} to {best_match.name} ({best_score:.3f})')
+            matches[node.uuid] = best_match
+
+    return matches
+
+
 async def resolve_extracted_nodes(
     clients: GraphitiClients,
     extracted_nodes: list[EntityNode],
@@ -271,6 +411,29 @@ async def resolve_extracted_nodes(
 
     entity_types_dict: dict[str, BaseModel] = entity_types if entity_types is not None else {}
 
+    resolved_nodes: list[EntityNode] = []
+    uuid_map: dict[str, str] = {}
+    node_duplicates: list[tuple[EntityNode, EntityNode]] = []
+
+    # Near-identical matches are merged without asking the LLM; only the rest go to the prompt
+    auto_merged = find_similar_name_matches(clients, extracted_nodes, existing_nodes)
+    auto_merged.update(
+        await find_auto_merge_matches(
+            clients,
+            [node for node in extracted_nodes if node.uuid not in auto_merged],
+            existing_nodes,
+        )
+    )
+    for extracted_node in extracted_nodes:
+        match = auto_merged.get(extracted_node.uuid)
+        if match is None:
+            continue
+        merge_node_labels(match, extracted_node)
+        resolved_nodes.append(match)
+        uuid_map[extracted_node.uuid] = match.uuid
+
+    ambiguous_nodes = [node for node in extracted_nodes if node.uuid not in auto_merged]
+
     # Prepare context for LLM
     extracted_nodes_context = [
         {
@@ -282,7 +445,7 @@ async def resolve_extracted_nodes(
             ).__doc__
             or 'Default Entity Type',
         }
-        for i, node in enumerate(extracted_nodes)
+        for i, node in enumerate(ambiguous_nodes)
     ]
 
     context = {
@@ -294,21 +457,19 @@ async def resolve_extracted_nodes(
         else [],
     }
 
-    llm_response = await llm_client.generate_response(
-        prompt_library.dedupe_nodes.nodes(context),
-        response_model=NodeResolutions,
-    )
-
-    node_resolutions: list = llm_response.get('entity_resolutions', [])
+    node_resolutions: list = []
+    if len(ambiguous_nodes) > 0:
+        llm_response = await llm_client.generate_response(
+            prompt_library.dedupe_nodes.nodes(context),
+            response_model=NodeResolutions,
+        )
+        node_resolutions = llm_response.get('entity_resolutions', [])
 
-    resolved_nodes: list[EntityNode] = []
-    uuid_map: dict[str, str] = {}
-    node_duplicates: list[tuple[EntityNode, EntityNode]] = []
     for resolution in node_resolutions:
         resolution_id: int = resolution.get('id', -1)
         duplicate_idx: int = resolution.get('duplicate_idx', -1)
 
-        extracted_node = extracted_nodes[resolution_id]
+        extracted_node = ambiguous_nodes[resolution_id]
 
         resolved_node = (
             existing_nodes[duplicate_idx]
@@ -318,6 +479,10 @@ async def resolve_extracted_nodes(
 
         # resolved_node.name = resolution.get('name')
 
+        # Keep the classified entity type when resolving to an existing node
+        if resolved_node is not extracted_node:
+            merge_node_labels(resolved_node, extracted_node)
+
         resolved_nodes.append(resolved_node)
         uuid_map[extracted_node.uuid] = resolved_node.uuid
 
@@ -357,12 +522,22 @@ async def extract_attributes_from_nodes(
                 entity_types.get(next((item for item in node.labels if item != 'Entity'), ''))
                 if entity_types is not None
                 else None,
+                clients.max_summary_words,
+                clients.attribute_validation,
+                clients.attribute_conflict_policy,
             )
             for node in nodes
         ]
     )
 
-    await create_entity_node_embeddings(embedder, updated_nodes)
+    if clients.embeddings_enabled:
+        composition = clients.entity_embedding_text
+        mentions = (
+            await get_entity_mentions(clients.driver, updated_nodes, episode)
+            if composition == EntityEmbeddingText.name_summary_and_mentions
+            else None
+        )
+        await create_entity_node_embeddings(embedder, updated_nodes, composition, mentions)
 
     return updated_nodes
 
@@ -373,6 +548,9 @@ async def extract_attributes_from_node(
     episode: EpisodicNode | None = None,
     previous_episodes: list[EpisodicNode] | None = None,
     entity_type: BaseModel | None = None,
+    max_summary_words: int | None = DEFAULT_MAX_SUMMARY_WORDS,
+    attribute_validation: AttributeValidationPolicy = AttributeValidationPolicy.keep,
+    attribute_conflict_policy: AttributeConflictPolicy = AttributeConflictPolicy.overwrite,
 ) -> EntityNode:
     node_context: dict[str, Any] = {
         'name': node.name,
@@ -414,17 +592,91 @@ async def extract_attributes_from_node(
         model_size=ModelSize.small,
     )
 
-    node.summary = llm_response.get('summary', node.summary)
+    node.summary = truncate_words(llm_response.get('summary', node.summary), max_summary_words)
     node_attributes = {key: value for key, value in llm_response.items()}
 
     with suppress(KeyError):
         del node_attributes['summary']
 
-    node.attributes.update(node_attributes)
+    if entity_type is not None:
+        node_attributes, _ = validate_attributes(
+            node_attributes,
+            entity_type,  # type: ignore[arg-type]
+            attribute_validation,
+        )
+
+    merge_extracted_attributes(
+        node,
+        node_attributes,
+        attribute_conflict_policy,
+        episode.valid_at if episode is not None else utc_now(),
+    )
 
     return node
 
 
+def merge_extracted_attributes(
+    node: EntityNode,
+    attributes: dict[str, Any],
+    policy: AttributeConflictPolicy,
+    observed_at: datetime,
+):
+    """
+    Merge attribute values extracted from an episode observed at `observed_at` into the node.
+
+    With overwrite, the extracted values simply replace the stored ones. The other policies
+    ignore values the LLM left empty and record when each value was observed in the node's
+    attribute_metadata: keep_newest keeps a stored value observed after `observed_at`, and
+    append_history moves each replaced value to the attribute history along with when it was
+    observed and replaced.
+    """
+    if policy == AttributeConflictPolicy.overwrite:
+        node.attributes.update(attributes)
+        return
+
+    observed_times: dict[str, str] = json.loads(
+        node.attribute_metadata.get(ATTRIBUTE_OBSERVED_AT_KEY, '{}')
+    )
+    history: dict[str, list[dict[str, Any]]] = json.loads(
+        node.attribute_metadata.get(ATTRIBUTE_HISTORY_KEY, '{}')
+    )
+    # Naive times are taken to be UTC, so they compare with the aware times already stored
+    observed_at = ensure_utc(observed_at) or observed_at
+    observed = observed_at.isoformat()
+
+    for key, value in attributes.items():
+        if value is None:
+            continue
+        current = node.attributes.get(key)
+        previously_observed = observed_times.get(key)
+        previously_observed_at = (
+            ensure_utc(datetime.fromisoformat(previously_observed))
+            if previously_observed is not None
+            else None
+        )
+        if (
+            policy == AttributeConflictPolicy.keep_newest
+            and previously_observed_at is not None
+            and previously_observed_at > observed_at
+        ):
+            continue
+        if (
+            policy == AttributeConflictPolicy.append_history
+            and current is not None
+            and current != value
+        ):
+            history.setdefault(key, []).append(
+                {'value': current, 'observed_at': previously_observed, 'replaced_at': observed}
+            )
+
+        node.attributes[key] = value
+        observed_times[key] = observed
+
+    node.attribute_metadata[ATTRIBUTE_OBSERVED_AT_KEY] = json.dumps(observed_times)
+    if history:
+        node.attribute_metadata[ATTRIBUTE_HISTORY_KEY] = json.dumps(history, default=str)
+
+
 async def dedupe_node_list(
     llm_client: LLMClient,
     nodes: list[EntityNode],
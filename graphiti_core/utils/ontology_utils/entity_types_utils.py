@@ -14,11 +14,17 @@ See the License for the specific language governing permissions and
 limitations under the License.
 """
 
-from pydantic import BaseModel
+import logging
+from typing import Any
+
+from pydantic import BaseModel, TypeAdapter, ValidationError
 
 from graphiti_core.errors import EntityTypeValidationError
+from graphiti_core.helpers import AttributeValidationPolicy
 from graphiti_core.nodes import EntityNode
 
+logger = logging.getLogger(__name__)
+
 
 def validate_entity_types(
     entity_types: dict[str, BaseModel] | None,
@@ -35,3 +41,47 @@ def validate_entity_types(
                 raise EntityTypeValidationError(entity_type_name, entity_type_field_name)
 
     return True
+
+
+def validate_attributes(
+    attributes: dict[str, Any],
+    type_model: type[BaseModel],
+    policy: AttributeValidationPolicy,
+) -> tuple[dict[str, Any], list[str]]:
+    """
+    Check extracted attributes against the fields of an entity or edge type. Returns the
+    attributes to store and a warning for each field that was dropped or coerced.
+    """
+    if policy == AttributeValidationPolicy.keep:
+        return attributes, []
+
+    strict = policy == AttributeValidationPolicy.drop
+    validated: dict[str, Any] = {}
+    warnings: list[str] = []
+    for name, value in attributes.items():
+        field_info = type_model.model_fields.get(name)
+        if field_info is None:
+            warnings.append(f'{type_model.__name__}: dropped unknown attribute {name!r}')
+            continue
+
+        adapter: TypeAdapter[Any] = TypeAdapter(field_info.annotation)
+        try:
+            parsed = adapter.validate_python(value, strict=strict)
+            # Dumped in JSON mode so that the stored value is one the graph database accepts
+            coerced = adapter.dump_python(parsed, mode='json')
+        except ValidationError:
+            warnings.append(
+                f'{type_model.__name__}: dropped attribute {name!r} with invalid value {value!r}'
+            )
+            continue
+
+        if coerced != value:
+            warnings.append(
+                f'{type_model.__name__}: coerced attribute {name!r} from {value!r} to {coerced!r}'
+            )
+        validated[name] = coerced
+
+    for warning in warnings:
+        logger.warning(warning)
+
+    return validated, warnings
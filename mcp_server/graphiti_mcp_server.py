@@ -5,13 +5,15 @@ Graphiti MCP Server - Exposes Graphiti functionality through the Model Context P
 
 import argparse
 import asyncio
+import functools
 import logging
 import os
 import sys
-from collections.abc import Callable
+from collections.abc import Awaitable, Callable
 from datetime import datetime, timezone
 from typing import Any, TypedDict, cast
 
+import pydantic_core
 from azure.identity import DefaultAzureCredential, get_bearer_token_provider
 from dotenv import load_dotenv
 from mcp.server.fastmcp import FastMCP
@@ -28,12 +30,8 @@ from graphiti_core.llm_client.azure_openai_client import AzureOpenAILLMClient
 from graphiti_core.llm_client.config import LLMConfig
 from graphiti_core.llm_client.openai_client import OpenAIClient
 from graphiti_core.nodes import EpisodeType, EpisodicNode
-from graphiti_core.search.search_config_recipes import (
-    NODE_HYBRID_SEARCH_NODE_DISTANCE,
-    NODE_HYBRID_SEARCH_RRF,
-)
+from graphiti_core.search.search_config import EdgeReranker, NodeReranker, SearchConfig
 from graphiti_core.search.search_filters import SearchFilters
-from graphiti_core.utils.maintenance.graph_data_operations import clear_data
 
 load_dotenv()
 
@@ -478,6 +476,8 @@ class GraphitiConfig(BaseModel):
     group_id: str | None = None
     use_custom_entities: bool = False
     destroy_graph: bool = False
+    # Serialize tool results as compact JSON, which costs agents fewer tokens to read back
+    compact_tool_output: bool = False
 
     @classmethod
     def from_env(cls) -> 'GraphitiConfig':
@@ -486,6 +486,7 @@ class GraphitiConfig(BaseModel):
             llm=GraphitiLLMConfig.from_env(),
             embedder=GraphitiEmbedderConfig.from_env(),
             neo4j=Neo4jConfig.from_env(),
+            compact_tool_output=os.environ.get('COMPACT_TOOL_OUTPUT', 'false').lower() == 'true',
         )
 
     @classmethod
@@ -502,6 +503,8 @@ class GraphitiConfig(BaseModel):
 
         config.use_custom_entities = args.use_custom_entities
         config.destroy_graph = args.destroy_graph
+        if args.compact_tool_output:
+            config.compact_tool_output = True
 
         # Update LLM config using CLI args
         config.llm = GraphitiLLMConfig.from_cli_and_env(args)
@@ -602,7 +605,7 @@ async def initialize_graphiti():
         # Destroy graph if requested
         if config.destroy_graph:
             logger.info('Destroying graph...')
-            await clear_data(graphiti_client.driver)
+            await graphiti_client.clear()
 
         # Initialize the graph database with Graphiti's indices
         await graphiti_client.build_indices_and_constraints()
@@ -647,6 +650,27 @@ def format_fact_result(edge: EntityEdge) -> dict[str, Any]:
     return result
 
 
+def format_tool_output(result: Any) -> str:
+    """Serialize a tool result as compact JSON, like FastMCP does but without indentation."""
+    return pydantic_core.to_json(result, fallback=str).decode()
+
+
+def tool_output(func: Callable[..., Awaitable[Any]]) -> Callable[..., Awaitable[Any]]:
+    """Return the tool's result as compact JSON text when compact_tool_output is enabled.
+
+    Otherwise the result is returned as is and FastMCP pretty-prints it.
+    """
+
+    @functools.wraps(func)
+    async def wrapper(*args: Any, **kwargs: Any) -> Any:
+        result = await func(*args, **kwargs)
+        if config.compact_tool_output:
+            return format_tool_output(result)
+        return result
+
+    return wrapper
+
+
 # Dictionary to store queues for each group_id
 # Each queue is a list of tasks to be processed sequentially
 episode_queues: dict[str, asyncio.Queue] = {}
@@ -689,6 +713,7 @@ async def process_episode_queue(group_id: str):
 
 
 @mcp.tool()
+@tool_output
 async def add_memory(
     name: str,
     episode_body: str,
@@ -696,6 +721,8 @@ async def add_memory(
     source: str = 'text',
     source_description: str = '',
     uuid: str | None = None,
+    store_raw: bool | None = None,
+    model_override: str | None = None,
 ) -> SuccessResponse | ErrorResponse:
     """Add an episode to memory. This is the primary way to add information to the graph.
 
@@ -715,6 +742,10 @@ async def add_memory(
                                - 'message': For conversation-style content
         source_description (str, optional): Description of the source
         uuid (str, optional): Optional UUID for the episode
+        store_raw (bool, optional): Whether to keep the raw episode content in the graph. Set to
+                                   false for sensitive content; defaults to the server setting
+        model_override (str, optional): LLM model to process this episode with instead of the
+                                        server's configured model, e.g. for a tricky document
 
     Examples:
         # Adding plain text content
@@ -795,6 +826,8 @@ async def add_memory(
                     uuid=uuid,
                     reference_time=datetime.now(timezone.utc),
                     entity_types=entity_types,
+                    store_raw=store_raw,
+                    model_override=model_override,
                 )
                 logger.info(f"Episode '{name}' added successfully")
 
@@ -827,6 +860,7 @@ async def add_memory(
 
 
 @mcp.tool()
+@tool_output
 async def search_memory_nodes(
     query: str,
     group_ids: list[str] | None = None,
@@ -858,11 +892,10 @@ async def search_memory_nodes(
         )
 
         # Configure the search
-        if center_node_uuid is not None:
-            search_config = NODE_HYBRID_SEARCH_NODE_DISTANCE.model_copy(deep=True)
-        else:
-            search_config = NODE_HYBRID_SEARCH_RRF.model_copy(deep=True)
-        search_config.limit = max_nodes
+        search_config = SearchConfig.nodes_only(
+            limit=max_nodes,
+            reranker=NodeReranker.rrf if center_node_uuid is None else NodeReranker.node_distance,
+        )
 
         filters = SearchFilters()
         if entity != '':
@@ -908,11 +941,14 @@ async def search_memory_nodes(
 
 
 @mcp.tool()
+@tool_output
 async def search_memory_facts(
     query: str,
     group_ids: list[str] | None = None,
     max_facts: int = 10,
     center_node_uuid: str | None = None,
+    include_invalidated: bool = False,
+    model_override: str | None = None,
 ) -> FactSearchResponse | ErrorResponse:
     """Search the graph memory for relevant facts.
 
@@ -921,6 +957,8 @@ async def search_memory_facts(
         group_ids: Optional list of group IDs to filter results
         max_facts: Maximum number of facts to return (default: 10)
         center_node_uuid: Optional UUID of a node to center the search around
+        include_invalidated: Also return facts that are no longer true (default: False)
+        model_override: Optional LLM model for this search to use instead of the configured one
     """
     global graphiti_client
 
@@ -939,12 +977,19 @@ async def search_memory_facts(
         # Use cast to help the type checker understand that graphiti_client is not None
         client = cast(Graphiti, graphiti_client)
 
-        relevant_edges = await client.search(
-            group_ids=effective_group_ids,
+        search_config = SearchConfig.facts_only(
+            limit=max_facts,
+            reranker=EdgeReranker.rrf if center_node_uuid is None else EdgeReranker.node_distance,
+        )
+        search_results = await client.search_(
             query=query,
-            num_results=max_facts,
+            config=search_config,
+            group_ids=effective_group_ids,
             center_node_uuid=center_node_uuid,
+            search_filter=SearchFilters(include_invalidated=include_invalidated),
+            model_override=model_override,
         )
+        relevant_edges = search_results.edges
 
         if not relevant_edges:
             return {'message': 'No relevant facts found', 'facts': []}
@@ -958,6 +1003,7 @@ async def search_memory_facts(
 
 
 @mcp.tool()
+@tool_output
 async def delete_entity_edge(uuid: str) -> SuccessResponse | ErrorResponse:
     """Delete an entity edge from the graph memory.
 
@@ -988,6 +1034,7 @@ async def delete_entity_edge(uuid: str) -> SuccessResponse | ErrorResponse:
 
 
 @mcp.tool()
+@tool_output
 async def delete_episode(uuid: str) -> SuccessResponse | ErrorResponse:
     """Delete an episode from the graph memory.
 
@@ -1018,6 +1065,7 @@ async def delete_episode(uuid: str) -> SuccessResponse | ErrorResponse:
 
 
 @mcp.tool()
+@tool_output
 async def get_entity_edge(uuid: str) -> dict[str, Any] | ErrorResponse:
     """Get an entity edge from the graph memory by its UUID.
 
@@ -1049,6 +1097,7 @@ async def get_entity_edge(uuid: str) -> dict[str, Any] | ErrorResponse:
 
 
 @mcp.tool()
+@tool_output
 async def get_episodes(
     group_id: str | None = None, last_n: int = 10
 ) -> list[dict[str, Any]] | EpisodeSearchResponse | ErrorResponse:
@@ -1099,6 +1148,7 @@ async def get_episodes(
 
 
 @mcp.tool()
+@tool_output
 async def clear_graph() -> SuccessResponse | ErrorResponse:
     """Clear all data from the graph memory and rebuild indices."""
     global graphiti_client
@@ -1113,8 +1163,7 @@ async def clear_graph() -> SuccessResponse | ErrorResponse:
         # Use cast to help the type checker understand that graphiti_client is not None
         client = cast(Graphiti, graphiti_client)
 
-        # clear_data is already imported at the top
-        await clear_data(client.driver)
+        await client.clear()
         await client.build_indices_and_constraints()
         return {'message': 'Graph cleared successfully and indices rebuilt'}
     except Exception as e:
@@ -1138,7 +1187,7 @@ async def get_status() -> StatusResponse:
         # Use cast to help the type checker understand that graphiti_client is not None
         client = cast(Graphiti, graphiti_client)
 
-        # Test Neo4j connection
+        # Test the database connection
         await client.driver.verify_connectivity()
         return {'status': 'ok', 'message': 'Graphiti MCP server is running and connected to Neo4j'}
     except Exception as e:
@@ -1186,6 +1235,11 @@ async def initialize_server() -> MCPConfig:
         action='store_true',
         help='Enable entity extraction using the predefined ENTITY_TYPES',
     )
+    parser.add_argument(
+        '--compact-tool-output',
+        action='store_true',
+        help='Return tool results as compact JSON instead of pretty-printed JSON, to save tokens',
+    )
     parser.add_argument(
         '--host',
         default=os.environ.get('MCP_SERVER_HOST'),
@@ -0,0 +1,41 @@
+import asyncio
+import json
+
+import pytest
+
+import graphiti_mcp_server
+from graphiti_mcp_server import format_tool_output, tool_output
+
+RESULT = {'message': 'Facts retrieved successfully', 'facts': [{'uuid': '1', 'fact': 'A knows B'}]}
+
+
+@pytest.fixture
+def compact_config(monkeypatch):
+    monkeypatch.setattr(graphiti_mcp_server.config, 'compact_tool_output', True)
+
+
+def test_format_tool_output_is_compact():
+    output = format_tool_output(RESULT)
+
+    assert json.loads(output) == RESULT
+    assert '\n' not in output
+    assert ': ' not in output
+    assert ', ' not in output.replace('A knows B', '')
+
+
+def test_tool_output_is_compact_when_enabled(compact_config):
+    @tool_output
+    async def search() -> dict:
+        return RESULT
+
+    output = asyncio.run(search())
+
+    assert output == format_tool_output(RESULT)
+
+
+def test_tool_output_is_unchanged_by_default():
+    @tool_output
+    async def search() -> dict:
+        return RESULT
+
+    assert asyncio.run(search()) == RESULT
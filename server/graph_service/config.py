@@ -14,9 +14,33 @@ class Settings(BaseSettings):
     neo4j_uri: str
     neo4j_user: str
     neo4j_password: str
+    # How long to keep retrying the initial database connection on startup
+    neo4j_connect_retry_seconds: float = Field(60.0)
+    # Whether the LLM, embedder, and reranker are checked on startup; disable for offline use
+    check_providers_on_startup: bool = Field(True)
+    # Requests with larger bodies are rejected with 413; None disables the limit
+    max_request_body_bytes: int | None = Field(10 * 1024 * 1024)
+    # Requests that take longer are cancelled and answered with 408; None disables the timeout
+    request_timeout_seconds: float | None = Field(300.0)
+    # Comma-separated API keys accepted as bearer tokens; auth is disabled when unset
+    api_keys: str | None = Field(None)
+    # Requests allowed per minute for each API key, or each client IP for requests without one;
+    # None disables rate limiting
+    rate_limit_per_minute: float | None = Field(None)
+    # How many requests a client may make in a burst; defaults to rate_limit_per_minute
+    rate_limit_burst: int | None = Field(None)
+    # Episodes older than this are pruned in the background; None disables pruning
+    episode_retention_days: float | None = Field(None)
+    episode_prune_interval_seconds: float = Field(3600.0)
+    # Whether pruning also deletes entities left without episodes or valid facts
+    episode_prune_orphaned_entities: bool = Field(False)
 
     model_config = SettingsConfigDict(env_file='.env', extra='ignore')
 
+    @property
+    def api_key_set(self) -> set[str]:
+        return {key.strip() for key in (self.api_keys or '').split(',') if key.strip()}
+
 
 @lru_cache
 def get_settings():
@@ -1,5 +1,6 @@
 from .common import Message, Result
 from .ingest import AddEntityNodeRequest, AddMessagesRequest
+from .maintenance import MergeEntitiesRequest, PruneEpisodesRequest, WarmupEmbeddingsRequest
 from .retrieve import FactResult, GetMemoryRequest, GetMemoryResponse, SearchQuery, SearchResults
 
 __all__ = [
@@ -12,4 +13,7 @@ __all__ = [
     'Result',
     'GetMemoryRequest',
     'GetMemoryResponse',
+    'WarmupEmbeddingsRequest',
+    'PruneEpisodesRequest',
+    'MergeEntitiesRequest',
 ]
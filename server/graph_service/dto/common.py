@@ -26,3 +26,8 @@ class Message(BaseModel):
     source_description: str = Field(
         default='', description='The description of the source of the message'
     )
+    store_raw: bool | None = Field(
+        default=None,
+        description='Whether to keep the raw message content in the graph, '
+        'overriding the server default (optional)',
+    )
@@ -6,6 +6,10 @@ from graph_service.dto.common import Message
 class AddMessagesRequest(BaseModel):
     group_id: str = Field(..., description='The group id of the messages to add')
     messages: list[Message] = Field(..., description='The messages to add')
+    model_override: str | None = Field(
+        default=None,
+        description='The LLM model to process these messages with, instead of the configured one',
+    )
 
 
 class AddEntityNodeRequest(BaseModel):
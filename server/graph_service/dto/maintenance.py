@@ -0,0 +1,19 @@
+from datetime import datetime
+
+from pydantic import BaseModel, Field
+
+
+class WarmupEmbeddingsRequest(BaseModel):
+    queries: list[str] = Field(..., description='The search queries to precompute embeddings for')
+
+
+class MergeEntitiesRequest(BaseModel):
+    keep_uuid: str = Field(..., description='The uuid of the entity to keep')
+    merge_uuid: str = Field(..., description='The uuid of the duplicate entity to merge and delete')
+
+
+class PruneEpisodesRequest(BaseModel):
+    older_than: datetime = Field(..., description='Episodes created before this time are deleted')
+    delete_orphaned_entities: bool = Field(
+        False, description='Whether to delete entities left without episodes or valid facts'
+    )
@@ -6,11 +6,17 @@ from graph_service.dto.common import Message
 
 
 class SearchQuery(BaseModel):
-    group_ids: list[str] | None = Field(
-        None, description='The group ids for the memories to search'
+    group_ids: list[str] = Field(
+        ..., min_length=1, description='The group ids for the memories to search'
     )
     query: str
     max_facts: int = Field(default=10, description='The maximum number of facts to retrieve')
+    model_override: str | None = Field(
+        default=None, description='The LLM model to search with instead of the configured one'
+    )
+    timeout: float | None = Field(
+        default=None, gt=0, description='The most seconds the search may take'
+    )
 
 
 class FactResult(BaseModel):
@@ -1,10 +1,17 @@
-from contextlib import asynccontextmanager
+import asyncio
+from contextlib import asynccontextmanager, suppress
 
 from fastapi import FastAPI
 from fastapi.responses import JSONResponse
 
 from graph_service.config import get_settings
-from graph_service.routers import ingest, retrieve
+from graph_service.middleware import (
+    ApiKeyAuthMiddleware,
+    RateLimitMiddleware,
+    RequestLimitsMiddleware,
+)
+from graph_service.pruning import run_episode_pruning
+from graph_service.routers import ingest, maintenance, retrieve
 from graph_service.zep_graphiti import initialize_graphiti
 
 
@@ -12,16 +19,31 @@ from graph_service.zep_graphiti import initialize_graphiti
 async def lifespan(_: FastAPI):
     settings = get_settings()
     await initialize_graphiti(settings)
+    pruning_task = (
+        asyncio.create_task(run_episode_pruning(settings))
+        if settings.episode_retention_days is not None
+        else None
+    )
     yield
+    if pruning_task is not None:
+        pruning_task.cancel()
+        with suppress(asyncio.CancelledError):
+            await pruning_task
     # Shutdown
     # No need to close Graphiti here, as it's handled per-request
 
 
 app = FastAPI(lifespan=lifespan)
+app.add_middleware(RequestLimitsMiddleware)
+app.add_middleware(RateLimitMiddleware)
+# Added last so it runs first: unauthenticated requests are rejected before they count towards a
+# rate limit or have their body read
+app.add_middleware(ApiKeyAuthMiddleware)
 
 
 app.include_router(retrieve.router)
 app.include_router(ingest.router)
+app.include_router(maintenance.router)
 
 
 @app.get('/healthcheck')
@@ -0,0 +1,219 @@
+import asyncio
+import hmac
+import logging
+import math
+import time
+from collections import OrderedDict
+from collections.abc import Callable
+from dataclasses import dataclass
+
+from fastapi import HTTPException
+from fastapi.responses import JSONResponse
+from starlette.datastructures import Headers
+from starlette.types import ASGIApp, Message, Receive, Scope, Send
+
+from graph_service.config import Settings, get_settings
+
+logger = logging.getLogger(__name__)
+
+# Paths that stay reachable without an API key, so that probes keep working
+UNAUTHENTICATED_PATHS = {'/healthcheck', '/livez'}
+# Once this many clients are tracked, the buckets of the least recently seen ones are dropped
+MAX_TRACKED_RATE_LIMIT_CLIENTS = 10_000
+
+
+class RequestLimitsMiddleware:
+    """
+    Rejects request bodies larger than `max_request_body_bytes` with 413 and requests that take
+    longer than `request_timeout_seconds` with 408.
+
+    Bodies are checked against Content-Length up front and counted as they stream in, so chunked
+    uploads can't get around the limit. Settings are read on each request unless given explicitly.
+    """
+
+    def __init__(self, app: ASGIApp, settings: Settings | None = None):
+        self.app = app
+        self.settings = settings
+
+    async def __call__(self, scope: Scope, receive: Receive, send: Send):
+        if scope['type'] != 'http':
+            await self.app(scope, receive, send)
+            return
+
+        settings = self.settings or get_settings()
+        max_body_bytes = settings.max_request_body_bytes
+        timeout = settings.request_timeout_seconds
+
+        if max_body_bytes is not None:
+            content_length = Headers(scope=scope).get('content-length', '')
+            if content_length.isdigit() and int(content_length) > max_body_bytes:
+                await _body_too_large(max_body_bytes)(scope, receive, send)
+                return
+
+        received = 0
+
+        async def limited_receive() -> Message:
+            nonlocal received
+            message = await receive()
+            if max_body_bytes is not None and message['type'] == 'http.request':
+                received += len(message.get('body', b''))
+                if received > max_body_bytes:
+                    raise HTTPException(
+                        status_code=413,
+                        detail=f'Request body exceeds the limit of {max_body_bytes} bytes',
+                    )
+            return message
+
+        response_started = False
+
+        async def tracked_send(message: Message):
+            nonlocal response_started
+            if message['type'] == 'http.response.start':
+                response_started = True
+            await send(message)
+
+        try:
+            await asyncio.wait_for(self.app(scope, limited_receive, tracked_send), timeout)
+        except asyncio.TimeoutError:
+            logger.warning(f'Request to {scope["path"]} timed out after {timeout} seconds')
+            if response_started:
+                # Part of the response has already been sent, so the connection is just closed
+                return
+            response = JSONResponse(
+                content={'detail': f'Request timed out after {timeout} seconds'},
+                status_code=408,
+            )
+            await response(scope, receive, send)
+
+
+class ApiKeyAuthMiddleware:
+    """
+    Requires an `Authorization: Bearer <key>` header with one of the configured `api_keys` and
+    answers 401 otherwise. Auth is opt-in: when no keys are configured, every request is let
+    through. Health check paths are always open.
+    """
+
+    def __init__(self, app: ASGIApp, settings: Settings | None = None):
+        self.app = app
+        self.settings = settings
+
+    async def __call__(self, scope: Scope, receive: Receive, send: Send):
+        if scope['type'] != 'http' or scope['path'] in UNAUTHENTICATED_PATHS:
+            await self.app(scope, receive, send)
+            return
+
+        api_keys = (self.settings or get_settings()).api_key_set
+        if not api_keys:
+            await self.app(scope, receive, send)
+            return
+
+        scheme, _, token = Headers(scope=scope).get('authorization', '').partition(' ')
+        token = token.strip()
+        # Compare against every key so the time taken doesn't reveal which one matched
+        valid = False
+        for key in api_keys:
+            valid |= hmac.compare_digest(token.encode(), key.encode())
+
+        if scheme.lower() != 'bearer' or not valid:
+            response = JSONResponse(
+                content={'detail': 'Missing or invalid API key'},
+                status_code=401,
+                headers={'WWW-Authenticate': 'Bearer'},
+            )
+            await response(scope, receive, send)
+            return
+
+        await self.app(scope, receive, send)
+
+
+@dataclass
+class TokenBucket:
+    capacity: float
+    refill_per_second: float
+    tokens: float
+    updated_at: float
+
+    def refill(self, now: float):
+        elapsed = max(now - self.updated_at, 0.0)
+        self.tokens = min(self.capacity, self.tokens + elapsed * self.refill_per_second)
+        self.updated_at = now
+
+    def take(self, now: float) -> float | None:
+        """Takes a token, or returns how many seconds until one is available."""
+        self.refill(now)
+        if self.tokens >= 1:
+            self.tokens -= 1
+            return None
+        return (1 - self.tokens) / self.refill_per_second
+
+
+class RateLimitMiddleware:
+    """
+    Token-bucket rate limit per API key, falling back to the client IP for requests without a
+    valid key, including every request when auth is disabled. Requests over the limit get 429
+    with a Retry-After header. Health check paths are not limited.
+    """
+
+    def __init__(
+        self,
+        app: ASGIApp,
+        settings: Settings | None = None,
+        clock: Callable[[], float] = time.monotonic,
+    ):
+        self.app = app
+        self.settings = settings
+        self.clock = clock
+        self.buckets: OrderedDict[str, TokenBucket] = OrderedDict()
+
+    async def __call__(self, scope: Scope, receive: Receive, send: Send):
+        settings = self.settings or get_settings()
+        per_minute = settings.rate_limit_per_minute
+        if scope['type'] != 'http' or scope['path'] in UNAUTHENTICATED_PATHS or not per_minute:
+            await self.app(scope, receive, send)
+            return
+
+        now = self.clock()
+        client_key = _rate_limit_key(scope, settings.api_key_set)
+        bucket = self.buckets.get(client_key)
+        if bucket is not None:
+            self.buckets.move_to_end(client_key)
+        else:
+            while len(self.buckets) >= MAX_TRACKED_RATE_LIMIT_CLIENTS:
+                self.buckets.popitem(last=False)
+            capacity = float(settings.rate_limit_burst or max(per_minute, 1))
+            bucket = TokenBucket(
+                capacity=capacity,
+                refill_per_second=per_minute / 60,
+                tokens=capacity,
+                updated_at=now,
+            )
+            self.buckets[client_key] = bucket
+
+        retry_after = bucket.take(now)
+        if retry_after is not None:
+            response = JSONResponse(
+                content={'detail': 'Rate limit exceeded'},
+                status_code=429,
+                headers={'Retry-After': str(math.ceil(retry_after))},
+            )
+            await response(scope, receive, send)
+            return
+
+        await self.app(scope, receive, send)
+
+
+def _rate_limit_key(scope: Scope, api_keys: set[str]) -> str:
+    # Only configured keys get their own bucket, so rotating made-up tokens doesn't get around
+    # the per-IP limit
+    scheme, _, token = Headers(scope=scope).get('authorization', '').partition(' ')
+    if scheme.lower() == 'bearer' and token.strip() in api_keys:
+        return f'key:{token.strip()}'
+    client = scope.get('client')
+    return f'ip:{client[0] if client else "unknown"}'
+
+
+def _body_too_large(max_body_bytes: int) -> JSONResponse:
+    return JSONResponse(
+        content={'detail': f'Request body exceeds the limit of {max_body_bytes} bytes'},
+        status_code=413,
+    )
@@ -0,0 +1,45 @@
+import asyncio
+import logging
+from datetime import datetime, timedelta, timezone
+
+from graphiti_core.utils.maintenance.graph_data_operations import (  # type: ignore
+    get_episode_group_ids,
+)
+
+from graph_service.config import Settings
+from graph_service.zep_graphiti import create_graphiti
+
+logger = logging.getLogger(__name__)
+
+
+async def prune_expired_episodes(settings: Settings):
+    if settings.episode_retention_days is None:
+        return
+
+    older_than = datetime.now(timezone.utc) - timedelta(days=settings.episode_retention_days)
+    graphiti = create_graphiti(settings)
+    try:
+        for group_id in await get_episode_group_ids(graphiti.driver):
+            result = await graphiti.prune_episodes(
+                group_id, older_than, settings.episode_prune_orphaned_entities
+            )
+            if result.episode_uuids:
+                logger.info(
+                    f'Pruned {len(result.episode_uuids)} episodes and '
+                    f'{len(result.entity_uuids)} entities from group {group_id}'
+                )
+    finally:
+        await graphiti.close()
+
+
+async def run_episode_pruning(settings: Settings):
+    """Prunes expired episodes every episode_prune_interval_seconds until cancelled."""
+    while True:
+        try:
+            await prune_expired_episodes(settings)
+        except asyncio.CancelledError:
+            raise
+        except Exception as e:
+            # Keep pruning on later runs, e.g. once the database is reachable again
+            logger.error(f'Episode pruning failed: {e}')
+        await asyncio.sleep(settings.episode_prune_interval_seconds)
@@ -2,9 +2,13 @@ import asyncio
 from contextlib import asynccontextmanager
 from functools import partial
 
-from fastapi import APIRouter, FastAPI, status
+from fastapi import APIRouter, FastAPI, HTTPException, status
+from graphiti_core.errors import ImportConflictError  # type: ignore
 from graphiti_core.nodes import EpisodeType  # type: ignore
-from graphiti_core.utils.maintenance.graph_data_operations import clear_data  # type: ignore
+from graphiti_core.utils.maintenance.graph_data_operations import (  # type: ignore
+    ConflictPolicy,
+    GraphExport,
+)
 
 from graph_service.dto import AddEntityNodeRequest, AddMessagesRequest, Message, Result
 from graph_service.zep_graphiti import ZepGraphitiDep
@@ -62,6 +66,8 @@ async def add_messages(
             reference_time=m.timestamp,
             source=EpisodeType.message,
             source_description=m.source_description,
+            store_raw=m.store_raw,
+            model_override=request.model_override,
         )
 
     for m in request.messages:
@@ -102,10 +108,23 @@ async def delete_episode(uuid: str, graphiti: ZepGraphitiDep):
     return Result(message='Episode deleted', success=True)
 
 
+@router.post('/group/import', status_code=status.HTTP_201_CREATED)
+async def import_group(
+    export: GraphExport,
+    graphiti: ZepGraphitiDep,
+    conflict_policy: ConflictPolicy = ConflictPolicy.error,
+):
+    try:
+        await graphiti.import_group(export, conflict_policy)
+    except ImportConflictError as e:
+        raise HTTPException(status_code=409, detail=e.message) from e
+    return Result(message='Group imported', success=True)
+
+
 @router.post('/clear', status_code=status.HTTP_200_OK)
 async def clear(
     graphiti: ZepGraphitiDep,
 ):
-    await clear_data(graphiti.driver)
+    await graphiti.clear()
     await graphiti.build_indices_and_constraints()
     return Result(message='Graph cleared', success=True)
@@ -0,0 +1,34 @@
+from fastapi import APIRouter, status
+
+from graph_service.dto import (
+    MergeEntitiesRequest,
+    PruneEpisodesRequest,
+    Result,
+    WarmupEmbeddingsRequest,
+)
+from graph_service.zep_graphiti import ZepGraphitiDep
+
+router = APIRouter()
+
+
+@router.post('/maintenance/warmup-embeddings', status_code=status.HTTP_200_OK)
+async def warmup_embeddings(request: WarmupEmbeddingsRequest, graphiti: ZepGraphitiDep):
+    warmed = await graphiti.warmup_embeddings(request.queries)
+    return Result(message=f'Embedded {warmed} new queries', success=True)
+
+
+@router.post('/group/{group_id}/prune', status_code=status.HTTP_200_OK)
+async def prune_episodes(group_id: str, request: PruneEpisodesRequest, graphiti: ZepGraphitiDep):
+    return await graphiti.prune_episodes(
+        group_id, request.older_than, request.delete_orphaned_entities
+    )
+
+
+@router.post('/group/{group_id}/dedupe', status_code=status.HTTP_200_OK)
+async def run_dedup_maintenance(group_id: str, graphiti: ZepGraphitiDep):
+    return await graphiti.run_dedup_maintenance(group_id)
+
+
+@router.post('/entity-node/merge', status_code=status.HTTP_200_OK)
+async def merge_entities(request: MergeEntitiesRequest, graphiti: ZepGraphitiDep):
+    return await graphiti.merge_entities(request.keep_uuid, request.merge_uuid)
@@ -1,6 +1,10 @@
+from collections.abc import AsyncIterator
 from datetime import datetime, timezone
+from typing import Annotated
 
-from fastapi import APIRouter, status
+from fastapi import APIRouter, HTTPException, Query, status
+from fastapi.responses import StreamingResponse
+from graphiti_core.errors import CrossGroupSearchError, SearchTimeoutError  # type: ignore
 
 from graph_service.dto import (
     GetMemoryRequest,
@@ -13,26 +17,69 @@ from graph_service.zep_graphiti import ZepGraphitiDep, get_fact_result_from_edge
 
 router = APIRouter()
 
+MAX_SEARCH_STREAM_LIMIT = 1000
+
 
 @router.post('/search', status_code=status.HTTP_200_OK)
 async def search(query: SearchQuery, graphiti: ZepGraphitiDep):
-    relevant_edges = await graphiti.search(
-        group_ids=query.group_ids,
-        query=query.query,
-        num_results=query.max_facts,
-    )
+    try:
+        relevant_edges = await graphiti.search(
+            group_ids=query.group_ids,
+            query=query.query,
+            num_results=query.max_facts,
+            model_override=query.model_override,
+            timeout=query.timeout,
+        )
+    except CrossGroupSearchError as e:
+        raise HTTPException(status_code=400, detail=e.message) from e
+    except SearchTimeoutError as e:
+        raise HTTPException(status_code=504, detail=e.message) from e
     facts = [get_fact_result_from_edge(edge) for edge in relevant_edges]
     return SearchResults(
         facts=facts,
     )
 
 
+@router.get('/search/stream', status_code=status.HTTP_200_OK)
+async def search_stream(
+    query: str,
+    graphiti: ZepGraphitiDep,
+    group_ids: Annotated[list[str], Query(min_length=1)],
+    limit: Annotated[int, Query(ge=1, le=MAX_SEARCH_STREAM_LIMIT)] = 10,
+):
+    """
+    Returns matching facts as newline-delimited JSON, one fact per line in rank order, so clients
+    can parse the response a fact at a time. The search runs to completion before the first line
+    is sent, so this changes the framing of the response, not when results arrive.
+    """
+    try:
+        relevant_edges = await graphiti.search(group_ids=group_ids, query=query, num_results=limit)
+    except CrossGroupSearchError as e:
+        raise HTTPException(status_code=400, detail=e.message) from e
+    except SearchTimeoutError as e:
+        raise HTTPException(status_code=504, detail=e.message) from e
+
+    async def fact_lines() -> AsyncIterator[str]:
+        for edge in relevant_edges[:limit]:
+            yield get_fact_result_from_edge(edge).model_dump_json() + '\n'
+
+    return StreamingResponse(fact_lines(), media_type='application/x-ndjson')
+
+
 @router.get('/entity-edge/{uuid}', status_code=status.HTTP_200_OK)
 async def get_entity_edge(uuid: str, graphiti: ZepGraphitiDep):
     entity_edge = await graphiti.get_entity_edge(uuid)
     return get_fact_result_from_edge(entity_edge)
 
 
+@router.get('/facts', status_code=status.HTTP_200_OK)
+async def get_facts_between(
+    source: str, target: str, graphiti: ZepGraphitiDep, include_invalid: bool = False
+):
+    edges = await graphiti.get_facts_between(source, target, include_invalid)
+    return [get_fact_result_from_edge(edge) for edge in edges]
+
+
 @router.get('/episodes/{group_id}', status_code=status.HTTP_200_OK)
 async def get_episodes(group_id: str, last_n: int, graphiti: ZepGraphitiDep):
     episodes = await graphiti.retrieve_episodes(
@@ -41,6 +88,31 @@ async def get_episodes(group_id: str, last_n: int, graphiti: ZepGraphitiDep):
     return episodes
 
 
+@router.get('/entity-node/{uuid}/episodes', status_code=status.HTTP_200_OK)
+async def get_entity_episodes(uuid: str, graphiti: ZepGraphitiDep, limit: int = 20):
+    return await graphiti.get_entity_episodes(uuid, limit)
+
+
+@router.get('/group/{group_id}/communities', status_code=status.HTTP_200_OK)
+async def list_communities(group_id: str, graphiti: ZepGraphitiDep):
+    return await graphiti.list_communities(group_id)
+
+
+@router.get('/community/{uuid}/members', status_code=status.HTTP_200_OK)
+async def get_community_members(uuid: str, graphiti: ZepGraphitiDep):
+    return await graphiti.get_community_members(uuid)
+
+
+@router.get('/group/{group_id}/export', status_code=status.HTTP_200_OK)
+async def export_group(group_id: str, graphiti: ZepGraphitiDep, include_embeddings: bool = True):
+    return await graphiti.export_group(group_id, include_embeddings)
+
+
+@router.get('/group/{group_id}/stats', status_code=status.HTTP_200_OK)
+async def get_group_stats(group_id: str, graphiti: ZepGraphitiDep):
+    return await graphiti.group_stats(group_id)
+
+
 @router.post('/get-memory', status_code=status.HTTP_200_OK)
 async def get_memory(
     request: GetMemoryRequest,
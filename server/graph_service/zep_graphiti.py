@@ -4,19 +4,31 @@ from typing import Annotated
 from fastapi import Depends, HTTPException
 from graphiti_core import Graphiti  # type: ignore
 from graphiti_core.edges import EntityEdge  # type: ignore
+from graphiti_core.embedder import (  # type: ignore
+    CachedEmbedderClient,
+    EmbedderClient,
+    OpenAIEmbedder,
+)
 from graphiti_core.errors import EdgeNotFoundError, GroupsEdgesNotFoundError, NodeNotFoundError
 from graphiti_core.llm_client import LLMClient  # type: ignore
 from graphiti_core.nodes import EntityNode, EpisodicNode  # type: ignore
 
-from graph_service.config import ZepEnvDep
+from graph_service.config import Settings, ZepEnvDep
 from graph_service.dto import FactResult
 
 logger = logging.getLogger(__name__)
 
 
 class ZepGraphiti(Graphiti):
-    def __init__(self, uri: str, user: str, password: str, llm_client: LLMClient | None = None):
-        super().__init__(uri, user, password, llm_client)
+    def __init__(
+        self,
+        uri: str,
+        user: str,
+        password: str,
+        llm_client: LLMClient | None = None,
+        embedder: EmbedderClient | None = None,
+    ):
+        super().__init__(uri, user, password, llm_client, embedder)
 
     async def save_entity_node(self, name: str, uuid: str, group_id: str, summary: str = ''):
         new_node = EntityNode(
@@ -71,11 +83,23 @@ class ZepGraphiti(Graphiti):
             raise HTTPException(status_code=404, detail=e.message) from e
 
 
-async def get_graphiti(settings: ZepEnvDep):
+# Clients are created per request, so the embedding cache is shared at module level
+_cached_embedder: CachedEmbedderClient | None = None
+
+
+def get_cached_embedder() -> CachedEmbedderClient:
+    global _cached_embedder
+    if _cached_embedder is None:
+        _cached_embedder = CachedEmbedderClient(OpenAIEmbedder())
+    return _cached_embedder
+
+
+def create_graphiti(settings: Settings) -> ZepGraphiti:
     client = ZepGraphiti(
         uri=settings.neo4j_uri,
         user=settings.neo4j_user,
         password=settings.neo4j_password,
+        embedder=get_cached_embedder(),
     )
     if settings.openai_base_url is not None:
         client.llm_client.config.base_url = settings.openai_base_url
@@ -83,7 +107,11 @@ async def get_graphiti(settings: ZepEnvDep):
         client.llm_client.config.api_key = settings.openai_api_key
     if settings.model_name is not None:
         client.llm_client.model = settings.model_name
+    return client
+
 
+async def get_graphiti(settings: ZepEnvDep):
+    client = create_graphiti(settings)
     try:
         yield client
     finally:
@@ -91,11 +119,11 @@ async def get_graphiti(settings: ZepEnvDep):
 
 
 async def initialize_graphiti(settings: ZepEnvDep):
-    client = ZepGraphiti(
-        uri=settings.neo4j_uri,
-        user=settings.neo4j_user,
-        password=settings.neo4j_password,
-    )
+    # Configured like the per-request clients, so that the provider check covers what they use
+    client = create_graphiti(settings)
+    await client.wait_for_database(settings.neo4j_connect_retry_seconds)
+    if settings.check_providers_on_startup:
+        await client.check_providers()
     await client.build_indices_and_constraints()
 
 
@@ -0,0 +1,194 @@
+import asyncio
+from unittest.mock import AsyncMock, patch
+
+from fastapi import FastAPI, Request
+from fastapi.testclient import TestClient
+
+from graph_service.config import Settings
+from graph_service.middleware import (
+    ApiKeyAuthMiddleware,
+    RateLimitMiddleware,
+    RequestLimitsMiddleware,
+)
+
+
+def _settings(**kwargs) -> Settings:
+    return Settings(
+        openai_api_key='test',
+        neo4j_uri='bolt://localhost:7687',
+        neo4j_user='neo4j',
+        neo4j_password='password',
+        **kwargs,
+    )
+
+
+class FakeClock:
+    def __init__(self):
+        self.now = 0.0
+
+    def __call__(self) -> float:
+        return self.now
+
+
+def _client(settings: Settings, clock: FakeClock | None = None) -> TestClient:
+    app = FastAPI()
+    app.add_middleware(RequestLimitsMiddleware, settings=settings)
+    app.add_middleware(RateLimitMiddleware, settings=settings, clock=clock or FakeClock())
+    app.add_middleware(ApiKeyAuthMiddleware, settings=settings)
+
+    @app.get('/healthcheck')
+    async def healthcheck():
+        return {'status': 'healthy'}
+
+    @app.post('/echo')
+    async def echo(request: Request):
+        return {'size': len(await request.body())}
+
+    @app.get('/slow')
+    async def slow():
+        await asyncio.sleep(1)
+        return {'status': 'done'}
+
+    return TestClient(app)
+
+
+def test_body_within_limit_is_accepted():
+    client = _client(_settings(max_request_body_bytes=100))
+
+    response = client.post('/echo', content=b'x' * 100)
+
+    assert response.status_code == 200
+    assert response.json() == {'size': 100}
+
+
+def test_body_over_limit_is_rejected():
+    client = _client(_settings(max_request_body_bytes=100))
+
+    response = client.post('/echo', content=b'x' * 101)
+
+    assert response.status_code == 413
+
+
+def test_streamed_body_over_limit_is_rejected():
+    client = _client(_settings(max_request_body_bytes=100))
+
+    # A generator body is sent chunked, without a Content-Length header
+    response = client.post('/echo', content=(b'x' * 60 for _ in range(2)))
+
+    assert response.status_code == 413
+
+
+def test_slow_request_times_out():
+    client = _client(_settings(request_timeout_seconds=0.05))
+
+    response = client.get('/slow')
+
+    assert response.status_code == 408
+
+
+def test_limits_can_be_disabled():
+    client = _client(_settings(max_request_body_bytes=None, request_timeout_seconds=None))
+
+    assert client.post('/echo', content=b'x' * 1000).status_code == 200
+    assert client.get('/slow').status_code == 200
+
+
+def test_auth_is_disabled_without_keys():
+    client = _client(_settings())
+
+    assert client.post('/echo', content=b'x').status_code == 200
+
+
+def test_missing_api_key_is_rejected():
+    client = _client(_settings(api_keys='key-1,key-2'))
+
+    response = client.post('/echo', content=b'x')
+
+    assert response.status_code == 401
+    assert response.headers['www-authenticate'] == 'Bearer'
+
+
+def test_invalid_api_key_is_rejected():
+    client = _client(_settings(api_keys='key-1,key-2'))
+
+    assert client.post('/echo', headers={'Authorization': 'Bearer key-3'}).status_code == 401
+    # The key must be sent as a bearer token
+    assert client.post('/echo', headers={'Authorization': 'key-1'}).status_code == 401
+
+
+def test_valid_api_key_is_accepted():
+    client = _client(_settings(api_keys='key-1, key-2'))
+
+    response = client.post('/echo', content=b'x', headers={'Authorization': 'Bearer key-2'})
+
+    assert response.status_code == 200
+
+
+def test_healthcheck_does_not_require_api_key():
+    client = _client(_settings(api_keys='key-1'))
+
+    assert client.get('/healthcheck').status_code == 200
+
+
+def test_requests_past_the_rate_limit_get_429():
+    clock = FakeClock()
+    client = _client(_settings(rate_limit_per_minute=6, rate_limit_burst=3), clock)
+
+    statuses = [client.post('/echo').status_code for _ in range(5)]
+
+    assert statuses == [200, 200, 200, 429, 429]
+    # One request is allowed every 10 seconds
+    assert client.post('/echo').headers['retry-after'] == '10'
+
+    clock.now += 10
+    assert client.post('/echo').status_code == 200
+    assert client.post('/echo').status_code == 429
+
+
+def test_rate_limit_is_per_api_key():
+    client = _client(_settings(api_keys='key-1,key-2', rate_limit_per_minute=1))
+
+    key_1 = {'Authorization': 'Bearer key-1'}
+    key_2 = {'Authorization': 'Bearer key-2'}
+    assert client.post('/echo', headers=key_1).status_code == 200
+    assert client.post('/echo', headers=key_1).status_code == 429
+    assert client.post('/echo', headers=key_2).status_code == 200
+
+
+def test_healthcheck_is_not_rate_limited():
+    client = _client(_settings(rate_limit_per_minute=1))
+
+    assert [client.get('/healthcheck').status_code for _ in range(3)] == [200, 200, 200]
+
+
+def test_made_up_tokens_are_limited_by_ip_when_auth_is_disabled():
+    client = _client(_settings(rate_limit_per_minute=1))
+
+    statuses = [
+        client.post('/echo', headers={'Authorization': f'Bearer token-{i}'}).status_code
+        for i in range(3)
+    ]
+
+    assert statuses == [200, 429, 429]
+
+
+def test_least_recently_seen_clients_are_dropped_at_the_limit():
+    async def app(scope, receive, send):
+        pass
+
+    settings = _settings(rate_limit_per_minute=1)
+    middleware = RateLimitMiddleware(app, settings=settings, clock=FakeClock())
+
+    async def request(ip: str):
+        scope = {'type': 'http', 'path': '/echo', 'headers': [], 'client': (ip, 1234)}
+        await middleware(scope, AsyncMock(), AsyncMock())
+
+    async def requests():
+        for ip in ['10.0.0.1', '10.0.0.2', '10.0.0.1', '10.0.0.3']:
+            await request(ip)
+
+    with patch('graph_service.middleware.MAX_TRACKED_RATE_LIMIT_CLIENTS', 2):
+        asyncio.run(requests())
+
+    # 10.0.0.2 was seen least recently when 10.0.0.3 needed a bucket
+    assert list(middleware.buckets) == ['ip:10.0.0.1', 'ip:10.0.0.3']
@@ -0,0 +1,104 @@
+import json
+from datetime import datetime, timezone
+from unittest.mock import AsyncMock, MagicMock
+
+from fastapi import FastAPI
+from fastapi.testclient import TestClient
+
+from graph_service.routers import retrieve
+from graph_service.zep_graphiti import get_graphiti
+from graphiti_core.edges import EntityEdge
+from graphiti_core.nodes import CommunityNode, EntityNode
+
+
+def _edges(count: int) -> list[EntityEdge]:
+    return [
+        EntityEdge(
+            source_node_uuid='alice',
+            target_node_uuid='bob',
+            name='KNOWS',
+            fact=f'Alice knows Bob {i}',
+            group_id='group_1',
+            created_at=datetime.now(timezone.utc),
+        )
+        for i in range(count)
+    ]
+
+
+def _client(graphiti: MagicMock) -> TestClient:
+    app = FastAPI()
+    app.include_router(retrieve.router)
+    app.dependency_overrides[get_graphiti] = lambda: graphiti
+    return TestClient(app)
+
+
+def test_search_stream_yields_one_line_per_fact():
+    graphiti = MagicMock(search=AsyncMock(return_value=_edges(25)))
+
+    response = _client(graphiti).get(
+        '/search/stream', params={'query': 'Alice', 'group_ids': ['group_1'], 'limit': 25}
+    )
+
+    assert response.status_code == 200
+    assert response.headers['content-type'] == 'application/x-ndjson'
+    lines = response.text.splitlines()
+    assert len(lines) == 25
+    assert json.loads(lines[0])['fact'] == 'Alice knows Bob 0'
+    graphiti.search.assert_awaited_once_with(group_ids=['group_1'], query='Alice', num_results=25)
+
+
+def test_search_stream_caps_results_at_limit():
+    graphiti = MagicMock(search=AsyncMock(return_value=_edges(5)))
+
+    response = _client(graphiti).get(
+        '/search/stream', params={'query': 'Alice', 'group_ids': ['group_1'], 'limit': 3}
+    )
+
+    assert len(response.text.splitlines()) == 3
+
+
+def test_search_routes_require_group_ids():
+    graphiti = MagicMock(search=AsyncMock(return_value=[]))
+    client = _client(graphiti)
+
+    search_response = client.post('/search', json={'query': 'Alice'})
+    empty_response = client.post('/search', json={'query': 'Alice', 'group_ids': []})
+    stream_response = client.get('/search/stream', params={'query': 'Alice'})
+
+    assert search_response.status_code == 422
+    assert empty_response.status_code == 422
+    assert stream_response.status_code == 422
+    graphiti.search.assert_not_called()
+
+
+def test_search_stream_rejects_limits_over_the_maximum():
+    graphiti = MagicMock(search=AsyncMock(return_value=[]))
+
+    response = _client(graphiti).get(
+        '/search/stream',
+        params={
+            'query': 'Alice',
+            'group_ids': ['group_1'],
+            'limit': retrieve.MAX_SEARCH_STREAM_LIMIT + 1,
+        },
+    )
+
+    assert response.status_code == 422
+    graphiti.search.assert_not_called()
+
+
+def test_community_routes_return_communities_and_members():
+    community = CommunityNode(name='Friends', group_id='group_1', summary='Alice and Bob')
+    members = [EntityNode(name=name, group_id='group_1') for name in ['Alice', 'Bob']]
+    graphiti = MagicMock(
+        list_communities=AsyncMock(return_value=[community]),
+        get_community_members=AsyncMock(return_value=members),
+    )
+    client = _client(graphiti)
+
+    communities = client.get('/group/group_1/communities').json()
+    members_response = client.get(f'/community/{community.uuid}/members').json()
+
+    assert [c['uuid'] for c in communities] == [community.uuid]
+    assert [node['name'] for node in members_response] == ['Alice', 'Bob']
+    graphiti.get_community_members.assert_awaited_once_with(community.uuid)
@@ -0,0 +1,45 @@
+from collections.abc import Callable
+from typing import Any
+from unittest.mock import AsyncMock, MagicMock
+
+import pytest
+
+from graphiti_core.driver.driver import GraphDriver
+from graphiti_core.graphiti import Graphiti
+from graphiti_core.testing import MockCrossEncoderClient, MockEmbedderClient, MockLLMClient
+
+
+@pytest.fixture
+def mock_driver() -> MagicMock:
+    """A graph driver whose queries return no records and whose sessions accept any write."""
+    driver = MagicMock(spec=GraphDriver)
+    driver.provider = 'neo4j'
+    driver.execute_query = AsyncMock(return_value=([], None, None))
+    driver.close = AsyncMock()
+    session = MagicMock()
+    session.execute_write = AsyncMock()
+    session.close = AsyncMock()
+    session.__aenter__.return_value = session
+    driver.session.return_value = session
+    return driver
+
+
+@pytest.fixture
+def make_graphiti(mock_driver: MagicMock) -> Callable[..., Graphiti]:
+    """
+    Builds a Graphiti over mock_driver and the mock clients from graphiti_core.testing. Any
+    Graphiti argument, including the driver and clients, can be overridden.
+    """
+
+    def make(**kwargs: Any) -> Graphiti:
+        return Graphiti(
+            **{
+                'graph_driver': mock_driver,
+                'llm_client': MockLLMClient(),
+                'embedder': MockEmbedderClient(),
+                'cross_encoder': MockCrossEncoderClient(),
+                **kwargs,
+            }
+        )
+
+    return make
@@ -0,0 +1,127 @@
+"""
+Copyright 2024, Zep Software, Inc.
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+"""
+
+# Running tests: pytest -xvs tests/cross_encoder/test_openai_reranker_client.py
+
+import asyncio
+import json
+import math
+from types import SimpleNamespace
+from unittest.mock import AsyncMock, MagicMock
+
+import pytest
+
+from graphiti_core.cross_encoder.client import CrossEncoderClient
+from graphiti_core.cross_encoder.openai_reranker_client import OpenAIRerankerClient
+
+
+def _completion(content: dict) -> SimpleNamespace:
+    return SimpleNamespace(
+        choices=[SimpleNamespace(message=SimpleNamespace(content=json.dumps(content)))]
+    )
+
+
+@pytest.mark.asyncio
+async def test_rank_batch_scores_all_passages_in_one_request():
+    openai_client = MagicMock()
+    openai_client.chat.completions.create = AsyncMock(
+        return_value=_completion(
+            {'scores': [{'id': 2, 'score': 0.9}, {'id': 0, 'score': 0.1}, {'id': 1, 'score': 0.5}]}
+        )
+    )
+    reranker = OpenAIRerankerClient(client=openai_client, batch_scoring=True)
+    passages = ['cats', 'dogs', 'dogs and cats']
+
+    results = await reranker.rank_batch('dogs', passages)
+
+    openai_client.chat.completions.create.assert_awaited_once()
+    prompt = openai_client.chat.completions.create.await_args.kwargs['messages'][1]['content']
+    assert all(passage in prompt for passage in passages)
+    # Indices refer to the input order, sorted by score
+    assert results == [(2, 0.9), (1, 0.5), (0, 0.1)]
+
+
+@pytest.mark.asyncio
+async def test_rank_batch_ranks_unscored_passages_last():
+    openai_client = MagicMock()
+    openai_client.chat.completions.create = AsyncMock(
+        return_value=_completion({'scores': [{'id': 1, 'score': 1.5}, {'id': 7, 'score': 0.8}]})
+    )
+    reranker = OpenAIRerankerClient(client=openai_client, batch_scoring=True)
+
+    results = await reranker.rank_batch('query', ['a', 'b'])
+
+    assert results == [(1, 1.0), (0, 0.0)]
+
+
+@pytest.mark.asyncio
+async def test_rank_batch_uses_rank_unless_batch_scoring_is_enabled():
+    openai_client = MagicMock()
+    openai_client.chat.completions.create = AsyncMock()
+    reranker = OpenAIRerankerClient(client=openai_client)
+    reranker.rank = AsyncMock(return_value=[('b', 0.9), ('a', 0.2)])
+
+    results = await reranker.rank_batch('query', ['a', 'b'])
+
+    reranker.rank.assert_awaited_once_with('query', ['a', 'b'])
+    openai_client.chat.completions.create.assert_not_called()
+    assert results == [(1, 0.9), (0, 0.2)]
+
+
+@pytest.mark.asyncio
+async def test_rank_keeps_scores_aligned_when_responses_complete_out_of_order():
+    relevance = {'first': 0.2, 'second': 0.9, 'third': 0.6, 'fourth': 0.4}
+    in_flight = 0
+    max_in_flight = 0
+
+    async def create(messages, **kwargs):
+        nonlocal in_flight, max_in_flight
+        passage = next(p for p in relevance if p in messages[1].content)
+        in_flight += 1
+        max_in_flight = max(max_in_flight, in_flight)
+        # Earlier passages take longer, so responses complete in reverse order
+        await asyncio.sleep(0.01 * (len(relevance) - list(relevance).index(passage)))
+        in_flight -= 1
+        logprob = SimpleNamespace(token='True', logprob=math.log(relevance[passage]))
+        content = [SimpleNamespace(top_logprobs=[logprob])]
+        return SimpleNamespace(choices=[SimpleNamespace(logprobs=SimpleNamespace(content=content))])
+
+    openai_client = MagicMock()
+    openai_client.chat.completions.create = AsyncMock(side_effect=create)
+    reranker = OpenAIRerankerClient(client=openai_client, max_concurrency=2)
+
+    results = await reranker.rank('query', list(relevance))
+
+    assert [passage for passage, _ in results] == ['second', 'third', 'fourth', 'first']
+    assert [score for _, score in results] == pytest.approx([0.9, 0.6, 0.4, 0.2])
+    assert max_in_flight == 2
+
+
+def test_max_concurrency_must_be_positive():
+    with pytest.raises(ValueError):
+        OpenAIRerankerClient(client=MagicMock(), max_concurrency=0)
+
+
+class PassageLengthCrossEncoder(CrossEncoderClient):
+    async def rank(self, query: str, passages: list[str]) -> list[tuple[str, float]]:
+        return sorted(((p, float(len(p))) for p in passages), key=lambda x: x[1], reverse=True)
+
+
+@pytest.mark.asyncio
+async def test_default_rank_batch_maps_scores_to_input_order():
+    results = await PassageLengthCrossEncoder().rank_batch('query', ['ab', 'abc', 'ab', 'a'])
+
+    assert results == [(1, 3.0), (0, 2.0), (2, 2.0), (3, 1.0)]
@@ -0,0 +1,47 @@
+from unittest.mock import AsyncMock, MagicMock
+
+import pytest
+
+from graphiti_core.driver.driver import GraphDriver, execute_upsert
+from graphiti_core.errors import DatabaseConflictError
+from graphiti_core.utils.maintenance.graph_data_operations import build_indices_and_constraints
+
+
+@pytest.mark.asyncio
+async def test_execute_upsert_retries_once_on_conflict():
+    driver = MagicMock(spec=GraphDriver)
+    driver.execute_query = AsyncMock(
+        side_effect=[DatabaseConflictError('already exists'), ([], None, None)]
+    )
+
+    result = await execute_upsert(driver, 'MERGE (n:Entity {uuid: $uuid})', uuid='node')
+
+    assert result == ([], None, None)
+    assert driver.execute_query.await_count == 2
+
+
+@pytest.mark.asyncio
+async def test_execute_upsert_raises_repeated_conflict():
+    driver = MagicMock(spec=GraphDriver)
+    driver.execute_query = AsyncMock(side_effect=DatabaseConflictError('already exists'))
+
+    with pytest.raises(DatabaseConflictError):
+        await execute_upsert(driver, 'MERGE (n:Entity {uuid: $uuid})', uuid='node')
+
+
+@pytest.mark.asyncio
+async def test_build_indices_creates_node_uuid_constraints_before_indices():
+    driver = MagicMock(spec=GraphDriver)
+    driver.provider = 'neo4j'
+    driver.execute_query = AsyncMock(return_value=([], None, None))
+
+    await build_indices_and_constraints(driver)
+
+    queries = [call.args[0] for call in driver.execute_query.await_args_list]
+    constraints = [query for query in queries if query.startswith('CREATE CONSTRAINT')]
+    assert len(constraints) == 3
+    for label in ['Entity', 'Episodic', 'Community']:
+        assert any(f'(n:{label}) REQUIRE n.uuid IS UNIQUE' in query for query in constraints)
+    assert queries.index(constraints[-1]) < queries.index(
+        next(query for query in queries if query.startswith('CREATE INDEX'))
+    )
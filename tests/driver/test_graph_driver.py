@@ -0,0 +1,60 @@
+from unittest.mock import AsyncMock, MagicMock
+
+import pytest
+
+from graphiti_core.driver.driver import GraphDriver
+from graphiti_core.driver.neo4j_driver import Neo4jDriver
+from graphiti_core.search.search_utils import node_distance_reranker
+
+
+class QueryOnlyDriver(GraphDriver):
+    provider = 'test'
+
+    def __init__(self):
+        self.queries: list[str] = []
+
+    async def execute_query(self, cypher_query_: str, **kwargs):
+        self.queries.append(cypher_query_)
+        return [], [], None
+
+    def session(self, database: str):
+        raise NotImplementedError()
+
+    def close(self):
+        raise NotImplementedError()
+
+    def delete_all_indexes(self, database_: str = ''):
+        raise NotImplementedError()
+
+
+@pytest.mark.asyncio
+async def test_verify_connectivity_runs_a_query_by_default():
+    driver = QueryOnlyDriver()
+
+    await driver.verify_connectivity()
+
+    assert driver.queries == ['RETURN 1']
+
+
+@pytest.mark.asyncio
+async def test_neo4j_verify_connectivity_uses_native_check():
+    driver = Neo4jDriver.__new__(Neo4jDriver)
+    driver.client = MagicMock(verify_connectivity=AsyncMock())
+
+    await driver.verify_connectivity()
+
+    driver.client.verify_connectivity.assert_awaited_once()
+
+
+@pytest.mark.parametrize('provider', ['neo4j', 'falkordb'])
+@pytest.mark.asyncio
+async def test_node_distance_reranker_is_backend_agnostic(provider: str):
+    driver = MagicMock(spec=GraphDriver)
+    driver.provider = provider
+    driver.execute_query = AsyncMock(
+        return_value=([{'score': 1, 'uuid': 'near'}], ['score', 'uuid'], None)
+    )
+
+    reranked = await node_distance_reranker(driver, ['far', 'near', 'center'], 'center')
+
+    assert reranked == ['center', 'near', 'far']
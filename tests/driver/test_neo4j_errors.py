@@ -0,0 +1,73 @@
+from unittest.mock import AsyncMock, MagicMock
+
+import pytest
+from neo4j.exceptions import ClientError, ConstraintError, TransientError
+
+from graphiti_core.driver.neo4j_driver import Neo4jDriver, transient_database_error
+from graphiti_core.errors import (
+    DatabaseConflictError,
+    DatabasePoolExhaustedError,
+    DatabaseTimeoutError,
+    TransientDatabaseError,
+)
+
+
+def _neo4j_error(error_type: type, code: str | None, message: str):
+    return MagicMock(spec=error_type, code=code, message=message)
+
+
+def _driver(error: Exception) -> Neo4jDriver:
+    driver = Neo4jDriver('bolt://localhost:7687', 'neo4j', 'password')
+    driver.client = MagicMock(execute_query=AsyncMock(side_effect=error))
+    return driver
+
+
+@pytest.mark.parametrize(
+    'code',
+    [
+        'Neo.ClientError.Transaction.TransactionTimedOut',
+        'Neo.ClientError.Transaction.TransactionTimedOutClientConfiguration',
+    ],
+)
+def test_transaction_timeout_maps_to_timeout_error(code: str):
+    error = transient_database_error(_neo4j_error(ClientError, code, 'transaction timed out'))
+
+    assert isinstance(error, DatabaseTimeoutError)
+    assert isinstance(error, TransientDatabaseError)
+    assert error.message == 'transaction timed out'
+
+
+def test_pool_acquisition_failure_maps_to_pool_exhausted_error():
+    message = 'failed to obtain a connection from the pool within 60.0s (timeout)'
+    error = transient_database_error(_neo4j_error(ClientError, None, message))
+
+    assert isinstance(error, DatabasePoolExhaustedError)
+    assert isinstance(error, TransientDatabaseError)
+
+
+def test_other_errors_are_not_transient():
+    syntax_error = _neo4j_error(ClientError, 'Neo.ClientError.Statement.SyntaxError', 'bad query')
+    deadlock = _neo4j_error(
+        TransientError, 'Neo.TransientError.Transaction.DeadlockDetected', 'deadlock'
+    )
+
+    assert transient_database_error(syntax_error) is None
+    assert transient_database_error(deadlock) is None
+
+
+@pytest.mark.asyncio
+async def test_execute_query_raises_pool_exhausted_error():
+    driver = _driver(ClientError('failed to obtain a connection from the pool within 60.0s'))
+
+    with pytest.raises(DatabasePoolExhaustedError) as exc_info:
+        await driver.execute_query('RETURN 1')
+
+    assert isinstance(exc_info.value.__cause__, ClientError)
+
+
+@pytest.mark.asyncio
+async def test_execute_query_keeps_conflict_and_other_errors():
+    with pytest.raises(DatabaseConflictError):
+        await _driver(ConstraintError('already exists')).execute_query('RETURN 1')
+    with pytest.raises(ClientError):
+        await _driver(ClientError('bad query')).execute_query('RETURN 1')
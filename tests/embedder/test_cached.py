@@ -0,0 +1,102 @@
+"""
+Copyright 2024, Zep Software, Inc.
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+"""
+
+from collections.abc import Iterable
+
+import pytest
+
+from graphiti_core.embedder.cached import CachedEmbedderClient
+from graphiti_core.embedder.client import EmbedderClient
+from tests.embedder.embedder_fixtures import create_embedding_values
+
+
+class CountingEmbedder(EmbedderClient):
+    """Embedder that records how many texts it was asked to embed."""
+
+    def __init__(self):
+        self.create_calls = 0
+        self.batch_calls = 0
+        self.embedded_texts: list[str] = []
+
+    async def create(
+        self, input_data: str | list[str] | Iterable[int] | Iterable[Iterable[int]]
+    ) -> list[float]:
+        self.create_calls += 1
+        return create_embedding_values(0.1, 8)
+
+    async def create_batch(self, input_data_list: list[str]) -> list[list[float]]:
+        self.batch_calls += 1
+        self.embedded_texts.extend(input_data_list)
+        return [create_embedding_values(0.1 * (i + 1), 8) for i in range(len(input_data_list))]
+
+
+@pytest.mark.asyncio
+async def test_warmup_makes_queries_cache_hits():
+    inner = CountingEmbedder()
+    embedder = CachedEmbedderClient(inner)
+
+    warmed = await embedder.warmup(['what is graphiti?', 'how do I add an episode?'])
+    assert warmed == 2
+    assert inner.batch_calls == 1
+
+    await embedder.create(input_data=['what is graphiti?'])
+    await embedder.create(input_data='how do I add an episode?')
+
+    assert inner.create_calls == 0
+    assert inner.batch_calls == 1
+
+
+@pytest.mark.asyncio
+async def test_warmup_skips_cached_texts():
+    inner = CountingEmbedder()
+    embedder = CachedEmbedderClient(inner)
+
+    await embedder.warmup(['a', 'b'])
+    warmed = await embedder.warmup(['a', 'b', 'c', 'c'])
+
+    assert warmed == 1
+    assert inner.embedded_texts == ['a', 'b', 'c']
+
+
+@pytest.mark.asyncio
+async def test_create_batch_preserves_order_with_partial_cache():
+    inner = CountingEmbedder()
+    embedder = CachedEmbedderClient(inner)
+
+    await embedder.warmup(['b'])
+    cached_b = await embedder.create('b')
+    embeddings = await embedder.create_batch(['a', 'b', 'c'])
+
+    assert embeddings[1] == cached_b
+    assert inner.embedded_texts == ['b', 'a', 'c']
+
+
+@pytest.mark.asyncio
+async def test_cache_evicts_least_recently_used():
+    inner = CountingEmbedder()
+    embedder = CachedEmbedderClient(inner, max_size=2)
+
+    await embedder.create('a')
+    await embedder.create('b')
+    await embedder.create('a')
+    await embedder.create('c')
+    assert inner.create_calls == 3
+
+    await embedder.create('a')
+    assert inner.create_calls == 3
+
+    await embedder.create('b')
+    assert inner.create_calls == 4
@@ -14,20 +14,34 @@ See the License for the specific language governing permissions and
 limitations under the License.
 """
 
+import math
 from collections.abc import Generator
+from datetime import datetime, timezone
 from typing import Any
 from unittest.mock import AsyncMock, MagicMock, patch
 
+import httpx
+import openai
 import pytest
 
+from graphiti_core.embedder.client import EmptyInputPolicy
 from graphiti_core.embedder.openai import (
     DEFAULT_EMBEDDING_MODEL,
     OpenAIEmbedder,
     OpenAIEmbedderConfig,
+    TruncationStrategy,
 )
+from graphiti_core.errors import EmptyEmbeddingInputError
+from graphiti_core.helpers import cosine_similarity
+from graphiti_core.nodes import EpisodeType, EpisodicNode
 from tests.embedder.embedder_fixtures import create_embedding_values
 
 
+def _words(count: int) -> str:
+    # Each word is one token
+    return ' '.join(['word'] * count)
+
+
 def create_openai_embedding(multiplier: float = 0.1) -> MagicMock:
     """Create a mock OpenAI embedding with specified value multiplier."""
     mock_embedding = MagicMock()
@@ -122,5 +136,216 @@ async def test_create_batch_processes_multiple_inputs(
     ]
 
 
+def test_timeouts_and_shared_http_client_are_applied() -> None:
+    http_client = httpx.AsyncClient()
+    config = OpenAIEmbedderConfig(api_key='test_api_key', timeout=30.0, connect_timeout=2.0)
+
+    embedder = OpenAIEmbedder(config=config, http_client=http_client)
+
+    assert embedder.client.timeout == httpx.Timeout(30.0, connect=2.0)
+    assert embedder.client._client is http_client
+
+
+@pytest.mark.asyncio
+async def test_create_batch_skips_empty_inputs(
+    openai_embedder: OpenAIEmbedder, mock_openai_client: Any
+) -> None:
+    mock_result = MagicMock()
+    mock_result.data = [create_openai_embedding(0.1), create_openai_embedding(0.2)]
+    mock_openai_client.embeddings.create.return_value = mock_result
+    openai_embedder.config.empty_input_policy = EmptyInputPolicy.zero_vector
+    dim = openai_embedder.config.embedding_dim
+
+    result = await openai_embedder.create_batch(['Input 1', '', 'Input 2', '   '])
+
+    # Empty inputs don't consume API quota and the output stays aligned with the input
+    _, kwargs = mock_openai_client.embeddings.create.call_args
+    assert kwargs['input'] == ['Input 1', 'Input 2']
+    assert result == [
+        mock_result.data[0].embedding[:dim],
+        [0.0] * dim,
+        mock_result.data[1].embedding[:dim],
+        [0.0] * dim,
+    ]
+
+
+@pytest.mark.asyncio
+async def test_empty_input_raises_by_default(
+    openai_embedder: OpenAIEmbedder, mock_openai_client: Any
+) -> None:
+    with pytest.raises(EmptyEmbeddingInputError):
+        await openai_embedder.create('  ')
+    with pytest.raises(EmptyEmbeddingInputError):
+        await openai_embedder.create(['  '])
+    with pytest.raises(EmptyEmbeddingInputError):
+        await openai_embedder.create_batch(['Input 1', ''])
+
+    mock_openai_client.embeddings.create.assert_not_called()
+
+
+@pytest.mark.asyncio
+async def test_empty_single_text_list_embeds_to_a_zero_vector(
+    openai_embedder: OpenAIEmbedder, mock_openai_client: Any
+) -> None:
+    openai_embedder.config.empty_input_policy = EmptyInputPolicy.zero_vector
+
+    result = await openai_embedder.create([''])
+
+    assert result == [0.0] * openai_embedder.config.embedding_dim
+    mock_openai_client.embeddings.create.assert_not_called()
+
+
+@pytest.mark.asyncio
+async def test_over_length_input_is_truncated_to_the_limit(
+    openai_embedder: OpenAIEmbedder, mock_openai_client: Any, mock_openai_response: MagicMock
+) -> None:
+    mock_openai_client.embeddings.create.return_value = mock_openai_response
+    openai_embedder.config.max_input_tokens = 10
+
+    result = await openai_embedder.create(_words(50))
+
+    _, kwargs = mock_openai_client.embeddings.create.call_args
+    assert kwargs['input'] == _words(10)
+    assert len(result) == openai_embedder.config.embedding_dim
+
+
+@pytest.mark.asyncio
+async def test_over_length_single_text_list_is_truncated_to_the_limit(
+    openai_embedder: OpenAIEmbedder, mock_openai_client: Any, mock_openai_response: MagicMock
+) -> None:
+    mock_openai_client.embeddings.create.return_value = mock_openai_response
+    openai_embedder.config.max_input_tokens = 10
+    episode = EpisodicNode(
+        name='episode',
+        group_id='group',
+        source=EpisodeType.text,
+        source_description='test',
+        content=_words(50),
+        valid_at=datetime.now(timezone.utc),
+    )
+
+    # Episodes are embedded as [content]
+    await episode.generate_content_embedding(openai_embedder)
+
+    _, kwargs = mock_openai_client.embeddings.create.call_args
+    assert kwargs['input'] == _words(10)
+    assert len(episode.content_embedding or []) == openai_embedder.config.embedding_dim
+
+
+@pytest.mark.asyncio
+async def test_over_length_input_is_chunked_and_mean_pooled(
+    openai_embedder: OpenAIEmbedder, mock_openai_client: Any
+) -> None:
+    mock_result = MagicMock()
+    mock_result.data = [
+        create_openai_embedding(0.1),
+        create_openai_embedding(0.4),
+        create_openai_embedding(0.5),
+        create_openai_embedding(0.6),
+    ]
+    mock_openai_client.embeddings.create.return_value = mock_result
+    openai_embedder.config.max_input_tokens = 10
+    openai_embedder.config.truncation_strategy = TruncationStrategy.chunk_mean_pool
+    dim = openai_embedder.config.embedding_dim
+    first_chunk, second_chunk = _words(10), ' ' + _words(5)
+
+    # The first text is split into a full chunk and a half-length one
+    result = await openai_embedder.create_batch([_words(15), 'a', 'b'])
+
+    _, kwargs = mock_openai_client.embeddings.create.call_args
+    assert kwargs['input'] == [first_chunk, second_chunk, 'a', 'b']
+    assert len(result) == 3
+    assert all(len(embedding) == dim for embedding in result)
+    # Weighted by chunk length in characters
+    pooled = (len(first_chunk) * 0.1 + len(second_chunk) * 0.4) / len(_words(15))
+    assert result[0] == pytest.approx([pooled] * dim)
+    assert result[1] == mock_result.data[2].embedding[:dim]
+    assert result[2] == mock_result.data[3].embedding[:dim]
+
+
+def _embedding_response(*vectors: list[float]) -> MagicMock:
+    mock_result = MagicMock()
+    mock_result.data = [MagicMock(embedding=vector) for vector in vectors]
+    return mock_result
+
+
+@pytest.mark.asyncio
+async def test_normalized_embeddings_have_unit_norm(
+    openai_embedder: OpenAIEmbedder, mock_openai_client: Any, mock_openai_response: MagicMock
+) -> None:
+    mock_openai_client.embeddings.create.return_value = mock_openai_response
+    openai_embedder.config.normalize = True
+
+    result = await openai_embedder.create('Test input')
+
+    assert math.sqrt(sum(value * value for value in result)) == pytest.approx(1.0)
+
+
+@pytest.mark.asyncio
+async def test_normalized_dot_product_ranks_like_cosine_similarity(mock_openai_client: Any) -> None:
+    openai_embedder = OpenAIEmbedder(
+        config=OpenAIEmbedderConfig(embedding_dim=2, normalize=True), client=mock_openai_client
+    )
+    # The long vector points away from the query, so it only wins on unnormalized dot product
+    mock_openai_client.embeddings.create.side_effect = [
+        _embedding_response([10.0, 0.0], [1.0, 1.2]),
+        _embedding_response([1.0, 1.0]),
+    ]
+
+    stored = await openai_embedder.create_batch(['far', 'near'])
+    query = await openai_embedder.create('query')
+
+    by_dot_product = sorted(
+        range(len(stored)), key=lambda i: -sum(q * s for q, s in zip(query, stored[i]))
+    )
+    by_cosine = sorted(range(len(stored)), key=lambda i: -cosine_similarity(query, stored[i]))
+    assert by_dot_product == by_cosine == [1, 0]
+
+
+
+def _embed_by_first_letter(input: list[str], model: str) -> MagicMock:
+    return _embedding_response(*[[float(ord(text[0]))] * 2 for text in input])
+
+
+def _split_embedder(mock_openai_client: Any) -> OpenAIEmbedder:
+    # Each text below is 5 tokens, so a request fits two of them
+    config = OpenAIEmbedderConfig(embedding_dim=2, max_request_tokens=10, batch_retry_delay=0)
+    return OpenAIEmbedder(config=config, client=mock_openai_client)
+
+
+@pytest.mark.asyncio
+async def test_batch_over_the_request_limit_is_split_and_reassembled_in_order(
+    mock_openai_client: Any,
+) -> None:
+    mock_openai_client.embeddings.create.side_effect = _embed_by_first_letter
+    texts = [' '.join([letter] * 5) for letter in 'abcde']
+
+    result = await _split_embedder(mock_openai_client).create_batch(texts)
+
+    calls = mock_openai_client.embeddings.create.call_args_list
+    requests = [call.kwargs['input'] for call in calls]
+    assert requests == [texts[0:2], texts[2:4], texts[4:5]]
+    assert result == [[float(ord(letter))] * 2 for letter in 'abcde']
+
+
+@pytest.mark.asyncio
+async def test_only_the_failed_request_is_retried(mock_openai_client: Any) -> None:
+    failures = [openai.APIConnectionError(request=httpx.Request('POST', 'https://example.com'))]
+
+    async def fail_once_on_c(input: list[str], model: str) -> MagicMock:
+        if input[0].startswith('c') and failures:
+            raise failures.pop()
+        return _embed_by_first_letter(input, model)
+
+    mock_openai_client.embeddings.create.side_effect = fail_once_on_c
+    texts = [' '.join([letter] * 5) for letter in 'abcde']
+
+    result = await _split_embedder(mock_openai_client).create_batch(texts)
+
+    calls = mock_openai_client.embeddings.create.call_args_list
+    requests = [call.kwargs['input'] for call in calls]
+    assert sorted(requests) == [texts[0:2], texts[2:4], texts[2:4], texts[4:5]]
+    assert result == [[float(ord(letter))] * 2 for letter in 'abcde']
+
 if __name__ == '__main__':
     pytest.main(['-xvs', __file__])
@@ -14,9 +14,26 @@ See the License for the specific language governing permissions and
 limitations under the License.
 """
 
+import math
+from unittest.mock import patch
+
 import pytest
 
-from graphiti_core.helpers import lucene_sanitize
+from graphiti_core.helpers import (
+    CHARS_PER_TOKEN,
+    StringSimilarity,
+    _encoding_for_model,
+    chunk_text,
+    cosine_similarity,
+    count_tokens,
+    detect_script,
+    lucene_sanitize,
+    normalize_name,
+    split_tokens,
+    string_similarity,
+    truncate_words,
+)
+from graphiti_core.search.search_utils import maximal_marginal_relevance
 
 
 def test_lucene_sanitize():
@@ -34,5 +51,109 @@ def test_lucene_sanitize():
         assert assert_result == result
 
 
+def test_normalize_name():
+    assert normalize_name('  OpenAI ') == 'openai'
+    assert normalize_name('Open   AI') == 'open ai'
+    assert normalize_name('Open AI', strip_punctuation=True) == 'openai'
+    assert normalize_name('Open-AI, Inc.', strip_punctuation=True) == 'openaiinc'
+
+
+def test_string_similarity():
+    jaro_winkler = StringSimilarity.jaro_winkler
+    assert string_similarity('martha', 'marhta', jaro_winkler) == pytest.approx(0.961, abs=1e-3)
+    assert string_similarity('microsoft', 'micorsoft', jaro_winkler) > 0.95
+    assert string_similarity('apple', 'microsoft', jaro_winkler) == 0.0
+    assert string_similarity('kitten', 'sitting', StringSimilarity.levenshtein) == 1 - 3 / 7
+    assert string_similarity('same', 'same', StringSimilarity.levenshtein) == 1.0
+    assert string_similarity('', 'abc', StringSimilarity.levenshtein) == 0.0
+
+
+def test_chunk_text():
+    text = ' '.join(f'word{i}' for i in range(300))
+
+    chunks = chunk_text(text, max_chars=200, overlap=50)
+
+    assert len(chunks) > 1
+    assert all(len(chunk) <= 200 for chunk in chunks)
+    assert chunks[0].startswith('word0 ')
+    assert chunks[-1].endswith(' word299')
+    # Consecutive chunks overlap and start on a word boundary
+    assert chunks[1].split()[0] in chunks[0]
+    assert chunks[1].startswith('word')
+    assert chunk_text('short', max_chars=200, overlap=50) == ['short']
+
+
+def test_truncate_words():
+    assert truncate_words('one two,  three four', 2) == 'one two...'
+    assert truncate_words('one two three', 3) == 'one two three'
+    assert truncate_words('one two three', None) == 'one two three'
+    assert len(truncate_words(' '.join(['word'] * 2000), 250).split()) == 250
+
+
+def test_count_tokens():
+    assert count_tokens('tiktoken is great!', 'gpt-4') == 6
+    assert count_tokens('Hello, world!', 'gpt-4o') == 4
+    assert count_tokens('') == 0
+    # Models tiktoken doesn't know are counted with the default encoding
+    assert count_tokens('tiktoken is great!', 'claude-sonnet') == count_tokens('tiktoken is great!')
+
+
+def test_split_tokens():
+    text = ' '.join(['word'] * 25)
+
+    pieces = split_tokens(text, 10, 'gpt-4')
+
+    assert [count_tokens(piece, 'gpt-4') for piece in pieces] == [10, 10, 5]
+    assert ''.join(pieces) == text
+
+
+def test_token_counts_are_estimated_when_the_encoding_cannot_be_loaded():
+    _encoding_for_model.cache_clear()
+    try:
+        with patch(
+            'graphiti_core.helpers.tiktoken.get_encoding', side_effect=OSError('offline')
+        ):
+            assert count_tokens('x' * 10, 'unknown-model') == math.ceil(10 / CHARS_PER_TOKEN)
+            assert split_tokens('x' * 10, 1, 'unknown-model') == ['x' * CHARS_PER_TOKEN] * 2 + [
+                'x' * (10 - 2 * CHARS_PER_TOKEN)
+            ]
+    finally:
+        _encoding_for_model.cache_clear()
+
+
+def test_detect_script():
+    assert detect_script('Мария переехала в Москву.') == 'Cyrillic'
+    assert detect_script('我住在北京') == 'Han'
+    assert detect_script('서울에 살아요') == 'Hangul'
+    # Latin-script text, including accented letters, gets no hint
+    assert detect_script('Zoë moved to Zürich.') is None
+    assert detect_script('123 !?') is None
+
+
+def test_cosine_similarity_with_non_finite_values():
+    query = [1.0, 0.0]
+    candidates = {
+        'close': [0.9, 0.1],
+        'corrupt': [float('nan'), 1.0],
+        'far': [-1.0, 0.0],
+        'infinite': [float('inf'), 0.0],
+    }
+
+    scores = {uuid: cosine_similarity(query, vector) for uuid, vector in candidates.items()}
+
+    assert all(math.isfinite(score) for score in scores.values())
+    assert scores['corrupt'] == 0.0
+    assert scores['infinite'] == 0.0
+    ranked = sorted(scores, key=lambda uuid: scores[uuid], reverse=True)
+    assert ranked[0] == 'close'
+    assert ranked[-1] == 'far'
+
+
+def test_mmr_skips_non_finite_embeddings():
+    candidates = {'a': [1.0, 0.0], 'b': [float('nan'), 0.0], 'c': [0.0, 1.0]}
+
+    assert maximal_marginal_relevance([1.0, 0.0], candidates) == ['a', 'c']
+
+
 if __name__ == '__main__':
     pytest.main([__file__])
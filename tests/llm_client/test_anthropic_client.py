@@ -250,6 +250,25 @@ class TestAnthropicClientGenerateResponse:
         assert mock_async_anthropic.messages.create.call_count == 2
         assert result['test_field'] == 'correct_value'
 
+    @pytest.mark.asyncio
+    async def test_auto_max_tokens_caps_to_remaining_context(self, mock_async_anthropic):
+        """Test that max_tokens is capped at the room the prompt leaves in the context window."""
+        config = LLMConfig(api_key='test_api_key', auto_max_tokens=True, context_window=2_000)
+        client = AnthropicClient(config=config, cache=False, client=mock_async_anthropic)
+        content_item = MagicMock()
+        content_item.type = 'tool_use'
+        content_item.input = {'test_field': 'test_value'}
+        mock_async_anthropic.messages.create.return_value = MagicMock(content=[content_item])
+
+        messages = [
+            Message(role='system', content='System message'),
+            Message(role='user', content=' '.join(['word'] * 1_200)),
+        ]
+        await client.generate_response(messages, response_model=ResponseModel)
+
+        max_tokens = mock_async_anthropic.messages.create.call_args.kwargs['max_tokens']
+        assert 0 < max_tokens < 800
+
 
 if __name__ == '__main__':
     pytest.main(['-v', 'test_anthropic_client.py'])
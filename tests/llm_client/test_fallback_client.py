@@ -0,0 +1,113 @@
+"""
+Copyright 2024, Zep Software, Inc.
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+"""
+
+# Running tests: pytest -xvs tests/llm_client/test_fallback_client.py
+
+import httpx
+import pytest
+
+from graphiti_core.llm_client.client import LLMClient
+from graphiti_core.llm_client.config import LLMConfig
+from graphiti_core.llm_client.errors import RefusalError
+from graphiti_core.llm_client.fallback_client import FallbackLLMClient
+from graphiti_core.prompts.models import Message
+
+
+class FailingLLMClient(LLMClient):
+    """LLM client that always fails with the given error"""
+
+    def __init__(self, error: Exception):
+        super().__init__(LLMConfig(model='failing-model'))
+        self.error = error
+        self.calls = 0
+
+    async def _generate_response(
+        self, messages, response_model=None, max_tokens=0, model_size=None
+    ):
+        self.calls += 1
+        raise self.error
+
+
+class StaticLLMClient(LLMClient):
+    """LLM client that always returns the same response"""
+
+    def __init__(self, response: dict):
+        super().__init__(LLMConfig(model='static-model'))
+        self.response = response
+        self.received_messages: list[Message] = []
+
+    async def _generate_response(
+        self, messages, response_model=None, max_tokens=0, model_size=None
+    ):
+        self.received_messages = messages
+        return self.response
+
+
+def _messages() -> list[Message]:
+    return [
+        Message(role='system', content='You are a helpful assistant.'),
+        Message(role='user', content='Hello'),
+    ]
+
+
+@pytest.mark.asyncio
+async def test_falls_back_to_next_client_on_network_error():
+    primary = FailingLLMClient(httpx.ConnectError('connection refused'))
+    secondary = StaticLLMClient({'content': 'from secondary'})
+    client = FallbackLLMClient([primary, secondary])
+
+    response = await client.generate_response(_messages())
+
+    assert response == {'content': 'from secondary'}
+    assert primary.calls == 1
+
+
+@pytest.mark.asyncio
+async def test_fallback_sends_unmodified_messages_to_each_client():
+    primary = FailingLLMClient(httpx.ConnectError('connection refused'))
+    secondary = StaticLLMClient({'content': 'ok'})
+    client = FallbackLLMClient([primary, secondary])
+    messages = _messages()
+
+    await client.generate_response(messages)
+
+    # The instructions appended by the primary must not leak into the secondary's request
+    assert messages[0].content == 'You are a helpful assistant.'
+    assert secondary.received_messages[0].content.count('same language') == 1
+
+
+@pytest.mark.asyncio
+async def test_non_fallback_errors_are_raised():
+    primary = FailingLLMClient(RefusalError('refused'))
+    secondary = StaticLLMClient({'content': 'from secondary'})
+    client = FallbackLLMClient([primary, secondary])
+
+    with pytest.raises(RefusalError):
+        await client.generate_response(_messages())
+
+
+@pytest.mark.asyncio
+async def test_last_error_is_raised_when_all_clients_fail():
+    error = httpx.ConnectError('connection refused')
+    client = FallbackLLMClient([FailingLLMClient(error), FailingLLMClient(error)])
+
+    with pytest.raises(httpx.ConnectError):
+        await client.generate_response(_messages())
+
+
+def test_requires_at_least_one_client():
+    with pytest.raises(ValueError):
+        FallbackLLMClient([])
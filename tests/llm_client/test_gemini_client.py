@@ -370,6 +370,24 @@ class TestGeminiClientGenerateResponse:
         config = call_args[1]['config']
         assert config.max_output_tokens == 500
 
+    @pytest.mark.asyncio
+    async def test_auto_max_tokens_caps_to_remaining_context(self, mock_gemini_client):
+        """Test that max tokens is capped at the room the prompt leaves in the context window."""
+        config = LLMConfig(api_key='test_api_key', auto_max_tokens=True, context_window=2_000)
+        client = GeminiClient(config=config, cache=False)
+        client.client = mock_gemini_client
+        mock_response = MagicMock()
+        mock_response.text = 'Test response'
+        mock_response.candidates = []
+        mock_response.prompt_feedback = None
+        mock_gemini_client.aio.models.generate_content.return_value = mock_response
+
+        messages = [Message(role='user', content=' '.join(['word'] * 1_200))]
+        await client.generate_response(messages)
+
+        config = mock_gemini_client.aio.models.generate_content.call_args[1]['config']
+        assert 0 < config.max_output_tokens < 800
+
     @pytest.mark.asyncio
     async def test_model_size_selection(self, gemini_client, mock_gemini_client):
         """Test that the correct model is selected based on model size."""
@@ -0,0 +1,146 @@
+"""
+Copyright 2024, Zep Software, Inc.
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+"""
+
+import json
+from types import SimpleNamespace
+from unittest.mock import AsyncMock, MagicMock
+
+import pytest
+
+from graphiti_core.llm_client.client import _token_usage
+from graphiti_core.llm_client.config import LLMConfig
+from graphiti_core.llm_client.observer import JSONLFileObserver, LLMObserver, TokenUsage
+from graphiti_core.llm_client.openai_client import OpenAIClient
+from graphiti_core.prompts.models import Message
+
+
+class CapturingObserver(LLMObserver):
+    def __init__(self):
+        self.requests: list[list[str]] = []
+        self.responses: list[tuple[dict, TokenUsage]] = []
+
+    def on_request(self, messages):
+        self.requests.append([message.content for message in messages])
+
+    def on_response(self, response, usage):
+        self.responses.append((response, usage))
+
+
+def _completion(content: str, prompt_tokens: int, completion_tokens: int):
+    return SimpleNamespace(
+        choices=[SimpleNamespace(message=SimpleNamespace(content=content))],
+        usage=SimpleNamespace(prompt_tokens=prompt_tokens, completion_tokens=completion_tokens),
+    )
+
+
+def _messages(text: str) -> list[Message]:
+    return [Message(role='system', content='system'), Message(role='user', content=text)]
+
+
+@pytest.mark.asyncio
+async def test_observer_sees_one_request_and_response_per_call():
+    openai = MagicMock()
+    openai.chat.completions.create = AsyncMock(
+        side_effect=[_completion('{"answer": 1}', 10, 2), _completion('{"answer": 2}', 20, 4)]
+    )
+    client = OpenAIClient(config=LLMConfig(api_key='sk-test'), client=openai)
+    observer = CapturingObserver()
+    client.observer = observer
+
+    await client.generate_response(_messages('first'))
+    await client.generate_response(_messages('second'))
+
+    assert len(observer.requests) == 2
+    assert observer.requests[0][1] == 'first'
+    assert observer.requests[1][1] == 'second'
+    assert [response for response, _ in observer.responses] == [{'answer': 1}, {'answer': 2}]
+    assert observer.responses[0][1] == TokenUsage(input_tokens=10, output_tokens=2)
+    assert observer.responses[1][1] == TokenUsage(input_tokens=20, output_tokens=4)
+
+
+@pytest.mark.asyncio
+async def test_observer_usage_sums_retries():
+    openai = MagicMock()
+    openai.chat.completions.create = AsyncMock(
+        side_effect=[_completion('not json', 10, 2), _completion('{"answer": 1}', 12, 3)]
+    )
+    client = OpenAIClient(client=openai)
+    observer = CapturingObserver()
+    client.observer = observer
+
+    await client.generate_response(_messages('question'))
+
+    assert len(observer.requests) == 1
+    assert observer.responses == [({'answer': 1}, TokenUsage(input_tokens=22, output_tokens=5))]
+
+
+@pytest.mark.asyncio
+async def test_token_usage_does_not_outlive_the_call():
+    openai = MagicMock()
+    openai.chat.completions.create = AsyncMock(return_value=_completion('{"answer": 1}', 10, 2))
+    client = OpenAIClient(client=openai)
+    client.observer = CapturingObserver()
+
+    await client.generate_response(_messages('question'))
+
+    assert _token_usage.get() is None
+
+
+def test_jsonl_file_observer_redacts_api_key(tmp_path):
+    path = tmp_path / 'llm.jsonl'
+    observer = JSONLFileObserver(path, LLMConfig(api_key='sk-secret', model='gpt-4.1-mini'))
+
+    observer.on_request(_messages('question'))
+    observer.on_response({'answer': 1}, TokenUsage(input_tokens=3, output_tokens=1))
+    observer.flush()
+
+    contents = path.read_text()
+    assert 'sk-secret' not in contents
+
+    request, response = [json.loads(line) for line in contents.splitlines()]
+    assert request['type'] == 'request'
+    assert request['config']['api_key'] == '***'
+    assert request['config']['model'] == 'gpt-4.1-mini'
+    assert request['messages'][1]['content'] == 'question'
+    assert response['response'] == {'answer': 1}
+    assert response['usage'] == {'input_tokens': 3, 'output_tokens': 1}
+
+
+def test_jsonl_file_observer_close_writes_queued_records_and_stops(tmp_path):
+    path = tmp_path / 'llm.jsonl'
+    observer = JSONLFileObserver(path)
+
+    observer.on_request(_messages('before close'))
+    observer.close()
+    observer.on_request(_messages('after close'))
+
+    assert not observer._writer.is_alive()
+    records = [json.loads(line) for line in path.read_text().splitlines()]
+    assert [record['messages'][1]['content'] for record in records] == [
+        'before close',
+        'after close',
+    ]
+
+
+@pytest.mark.asyncio
+async def test_graphiti_close_closes_the_observer(make_graphiti, tmp_path):
+    observer = JSONLFileObserver(tmp_path / 'llm.jsonl')
+    graphiti = make_graphiti()
+    graphiti.llm_client.observer = observer
+
+    await graphiti.close()
+
+    assert not observer._writer.is_alive()
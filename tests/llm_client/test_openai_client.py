@@ -0,0 +1,220 @@
+"""
+Copyright 2024, Zep Software, Inc.
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+"""
+
+import json
+from unittest.mock import AsyncMock, MagicMock
+
+import httpx
+import openai
+import pytest
+
+from graphiti_core.llm_client.azure_openai_client import AzureOpenAILLMClient
+from graphiti_core.llm_client.client import use_model_override
+from graphiti_core.llm_client.config import LLMConfig, ModelSize
+from graphiti_core.llm_client.errors import PromptTooLongError
+from graphiti_core.llm_client.openai_client import OpenAIClient
+from graphiti_core.prompts.models import Message
+
+
+def _client(**config) -> tuple[OpenAIClient, MagicMock]:
+    completion = MagicMock()
+    completion.choices[0].message.content = '{"ok": true}'
+    openai_client = MagicMock()
+    openai_client.chat.completions.create = AsyncMock(return_value=completion)
+    return OpenAIClient(config=LLMConfig(**config), client=openai_client), openai_client
+
+
+def _messages(user_tokens: int) -> list[Message]:
+    return [
+        Message(role='system', content='You are a helpful assistant.'),
+        # Each word is one token
+        Message(role='user', content=' '.join(['word'] * user_tokens)),
+    ]
+
+
+@pytest.mark.asyncio
+async def test_auto_max_tokens_caps_to_remaining_context():
+    client, openai_client = _client(auto_max_tokens=True, context_window=10_000)
+
+    await client.generate_response(_messages(user_tokens=6_000), max_tokens=8192)
+
+    max_tokens = openai_client.chat.completions.create.call_args.kwargs['max_tokens']
+    assert 0 < max_tokens < 10_000 - 5_000
+
+
+@pytest.mark.asyncio
+async def test_auto_max_tokens_keeps_configured_max_for_short_prompts():
+    client, openai_client = _client(auto_max_tokens=True, context_window=128_000)
+
+    await client.generate_response(_messages(user_tokens=100), max_tokens=8192)
+
+    assert openai_client.chat.completions.create.call_args.kwargs['max_tokens'] == 8192
+
+
+@pytest.mark.asyncio
+async def test_prompt_larger_than_context_fails_before_the_request():
+    client, openai_client = _client(auto_max_tokens=True, context_window=1_000)
+
+    with pytest.raises(PromptTooLongError):
+        await client.generate_response(_messages(user_tokens=10_000))
+
+    openai_client.chat.completions.create.assert_not_called()
+
+
+@pytest.mark.asyncio
+async def test_max_tokens_is_fixed_without_auto_sizing():
+    client, openai_client = _client(context_window=1_000)
+
+    await client.generate_response(_messages(user_tokens=10_000), max_tokens=8192)
+
+    assert openai_client.chat.completions.create.call_args.kwargs['max_tokens'] == 8192
+
+
+def _chat_completion(content: str) -> httpx.Response:
+    return httpx.Response(
+        200,
+        json={
+            'id': 'chatcmpl-test',
+            'object': 'chat.completion',
+            'created': 0,
+            'model': 'gpt-4.1-mini',
+            'choices': [
+                {
+                    'index': 0,
+                    'message': {'role': 'assistant', 'content': content},
+                    'finish_reason': 'stop',
+                }
+            ],
+            'usage': {'prompt_tokens': 1, 'completion_tokens': 1, 'total_tokens': 2},
+        },
+    )
+
+
+def _served_client(
+    responses: list[httpx.Response | Exception], **config
+) -> tuple[OpenAIClient, list[dict]]:
+    # Serves the given responses in order from a mock server, recording the request bodies
+    requests: list[dict] = []
+    pending = iter(responses)
+
+    def handle(request: httpx.Request) -> httpx.Response:
+        requests.append(json.loads(request.content))
+        response = next(pending)
+        if isinstance(response, Exception):
+            raise response
+        return response
+
+    client = OpenAIClient(
+        config=LLMConfig(api_key='test', **config),
+        http_client=httpx.AsyncClient(transport=httpx.MockTransport(handle)),
+    )
+    client.NETWORK_RETRY_BASE_DELAY = 0
+    return client, requests
+
+
+@pytest.mark.asyncio
+async def test_network_errors_are_resent_without_changing_the_prompt():
+    client, requests = _served_client(
+        [
+            httpx.ConnectError('connection refused'),
+            httpx.Response(500, json={'error': {'message': 'server error'}}),
+            _chat_completion('{"ok": true}'),
+        ],
+        validation_retries=0,
+    )
+
+    assert await client.generate_response(_messages(user_tokens=10)) == {'ok': True}
+
+    assert len(requests) == 3
+    assert requests[0]['messages'] == requests[1]['messages'] == requests[2]['messages']
+
+
+@pytest.mark.asyncio
+async def test_invalid_responses_are_retried_with_the_error_in_the_prompt():
+    client, requests = _served_client(
+        [_chat_completion('not json'), _chat_completion('{"ok": true}')],
+        network_retries=0,
+    )
+
+    assert await client.generate_response(_messages(user_tokens=10)) == {'ok': True}
+
+    assert len(requests) == 2
+    assert len(requests[1]['messages']) == len(requests[0]['messages']) + 1
+    assert 'The previous response attempt was invalid' in requests[1]['messages'][-1]['content']
+
+
+@pytest.mark.asyncio
+async def test_network_retries_are_limited_independently():
+    client, requests = _served_client(
+        [httpx.ConnectError('connection refused')] * 2, network_retries=1, validation_retries=5
+    )
+
+    with pytest.raises(openai.APIConnectionError):
+        await client.generate_response(_messages(user_tokens=10))
+
+    assert len(requests) == 2
+
+
+@pytest.mark.asyncio
+async def test_injected_clients_do_not_retry_on_their_own():
+    requests: list[httpx.Request] = []
+
+    def handle(request: httpx.Request) -> httpx.Response:
+        requests.append(request)
+        raise httpx.ConnectError('connection refused')
+
+    http_client = httpx.AsyncClient(transport=httpx.MockTransport(handle))
+    client = OpenAIClient(
+        config=LLMConfig(network_retries=0),
+        client=openai.AsyncOpenAI(api_key='test', http_client=http_client),
+    )
+    azure_client = AzureOpenAILLMClient(
+        openai.AsyncAzureOpenAI(
+            api_key='test', api_version='2024-10-21', azure_endpoint='https://example.com'
+        )
+    )
+
+    with pytest.raises(openai.APIConnectionError):
+        await client.generate_response(_messages(user_tokens=10))
+
+    assert len(requests) == 1
+    assert azure_client.client.max_retries == 0
+
+
+@pytest.mark.asyncio
+async def test_validation_retries_are_limited_independently():
+    client, requests = _served_client(
+        [_chat_completion('not json')] * 2, network_retries=5, validation_retries=1
+    )
+
+    with pytest.raises(json.JSONDecodeError):
+        await client.generate_response(_messages(user_tokens=10))
+
+    assert len(requests) == 2
+
+
+@pytest.mark.asyncio
+async def test_model_override_applies_only_within_its_context():
+    client, openai_client = _client(model='default-model', small_model='small-model')
+    create = openai_client.chat.completions.create
+
+    with use_model_override('override-model'):
+        await client.generate_response(_messages(user_tokens=100))
+        await client.generate_response(_messages(user_tokens=100), model_size=ModelSize.small)
+    await client.generate_response(_messages(user_tokens=100))
+
+    models = [call.kwargs['model'] for call in create.call_args_list]
+    assert models == ['override-model', 'override-model', 'default-model']
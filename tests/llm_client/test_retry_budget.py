@@ -0,0 +1,92 @@
+"""
+Copyright 2024, Zep Software, Inc.
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+"""
+
+from datetime import datetime, timezone
+from unittest.mock import AsyncMock, MagicMock
+
+import pytest
+
+from graphiti_core.llm_client import RetryBudget, RetryBudgetExhaustedError, use_retry_budget
+from graphiti_core.llm_client.config import LLMConfig
+from graphiti_core.llm_client.openai_client import OpenAIClient
+from graphiti_core.prompts.models import Message
+
+
+def _completion(content: str) -> MagicMock:
+    completion = MagicMock()
+    completion.choices[0].message.content = content
+    completion.choices[0].message.parsed = None
+    completion.choices[0].message.refusal = None
+    return completion
+
+
+def _flaky_client() -> tuple[OpenAIClient, MagicMock]:
+    # Every call returns invalid JSON on its first attempt and succeeds when retried
+    responses = iter(
+        [_completion('not json') if i % 2 == 0 else _completion('{}') for i in range(100)]
+    )
+    openai_client = MagicMock()
+    openai_client.chat.completions.create = AsyncMock(side_effect=lambda **_: next(responses))
+    openai_client.beta.chat.completions.parse = AsyncMock(
+        side_effect=lambda **_: _completion('not json')
+    )
+    return OpenAIClient(config=LLMConfig(api_key='test'), client=openai_client), openai_client
+
+
+def _messages() -> list[Message]:
+    return [Message(role='system', content='system'), Message(role='user', content='user')]
+
+
+@pytest.mark.asyncio
+async def test_calls_stop_retrying_once_the_budget_is_used_up():
+    client, openai_client = _flaky_client()
+
+    with use_retry_budget(RetryBudget(2)):
+        assert await client.generate_response(_messages()) == {}
+        assert await client.generate_response(_messages()) == {}
+        with pytest.raises(RetryBudgetExhaustedError):
+            await client.generate_response(_messages())
+
+    # Two calls that each needed a retry, then a single attempt
+    assert openai_client.chat.completions.create.await_count == 5
+
+
+@pytest.mark.asyncio
+async def test_calls_retry_independently_without_a_budget():
+    client, openai_client = _flaky_client()
+
+    for _ in range(3):
+        assert await client.generate_response(_messages()) == {}
+
+    assert openai_client.chat.completions.create.await_count == 6
+
+
+@pytest.mark.asyncio
+async def test_add_episode_aborts_when_the_budget_is_exhausted(make_graphiti):
+    llm_client, openai_client = _flaky_client()
+    graphiti = make_graphiti(llm_client=llm_client, max_operation_retries=1)
+
+    with pytest.raises(RetryBudgetExhaustedError):
+        await graphiti.add_episode(
+            name='episode',
+            episode_body='Alice met Bob',
+            source_description='test',
+            reference_time=datetime.now(timezone.utc),
+            group_id='group_1',
+        )
+
+    # Entity extraction never returns a valid response, and is given up after a single retry
+    assert openai_client.beta.chat.completions.parse.await_count == 2
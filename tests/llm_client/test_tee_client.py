@@ -0,0 +1,78 @@
+"""
+Copyright 2024, Zep Software, Inc.
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+"""
+
+import json
+
+import pytest
+from pydantic import BaseModel
+
+from graphiti_core.llm_client.tee_client import TeeLLMClient
+from graphiti_core.prompts.models import Message
+from graphiti_core.testing import MockLLMClient
+
+
+FRANCE = 'What is the capital of France?'
+
+
+class Answer(BaseModel):
+    answer: str
+
+
+def _messages(question: str) -> list[Message]:
+    return [
+        Message(role='system', content='You answer questions.'),
+        Message(role='user', content=question),
+    ]
+
+
+@pytest.mark.asyncio
+async def test_tee_records_one_record_per_call_and_returns_the_response(tmp_path):
+    path = tmp_path / 'recording.jsonl'
+    inner = (
+        MockLLMClient()
+        .add_response('capital of France', {'answer': 'Paris'})
+        .add_response('capital of Italy', {'answer': 'Rome'})
+    )
+    tee = TeeLLMClient(inner, path)
+
+    first = await tee.generate_response(_messages(FRANCE), Answer)
+    second = await tee.generate_response(_messages('What is the capital of Italy?'))
+
+    assert first == {'answer': 'Paris'}
+    assert second == {'answer': 'Rome'}
+    assert inner.call_count == 2
+
+    records = [json.loads(line) for line in path.read_text().splitlines()]
+    assert len(records) == 2
+    assert records[0]['messages'] == [message.model_dump() for message in _messages(FRANCE)]
+    assert records[0]['response_model'] == 'Answer'
+    assert records[0]['response'] == {'answer': 'Paris'}
+    assert records[1]['response_model'] is None
+    assert records[1]['response'] == {'answer': 'Rome'}
+
+
+@pytest.mark.asyncio
+async def test_recording_can_be_replayed(tmp_path):
+    path = tmp_path / 'recording.jsonl'
+    tee = TeeLLMClient(MockLLMClient(default_response={'answer': 'Paris'}), path)
+    await tee.generate_response(_messages(FRANCE), Answer)
+
+    replay = MockLLMClient.from_recording(path)
+
+    response = await replay.generate_response(_messages(FRANCE), Answer)
+    assert response == {'answer': 'Paris'}
+    with pytest.raises(ValueError):
+        await replay.generate_response(_messages('What is the capital of Spain?'), Answer)
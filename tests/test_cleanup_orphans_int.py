@@ -0,0 +1,141 @@
+"""
+Copyright 2024, Zep Software, Inc.
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+"""
+
+import os
+from datetime import datetime, timezone
+from unittest.mock import MagicMock
+from uuid import uuid4
+
+import pytest
+
+from graphiti_core.cross_encoder.client import CrossEncoderClient
+from graphiti_core.edges import CommunityEdge, EntityEdge, EpisodicEdge
+from graphiti_core.embedder.client import EmbedderClient
+from graphiti_core.graphiti import Graphiti
+from graphiti_core.llm_client import LLMClient
+from graphiti_core.nodes import CommunityNode, EntityNode, EpisodeType, EpisodicNode
+from graphiti_core.utils.maintenance.graph_data_operations import OrphanPolicy, clear_data
+
+pytestmark = pytest.mark.integration
+
+NEO4J_URI = os.getenv('NEO4J_URI', 'bolt://localhost:7687')
+NEO4J_USER = os.getenv('NEO4J_USER', 'neo4j')
+NEO4J_PASSWORD = os.getenv('NEO4J_PASSWORD', 'test')
+
+
+def _graphiti() -> Graphiti:
+    return Graphiti(
+        NEO4J_URI,
+        NEO4J_USER,
+        NEO4J_PASSWORD,
+        llm_client=MagicMock(spec=LLMClient),
+        embedder=MagicMock(spec=EmbedderClient),
+        cross_encoder=MagicMock(spec=CrossEncoderClient),
+    )
+
+
+async def _graph_with_invalidated_fact(graphiti: Graphiti, group_id: str) -> dict[str, EntityNode]:
+    now = datetime.now(timezone.utc).replace(microsecond=0)
+    nodes = {
+        name: EntityNode(
+            name=name, group_id=group_id, labels=['Entity'], name_embedding=[0.5] * 1024
+        )
+        for name in ['Alice', 'Bob', 'Carol', 'Dave']
+    }
+    for node in nodes.values():
+        await node.save(graphiti.driver)
+
+    # Alice and Bob's only fact is invalidated
+    await EntityEdge(
+        source_node_uuid=nodes['Alice'].uuid,
+        target_node_uuid=nodes['Bob'].uuid,
+        name='KNOWS',
+        fact='Alice knows Bob',
+        fact_embedding=[0.5] * 1024,
+        group_id=group_id,
+        created_at=now,
+        invalid_at=now,
+    ).save(graphiti.driver)
+
+    # Carol is still mentioned by an episode and Dave belongs to a community
+    episode = EpisodicNode(
+        name='Episode',
+        group_id=group_id,
+        source=EpisodeType.text,
+        source_description='test',
+        content='Carol',
+        valid_at=now,
+    )
+    await episode.save(graphiti.driver)
+    await EpisodicEdge(
+        source_node_uuid=episode.uuid,
+        target_node_uuid=nodes['Carol'].uuid,
+        group_id=group_id,
+        created_at=now,
+    ).save(graphiti.driver)
+
+    community = CommunityNode(
+        name='Dave', group_id=group_id, summary='Dave', name_embedding=[0.5] * 1024
+    )
+    await community.save(graphiti.driver)
+    await CommunityEdge(
+        source_node_uuid=community.uuid,
+        target_node_uuid=nodes['Dave'].uuid,
+        group_id=group_id,
+        created_at=now,
+    ).save(graphiti.driver)
+
+    return nodes
+
+
+@pytest.mark.asyncio
+async def test_cleanup_orphans_deletes_entities_whose_facts_were_invalidated():
+    graphiti = _graphiti()
+    group_id = f'orphan_test_{uuid4().hex}'
+
+    try:
+        nodes = await _graph_with_invalidated_fact(graphiti, group_id)
+
+        orphan_uuids = await graphiti.cleanup_orphans(group_id)
+
+        assert set(orphan_uuids) == {nodes['Alice'].uuid, nodes['Bob'].uuid}
+        remaining_nodes = await EntityNode.get_by_group_ids(graphiti.driver, [group_id])
+        assert {node.name for node in remaining_nodes} == {'Carol', 'Dave'}
+    finally:
+        await clear_data(graphiti.driver, [group_id])
+        await graphiti.close()
+
+
+@pytest.mark.asyncio
+async def test_cleanup_orphans_can_flag_instead_of_delete():
+    graphiti = _graphiti()
+    group_id = f'orphan_test_{uuid4().hex}'
+
+    try:
+        nodes = await _graph_with_invalidated_fact(graphiti, group_id)
+
+        orphan_uuids = await graphiti.cleanup_orphans(group_id, OrphanPolicy.flag)
+
+        assert set(orphan_uuids) == {nodes['Alice'].uuid, nodes['Bob'].uuid}
+        remaining_nodes = await EntityNode.get_by_group_ids(graphiti.driver, [group_id])
+        assert {node.name for node in remaining_nodes if 'Orphaned' in node.labels} == {
+            'Alice',
+            'Bob',
+        }
+        assert len(remaining_nodes) == 4
+    finally:
+        await clear_data(graphiti.driver, [group_id])
+        await graphiti.close()
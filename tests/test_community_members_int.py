@@ -0,0 +1,79 @@
+"""
+Copyright 2024, Zep Software, Inc.
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+"""
+
+import os
+from unittest.mock import MagicMock
+from uuid import uuid4
+
+import pytest
+
+from graphiti_core.cross_encoder.client import CrossEncoderClient
+from graphiti_core.edges import CommunityEdge
+from graphiti_core.embedder.client import EmbedderClient
+from graphiti_core.graphiti import Graphiti
+from graphiti_core.llm_client import LLMClient
+from graphiti_core.nodes import CommunityNode, EntityNode
+from graphiti_core.utils.datetime_utils import utc_now
+from graphiti_core.utils.maintenance.graph_data_operations import clear_data
+
+pytestmark = pytest.mark.integration
+
+NEO4J_URI = os.getenv('NEO4J_URI', 'bolt://localhost:7687')
+NEO4J_USER = os.getenv('NEO4J_USER', 'neo4j')
+NEO4J_PASSWORD = os.getenv('NEO4J_PASSWORD', 'test')
+
+
+@pytest.mark.asyncio
+async def test_community_members_are_returned():
+    graphiti = Graphiti(
+        NEO4J_URI,
+        NEO4J_USER,
+        NEO4J_PASSWORD,
+        llm_client=MagicMock(spec=LLMClient),
+        embedder=MagicMock(spec=EmbedderClient),
+        cross_encoder=MagicMock(spec=CrossEncoderClient),
+    )
+    group_id = f'community_test_{uuid4().hex}'
+
+    try:
+        alice, bob, carol = [
+            EntityNode(name=name, group_id=group_id, labels=['Entity'], name_embedding=[0.5] * 1024)
+            for name in ['Alice', 'Bob', 'Carol']
+        ]
+        for node in [alice, bob, carol]:
+            await node.save(graphiti.driver)
+
+        # Carol is not part of the community
+        community = CommunityNode(
+            name='Alice and Bob', group_id=group_id, summary='Friends', name_embedding=[0.5] * 1024
+        )
+        await community.save(graphiti.driver)
+        for member in [alice, bob]:
+            await CommunityEdge(
+                source_node_uuid=community.uuid,
+                target_node_uuid=member.uuid,
+                group_id=group_id,
+                created_at=utc_now(),
+            ).save(graphiti.driver)
+
+        communities = await graphiti.list_communities(group_id)
+        members = await graphiti.get_community_members(community.uuid)
+
+        assert [c.uuid for c in communities] == [community.uuid]
+        assert [member.uuid for member in members] == [alice.uuid, bob.uuid]
+    finally:
+        await clear_data(graphiti.driver, [group_id])
+        await graphiti.close()
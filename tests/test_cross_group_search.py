@@ -0,0 +1,44 @@
+from unittest.mock import AsyncMock, patch
+
+import pytest
+
+from graphiti_core.errors import CrossGroupSearchError
+from graphiti_core.search.search_config import SearchResults
+
+EMPTY_RESULTS = SearchResults(edges=[], nodes=[], episodes=[], communities=[])
+
+
+@pytest.mark.asyncio
+async def test_search_without_groups_is_rejected_by_default(make_graphiti):
+    graphiti = make_graphiti()
+    mock_search = AsyncMock(return_value=EMPTY_RESULTS)
+
+    with patch('graphiti_core.graphiti.search', mock_search):
+        with pytest.raises(CrossGroupSearchError):
+            await graphiti.search('fact')
+        with pytest.raises(CrossGroupSearchError):
+            await graphiti.search_('fact', group_ids=[])
+        with pytest.raises(CrossGroupSearchError):
+            await graphiti.search_and_expand('fact')
+        # The default group id would otherwise be widened to every group
+        with pytest.raises(CrossGroupSearchError):
+            await graphiti.search('fact', group_ids=[''])
+        with pytest.raises(CrossGroupSearchError):
+            await graphiti.search_('fact', group_ids=['', ''])
+
+        await graphiti.search('fact', group_ids=['group_1'])
+
+    assert mock_search.await_count == 1
+    assert mock_search.await_args.args[2] == ['group_1']
+
+
+@pytest.mark.asyncio
+async def test_search_without_groups_is_allowed_when_enabled(make_graphiti):
+    graphiti = make_graphiti(allow_cross_group_search=True)
+    mock_search = AsyncMock(return_value=EMPTY_RESULTS)
+
+    with patch('graphiti_core.graphiti.search', mock_search):
+        await graphiti.search('fact')
+
+    assert mock_search.await_count == 1
+    assert mock_search.await_args.args[2] is None
@@ -0,0 +1,102 @@
+"""
+Copyright 2024, Zep Software, Inc.
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+"""
+
+import os
+from uuid import uuid4
+
+import pytest
+
+from graphiti_core.edges import EntityEdge
+from graphiti_core.errors import NodeNotFoundError
+from graphiti_core.graphiti import Graphiti
+from graphiti_core.nodes import EntityNode
+from graphiti_core.testing import MockCrossEncoderClient, MockEmbedderClient, MockLLMClient
+from graphiti_core.utils.datetime_utils import utc_now
+from graphiti_core.utils.maintenance.graph_data_operations import clear_data
+
+pytestmark = pytest.mark.integration
+
+NEO4J_URI = os.getenv('NEO4J_URI', 'bolt://localhost:7687')
+NEO4J_USER = os.getenv('NEO4J_USER', 'neo4j')
+NEO4J_PASSWORD = os.getenv('NEO4J_PASSWORD', 'test')
+
+
+@pytest.mark.asyncio
+async def test_dedup_maintenance_merges_duplicate_missed_at_ingest():
+    group_id = f'dedup_test_{uuid4().hex}'
+    now = utc_now()
+    embedder = MockEmbedderClient()
+
+    # The names don't match, so only the LLM can tell these are the same person. Their stored
+    # embeddings are identical, so they are the only candidate pair.
+    robert, bob, acme, carol = [
+        EntityNode(
+            name=name,
+            group_id=group_id,
+            labels=['Entity'],
+            summary=summary,
+            name_embedding=embedder.embed(embedding_text),
+        )
+        for name, summary, embedding_text in [
+            ('Robert Smith', 'Engineer at Acme', 'Smith'),
+            ('Bob Smith', 'Friend of Carol', 'Smith'),
+            ('Acme', '', 'Acme'),
+            ('Carol', '', 'Carol'),
+        ]
+    ]
+    llm_client = MockLLMClient().add_response(
+        'deduplicate a list of nodes',
+        {'nodes': [{'uuids': [robert.uuid, bob.uuid], 'summary': 'Engineer at Acme'}]},
+    )
+    graphiti = Graphiti(
+        NEO4J_URI,
+        NEO4J_USER,
+        NEO4J_PASSWORD,
+        llm_client=llm_client,
+        embedder=embedder,
+        cross_encoder=MockCrossEncoderClient(),
+    )
+
+    try:
+        for node in [robert, bob, acme, carol]:
+            await node.save(graphiti.driver)
+        for source, target, name in [(robert, acme, 'WORKS_AT'), (bob, carol, 'KNOWS')]:
+            await EntityEdge(
+                source_node_uuid=source.uuid,
+                target_node_uuid=target.uuid,
+                name=name,
+                fact=f'{source.name} {name} {target.name}',
+                fact_embedding=embedder.embed(name),
+                episodes=[],
+                group_id=group_id,
+                created_at=now,
+            ).save(graphiti.driver)
+
+        merged = await graphiti.run_dedup_maintenance(group_id)
+
+        assert merged == {bob.uuid: robert.uuid}
+        assert llm_client.call_count == 1
+        with pytest.raises(NodeNotFoundError):
+            await EntityNode.get_by_uuid(graphiti.driver, bob.uuid)
+
+        edges = await EntityEdge.get_by_node_uuid(graphiti.driver, robert.uuid)
+        assert sorted((edge.name, edge.target_node_uuid) for edge in edges) == [
+            ('KNOWS', carol.uuid),
+            ('WORKS_AT', acme.uuid),
+        ]
+    finally:
+        await clear_data(graphiti.driver, [group_id])
+        await graphiti.close()
@@ -0,0 +1,70 @@
+from datetime import datetime, timezone
+from unittest.mock import AsyncMock, MagicMock
+
+import pytest
+
+from graphiti_core.driver.driver import GraphDriver
+from graphiti_core.edges import EntityEdge, get_entity_edge_from_record
+
+
+def _edge(**kwargs) -> EntityEdge:
+    return EntityEdge(
+        source_node_uuid='alice',
+        target_node_uuid='bob',
+        name='KNOWS',
+        group_id='group',
+        fact='Alice probably knows Bob',
+        created_at=datetime(2024, 6, 1, tzinfo=timezone.utc),
+        **kwargs,
+    )
+
+
+async def _saved_properties(edge: EntityEdge) -> dict:
+    driver = MagicMock(spec=GraphDriver, execute_query=AsyncMock(return_value=([], None, None)))
+    await edge.save(driver)
+    return driver.execute_query.call_args.kwargs['edge_data']
+
+
+def _record(properties: dict) -> dict:
+    # Mirrors ENTITY_EDGE_RETURN, where attributes holds every property of the relationship
+    return {
+        'uuid': properties['uuid'],
+        'source_node_uuid': properties['source_uuid'],
+        'target_node_uuid': properties['target_uuid'],
+        'fact': properties['fact'],
+        'name': properties['name'],
+        'group_id': properties['group_id'],
+        'episodes': properties['episodes'],
+        'created_at': properties['created_at'],
+        'expired_at': properties['expired_at'],
+        'valid_at': properties['valid_at'],
+        'invalid_at': properties['invalid_at'],
+        'attributes': dict(properties),
+    }
+
+
+@pytest.mark.asyncio
+async def test_confidence_round_trips_through_save():
+    edge = _edge(confidence=0.4, attributes={'source': 'chat'})
+
+    properties = await _saved_properties(edge)
+    loaded = get_entity_edge_from_record(_record(properties))
+
+    assert properties['confidence'] == 0.4
+    assert loaded.confidence == 0.4
+    # confidence is a field, not an extra attribute
+    assert 'confidence' not in loaded.attributes
+    assert loaded.attributes['source'] == 'chat'
+
+
+@pytest.mark.asyncio
+async def test_edges_without_confidence_load_as_none():
+    properties = await _saved_properties(_edge())
+    del properties['confidence']
+
+    assert get_entity_edge_from_record(_record(properties)).confidence is None
+
+
+def test_confidence_must_be_between_zero_and_one():
+    with pytest.raises(ValueError):
+        _edge(confidence=1.5)
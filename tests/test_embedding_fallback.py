@@ -0,0 +1,120 @@
+from datetime import datetime, timezone
+from unittest.mock import AsyncMock, MagicMock, patch
+
+import pytest
+
+from graphiti_core.edges import EntityEdge
+from graphiti_core.embedder.client import EmbedderClient
+from graphiti_core.models.edges.edge_db_queries import ENTITY_EDGE_SAVE
+from graphiti_core.models.nodes.node_db_queries import ENTITY_NODE_SAVE
+from graphiti_core.nodes import EntityNode
+from graphiti_core.prompts.dedupe_nodes import NodeResolutions
+from graphiti_core.testing import MockLLMClient
+from graphiti_core.utils.bulk_utils import add_nodes_and_edges_bulk_tx
+
+NOW = datetime(2024, 6, 1, tzinfo=timezone.utc)
+
+
+def _llm_client() -> MockLLMClient:
+    return MockLLMClient(default_response={'summary': 'summary'}).add_response(
+        NodeResolutions,
+        {
+            'entity_resolutions': [
+                {'id': 0, 'name': 'Alice', 'duplicate_idx': -1, 'additional_duplicates': []},
+                {'id': 1, 'name': 'Bob', 'duplicate_idx': -1, 'additional_duplicates': []},
+            ]
+        },
+    )
+
+
+@pytest.mark.asyncio
+async def test_episode_is_saved_without_embeddings_when_the_embedder_fails(make_graphiti):
+    embedder = MagicMock(spec=EmbedderClient)
+    embedder.create = AsyncMock(side_effect=ConnectionError('embedder is down'))
+    embedder.create_batch = AsyncMock(side_effect=ConnectionError('embedder is down'))
+    graphiti = make_graphiti(llm_client=_llm_client(), embedder=embedder, embedding_fallback=True)
+
+    alice = EntityNode(name='Alice', group_id='group_1', labels=['Entity'])
+    bob = EntityNode(name='Bob', group_id='group_1', labels=['Entity'])
+    edge = EntityEdge(
+        source_node_uuid=alice.uuid,
+        target_node_uuid=bob.uuid,
+        name='KNOWS',
+        fact='Alice knows Bob',
+        group_id='group_1',
+        created_at=NOW,
+    )
+    tx = MagicMock()
+    tx.run = AsyncMock()
+
+    async def save_bulk(driver, *args):
+        await add_nodes_and_edges_bulk_tx(tx, *args, driver=driver)
+
+    with (
+        patch('graphiti_core.graphiti.retrieve_episodes', AsyncMock(return_value=[])),
+        patch('graphiti_core.graphiti.extract_nodes', AsyncMock(return_value=[alice, bob])),
+        patch('graphiti_core.graphiti.extract_edges', AsyncMock(return_value=[edge])),
+        patch('graphiti_core.graphiti.add_nodes_and_edges_bulk', side_effect=save_bulk),
+    ):
+        result = await graphiti.add_episode(
+            name='episode',
+            episode_body='Alice knows Bob',
+            source_description='test',
+            reference_time=NOW,
+            group_id='group_1',
+        )
+
+    assert [node.name for node in result.nodes] == ['Alice', 'Bob']
+    assert [edge.fact for edge in result.edges] == ['Alice knows Bob']
+    assert all(node.name_embedding is None for node in result.nodes)
+    assert all(edge.fact_embedding is None for edge in result.edges)
+    assert result.episode.content_embedding is None
+    # Only the up-front episode embedding was attempted
+    embedder.create.assert_awaited_once()
+    embedder.create_batch.assert_not_called()
+
+
+@pytest.mark.asyncio
+async def test_backfill_embeds_entities_and_facts_without_embeddings(make_graphiti, mock_driver):
+    node_record = {
+        'uuid': 'alice',
+        'name': 'Alice',
+        'group_id': 'group_1',
+        'labels': ['Entity'],
+        'created_at': NOW.isoformat(),
+        'summary': '',
+        'attributes': {},
+    }
+    edge_record = {
+        'uuid': 'knows',
+        'source_node_uuid': 'alice',
+        'target_node_uuid': 'bob',
+        'name': 'KNOWS',
+        'group_id': 'group_1',
+        'fact': 'Alice knows Bob',
+        'episodes': [],
+        'created_at': NOW.isoformat(),
+        'expired_at': None,
+        'valid_at': None,
+        'invalid_at': None,
+        'attributes': {},
+    }
+
+    async def execute_query(query, **kwargs):
+        if 'n.name_embedding IS NULL' in query:
+            return [node_record], None, None
+        if 'e.fact_embedding IS NULL' in query:
+            return [edge_record], None, None
+        return [], None, None
+
+    mock_driver.execute_query.side_effect = execute_query
+    embedder = MagicMock(spec=EmbedderClient)
+    embedder.create_batch = AsyncMock(side_effect=lambda texts: [[0.5, 0.5] for _ in texts])
+    graphiti = make_graphiti(llm_client=_llm_client(), embedder=embedder, embedding_fallback=True)
+
+    backfilled = await graphiti.backfill_embeddings('group_1')
+
+    saves = {call.args[0]: call.kwargs for call in mock_driver.execute_query.await_args_list}
+    assert backfilled == 2
+    assert saves[ENTITY_NODE_SAVE]['entity_data']['name_embedding'] == [0.5, 0.5]
+    assert saves[ENTITY_EDGE_SAVE]['edge_data']['fact_embedding'] == [0.5, 0.5]
@@ -0,0 +1,139 @@
+from datetime import datetime, timezone
+from unittest.mock import AsyncMock, MagicMock
+
+import pytest
+
+from graphiti_core.driver.driver import GraphDriver
+from graphiti_core.edges import EntityEdge, get_entity_edge_from_record
+from graphiti_core.encryption import (
+    ENCRYPTED_PREFIX,
+    GroupEncryption,
+    attach_group_encryption,
+    use_group_encryption,
+)
+from graphiti_core.errors import GroupDecryptionError
+from graphiti_core.nodes import (
+    EntityNode,
+    EpisodeType,
+    EpisodicNode,
+    get_entity_node_from_record,
+    get_episodic_node_from_record,
+)
+
+
+def _driver() -> MagicMock:
+    return MagicMock(spec=GraphDriver, execute_query=AsyncMock(return_value=([], None, None)))
+
+
+def _episode(group_id: str) -> EpisodicNode:
+    return EpisodicNode(
+        name='episode',
+        group_id=group_id,
+        source=EpisodeType.text,
+        source_description='test',
+        content='Alice was diagnosed with asthma',
+        valid_at=datetime.now(timezone.utc),
+    )
+
+
+def _as_record(params: dict) -> dict:
+    # Query parameters as they'd be read back from the database
+    return {
+        key: value.isoformat() if isinstance(value, datetime) else value
+        for key, value in params.items()
+        if not key.endswith('_')
+    }
+
+
+def _stored_record(driver: MagicMock) -> dict:
+    return _as_record(driver.execute_query.call_args.kwargs)
+
+
+@pytest.mark.asyncio
+async def test_episode_content_is_stored_encrypted_and_read_back_in_plaintext():
+    encryption = GroupEncryption({'patients': GroupEncryption.generate_key()})
+    driver = _driver()
+    episode = _episode('patients')
+
+    with use_group_encryption(encryption):
+        await episode.save(driver)
+        record = _stored_record(driver)
+        read_back = get_episodic_node_from_record(record)
+
+    assert record['content'].startswith(ENCRYPTED_PREFIX)
+    assert 'asthma' not in record['content']
+    assert read_back.content == 'Alice was diagnosed with asthma'
+    # The in-memory episode is left in plaintext
+    assert episode.content == 'Alice was diagnosed with asthma'
+
+
+@pytest.mark.asyncio
+async def test_other_groups_are_stored_in_plaintext():
+    encryption = GroupEncryption({'patients': GroupEncryption.generate_key()})
+    driver = _driver()
+
+    with use_group_encryption(encryption):
+        await _episode('public').save(driver)
+
+    assert _stored_record(driver)['content'] == 'Alice was diagnosed with asthma'
+
+
+@pytest.mark.asyncio
+async def test_edge_fact_is_encrypted_but_its_embedding_is_not():
+    encryption = GroupEncryption({'patients': GroupEncryption.generate_key()})
+    driver = _driver()
+    edge = EntityEdge(
+        source_node_uuid='alice',
+        target_node_uuid='asthma',
+        name='HAS_CONDITION',
+        fact='Alice has asthma',
+        fact_embedding=[0.1, 0.2],
+        group_id='patients',
+        created_at=datetime.now(timezone.utc),
+    )
+
+    with use_group_encryption(encryption):
+        await edge.save(driver)
+        edge_data = driver.execute_query.call_args.kwargs['edge_data']
+        record = {
+            **_as_record(edge_data),
+            'source_node_uuid': edge_data['source_uuid'],
+            'target_node_uuid': edge_data['target_uuid'],
+            'attributes': {},
+        }
+        read_back = get_entity_edge_from_record(record)
+
+    assert edge_data['fact'].startswith(ENCRYPTED_PREFIX)
+    assert edge_data['fact_embedding'] == [0.1, 0.2]
+    assert read_back.fact == 'Alice has asthma'
+
+
+@pytest.mark.asyncio
+async def test_wrong_key_fails_to_decrypt():
+    driver = _driver()
+
+    with use_group_encryption(GroupEncryption({'patients': GroupEncryption.generate_key()})):
+        await _episode('patients').save(driver)
+    record = _stored_record(driver)
+
+    with use_group_encryption(GroupEncryption({'patients': GroupEncryption.generate_key()})):
+        with pytest.raises(GroupDecryptionError):
+            get_episodic_node_from_record(record)
+
+
+@pytest.mark.asyncio
+async def test_keys_attached_to_the_driver_apply_outside_of_an_operation():
+    driver = _driver()
+    attach_group_encryption(driver, GroupEncryption({'patients': GroupEncryption.generate_key()}))
+    node = EntityNode(
+        name='Alice', group_id='patients', labels=['Entity'], summary='Alice has asthma'
+    )
+
+    await node.save(driver)
+    entity_data = driver.execute_query.call_args.kwargs['entity_data']
+    record = {**_as_record(entity_data), 'labels': ['Entity'], 'attributes': {}}
+
+    assert entity_data['summary'].startswith(ENCRYPTED_PREFIX)
+    assert get_entity_node_from_record(record, driver).summary == 'Alice has asthma'
+    # Without the driver, the stored ciphertext is returned as is
+    assert get_entity_node_from_record(record).summary == entity_data['summary']
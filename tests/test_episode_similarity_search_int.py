@@ -0,0 +1,99 @@
+"""
+Copyright 2024, Zep Software, Inc.
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+"""
+
+import os
+from datetime import datetime, timezone
+from unittest.mock import AsyncMock, MagicMock
+from uuid import uuid4
+
+import pytest
+
+from graphiti_core.cross_encoder.client import CrossEncoderClient
+from graphiti_core.embedder.client import EmbedderClient
+from graphiti_core.graphiti import Graphiti
+from graphiti_core.llm_client import LLMClient
+from graphiti_core.nodes import EpisodeType, EpisodicNode
+from graphiti_core.search.search_config import (
+    EpisodeSearchConfig,
+    EpisodeSearchMethod,
+    SearchConfig,
+)
+from graphiti_core.utils.maintenance.graph_data_operations import clear_data
+
+pytestmark = pytest.mark.integration
+
+NEO4J_URI = os.getenv('NEO4J_URI', 'bolt://localhost:7687')
+NEO4J_USER = os.getenv('NEO4J_USER', 'neo4j')
+NEO4J_PASSWORD = os.getenv('NEO4J_PASSWORD', 'test')
+
+# Toy embeddings: the first axis is about pets, the second about finance
+EMBEDDINGS = {
+    'The puppy chased its tail around the garden': [1.0, 0.0, 0.0],
+    'Quarterly revenue beat analyst expectations': [0.0, 1.0, 0.0],
+    'dogs': [0.9, 0.1, 0.0],
+}
+
+
+async def _embed(input_data):
+    return EMBEDDINGS[input_data[0]]
+
+
+@pytest.mark.asyncio
+async def test_episode_vector_search_finds_episodes_without_shared_keywords():
+    graphiti = Graphiti(
+        NEO4J_URI,
+        NEO4J_USER,
+        NEO4J_PASSWORD,
+        llm_client=MagicMock(spec=LLMClient),
+        embedder=MagicMock(spec=EmbedderClient, create=AsyncMock(side_effect=_embed)),
+        cross_encoder=MagicMock(spec=CrossEncoderClient),
+    )
+    group_id = f'episode_similarity_test_{uuid4().hex}'
+    now = datetime.now(timezone.utc)
+
+    try:
+        pets, finance = [
+            EpisodicNode(
+                name=content,
+                group_id=group_id,
+                source=EpisodeType.text,
+                source_description='test',
+                content=content,
+                valid_at=now,
+            )
+            for content in list(EMBEDDINGS)[:2]
+        ]
+        for episode in [pets, finance]:
+            await episode.generate_content_embedding(graphiti.embedder)
+            await episode.save(graphiti.driver)
+
+        config = SearchConfig(
+            episode_config=EpisodeSearchConfig(
+                search_methods=[EpisodeSearchMethod.cosine_similarity], sim_min_score=0.5
+            )
+        )
+        results = await graphiti.search_('dogs', config=config, group_ids=[group_id])
+
+        # "dogs" shares no keywords with the episode, so only vector search can find it
+        assert [episode.uuid for episode in results.episodes] == [pets.uuid]
+
+        results = await graphiti.search_(
+            'dogs', config=SearchConfig.episodes_only(), group_ids=[group_id]
+        )
+        assert results.episodes == []
+    finally:
+        await clear_data(graphiti.driver, [group_id])
+        await graphiti.close()
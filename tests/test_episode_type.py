@@ -0,0 +1,63 @@
+from datetime import datetime, timezone
+from unittest.mock import AsyncMock, patch
+
+import pytest
+
+from graphiti_core.errors import InvalidEpisodeTypeError
+from graphiti_core.nodes import EpisodeType
+
+
+def test_resolve_accepts_base_types_and_aliases():
+    aliases = {'Email': EpisodeType.message, 'transcript': EpisodeType.message}
+
+    assert EpisodeType.resolve(EpisodeType.json, aliases) == EpisodeType.json
+    assert EpisodeType.resolve('text', aliases) == EpisodeType.text
+    assert EpisodeType.resolve('email', aliases) == EpisodeType.message
+    assert EpisodeType.resolve('Transcript', aliases) == EpisodeType.message
+
+
+def test_resolve_unknown_source_lists_accepted_values():
+    with pytest.raises(InvalidEpisodeTypeError) as exc_info:
+        EpisodeType.resolve('fax', {'email': EpisodeType.message})
+
+    assert 'fax' in exc_info.value.message
+    assert 'message, json, text, email' in exc_info.value.message
+
+
+@pytest.mark.asyncio
+async def test_add_episode_resolves_registered_alias(make_graphiti):
+    graphiti = make_graphiti(episode_type_aliases={'email': EpisodeType.message})
+    retrieve_episodes = AsyncMock(return_value=[])
+
+    with (
+        patch('graphiti_core.graphiti.retrieve_episodes', retrieve_episodes),
+        patch('graphiti_core.graphiti.extract_nodes', AsyncMock(return_value=[])),
+        patch(
+            'graphiti_core.graphiti.resolve_extracted_nodes', AsyncMock(return_value=([], {}, []))
+        ),
+        patch('graphiti_core.graphiti.extract_edges', AsyncMock(return_value=[])),
+        patch(
+            'graphiti_core.graphiti.resolve_extracted_edges', AsyncMock(return_value=([], []))
+        ),
+        patch('graphiti_core.graphiti.extract_attributes_from_nodes', AsyncMock(return_value=[])),
+        patch('graphiti_core.graphiti.add_nodes_and_edges_bulk', AsyncMock()),
+    ):
+        results = await graphiti.add_episode(
+            name='episode',
+            episode_body='user: hello',
+            source_description='inbox',
+            reference_time=datetime.now(timezone.utc),
+            source='email',
+        )
+
+        with pytest.raises(InvalidEpisodeTypeError):
+            await graphiti.add_episode(
+                name='episode',
+                episode_body='user: hello',
+                source_description='inbox',
+                reference_time=datetime.now(timezone.utc),
+                source='fax',
+            )
+
+    assert results.episode.source == EpisodeType.message
+    assert retrieve_episodes.await_args.args[4] == EpisodeType.message
@@ -0,0 +1,90 @@
+"""
+Copyright 2024, Zep Software, Inc.
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+"""
+
+import os
+from datetime import datetime, timedelta, timezone
+from unittest.mock import MagicMock
+from uuid import uuid4
+
+import pytest
+
+from graphiti_core.cross_encoder.client import CrossEncoderClient
+from graphiti_core.edges import EntityEdge
+from graphiti_core.embedder.client import EmbedderClient
+from graphiti_core.graphiti import Graphiti
+from graphiti_core.llm_client import LLMClient
+from graphiti_core.nodes import EntityNode
+from graphiti_core.utils.maintenance.graph_data_operations import clear_data
+
+pytestmark = pytest.mark.integration
+
+NEO4J_URI = os.getenv('NEO4J_URI', 'bolt://localhost:7687')
+NEO4J_USER = os.getenv('NEO4J_USER', 'neo4j')
+NEO4J_PASSWORD = os.getenv('NEO4J_PASSWORD', 'test')
+
+
+@pytest.mark.asyncio
+async def test_get_facts_between_entities():
+    graphiti = Graphiti(
+        NEO4J_URI,
+        NEO4J_USER,
+        NEO4J_PASSWORD,
+        llm_client=MagicMock(spec=LLMClient),
+        embedder=MagicMock(spec=EmbedderClient),
+        cross_encoder=MagicMock(spec=CrossEncoderClient),
+    )
+    group_id = f'facts_between_test_{uuid4().hex}'
+    now = datetime.now(timezone.utc).replace(microsecond=0)
+
+    try:
+        alice, bob, carol = [
+            EntityNode(name=name, group_id=group_id, labels=['Entity'], name_embedding=[0.5] * 1024)
+            for name in ['Alice', 'Bob', 'Carol']
+        ]
+        for node in [alice, bob, carol]:
+            await node.save(graphiti.driver)
+
+        # An invalidated fact in one direction, a valid one in the other, and an unrelated fact
+        edges = [
+            EntityEdge(
+                source_node_uuid=source.uuid,
+                target_node_uuid=target.uuid,
+                name=name,
+                fact=fact,
+                fact_embedding=[0.5] * 1024,
+                group_id=group_id,
+                created_at=now - timedelta(days=days_ago),
+                invalid_at=invalid_at,
+            )
+            for source, target, name, fact, days_ago, invalid_at in [
+                (alice, bob, 'WORKS_WITH', 'Alice works with Bob', 2, now),
+                (bob, alice, 'MANAGES', 'Bob manages Alice', 1, None),
+                (alice, carol, 'KNOWS', 'Alice knows Carol', 1, None),
+            ]
+        ]
+        for edge in edges:
+            await edge.save(graphiti.driver)
+
+        valid_facts = await graphiti.get_facts_between(alice.uuid, bob.uuid)
+        assert [edge.fact for edge in valid_facts] == ['Bob manages Alice']
+        assert valid_facts[0].source_node_uuid == bob.uuid
+
+        all_facts = await graphiti.get_facts_between(bob.uuid, alice.uuid, include_invalid=True)
+        assert [edge.fact for edge in all_facts] == ['Alice works with Bob', 'Bob manages Alice']
+        assert all_facts[0].invalid_at == now
+    finally:
+        await clear_data(graphiti.driver, [group_id])
+        await graphiti.close()
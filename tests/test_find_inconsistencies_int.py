@@ -0,0 +1,96 @@
+"""
+Copyright 2024, Zep Software, Inc.
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+"""
+
+import os
+from unittest.mock import MagicMock
+from uuid import uuid4
+
+import pytest
+
+from graphiti_core.cross_encoder.client import CrossEncoderClient
+from graphiti_core.edges import EntityEdge
+from graphiti_core.embedder.client import EmbedderClient
+from graphiti_core.graphiti import Graphiti
+from graphiti_core.llm_client import LLMClient
+from graphiti_core.nodes import EntityNode
+from graphiti_core.utils.datetime_utils import utc_now
+from graphiti_core.utils.maintenance.graph_data_operations import clear_data
+
+pytestmark = pytest.mark.integration
+
+NEO4J_URI = os.getenv('NEO4J_URI', 'bolt://localhost:7687')
+NEO4J_USER = os.getenv('NEO4J_USER', 'neo4j')
+NEO4J_PASSWORD = os.getenv('NEO4J_PASSWORD', 'test')
+
+
+@pytest.mark.asyncio
+async def test_two_valid_employers_are_reported():
+    graphiti = Graphiti(
+        NEO4J_URI,
+        NEO4J_USER,
+        NEO4J_PASSWORD,
+        llm_client=MagicMock(spec=LLMClient),
+        embedder=MagicMock(spec=EmbedderClient),
+        cross_encoder=MagicMock(spec=CrossEncoderClient),
+        single_valued_relations=['WORKS_AT'],
+    )
+    group_id = f'inconsistency_test_{uuid4().hex}'
+    now = utc_now()
+
+    try:
+        alice, bob, acme, globex, initech = [
+            EntityNode(name=name, group_id=group_id, labels=['Entity'], name_embedding=[0.5] * 1024)
+            for name in ['Alice', 'Bob', 'Acme', 'Globex', 'Initech']
+        ]
+        for node in [alice, bob, acme, globex, initech]:
+            await node.save(graphiti.driver)
+
+        alice_acme, alice_globex, alice_initech, bob_acme, alice_knows_bob = [
+            EntityEdge(
+                source_node_uuid=source.uuid,
+                target_node_uuid=target.uuid,
+                name=name,
+                fact=f'{source.name} {name} {target.name}',
+                fact_embedding=[0.5] * 1024,
+                group_id=group_id,
+                created_at=now,
+                invalid_at=invalid_at,
+            )
+            for source, target, name, invalid_at in [
+                (alice, acme, 'WORKS_AT', None),
+                (alice, globex, 'WORKS_AT', None),
+                # An employer Alice has left doesn't conflict with her current ones
+                (alice, initech, 'WORKS_AT', now),
+                (bob, acme, 'WORKS_AT', None),
+                (alice, bob, 'KNOWS', None),
+            ]
+        ]
+        for edge in [alice_acme, alice_globex, alice_initech, bob_acme, alice_knows_bob]:
+            await edge.save(graphiti.driver)
+
+        inconsistencies = await graphiti.find_inconsistencies(group_id)
+
+        assert len(inconsistencies) == 1
+        assert inconsistencies[0].source_node_uuid == alice.uuid
+        assert inconsistencies[0].relation == 'WORKS_AT'
+        assert sorted(edge.uuid for edge in inconsistencies[0].edges) == sorted(
+            [alice_acme.uuid, alice_globex.uuid]
+        )
+
+        assert await graphiti.find_inconsistencies(group_id, ['KNOWS']) == []
+    finally:
+        await clear_data(graphiti.driver, [group_id])
+        await graphiti.close()
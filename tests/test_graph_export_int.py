@@ -0,0 +1,131 @@
+"""
+Copyright 2024, Zep Software, Inc.
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+"""
+
+import os
+from datetime import datetime, timezone
+from unittest.mock import MagicMock
+from uuid import uuid4
+
+import pytest
+
+from graphiti_core.cross_encoder.client import CrossEncoderClient
+from graphiti_core.edges import EntityEdge, EpisodicEdge
+from graphiti_core.embedder.client import EmbedderClient
+from graphiti_core.errors import ImportConflictError
+from graphiti_core.graphiti import Graphiti
+from graphiti_core.llm_client import LLMClient
+from graphiti_core.nodes import EntityNode, EpisodeType, EpisodicNode
+from graphiti_core.utils.maintenance.graph_data_operations import (
+    ConflictPolicy,
+    GraphExport,
+    clear_data,
+)
+
+pytestmark = pytest.mark.integration
+
+NEO4J_URI = os.getenv('NEO4J_URI', 'bolt://localhost:7687')
+NEO4J_USER = os.getenv('NEO4J_USER', 'neo4j')
+NEO4J_PASSWORD = os.getenv('NEO4J_PASSWORD', 'test')
+
+
+def _normalized(export: GraphExport) -> dict:
+    data = export.model_dump(mode='json', exclude={'exported_at'})
+    for collection in data.values():
+        if isinstance(collection, list):
+            collection.sort(key=lambda item: item['uuid'])
+    for node in data['entity_nodes']:
+        node['labels'] = sorted(node['labels'])
+    return data
+
+
+async def _seed_group(graphiti: Graphiti, group_id: str):
+    now = datetime.now(timezone.utc)
+    episode = EpisodicNode(
+        name='Episode 1',
+        group_id=group_id,
+        source=EpisodeType.text,
+        source_description='test',
+        content='Alice knows Bob',
+        valid_at=now,
+    )
+    alice = EntityNode(
+        name='Alice', group_id=group_id, labels=['Entity'], name_embedding=[0.5] * 1024
+    )
+    bob = EntityNode(name='Bob', group_id=group_id, labels=['Entity'], name_embedding=[0.25] * 1024)
+    edge = EntityEdge(
+        source_node_uuid=alice.uuid,
+        target_node_uuid=bob.uuid,
+        name='KNOWS',
+        fact='Alice knows Bob',
+        fact_embedding=[0.5] * 1024,
+        group_id=group_id,
+        episodes=[episode.uuid],
+        created_at=now,
+        valid_at=now,
+    )
+    episode.entity_edges = [edge.uuid]
+
+    await episode.save(graphiti.driver)
+    await alice.save(graphiti.driver)
+    await bob.save(graphiti.driver)
+    await edge.save(graphiti.driver)
+    for node in [alice, bob]:
+        await EpisodicEdge(
+            source_node_uuid=episode.uuid,
+            target_node_uuid=node.uuid,
+            group_id=group_id,
+            created_at=now,
+        ).save(graphiti.driver)
+
+
+@pytest.mark.asyncio
+async def test_export_import_round_trip():
+    graphiti = Graphiti(
+        NEO4J_URI,
+        NEO4J_USER,
+        NEO4J_PASSWORD,
+        llm_client=MagicMock(spec=LLMClient),
+        embedder=MagicMock(spec=EmbedderClient),
+        cross_encoder=MagicMock(spec=CrossEncoderClient),
+    )
+    group_id = f'export_test_{uuid4().hex}'
+
+    try:
+        await _seed_group(graphiti, group_id)
+
+        exported = await graphiti.export_group(group_id)
+        assert len(exported.episodes) == 1
+        assert len(exported.entity_nodes) == 2
+        assert len(exported.entity_edges) == 1
+        assert len(exported.episodic_edges) == 2
+
+        serialized = exported.model_dump_json()
+
+        await clear_data(graphiti.driver, [group_id])
+        await graphiti.import_group(GraphExport.model_validate_json(serialized))
+
+        reimported = await graphiti.export_group(group_id)
+        assert _normalized(reimported) == _normalized(exported)
+
+        with pytest.raises(ImportConflictError):
+            await graphiti.import_group(exported)
+
+        # Skipping existing uuids makes a repeated import a no-op
+        await graphiti.import_group(exported, ConflictPolicy.skip)
+        assert _normalized(await graphiti.export_group(group_id)) == _normalized(exported)
+    finally:
+        await clear_data(graphiti.driver, [group_id])
+        await graphiti.close()
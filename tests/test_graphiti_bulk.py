@@ -0,0 +1,170 @@
+import asyncio
+import time
+from datetime import datetime, timezone
+from unittest.mock import patch
+
+import pytest
+
+from graphiti_core.edges import EntityEdge
+from graphiti_core.nodes import EntityNode, EpisodeType
+from graphiti_core.utils.bulk_utils import RawEpisode
+
+
+STEP_SECONDS = 0.2
+
+
+def _bulk_episodes(*contents: str) -> list[RawEpisode]:
+    return [
+        RawEpisode(
+            name=f'Episode {i}',
+            content=content,
+            source_description='test',
+            source=EpisodeType.text,
+            reference_time=datetime(2024, 1, 1, tzinfo=timezone.utc),
+        )
+        for i, content in enumerate(contents)
+    ]
+
+
+async def _extract_nodes(clients, episode, *args, **kwargs) -> list[EntityNode]:
+    return [
+        EntityNode(name=name, group_id=episode.group_id, labels=['Entity'])
+        for name in episode.content.split(' knows ')
+    ]
+
+
+async def _extract_edges(clients, episode, nodes, *args, **kwargs) -> list[EntityEdge]:
+    return [
+        EntityEdge(
+            source_node_uuid=nodes[0].uuid,
+            target_node_uuid=nodes[1].uuid,
+            name='KNOWS',
+            fact=episode.content,
+            group_id=episode.group_id,
+            episodes=[episode.uuid],
+            created_at=episode.created_at,
+        )
+    ]
+
+
+async def _identity_nodes(driver, llm_client, nodes, *args, **kwargs):
+    return nodes, {}
+
+
+@pytest.mark.asyncio
+async def test_add_episode_bulk_attributes_results_to_each_episode(make_graphiti):
+    graphiti = make_graphiti(max_coroutines=1)
+    bulk_episodes = _bulk_episodes('Alice knows Bob', 'Carol knows Dave')
+
+    with (
+        patch('graphiti_core.utils.bulk_utils.extract_nodes', side_effect=_extract_nodes),
+        patch('graphiti_core.utils.bulk_utils.extract_edges', side_effect=_extract_edges),
+        patch('graphiti_core.graphiti.dedupe_nodes_bulk', side_effect=_identity_nodes),
+        patch(
+            'graphiti_core.graphiti.extract_edge_dates_bulk',
+            side_effect=lambda llm_client, edges, pairs: edges,
+        ),
+        patch(
+            'graphiti_core.graphiti.dedupe_edges_bulk',
+            side_effect=lambda driver, llm_client, edges: edges,
+        ),
+    ):
+        bulk_results = await graphiti.add_episode_bulk(bulk_episodes, group_id='group_1')
+
+    results = bulk_results.results
+    assert bulk_results.errors == []
+    assert [result.episode.name for result in results] == ['Episode 0', 'Episode 1']
+    assert [node.name for node in results[0].nodes] == ['Alice', 'Bob']
+    assert [node.name for node in results[1].nodes] == ['Carol', 'Dave']
+    assert [edge.fact for edge in results[0].edges] == ['Alice knows Bob']
+    assert [edge.fact for edge in results[1].edges] == ['Carol knows Dave']
+
+
+@pytest.mark.asyncio
+async def test_independent_bulk_steps_run_concurrently(make_graphiti):
+    graphiti = make_graphiti(max_coroutines=None)
+
+    # Alice's nodes and Carol's edges are slow to extract
+    async def slow_extract_nodes(clients, episode, *args, **kwargs):
+        if episode.content.startswith('Alice'):
+            await asyncio.sleep(STEP_SECONDS)
+        return await _extract_nodes(clients, episode)
+
+    async def slow_extract_edges(clients, episode, nodes, *args, **kwargs):
+        if episode.content.startswith('Carol'):
+            await asyncio.sleep(STEP_SECONDS)
+        return await _extract_edges(clients, episode, nodes)
+
+    async def slow_dedupe_nodes(driver, llm_client, nodes, *args, **kwargs):
+        await asyncio.sleep(STEP_SECONDS)
+        return nodes, {}
+
+    async def slow_extract_edge_dates(llm_client, edges, pairs):
+        await asyncio.sleep(STEP_SECONDS)
+        return edges
+
+    with (
+        patch('graphiti_core.utils.bulk_utils.extract_nodes', side_effect=slow_extract_nodes),
+        patch('graphiti_core.utils.bulk_utils.extract_edges', side_effect=slow_extract_edges),
+        patch('graphiti_core.graphiti.dedupe_nodes_bulk', side_effect=slow_dedupe_nodes),
+        patch(
+            'graphiti_core.graphiti.extract_edge_dates_bulk', side_effect=slow_extract_edge_dates
+        ),
+        patch(
+            'graphiti_core.graphiti.dedupe_edges_bulk',
+            side_effect=lambda driver, llm_client, edges: edges,
+        ),
+    ):
+        start = time.monotonic()
+        bulk_results = await graphiti.add_episode_bulk(
+            _bulk_episodes('Alice knows Bob', 'Carol knows Dave'), group_id='group_1'
+        )
+        elapsed = time.monotonic() - start
+
+    assert [edge.fact for result in bulk_results.results for edge in result.edges] == [
+        'Alice knows Bob',
+        'Carol knows Dave',
+    ]
+    # Run serially, the four slow steps would take 4 * STEP_SECONDS; extraction overlaps across
+    # episodes and node dedupe overlaps with edge date extraction, leaving two steps
+    assert elapsed < 3 * STEP_SECONDS
+
+
+@pytest.mark.asyncio
+async def test_failed_episode_is_reported_and_the_rest_are_added(make_graphiti):
+    graphiti = make_graphiti(max_coroutines=1)
+
+    async def extract_nodes_or_fail(clients, episode, *args, **kwargs):
+        if 'knows' not in episode.content:
+            raise ValueError('malformed episode')
+        return await _extract_nodes(clients, episode)
+
+    with (
+        patch('graphiti_core.utils.bulk_utils.extract_nodes', side_effect=extract_nodes_or_fail),
+        patch('graphiti_core.utils.bulk_utils.extract_edges', side_effect=_extract_edges),
+        patch('graphiti_core.graphiti.dedupe_nodes_bulk', side_effect=_identity_nodes),
+        patch(
+            'graphiti_core.graphiti.extract_edge_dates_bulk',
+            side_effect=lambda llm_client, edges, pairs: edges,
+        ),
+        patch(
+            'graphiti_core.graphiti.dedupe_edges_bulk',
+            side_effect=lambda driver, llm_client, edges: edges,
+        ),
+    ):
+        bulk_results = await graphiti.add_episode_bulk(
+            _bulk_episodes('Alice knows Bob', '{"broken":', 'Carol knows Dave'),
+            group_id='group_1',
+        )
+
+    assert [result.episode.name for result in bulk_results.results] == ['Episode 0', 'Episode 2']
+    [error] = bulk_results.errors
+    assert (error.index, error.name) == (1, 'Episode 1')
+    assert 'malformed episode' in error.error
+    # The failed episode was saved up front, so it is deleted again
+    deleted_uuids = [
+        call.kwargs['uuid']
+        for call in graphiti.driver.execute_query.call_args_list
+        if call.args and 'DETACH DELETE' in call.args[0]
+    ]
+    assert len(deleted_uuids) == 1
@@ -0,0 +1,91 @@
+from datetime import datetime, timezone
+from unittest.mock import AsyncMock, patch
+
+import pytest
+
+from graphiti_core.graphiti import Graphiti
+from graphiti_core.nodes import EntityNode
+
+
+async def _extract_capitalized(clients, episode, *args, **kwargs) -> list[EntityNode]:
+    # Stand-in for the LLM: every capitalized word in the content is an entity
+    names = dict.fromkeys(word for word in episode.content.split() if word[0].isupper())
+    return [EntityNode(name=name, group_id=episode.group_id, labels=['Entity']) for name in names]
+
+
+async def _resolve_nodes(clients, nodes, *args, **kwargs):
+    return nodes, {node.uuid: node.uuid for node in nodes}, []
+
+
+async def _add_episode(graphiti: Graphiti, body: str):
+    mock_extract_nodes = AsyncMock(side_effect=_extract_capitalized)
+    with (
+        patch('graphiti_core.graphiti.retrieve_episodes', AsyncMock(return_value=[])),
+        patch('graphiti_core.graphiti.extract_nodes', mock_extract_nodes),
+        patch('graphiti_core.graphiti.resolve_extracted_nodes', side_effect=_resolve_nodes),
+        patch('graphiti_core.graphiti.extract_edges', AsyncMock(return_value=[])),
+        patch(
+            'graphiti_core.graphiti.resolve_extracted_edges', AsyncMock(return_value=([], []))
+        ),
+        patch(
+            'graphiti_core.graphiti.extract_attributes_from_nodes',
+            side_effect=lambda clients, nodes, *args: nodes,
+        ),
+        patch('graphiti_core.graphiti.add_nodes_and_edges_bulk', AsyncMock()),
+    ):
+        result = await graphiti.add_episode(
+            name='long document',
+            episode_body=body,
+            source_description='test',
+            reference_time=datetime.now(timezone.utc),
+            group_id='group_1',
+        )
+    return result, mock_extract_nodes
+
+
+@pytest.mark.asyncio
+async def test_long_episode_is_extracted_in_chunks(make_graphiti):
+    graphiti = make_graphiti(max_episode_chars=500, episode_chunk_overlap=100)
+    filler = ' '.join(['lorem ipsum dolor sit amet'] * 100)
+    body = f'Alice founded Acme. {filler} Acme later hired Zed.'
+
+    result, mock_extract_nodes = await _add_episode(graphiti, body)
+
+    assert mock_extract_nodes.await_count > 1
+    names = [node.name for node in result.nodes]
+    # Entities from both ends of the document are extracted, and merged across chunks
+    assert 'Alice' in names
+    assert 'Zed' in names
+    assert names.count('Acme') == 1
+    # The full content is still persisted as a single episode
+    assert result.episode.content == body
+
+
+@pytest.mark.asyncio
+async def test_chunks_are_extracted_with_the_episode_clients(make_graphiti):
+    graphiti = make_graphiti(max_episode_chars=500, episode_chunk_overlap=100)
+    # e.g. the clients without embeddings, after the episode failed to embed
+    clients = graphiti.clients.model_copy(update={'embeddings_enabled': False})
+    filler = ' '.join(['lorem ipsum dolor sit amet'] * 100)
+
+    with patch.object(
+        graphiti, '_episode_ingest_clients', AsyncMock(return_value=(clients, None))
+    ):
+        _, mock_extract_nodes = await _add_episode(graphiti, f'Alice founded Acme. {filler}')
+
+    assert mock_extract_nodes.await_count > 1
+    assert all(call.args[0] is clients for call in mock_extract_nodes.await_args_list)
+
+
+@pytest.mark.asyncio
+async def test_short_episode_is_not_chunked(make_graphiti):
+    graphiti = make_graphiti(max_episode_chars=500)
+
+    _, mock_extract_nodes = await _add_episode(graphiti, 'Alice founded Acme.')
+
+    assert mock_extract_nodes.await_count == 1
+
+
+def test_overlap_must_be_smaller_than_max_episode_chars(make_graphiti):
+    with pytest.raises(ValueError):
+        make_graphiti(max_episode_chars=100, episode_chunk_overlap=100)
@@ -0,0 +1,88 @@
+from datetime import datetime, timezone
+from unittest.mock import AsyncMock, MagicMock, patch
+
+import pytest
+
+from graphiti_core.edges import EntityEdge
+from graphiti_core.nodes import EntityNode
+from graphiti_core.prompts.dedupe_nodes import NodeResolutions
+from graphiti_core.search.search_config import (
+    EdgeSearchMethod,
+    EpisodeSearchMethod,
+    NodeReranker,
+    SearchConfig,
+    without_embeddings,
+)
+from graphiti_core.search.search_config_recipes import (
+    COMBINED_HYBRID_SEARCH_MMR,
+    EDGE_HYBRID_SEARCH_RRF,
+)
+from graphiti_core.testing import MockEmbedderClient, MockLLMClient
+from graphiti_core.utils.bulk_utils import add_nodes_and_edges_bulk_tx
+
+
+@pytest.mark.asyncio
+async def test_ingest_makes_no_embedding_calls_when_disabled(make_graphiti):
+    llm_client = MockLLMClient(default_response={'summary': 'summary'}).add_response(
+        NodeResolutions,
+        {
+            'entity_resolutions': [
+                {'id': 0, 'name': 'Alice', 'duplicate_idx': -1, 'additional_duplicates': []},
+                {'id': 1, 'name': 'Bob', 'duplicate_idx': -1, 'additional_duplicates': []},
+            ]
+        },
+    )
+    embedder = MockEmbedderClient()
+    graphiti = make_graphiti(llm_client=llm_client, embedder=embedder, enable_embeddings=False)
+
+    alice = EntityNode(name='Alice', group_id='group_1', labels=['Entity'])
+    bob = EntityNode(name='Bob', group_id='group_1', labels=['Entity'])
+    edge = EntityEdge(
+        source_node_uuid=alice.uuid,
+        target_node_uuid=bob.uuid,
+        name='KNOWS',
+        fact='Alice knows Bob',
+        group_id='group_1',
+        created_at=datetime.now(timezone.utc),
+    )
+    tx = MagicMock()
+    tx.run = AsyncMock()
+
+    async def save_bulk(driver, *args):
+        await add_nodes_and_edges_bulk_tx(tx, *args, driver=driver)
+
+    with (
+        patch('graphiti_core.graphiti.retrieve_episodes', AsyncMock(return_value=[])),
+        patch('graphiti_core.graphiti.extract_nodes', AsyncMock(return_value=[alice, bob])),
+        patch('graphiti_core.graphiti.extract_edges', AsyncMock(return_value=[edge])),
+        patch('graphiti_core.graphiti.add_nodes_and_edges_bulk', side_effect=save_bulk),
+    ):
+        result = await graphiti.add_episode(
+            name='episode',
+            episode_body='Alice knows Bob',
+            source_description='test',
+            reference_time=datetime.now(timezone.utc),
+            group_id='group_1',
+        )
+
+    assert [node.name for node in result.nodes] == ['Alice', 'Bob']
+    assert [edge.fact for edge in result.edges] == ['Alice knows Bob']
+    assert all(node.name_embedding is None for node in result.nodes)
+    assert embedder.inputs == []
+
+
+def test_without_embeddings_only_keeps_keyword_search():
+    edge_config = without_embeddings(EDGE_HYBRID_SEARCH_RRF).edge_config
+    assert edge_config is not None
+    assert edge_config.search_methods == [EdgeSearchMethod.bm25]
+
+    node_config = without_embeddings(COMBINED_HYBRID_SEARCH_MMR).node_config
+    assert node_config is not None
+    assert node_config.reranker == NodeReranker.rrf
+
+    episodes = SearchConfig.episodes_only()
+    assert episodes.episode_config is not None
+    episodes.episode_config.search_methods.append(EpisodeSearchMethod.cosine_similarity)
+    episode_config = without_embeddings(episodes).episode_config
+    assert episode_config is not None
+    assert episode_config.search_methods == [EpisodeSearchMethod.bm25]
@@ -82,7 +82,7 @@ async def test_graphiti_falkordb_init():
         password=FALKORDB_PASSWORD
     )
 
-    graphiti = Graphiti(graph_driver=falkor_driver)
+    graphiti = Graphiti(graph_driver=falkor_driver, allow_cross_group_search=True)
 
     results = await graphiti.search_(query='Who is the user?')
 
@@ -63,7 +63,7 @@ def setup_logging():
 @pytest.mark.asyncio
 async def test_graphiti_init():
     logger = setup_logging()
-    graphiti = Graphiti(NEO4J_URI, NEO4j_USER, NEO4j_PASSWORD)
+    graphiti = Graphiti(NEO4J_URI, NEO4j_USER, NEO4j_PASSWORD, allow_cross_group_search=True)
 
     results = await graphiti.search_(query='Who is the user?')
 
@@ -0,0 +1,71 @@
+from datetime import datetime, timezone
+from unittest.mock import AsyncMock, MagicMock, patch
+
+import pytest
+
+from graphiti_core.cross_encoder.openai_reranker_client import OpenAIRerankerClient
+from graphiti_core.llm_client.config import LLMConfig
+from graphiti_core.llm_client.openai_client import OpenAIClient
+from graphiti_core.prompts.models import Message
+from graphiti_core.search.search_config import SearchResults
+
+
+@pytest.mark.asyncio
+async def test_model_override_is_used_for_a_single_add_episode(make_graphiti):
+    completion = MagicMock()
+    completion.choices[0].message.content = '{}'
+    openai_client = MagicMock()
+    openai_client.chat.completions.create = AsyncMock(return_value=completion)
+    llm_client = OpenAIClient(config=LLMConfig(model='default-model'), client=openai_client)
+    graphiti = make_graphiti(llm_client=llm_client, enable_embeddings=False)
+
+    async def extract_nodes(clients, *args):
+        await clients.llm_client.generate_response([Message(role='user', content='extract')])
+        return []
+
+    with (
+        patch('graphiti_core.graphiti.retrieve_episodes', AsyncMock(return_value=[])),
+        patch('graphiti_core.graphiti.extract_nodes', side_effect=extract_nodes),
+        patch('graphiti_core.graphiti.extract_edges', AsyncMock(return_value=[])),
+        patch(
+            'graphiti_core.graphiti.resolve_extracted_nodes',
+            AsyncMock(return_value=([], {}, [])),
+        ),
+        patch('graphiti_core.graphiti.resolve_extracted_edges', AsyncMock(return_value=([], []))),
+        patch('graphiti_core.graphiti.extract_attributes_from_nodes', AsyncMock(return_value=[])),
+        patch('graphiti_core.graphiti.add_nodes_and_edges_bulk', AsyncMock()),
+    ):
+        for model_override in ['override-model', None]:
+            await graphiti.add_episode(
+                name='episode',
+                episode_body='Alice knows Bob',
+                source_description='test',
+                reference_time=datetime.now(timezone.utc),
+                group_id='group_1',
+                model_override=model_override,
+            )
+
+    models = [call.kwargs['model'] for call in openai_client.chat.completions.create.call_args_list]
+    assert models == ['override-model', 'default-model']
+
+
+@pytest.mark.asyncio
+async def test_model_override_is_used_by_the_reranker_and_the_search_cache(make_graphiti):
+    completion = MagicMock()
+    completion.choices[0].logprobs = None
+    openai_client = MagicMock()
+    openai_client.chat.completions.create = AsyncMock(return_value=completion)
+    reranker = OpenAIRerankerClient(client=openai_client)
+    graphiti = make_graphiti(cross_encoder=reranker, enable_embeddings=False, search_cache_ttl=10.0)
+
+    async def search(clients, query, *args, **kwargs):
+        await clients.cross_encoder.rank(query, ['Alice knows Bob'])
+        return SearchResults()
+
+    with patch('graphiti_core.graphiti.search', side_effect=search):
+        for model_override in ['override-model', None, 'override-model']:
+            await graphiti.search_('alice', group_ids=['group_1'], model_override=model_override)
+
+    # The third search is served from the cache entry of the first
+    models = [call.kwargs['model'] for call in openai_client.chat.completions.create.call_args_list]
+    assert models == ['override-model', 'gpt-4.1-nano']
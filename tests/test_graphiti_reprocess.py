@@ -0,0 +1,119 @@
+from datetime import datetime, timezone
+from unittest.mock import AsyncMock, patch
+
+import pytest
+
+from graphiti_core.edges import EntityEdge
+from graphiti_core.errors import ReprocessingDisabledError
+from graphiti_core.graphiti import Graphiti
+from graphiti_core.nodes import EntityNode, EpisodeType, EpisodicNode
+
+
+@pytest.fixture
+def stored_episode():
+    return EpisodicNode(
+        uuid='episode_1',
+        name='Episode 1',
+        group_id='group_1',
+        source=EpisodeType.text,
+        source_description='test',
+        content='Alice works with Bob at Acme.',
+        valid_at=datetime(2024, 1, 1, tzinfo=timezone.utc),
+        entity_edges=['stale_edge'],
+    )
+
+
+@pytest.mark.asyncio
+async def test_reprocess_episodes_requires_flag(make_graphiti):
+    graphiti = make_graphiti()
+
+    with pytest.raises(ReprocessingDisabledError):
+        await graphiti.reprocess_episodes('group_1')
+
+
+@pytest.mark.asyncio
+async def test_reprocess_episodes_requires_raw_content(make_graphiti):
+    graphiti = make_graphiti(allow_episode_reprocessing=True, store_raw_episode_content=False)
+
+    with pytest.raises(ReprocessingDisabledError):
+        await graphiti.reprocess_episodes('group_1')
+
+
+@pytest.mark.asyncio
+async def test_reprocess_episodes_adds_newly_extracted_entity(make_graphiti, stored_episode):
+    graphiti = make_graphiti(allow_episode_reprocessing=True)
+
+    # The graph as it was built with the old prompts only knows about Alice
+    alice = EntityNode(uuid='alice', name='Alice', group_id='group_1', labels=['Entity'])
+    graph_nodes: dict[str, EntityNode] = {alice.uuid: alice}
+
+    # The updated extraction now also finds Bob
+    bob = EntityNode(uuid='bob', name='Bob', group_id='group_1', labels=['Entity'])
+    extracted_nodes = [alice, bob]
+
+    stale_edge = EntityEdge(
+        uuid='stale_edge',
+        source_node_uuid='alice',
+        target_node_uuid='alice',
+        name='STALE',
+        fact='Alice is stale',
+        group_id='group_1',
+        episodes=['episode_1'],
+        created_at=datetime(2024, 1, 1, tzinfo=timezone.utc),
+    )
+
+    async def fake_bulk_save(driver, episodes, episodic_edges, nodes, edges, embedder):
+        for node in nodes:
+            graph_nodes[node.uuid] = node
+
+    with (
+        patch.object(
+            EpisodicNode, 'get_by_group_ids', AsyncMock(return_value=[stored_episode])
+        ),
+        patch.object(EpisodicNode, 'get_by_uuid', AsyncMock(return_value=stored_episode)),
+        patch.object(EntityEdge, 'get_by_uuids', AsyncMock(return_value=[stale_edge])),
+        patch.object(EntityEdge, 'save', AsyncMock()) as mock_edge_save,
+        patch('graphiti_core.graphiti.retrieve_episodes', AsyncMock(return_value=[])),
+        patch('graphiti_core.graphiti.extract_nodes', AsyncMock(return_value=extracted_nodes)),
+        patch(
+            'graphiti_core.graphiti.resolve_extracted_nodes',
+            AsyncMock(return_value=(extracted_nodes, {}, [])),
+        ),
+        patch('graphiti_core.graphiti.extract_edges', AsyncMock(return_value=[])),
+        patch(
+            'graphiti_core.graphiti.resolve_extracted_edges', AsyncMock(return_value=([], []))
+        ),
+        patch(
+            'graphiti_core.graphiti.extract_attributes_from_nodes',
+            AsyncMock(return_value=extracted_nodes),
+        ),
+        patch('graphiti_core.graphiti.add_nodes_and_edges_bulk', side_effect=fake_bulk_save),
+    ):
+        results = await graphiti.reprocess_episodes('group_1')
+
+    assert len(results) == 1
+    assert results[0].episode.uuid == 'episode_1'
+    assert 'bob' in graph_nodes
+    assert {node.uuid for node in results[0].nodes} == {'alice', 'bob'}
+
+    # The fact that is no longer extracted is expired, not deleted
+    assert stale_edge.expired_at is not None
+    mock_edge_save.assert_awaited_once()
+
+
+@pytest.mark.asyncio
+async def test_reprocess_episodes_respects_since(make_graphiti, stored_episode):
+    graphiti = make_graphiti(allow_episode_reprocessing=True)
+
+    with (
+        patch.object(
+            EpisodicNode, 'get_by_group_ids', AsyncMock(return_value=[stored_episode])
+        ),
+        patch.object(Graphiti, 'add_episode', AsyncMock()) as mock_add_episode,
+    ):
+        results = await graphiti.reprocess_episodes(
+            'group_1', since=datetime(2025, 1, 1, tzinfo=timezone.utc)
+        )
+
+    assert results == []
+    mock_add_episode.assert_not_called()
@@ -0,0 +1,53 @@
+from datetime import datetime, timezone
+from unittest.mock import AsyncMock, patch
+
+import pytest
+
+from graphiti_core.graphiti import Graphiti
+
+
+async def _stored_content(graphiti: Graphiti, body: str, store_raw: bool | None) -> str:
+    save = AsyncMock()
+    with (
+        patch('graphiti_core.graphiti.retrieve_episodes', AsyncMock(return_value=[])),
+        patch('graphiti_core.graphiti.extract_nodes', AsyncMock(return_value=[])),
+        patch(
+            'graphiti_core.graphiti.resolve_extracted_nodes', AsyncMock(return_value=([], {}, []))
+        ),
+        patch('graphiti_core.graphiti.extract_edges', AsyncMock(return_value=[])),
+        patch(
+            'graphiti_core.graphiti.resolve_extracted_edges', AsyncMock(return_value=([], []))
+        ),
+        patch('graphiti_core.graphiti.extract_attributes_from_nodes', AsyncMock(return_value=[])),
+        patch('graphiti_core.graphiti.add_nodes_and_edges_bulk', save),
+    ):
+        await graphiti.add_episode(
+            name='episode',
+            episode_body=body,
+            source_description='test',
+            reference_time=datetime.now(timezone.utc),
+            group_id='group_1',
+            store_raw=store_raw,
+        )
+    [episode] = save.call_args.args[1]
+    return episode.content
+
+
+@pytest.mark.asyncio
+async def test_store_raw_overrides_the_instance_setting_per_episode(make_graphiti):
+    graphiti = make_graphiti()
+
+    document = await _stored_content(graphiti, 'Quarterly report', store_raw=True)
+    chat = await _stored_content(graphiti, 'My password is hunter2', store_raw=False)
+
+    assert document == 'Quarterly report'
+    assert chat == ''
+
+
+@pytest.mark.asyncio
+async def test_store_raw_defaults_to_the_instance_setting(make_graphiti):
+    assert await _stored_content(make_graphiti(), 'kept', store_raw=None) == 'kept'
+
+    graphiti = make_graphiti(store_raw_episode_content=False)
+    assert await _stored_content(graphiti, 'dropped', store_raw=None) == ''
+    assert await _stored_content(graphiti, 'kept', store_raw=True) == 'kept'
@@ -0,0 +1,130 @@
+from contextlib import ExitStack
+from datetime import datetime, timezone
+from unittest.mock import AsyncMock, patch
+
+import pytest
+
+from graphiti_core.edges import EntityEdge
+from graphiti_core.nodes import EntityNode, EpisodeType, EpisodicNode
+
+REFERENCE_TIME = datetime(2024, 1, 1, tzinfo=timezone.utc)
+
+
+def _edge(uuid: str, target: str, fact: str) -> EntityEdge:
+    return EntityEdge(
+        uuid=uuid,
+        source_node_uuid='alice',
+        target_node_uuid=target,
+        name='RELATES_TO',
+        fact=fact,
+        group_id='group_1',
+        episodes=['episode_1'],
+        created_at=REFERENCE_TIME,
+    )
+
+
+def _patch_pipeline(stack: ExitStack, nodes: list[EntityNode], edges: list[EntityEdge]):
+    for pipeline_patch in (
+        patch('graphiti_core.graphiti.retrieve_episodes', AsyncMock(return_value=[])),
+        patch('graphiti_core.graphiti.extract_nodes', AsyncMock(return_value=nodes)),
+        patch(
+            'graphiti_core.graphiti.resolve_extracted_nodes',
+            AsyncMock(return_value=(nodes, {}, [])),
+        ),
+        patch('graphiti_core.graphiti.extract_edges', AsyncMock(return_value=edges)),
+        patch(
+            'graphiti_core.graphiti.resolve_extracted_edges', AsyncMock(return_value=(edges, []))
+        ),
+        patch(
+            'graphiti_core.graphiti.extract_attributes_from_nodes', AsyncMock(return_value=nodes)
+        ),
+        patch('graphiti_core.graphiti.add_nodes_and_edges_bulk', AsyncMock()),
+    ):
+        stack.enter_context(pipeline_patch)
+
+
+@pytest.mark.asyncio
+async def test_upsert_episode_expires_edge_of_dropped_entity(make_graphiti):
+    graphiti = make_graphiti()
+    stored_episode = EpisodicNode(
+        uuid='episode_1',
+        name='Episode 1',
+        group_id='group_1',
+        source=EpisodeType.text,
+        source_description='chat',
+        content='Alice works with Bob at Acme.',
+        valid_at=REFERENCE_TIME,
+        entity_edges=['bob_edge', 'acme_edge'],
+        external_id='msg-1',
+    )
+    bob_edge = _edge('bob_edge', 'bob', 'Alice works with Bob')
+    acme_edge = _edge('acme_edge', 'acme', 'Alice works at Acme')
+
+    # The corrected content no longer mentions Bob
+    nodes = [
+        EntityNode(uuid='alice', name='Alice', group_id='group_1', labels=['Entity']),
+        EntityNode(uuid='acme', name='Acme', group_id='group_1', labels=['Entity']),
+    ]
+
+    with (
+        patch.object(
+            EpisodicNode, 'get_by_external_id', AsyncMock(return_value=stored_episode)
+        ),
+        patch.object(EpisodicNode, 'get_by_uuid', AsyncMock(return_value=stored_episode)),
+        patch.object(EpisodicNode, 'save', AsyncMock()) as mock_episode_save,
+        patch.object(EntityEdge, 'get_by_uuids', AsyncMock(return_value=[bob_edge])),
+        patch.object(EntityEdge, 'save', AsyncMock()) as mock_edge_save,
+        ExitStack() as stack,
+    ):
+        _patch_pipeline(stack, nodes, [acme_edge])
+        result = await graphiti.upsert_episode(
+            external_id='msg-1',
+            name='Episode 1',
+            episode_body='Alice works at Acme.',
+            source_description='chat',
+            reference_time=REFERENCE_TIME,
+            source=EpisodeType.text,
+            group_id='group_1',
+        )
+
+    assert result.episode.uuid == 'episode_1'
+    assert result.episode.content == 'Alice works at Acme.'
+    assert result.episode.entity_edges == ['acme_edge']
+    mock_episode_save.assert_awaited_once()
+
+    assert bob_edge.expired_at is not None
+    assert acme_edge.expired_at is None
+    mock_edge_save.assert_awaited_once()
+
+
+@pytest.mark.asyncio
+async def test_upsert_episode_creates_new_episode_with_external_id(make_graphiti):
+    graphiti = make_graphiti()
+    saved: list[EpisodicNode] = []
+
+    async def save(episode, driver):
+        saved.append(episode)
+
+    async def get_by_uuid(driver, uuid):
+        return next(episode for episode in saved if episode.uuid == uuid)
+
+    with (
+        patch.object(EpisodicNode, 'get_by_external_id', AsyncMock(return_value=None)),
+        patch.object(EpisodicNode, 'get_by_uuid', side_effect=get_by_uuid),
+        patch.object(EpisodicNode, 'save', save),
+        patch.object(EntityEdge, 'get_by_uuids', AsyncMock()) as mock_get_edges,
+        ExitStack() as stack,
+    ):
+        _patch_pipeline(stack, [], [])
+        result = await graphiti.upsert_episode(
+            external_id='msg-2',
+            name='Episode 2',
+            episode_body='Alice joined Acme.',
+            source_description='chat',
+            reference_time=REFERENCE_TIME,
+            group_id='group_1',
+        )
+
+    assert result.episode.external_id == 'msg-2'
+    assert result.episode.content == 'Alice joined Acme.'
+    mock_get_edges.assert_not_called()
@@ -0,0 +1,147 @@
+import asyncio
+from datetime import datetime, timezone
+from unittest.mock import AsyncMock, patch
+
+import pytest
+
+from graphiti_core.graphiti import Graphiti
+from graphiti_core.helpers import KeyedLock
+from graphiti_core.nodes import EntityNode
+from graphiti_core.prompts.dedupe_nodes import NodeResolutions
+from graphiti_core.testing import MockLLMClient
+
+NOW = datetime(2024, 6, 1, tzinfo=timezone.utc)
+
+
+class FakeGraph:
+    """Keeps the saved entities, and finds them again by name during dedup."""
+
+    def __init__(self):
+        self.nodes: list[EntityNode] = []
+
+    async def execute_query(self, query, **kwargs):
+        if 'node_name_and_summary' in query:
+            records = [
+                {
+                    'uuid': node.uuid,
+                    'name': node.name,
+                    'group_id': node.group_id,
+                    'labels': node.labels,
+                    'created_at': node.created_at.isoformat(),
+                    'summary': node.summary,
+                    'attributes': {},
+                }
+                for node in self.nodes
+            ]
+            return records, None, None
+        return [], None, None
+
+    async def save(self, driver, episodes, episodic_edges, nodes, *args):
+        # Saving takes a while, so that a concurrent episode could resolve in the meantime
+        await asyncio.sleep(0.01)
+        self.nodes.extend(node for node in nodes if node not in self.nodes)
+
+
+@pytest.fixture
+def graph(mock_driver) -> FakeGraph:
+    graph = FakeGraph()
+    mock_driver.execute_query.side_effect = graph.execute_query
+    return graph
+
+
+def _llm_client() -> MockLLMClient:
+    return MockLLMClient(default_response={'summary': 'summary'}).add_response(
+        NodeResolutions,
+        {
+            'entity_resolutions': [
+                {'id': 0, 'name': 'Alice', 'duplicate_idx': -1, 'additional_duplicates': []}
+            ]
+        },
+    )
+
+
+async def _add_concurrently(
+    graphiti: Graphiti,
+    graph: FakeGraph,
+    group_ids: list[str],
+    extract_edges: AsyncMock | None = None,
+):
+    async def extract_alice(clients, episode, *args, **kwargs):
+        return [EntityNode(name='Alice', group_id=episode.group_id, labels=['Entity'])]
+
+    with (
+        patch('graphiti_core.graphiti.retrieve_episodes', AsyncMock(return_value=[])),
+        patch('graphiti_core.graphiti.extract_nodes', AsyncMock(side_effect=extract_alice)),
+        patch(
+            'graphiti_core.graphiti.extract_edges', extract_edges or AsyncMock(return_value=[])
+        ),
+        patch('graphiti_core.graphiti.add_nodes_and_edges_bulk', AsyncMock(side_effect=graph.save)),
+    ):
+        await asyncio.gather(
+            *[
+                graphiti.add_episode(
+                    name=f'episode {i}',
+                    episode_body='Alice said hello',
+                    source_description='test',
+                    reference_time=NOW,
+                    group_id=group_id,
+                )
+                for i, group_id in enumerate(group_ids)
+            ]
+        )
+
+
+@pytest.mark.asyncio
+async def test_concurrent_episodes_of_a_group_create_an_entity_once(make_graphiti, graph):
+    graphiti = make_graphiti(llm_client=_llm_client(), enable_embeddings=False)
+
+    await _add_concurrently(graphiti, graph, ['group_1', 'group_1'])
+
+    assert [node.name for node in graph.nodes] == ['Alice']
+
+
+@pytest.mark.asyncio
+async def test_concurrent_episodes_race_without_serialization(make_graphiti, graph):
+    graphiti = make_graphiti(
+        llm_client=_llm_client(), enable_embeddings=False, serialize_group_ingestion=False
+    )
+
+    await _add_concurrently(graphiti, graph, ['group_1', 'group_1'])
+
+    assert [node.name for node in graph.nodes] == ['Alice', 'Alice']
+
+
+@pytest.mark.asyncio
+async def test_concurrent_episodes_of_a_group_extract_facts_concurrently(make_graphiti, graph):
+    graphiti = make_graphiti(llm_client=_llm_client(), enable_embeddings=False)
+    events: list[str] = []
+
+    async def extract_edges(clients, episode, *args, **kwargs):
+        events.append(f'{episode.name} start')
+        await asyncio.sleep(0.01)
+        events.append(f'{episode.name} end')
+        return []
+
+    await _add_concurrently(
+        graphiti, graph, ['group_1', 'group_1'], AsyncMock(side_effect=extract_edges)
+    )
+
+    assert events.index('episode 1 start') < events.index('episode 0 end')
+
+
+@pytest.mark.asyncio
+async def test_keyed_lock_only_serializes_the_same_key():
+    lock = KeyedLock()
+    events: list[str] = []
+
+    async def work(key: str, name: str):
+        async with lock.hold(key):
+            events.append(f'{name} start')
+            await asyncio.sleep(0.01)
+            events.append(f'{name} end')
+
+    await asyncio.gather(work('a', 'a1'), work('a', 'a2'), work('b', 'b1'))
+
+    assert events.index('a1 end') < events.index('a2 start')
+    assert events.index('b1 start') < events.index('a1 end')
+    assert lock._locks == {}
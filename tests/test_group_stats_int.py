@@ -0,0 +1,97 @@
+"""
+Copyright 2024, Zep Software, Inc.
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+"""
+
+import os
+from datetime import datetime, timedelta, timezone
+from unittest.mock import MagicMock
+from uuid import uuid4
+
+import pytest
+
+from graphiti_core.cross_encoder.client import CrossEncoderClient
+from graphiti_core.edges import EntityEdge
+from graphiti_core.embedder.client import EmbedderClient
+from graphiti_core.graphiti import Graphiti
+from graphiti_core.llm_client import LLMClient
+from graphiti_core.nodes import CommunityNode, EntityNode, EpisodeType, EpisodicNode
+from graphiti_core.utils.maintenance.graph_data_operations import clear_data
+
+pytestmark = pytest.mark.integration
+
+NEO4J_URI = os.getenv('NEO4J_URI', 'bolt://localhost:7687')
+NEO4J_USER = os.getenv('NEO4J_USER', 'neo4j')
+NEO4J_PASSWORD = os.getenv('NEO4J_PASSWORD', 'test')
+
+
+@pytest.mark.asyncio
+async def test_group_stats_counts_seeded_group():
+    graphiti = Graphiti(
+        NEO4J_URI,
+        NEO4J_USER,
+        NEO4J_PASSWORD,
+        llm_client=MagicMock(spec=LLMClient),
+        embedder=MagicMock(spec=EmbedderClient),
+        cross_encoder=MagicMock(spec=CrossEncoderClient),
+    )
+    group_id = f'stats_test_{uuid4().hex}'
+    now = datetime.now(timezone.utc).replace(microsecond=0)
+
+    try:
+        for days_ago in [2, 1]:
+            await EpisodicNode(
+                name=f'Episode {days_ago}',
+                group_id=group_id,
+                source=EpisodeType.text,
+                source_description='test',
+                content='Alice knows Bob',
+                valid_at=now - timedelta(days=days_ago),
+            ).save(graphiti.driver)
+
+        nodes = [
+            EntityNode(name=name, group_id=group_id, labels=['Entity'], name_embedding=[0.5] * 1024)
+            for name in ['Alice', 'Bob', 'Carol']
+        ]
+        for node in nodes:
+            await node.save(graphiti.driver)
+
+        for target, invalid_at in [(nodes[1], None), (nodes[2], now)]:
+            await EntityEdge(
+                source_node_uuid=nodes[0].uuid,
+                target_node_uuid=target.uuid,
+                name='KNOWS',
+                fact=f'Alice knows {target.name}',
+                fact_embedding=[0.5] * 1024,
+                group_id=group_id,
+                created_at=now,
+                invalid_at=invalid_at,
+            ).save(graphiti.driver)
+
+        await CommunityNode(
+            name='Friends', group_id=group_id, name_embedding=[0.5] * 1024
+        ).save(graphiti.driver)
+
+        stats = await graphiti.group_stats(group_id)
+
+        assert stats.episode_count == 2
+        assert stats.entity_node_count == 3
+        assert stats.entity_edge_count == 2
+        assert stats.valid_entity_edge_count == 1
+        assert stats.invalidated_entity_edge_count == 1
+        assert stats.community_count == 1
+        assert stats.latest_valid_at == now - timedelta(days=1)
+    finally:
+        await clear_data(graphiti.driver, [group_id])
+        await graphiti.close()
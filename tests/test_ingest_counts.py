@@ -0,0 +1,77 @@
+from datetime import datetime, timezone
+from unittest.mock import AsyncMock, patch
+
+import pytest
+
+from graphiti_core.graphiti import IngestCounts
+from graphiti_core.nodes import EntityNode
+from graphiti_core.prompts.dedupe_nodes import NodeResolutions
+from graphiti_core.testing import MockLLMClient
+
+NOW = datetime(2024, 6, 1, tzinfo=timezone.utc)
+
+EXISTING_ALICE = {
+    'uuid': 'alice-existing',
+    'name': 'Alice',
+    'group_id': 'group_1',
+    'labels': ['Entity'],
+    'created_at': NOW.isoformat(),
+    'summary': '',
+    'attributes': {},
+}
+
+
+def _llm_client() -> MockLLMClient:
+    # Alice and Alicia are both the existing Alice, Bob is new
+    return MockLLMClient(default_response={'summary': 'summary'}).add_response(
+        NodeResolutions,
+        {
+            'entity_resolutions': [
+                {'id': 0, 'name': 'Alice', 'duplicate_idx': 0, 'additional_duplicates': []},
+                {'id': 1, 'name': 'Bob', 'duplicate_idx': -1, 'additional_duplicates': []},
+                {'id': 2, 'name': 'Alice', 'duplicate_idx': 0, 'additional_duplicates': []},
+            ]
+        },
+    )
+
+
+async def _execute_query(query, **kwargs):
+    if 'node_name_and_summary' in query:
+        return [EXISTING_ALICE], None, None
+    return [], None, None
+
+
+@pytest.mark.asyncio
+async def test_counts_reflect_merges_into_existing_entities(make_graphiti, mock_driver):
+    mock_driver.execute_query.side_effect = _execute_query
+    graphiti = make_graphiti(llm_client=_llm_client(), enable_embeddings=False)
+    extracted = [
+        EntityNode(name=name, group_id='group_1', labels=['Entity'])
+        for name in ['Alice', 'Bob', 'Alicia']
+    ]
+
+    with (
+        patch('graphiti_core.graphiti.retrieve_episodes', AsyncMock(return_value=[])),
+        patch('graphiti_core.graphiti.extract_nodes', AsyncMock(return_value=extracted)),
+        patch('graphiti_core.graphiti.extract_edges', AsyncMock(return_value=[])),
+        patch('graphiti_core.graphiti.add_nodes_and_edges_bulk', AsyncMock()),
+    ):
+        result = await graphiti.add_episode(
+            name='episode',
+            episode_body='Alice, also known as Alicia, met Bob',
+            source_description='test',
+            reference_time=NOW,
+            group_id='group_1',
+        )
+
+    assert {node.uuid for node in result.nodes} == {'alice-existing', extracted[1].uuid}
+    assert result.node_counts == IngestCounts(created=1, merged=1, skipped=1)
+    assert result.edge_counts == IngestCounts()
+
+
+def test_counts_from_resolutions():
+    counts = IngestCounts.from_resolutions(
+        [('a', 'a'), ('b', 'existing'), ('c', 'existing'), ('d', None), ('e', 'a')]
+    )
+
+    assert counts == IngestCounts(created=1, merged=1, skipped=3)
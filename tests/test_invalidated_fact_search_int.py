@@ -0,0 +1,97 @@
+"""
+Copyright 2024, Zep Software, Inc.
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+"""
+
+import os
+from datetime import datetime, timedelta, timezone
+from unittest.mock import AsyncMock, MagicMock
+from uuid import uuid4
+
+import pytest
+
+from graphiti_core.cross_encoder.client import CrossEncoderClient
+from graphiti_core.edges import EntityEdge
+from graphiti_core.embedder.client import EmbedderClient
+from graphiti_core.graphiti import Graphiti
+from graphiti_core.llm_client import LLMClient
+from graphiti_core.nodes import EntityNode
+from graphiti_core.search.search_config import EdgeSearchConfig, EdgeSearchMethod, SearchConfig
+from graphiti_core.search.search_filters import SearchFilters
+from graphiti_core.utils.maintenance.graph_data_operations import clear_data
+
+pytestmark = pytest.mark.integration
+
+NEO4J_URI = os.getenv('NEO4J_URI', 'bolt://localhost:7687')
+NEO4J_USER = os.getenv('NEO4J_USER', 'neo4j')
+NEO4J_PASSWORD = os.getenv('NEO4J_PASSWORD', 'test')
+
+FACT_CONFIG = SearchConfig(
+    edge_config=EdgeSearchConfig(search_methods=[EdgeSearchMethod.cosine_similarity])
+)
+
+
+@pytest.mark.asyncio
+async def test_fact_search_excludes_invalidated_edges_by_default():
+    graphiti = Graphiti(
+        NEO4J_URI,
+        NEO4J_USER,
+        NEO4J_PASSWORD,
+        llm_client=MagicMock(spec=LLMClient),
+        embedder=MagicMock(spec=EmbedderClient, create=AsyncMock(return_value=[1.0, 0.0, 0.0])),
+        cross_encoder=MagicMock(spec=CrossEncoderClient),
+    )
+    group_id = f'invalidated_test_{uuid4().hex}'
+    now = datetime.now(timezone.utc)
+
+    try:
+        alice, acme, globex = [
+            EntityNode(name=name, group_id=group_id, labels=['Entity'], name_embedding=[0.5] * 1024)
+            for name in ['Alice', 'Acme', 'Globex']
+        ]
+        for node in [alice, acme, globex]:
+            await node.save(graphiti.driver)
+
+        # Alice used to work at Acme and now works at Globex
+        old_job, new_job = [
+            EntityEdge(
+                source_node_uuid=alice.uuid,
+                target_node_uuid=employer.uuid,
+                name='WORKS_AT',
+                fact=f'Alice works at {employer.name}',
+                fact_embedding=[1.0, 0.0, 0.0],
+                group_id=group_id,
+                created_at=now - timedelta(days=30),
+                invalid_at=invalid_at,
+            )
+            for employer, invalid_at in [(acme, now - timedelta(days=1)), (globex, None)]
+        ]
+        for edge in [old_job, new_job]:
+            await edge.save(graphiti.driver)
+
+        results = await graphiti.search_('Where does Alice work?', FACT_CONFIG, [group_id])
+
+        assert [edge.uuid for edge in results.edges] == [new_job.uuid]
+
+        results = await graphiti.search_(
+            'Where does Alice work?',
+            FACT_CONFIG,
+            [group_id],
+            search_filter=SearchFilters(include_invalidated=True),
+        )
+
+        assert {edge.uuid for edge in results.edges} == {old_job.uuid, new_job.uuid}
+    finally:
+        await clear_data(graphiti.driver, [group_id])
+        await graphiti.close()
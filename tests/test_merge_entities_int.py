@@ -0,0 +1,144 @@
+"""
+Copyright 2024, Zep Software, Inc.
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+"""
+
+import os
+from uuid import uuid4
+
+import pytest
+
+from graphiti_core.edges import EntityEdge, EpisodicEdge
+from graphiti_core.errors import NodeNotFoundError
+from graphiti_core.graphiti import Graphiti
+from graphiti_core.nodes import EntityNode, EpisodeType, EpisodicNode
+from graphiti_core.prompts.dedupe_edges import EdgeDuplicate
+from graphiti_core.testing import MockCrossEncoderClient, MockEmbedderClient, MockLLMClient
+from graphiti_core.utils.datetime_utils import utc_now
+from graphiti_core.utils.maintenance.graph_data_operations import clear_data
+
+pytestmark = pytest.mark.integration
+
+NEO4J_URI = os.getenv('NEO4J_URI', 'bolt://localhost:7687')
+NEO4J_USER = os.getenv('NEO4J_USER', 'neo4j')
+NEO4J_PASSWORD = os.getenv('NEO4J_PASSWORD', 'test')
+
+
+@pytest.mark.asyncio
+async def test_merge_entities_consolidates_edges():
+    # Only the restated WORKS_AT fact duplicates the kept entity's fact
+    llm_client = (
+        MockLLMClient()
+        .add_response('Alicia WORKS_AT Acme', {'duplicate_fact_id': 0, 'contradicted_facts': []})
+        .add_response(EdgeDuplicate, {'duplicate_fact_id': -1, 'contradicted_facts': []})
+    )
+    graphiti = Graphiti(
+        NEO4J_URI,
+        NEO4J_USER,
+        NEO4J_PASSWORD,
+        llm_client=llm_client,
+        embedder=MockEmbedderClient(),
+        cross_encoder=MockCrossEncoderClient(),
+        max_summary_words=4,
+    )
+    group_id = f'merge_test_{uuid4().hex}'
+    now = utc_now()
+
+    try:
+        alice, alicia, acme, bob = [
+            EntityNode(
+                name=name,
+                group_id=group_id,
+                labels=['Entity'],
+                summary=summary,
+                name_embedding=[0.5] * 1024,
+            )
+            for name, summary in [
+                ('Alice', 'Engineer'),
+                ('Alicia', 'Lives in Paris since 2019'),
+                ('Acme', ''),
+                ('Bob', ''),
+            ]
+        ]
+        for node in [alice, alicia, acme, bob]:
+            await node.save(graphiti.driver)
+
+        episode = EpisodicNode(
+            name='episode',
+            group_id=group_id,
+            source=EpisodeType.text,
+            source_description='test',
+            content='Alicia knows Bob',
+            valid_at=now,
+        )
+        await episode.save(graphiti.driver)
+        await EpisodicEdge(
+            source_node_uuid=episode.uuid,
+            target_node_uuid=alicia.uuid,
+            group_id=group_id,
+            created_at=now,
+        ).save(graphiti.driver)
+
+        # Both entities work at Acme, so merging leaves a parallel edge to fold away
+        alice_works_at, alicia_works_at, alicia_knows, alice_alicia = [
+            EntityEdge(
+                source_node_uuid=source.uuid,
+                target_node_uuid=target.uuid,
+                name=name,
+                fact=f'{source.name} {name} {target.name}',
+                fact_embedding=[0.5] * 1024,
+                episodes=[episode_uuid],
+                group_id=group_id,
+                created_at=now,
+            )
+            for source, target, name, episode_uuid in [
+                (alice, acme, 'WORKS_AT', 'episode_1'),
+                (alicia, acme, 'WORKS_AT', 'episode_2'),
+                (alicia, bob, 'KNOWS', episode.uuid),
+                (alice, alicia, 'SAME_AS', 'episode_3'),
+            ]
+        ]
+        alicia_consults = EntityEdge(
+            source_node_uuid=alicia.uuid,
+            target_node_uuid=acme.uuid,
+            name='WORKS_AT',
+            fact='Alicia consults for Acme',
+            fact_embedding=[0.5] * 1024,
+            episodes=['episode_4'],
+            group_id=group_id,
+            created_at=now,
+        )
+        for edge in [alice_works_at, alicia_works_at, alicia_knows, alice_alicia, alicia_consults]:
+            await edge.save(graphiti.driver)
+
+        merged = await graphiti.merge_entities(alice.uuid, alicia.uuid)
+
+        assert merged.summary == 'Engineer\nLives in Paris...'
+        with pytest.raises(NodeNotFoundError):
+            await EntityNode.get_by_uuid(graphiti.driver, alicia.uuid)
+
+        edges = await EntityEdge.get_by_node_uuid(graphiti.driver, alice.uuid)
+        assert sorted((edge.name, edge.fact) for edge in edges) == [
+            ('KNOWS', 'Alicia KNOWS Bob'),
+            ('WORKS_AT', 'Alice WORKS_AT Acme'),
+            ('WORKS_AT', 'Alicia consults for Acme'),
+        ]
+        works_at = next(edge for edge in edges if edge.uuid == alice_works_at.uuid)
+        assert works_at.episodes == ['episode_1', 'episode_2']
+
+        episodes = await graphiti.get_entity_episodes(alice.uuid)
+        assert [e.uuid for e in episodes] == [episode.uuid]
+    finally:
+        await clear_data(graphiti.driver, [group_id])
+        await graphiti.close()
@@ -0,0 +1,118 @@
+from datetime import datetime, timezone
+
+import pytest
+
+from graphiti_core.llm_client.errors import RefusalError
+from graphiti_core.prompts.dedupe_nodes import NodeResolutions
+from graphiti_core.prompts.extract_edges import ExtractedEdges
+from graphiti_core.prompts.extract_nodes import ExtractedEntities
+from graphiti_core.prompts.models import Message
+from graphiti_core.testing import MockCrossEncoderClient, MockEmbedderClient, MockLLMClient
+
+
+def _messages(content: str) -> list[Message]:
+    return [Message(role='system', content='system'), Message(role='user', content=content)]
+
+
+@pytest.mark.asyncio
+async def test_mock_llm_client_scripted_responses():
+    llm_client = MockLLMClient(default_response={'answer': 'default'})
+    llm_client.add_response('first', RefusalError('refused'), times=1)
+    llm_client.add_response('first', {'answer': 'first'})
+
+    with pytest.raises(RefusalError):
+        await llm_client.generate_response(_messages('the first question'))
+    assert await llm_client.generate_response(_messages('the first question')) == {
+        'answer': 'first'
+    }
+    assert await llm_client.generate_response(_messages('another question')) == {
+        'answer': 'default'
+    }
+
+    assert llm_client.call_count == 3
+    assert llm_client.count_calls('first') == 2
+
+
+@pytest.mark.asyncio
+async def test_mock_llm_client_without_default_rejects_unscripted_calls():
+    llm_client = MockLLMClient()
+
+    with pytest.raises(ValueError):
+        await llm_client.generate_response(_messages('unscripted'))
+
+
+@pytest.mark.asyncio
+async def test_mock_embedder_is_deterministic():
+    embedder = MockEmbedderClient(embedding_dim=8)
+
+    alice = await embedder.create('Alice')
+    batch = await embedder.create_batch(['Alice', 'Bob'])
+
+    assert len(alice) == 8
+    assert batch[0] == alice
+    assert batch[1] != alice
+    assert embedder.inputs == ['Alice', 'Alice', 'Bob']
+
+
+@pytest.mark.asyncio
+async def test_mock_cross_encoder_ranks_by_word_overlap():
+    cross_encoder = MockCrossEncoderClient()
+
+    ranked = await cross_encoder.rank('alice knows bob', ['bob', 'alice knows bob', 'carol'])
+
+    assert [passage for passage, _ in ranked] == ['alice knows bob', 'bob', 'carol']
+    assert len(cross_encoder.calls) == 1
+
+
+@pytest.mark.asyncio
+async def test_add_episode_with_mock_clients(make_graphiti, mock_driver):
+    llm_client = (
+        MockLLMClient(default_response={'summary': 'summary'})
+        .add_response(
+            ExtractedEntities,
+            {
+                'extracted_entities': [
+                    {'name': 'Alice', 'entity_type_id': 0},
+                    {'name': 'Bob', 'entity_type_id': 0},
+                ]
+            },
+        )
+        .add_response(
+            NodeResolutions,
+            {
+                'entity_resolutions': [
+                    {'id': 0, 'name': 'Alice', 'duplicate_idx': -1, 'additional_duplicates': []},
+                    {'id': 1, 'name': 'Bob', 'duplicate_idx': -1, 'additional_duplicates': []},
+                ]
+            },
+        )
+        .add_response(
+            ExtractedEdges,
+            {
+                'edges': [
+                    {
+                        'relation_type': 'KNOWS',
+                        'source_entity_id': 0,
+                        'target_entity_id': 1,
+                        'fact': 'Alice knows Bob',
+                    }
+                ]
+            },
+        )
+    )
+    embedder = MockEmbedderClient()
+    graphiti = make_graphiti(llm_client=llm_client, embedder=embedder)
+
+    result = await graphiti.add_episode(
+        name='episode',
+        episode_body='Alice knows Bob',
+        source_description='test',
+        reference_time=datetime.now(timezone.utc),
+        group_id='group_1',
+    )
+
+    assert [node.name for node in result.nodes] == ['Alice', 'Bob']
+    assert [edge.fact for edge in result.edges] == ['Alice knows Bob']
+    assert llm_client.count_calls(ExtractedEntities) == 1
+    assert result.nodes[0].name_embedding == embedder.embed('Alice')
+    mock_driver.session.return_value.execute_write.assert_awaited_once()
@@ -20,13 +20,23 @@ from uuid import uuid4
 
 import pytest
 from neo4j import AsyncGraphDatabase
+from neo4j.exceptions import ClientError
 
+from graphiti_core.driver.neo4j_driver import Neo4jDriver
+from graphiti_core.edges import EpisodicEdge
+from graphiti_core.helpers import semaphore_gather
 from graphiti_core.nodes import (
     CommunityNode,
     EntityNode,
     EpisodeType,
     EpisodicNode,
 )
+from graphiti_core.search.search_filters import (
+    AttributeFilter,
+    ComparisonOperator,
+    SearchFilters,
+)
+from graphiti_core.search.search_utils import node_similarity_search
 
 NEO4J_URI = os.getenv('NEO4J_URI', 'bolt://localhost:7687')
 NEO4J_USER = os.getenv('NEO4J_USER', 'neo4j')
@@ -84,6 +94,86 @@ async def test_entity_node_save_get_and_delete(sample_entity_node):
     await neo4j_driver.close()
 
 
+@pytest.mark.asyncio
+@pytest.mark.integration
+async def test_entity_node_save_applies_entity_type_label(sample_entity_node):
+    neo4j_driver = AsyncGraphDatabase.driver(NEO4J_URI, auth=(NEO4J_USER, NEO4J_PASSWORD))
+    sample_entity_node.labels = ['Entity', 'Person']
+
+    await sample_entity_node.save(neo4j_driver)
+
+    records, _, _ = await neo4j_driver.execute_query(
+        'MATCH (n:Person {uuid: $uuid}) RETURN labels(n) AS labels', uuid=sample_entity_node.uuid
+    )
+    assert len(records) == 1
+    assert sorted(records[0]['labels']) == ['Entity', 'Person']
+
+    retrieved = await EntityNode.get_by_uuid(neo4j_driver, sample_entity_node.uuid)
+    assert 'Person' in retrieved.labels
+
+    await sample_entity_node.delete(neo4j_driver)
+
+    await neo4j_driver.close()
+
+
+@pytest.mark.asyncio
+@pytest.mark.integration
+async def test_entity_node_attributes_round_trip_and_filter(sample_entity_node):
+    driver = Neo4jDriver(NEO4J_URI, NEO4J_USER, NEO4J_PASSWORD)
+    sample_entity_node.attributes = {'age': 42, 'city': 'Paris'}
+
+    try:
+        await sample_entity_node.save(driver)
+
+        retrieved = await EntityNode.get_by_uuid(driver, sample_entity_node.uuid)
+        assert retrieved.attributes == {'age': 42, 'city': 'Paris'}
+
+        async def search_older_than(age: int) -> list[EntityNode]:
+            age_filter = AttributeFilter(
+                name='age', value=age, comparison_operator=ComparisonOperator.greater_than
+            )
+            return await node_similarity_search(
+                driver,
+                [0.5] * 1024,
+                SearchFilters(node_attributes=[age_filter]),
+                group_ids=['test_group'],
+            )
+
+        assert sample_entity_node.uuid in [node.uuid for node in await search_older_than(30)]
+        assert sample_entity_node.uuid not in [node.uuid for node in await search_older_than(50)]
+    finally:
+        await sample_entity_node.delete(driver)
+        await driver.close()
+
+
+@pytest.mark.asyncio
+@pytest.mark.integration
+async def test_concurrent_entity_node_saves_upsert(sample_entity_node):
+    driver = Neo4jDriver(NEO4J_URI, NEO4J_USER, NEO4J_PASSWORD)
+    try:
+        await driver.execute_query(
+            'CREATE CONSTRAINT entity_uuid_unique IF NOT EXISTS '
+            'FOR (n:Entity) REQUIRE n.uuid IS UNIQUE'
+        )
+    except ClientError as e:
+        await driver.close()
+        pytest.skip(f'could not create a uniqueness constraint on Entity.uuid: {e.message}')
+
+    try:
+        duplicate = sample_entity_node.model_copy(update={'summary': 'Updated Summary'})
+        await semaphore_gather(sample_entity_node.save(driver), duplicate.save(driver))
+
+        records, _, _ = await driver.execute_query(
+            'MATCH (n:Entity {uuid: $uuid}) RETURN count(n) AS count',
+            uuid=sample_entity_node.uuid,
+        )
+        assert records[0]['count'] == 1
+    finally:
+        await sample_entity_node.delete(driver)
+        await driver.execute_query('DROP CONSTRAINT entity_uuid_unique IF EXISTS')
+        await driver.close()
+
+
 @pytest.mark.asyncio
 @pytest.mark.integration
 async def test_community_node_save_get_and_delete(sample_community_node):
@@ -120,3 +210,57 @@ async def test_episodic_node_save_get_and_delete(sample_episodic_node):
     await sample_episodic_node.delete(neo4j_driver)
 
     await neo4j_driver.close()
+
+
+@pytest.mark.asyncio
+@pytest.mark.integration
+async def test_episodic_node_get_by_entity_node_uuid(sample_entity_node):
+    neo4j_driver = AsyncGraphDatabase.driver(NEO4J_URI, auth=(NEO4J_USER, NEO4J_PASSWORD))
+
+    older_episode = EpisodicNode(
+        name='Older Episode',
+        group_id='test_group',
+        source=EpisodeType.text,
+        source_description='First source',
+        content='Older content',
+        valid_at=datetime(2024, 1, 1, tzinfo=timezone.utc),
+    )
+    newer_episode = EpisodicNode(
+        name='Newer Episode',
+        group_id='test_group',
+        source=EpisodeType.message,
+        source_description='Second source',
+        content='Newer content',
+        valid_at=datetime(2024, 6, 1, tzinfo=timezone.utc),
+    )
+    mentions = [
+        EpisodicEdge(
+            source_node_uuid=episode.uuid,
+            target_node_uuid=sample_entity_node.uuid,
+            group_id='test_group',
+            created_at=datetime.now(timezone.utc),
+        )
+        for episode in [older_episode, newer_episode]
+    ]
+
+    await sample_entity_node.save(neo4j_driver)
+    for episode in [older_episode, newer_episode]:
+        await episode.save(neo4j_driver)
+    for edge in mentions:
+        await edge.save(neo4j_driver)
+
+    episodes = await EpisodicNode.get_by_entity_node_uuid(neo4j_driver, sample_entity_node.uuid)
+    assert [episode.uuid for episode in episodes] == [newer_episode.uuid, older_episode.uuid]
+    assert episodes[0].content == 'Newer content'
+    assert episodes[0].source_description == 'Second source'
+
+    limited = await EpisodicNode.get_by_entity_node_uuid(
+        neo4j_driver, sample_entity_node.uuid, limit=1
+    )
+    assert [episode.uuid for episode in limited] == [newer_episode.uuid]
+
+    for episode in [older_episode, newer_episode]:
+        await episode.delete(neo4j_driver)
+    await sample_entity_node.delete(neo4j_driver)
+
+    await neo4j_driver.close()
@@ -0,0 +1,77 @@
+"""
+Copyright 2024, Zep Software, Inc.
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+"""
+
+import os
+from unittest.mock import MagicMock
+from uuid import uuid4
+
+import pytest
+
+from graphiti_core.cross_encoder.client import CrossEncoderClient
+from graphiti_core.embedder.client import EmbedderClient
+from graphiti_core.graphiti import Graphiti
+from graphiti_core.llm_client import LLMClient
+from graphiti_core.nodes import EntityNode
+from graphiti_core.search.search_filters import SearchFilters
+from graphiti_core.search.search_utils import node_fulltext_search
+from graphiti_core.utils.maintenance.graph_data_operations import clear_data
+
+pytestmark = pytest.mark.integration
+
+NEO4J_URI = os.getenv('NEO4J_URI', 'bolt://localhost:7687')
+NEO4J_USER = os.getenv('NEO4J_USER', 'neo4j')
+NEO4J_PASSWORD = os.getenv('NEO4J_PASSWORD', 'test')
+
+
+@pytest.mark.asyncio
+async def test_name_match_ranks_above_summary_only_match():
+    graphiti = Graphiti(
+        NEO4J_URI,
+        NEO4J_USER,
+        NEO4J_PASSWORD,
+        llm_client=MagicMock(spec=LLMClient),
+        embedder=MagicMock(spec=EmbedderClient),
+        cross_encoder=MagicMock(spec=CrossEncoderClient),
+    )
+    group_id = f'name_boost_test_{uuid4().hex}'
+
+    try:
+        await graphiti.build_indices_and_constraints()
+
+        # The summary repeats the query term, so unweighted BM25 would favour it
+        mention = EntityNode(
+            name='Alice',
+            group_id=group_id,
+            labels=['Entity'],
+            name_embedding=[0.5] * 1024,
+            summary='Alice works at Acme. Acme is where Alice met Bob at Acme.',
+        )
+        acme = EntityNode(
+            name='Acme',
+            group_id=group_id,
+            labels=['Entity'],
+            name_embedding=[0.5] * 1024,
+            summary='A company that makes anvils and rockets for a living.',
+        )
+        for node in [mention, acme]:
+            await node.save(graphiti.driver)
+
+        nodes = await node_fulltext_search(graphiti.driver, 'Acme', SearchFilters(), [group_id])
+
+        assert [node.uuid for node in nodes] == [acme.uuid, mention.uuid]
+    finally:
+        await clear_data(graphiti.driver, [group_id])
+        await graphiti.close()
@@ -0,0 +1,79 @@
+from datetime import datetime, timezone
+
+import pytest
+
+from graphiti_core.prompts import (
+    DEFAULT_PROMPT_LIBRARY,
+    Message,
+    SystemInstructions,
+    prompt_library,
+    use_prompt_library,
+    use_system_instructions,
+)
+from graphiti_core.prompts.extract_edges import ExtractedEdges
+from graphiti_core.prompts.extract_nodes import ExtractedEntities
+from graphiti_core.prompts.prompt_helpers import DO_NOT_ESCAPE_UNICODE
+from graphiti_core.testing import MockLLMClient
+
+CUSTOM_SYSTEM_PROMPT = 'Extract only legal obligations between parties.'
+
+
+def custom_edge_prompt(context: dict) -> list[Message]:
+    return [
+        Message(role='system', content=CUSTOM_SYSTEM_PROMPT),
+        Message(role='user', content=context['episode_content']),
+    ]
+
+
+CUSTOM_LIBRARY = DEFAULT_PROMPT_LIBRARY.with_prompt('extract_edges', 'edge', custom_edge_prompt)
+
+
+def test_custom_prompt_is_used_within_the_context():
+    context = {'episode_content': 'The tenant pays rent monthly.'}
+
+    with use_prompt_library(CUSTOM_LIBRARY):
+        with use_system_instructions(SystemInstructions(default='Be precise.')):
+            messages = prompt_library.extract_edges.edge(context)
+
+    system_message = messages[0].content
+    assert system_message.startswith(CUSTOM_SYSTEM_PROMPT)
+    # Custom prompts are wrapped like the built-in ones
+    assert 'Be precise.' in system_message
+    assert system_message.endswith(DO_NOT_ESCAPE_UNICODE)
+    # Other prompts and the default library are unchanged
+    assert CUSTOM_LIBRARY.extract_edges.reflexion.func is (
+        DEFAULT_PROMPT_LIBRARY.extract_edges.reflexion.func
+    )
+    assert DEFAULT_PROMPT_LIBRARY.extract_edges.edge.func is not custom_edge_prompt
+
+
+def test_unknown_prompt_is_rejected():
+    with pytest.raises(ValueError):
+        DEFAULT_PROMPT_LIBRARY.with_prompt('extract_edges', 'missing', custom_edge_prompt)
+
+
+@pytest.mark.asyncio
+async def test_add_episode_uses_custom_prompt_library(make_graphiti):
+    llm_client = (
+        MockLLMClient(default_response={'summary': 'summary'})
+        .add_response(ExtractedEntities, {'extracted_entities': []})
+        .add_response(ExtractedEdges, {'edges': []})
+    )
+    graphiti = make_graphiti(llm_client=llm_client, prompt_library=CUSTOM_LIBRARY)
+
+    await graphiti.add_episode(
+        name='episode',
+        episode_body='The tenant pays rent monthly.',
+        source_description='lease',
+        reference_time=datetime.now(timezone.utc),
+        source='text',
+        group_id='group_1',
+    )
+
+    edge_calls = [
+        messages
+        for messages, response_model in llm_client.calls
+        if response_model is ExtractedEdges
+    ]
+    assert len(edge_calls) == 1
+    assert edge_calls[0][0].content.startswith(CUSTOM_SYSTEM_PROMPT)
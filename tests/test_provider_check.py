@@ -0,0 +1,48 @@
+import pytest
+
+from graphiti_core.embedder.client import EmbedderClient
+from graphiti_core.errors import ProviderCheckError
+from graphiti_core.testing import MockEmbedderClient, MockLLMClient
+
+
+class FailingEmbedderClient(EmbedderClient):
+    async def create(self, input_data):
+        raise ConnectionError('invalid API key')
+
+
+@pytest.fixture
+def make_checked_graphiti(make_graphiti):
+    def make(embedder: EmbedderClient, **kwargs):
+        return make_graphiti(
+            llm_client=MockLLMClient(default_response={}), embedder=embedder, **kwargs
+        )
+
+    return make
+
+
+@pytest.mark.asyncio
+async def test_check_providers_passes_for_working_providers(make_checked_graphiti):
+    graphiti = make_checked_graphiti(MockEmbedderClient(embedding_dim=8))
+
+    await graphiti.check_providers(embedding_dim=8)
+
+
+@pytest.mark.asyncio
+async def test_check_providers_fails_for_unreachable_embedder(make_checked_graphiti):
+    with pytest.raises(ProviderCheckError, match='embedder check failed.*invalid API key'):
+        await make_checked_graphiti(FailingEmbedderClient()).check_providers()
+
+
+@pytest.mark.asyncio
+async def test_check_providers_fails_for_wrong_embedding_dimension(make_checked_graphiti):
+    graphiti = make_checked_graphiti(MockEmbedderClient(embedding_dim=8))
+
+    with pytest.raises(ProviderCheckError, match='8-dimensional embeddings, expected 1024'):
+        await graphiti.check_providers(embedding_dim=1024)
+
+
+@pytest.mark.asyncio
+async def test_check_providers_skips_embedder_when_embeddings_are_disabled(make_checked_graphiti):
+    graphiti = make_checked_graphiti(FailingEmbedderClient(), enable_embeddings=False)
+
+    await graphiti.check_providers()
@@ -0,0 +1,95 @@
+"""
+Copyright 2024, Zep Software, Inc.
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+"""
+
+from unittest.mock import patch
+
+import pytest
+
+from graphiti_core.cross_encoder import GeminiRerankerClient, OpenAIRerankerClient
+from graphiti_core.embedder import OpenAIEmbedder
+from graphiti_core.embedder.gemini import GeminiEmbedder
+from graphiti_core.embedder.voyage import VoyageAIEmbedder
+from graphiti_core.llm_client import LLMConfig, OpenAIClient
+from graphiti_core.llm_client.anthropic_client import AnthropicClient
+from graphiti_core.llm_client.gemini_client import GeminiClient
+from graphiti_core.llm_client.groq_client import GroqClient
+from graphiti_core.llm_client.openai_generic_client import OpenAIGenericClient
+from graphiti_core.providers import (
+    LLMProvider,
+    create_cross_encoder,
+    create_embedder,
+    create_llm_client,
+)
+
+
+@pytest.mark.parametrize(
+    'provider, client_type',
+    [
+        ('openai', OpenAIClient),
+        ('openai_generic', OpenAIGenericClient),
+        ('anthropic', AnthropicClient),
+        ('gemini', GeminiClient),
+        ('groq', GroqClient),
+    ],
+)
+def test_create_llm_client(provider, client_type):
+    config = LLMConfig(api_key='test', model='test-model', max_tokens=1024)
+
+    client = create_llm_client(provider, config)
+
+    assert type(client) is client_type
+    assert client.model == 'test-model'
+    assert client.max_tokens == 1024
+
+
+@pytest.mark.parametrize(
+    'provider, embedder_type',
+    [('openai', OpenAIEmbedder), ('gemini', GeminiEmbedder), ('voyage', VoyageAIEmbedder)],
+)
+def test_create_embedder(provider, embedder_type):
+    embedder = create_embedder(provider, api_key='test', model='test-embedding')
+
+    assert type(embedder) is embedder_type
+    assert embedder.config.embedding_model == 'test-embedding'
+
+
+@pytest.mark.parametrize(
+    'provider, reranker_type',
+    [('openai', OpenAIRerankerClient), ('gemini', GeminiRerankerClient)],
+)
+def test_create_cross_encoder(provider, reranker_type):
+    reranker = create_cross_encoder(provider, LLMConfig(api_key='test'))
+
+    assert type(reranker) is reranker_type
+
+
+def test_create_bge_cross_encoder():
+    # The model itself isn't downloaded
+    with patch('graphiti_core.cross_encoder.bge_reranker_client.CrossEncoder') as cross_encoder:
+        reranker = create_cross_encoder('bge')
+
+    assert reranker.model is cross_encoder.return_value
+
+
+def test_unknown_provider_is_rejected():
+    with pytest.raises(ValueError):
+        create_llm_client('unknown')
+
+
+def test_providers_can_be_given_as_enums():
+    client = create_llm_client(LLMProvider.groq, LLMConfig(api_key='test'))
+
+    assert type(client) is GroqClient
@@ -0,0 +1,110 @@
+"""
+Copyright 2024, Zep Software, Inc.
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+"""
+
+import os
+from datetime import datetime, timedelta, timezone
+from unittest.mock import MagicMock
+from uuid import uuid4
+
+import pytest
+
+from graphiti_core.cross_encoder.client import CrossEncoderClient
+from graphiti_core.edges import EntityEdge, EpisodicEdge
+from graphiti_core.embedder.client import EmbedderClient
+from graphiti_core.graphiti import Graphiti
+from graphiti_core.llm_client import LLMClient
+from graphiti_core.nodes import EntityNode, EpisodeType, EpisodicNode
+from graphiti_core.utils.maintenance.graph_data_operations import clear_data
+
+pytestmark = pytest.mark.integration
+
+NEO4J_URI = os.getenv('NEO4J_URI', 'bolt://localhost:7687')
+NEO4J_USER = os.getenv('NEO4J_USER', 'neo4j')
+NEO4J_PASSWORD = os.getenv('NEO4J_PASSWORD', 'test')
+
+
+@pytest.mark.asyncio
+async def test_prune_episodes_deletes_only_old_episodes():
+    graphiti = Graphiti(
+        NEO4J_URI,
+        NEO4J_USER,
+        NEO4J_PASSWORD,
+        llm_client=MagicMock(spec=LLMClient),
+        embedder=MagicMock(spec=EmbedderClient),
+        cross_encoder=MagicMock(spec=CrossEncoderClient),
+    )
+    group_id = f'prune_test_{uuid4().hex}'
+    now = datetime.now(timezone.utc).replace(microsecond=0)
+
+    try:
+        old_episode, new_episode = [
+            EpisodicNode(
+                name=f'Episode {days_ago}',
+                group_id=group_id,
+                source=EpisodeType.text,
+                source_description='test',
+                content='Alice knows Bob',
+                created_at=now - timedelta(days=days_ago),
+                valid_at=now - timedelta(days=days_ago),
+            )
+            for days_ago in [30, 1]
+        ]
+        for episode in [old_episode, new_episode]:
+            await episode.save(graphiti.driver)
+
+        alice, bob, carol = [
+            EntityNode(name=name, group_id=group_id, labels=['Entity'], name_embedding=[0.5] * 1024)
+            for name in ['Alice', 'Bob', 'Carol']
+        ]
+        for node in [alice, bob, carol]:
+            await node.save(graphiti.driver)
+
+        # Alice and Carol are only mentioned by the old episode, but Alice has a valid fact
+        for episode, node in [(old_episode, alice), (old_episode, carol), (new_episode, bob)]:
+            await EpisodicEdge(
+                source_node_uuid=episode.uuid,
+                target_node_uuid=node.uuid,
+                group_id=group_id,
+                created_at=episode.created_at,
+            ).save(graphiti.driver)
+
+        await EntityEdge(
+            source_node_uuid=alice.uuid,
+            target_node_uuid=bob.uuid,
+            name='KNOWS',
+            fact='Alice knows Bob',
+            fact_embedding=[0.5] * 1024,
+            group_id=group_id,
+            created_at=now,
+        ).save(graphiti.driver)
+
+        result = await graphiti.prune_episodes(
+            group_id, now - timedelta(days=7), delete_orphaned_entities=True
+        )
+
+        assert result.episode_uuids == [old_episode.uuid]
+        assert result.entity_uuids == [carol.uuid]
+
+        remaining_episodes = await EpisodicNode.get_by_group_ids(graphiti.driver, [group_id])
+        assert [episode.uuid for episode in remaining_episodes] == [new_episode.uuid]
+        remaining_nodes = await EntityNode.get_by_group_ids(graphiti.driver, [group_id])
+        assert {node.name for node in remaining_nodes} == {'Alice', 'Bob'}
+
+        stats = await graphiti.group_stats(group_id)
+        assert stats.entity_edge_count == 1
+    finally:
+        await clear_data(graphiti.driver, [group_id])
+        await graphiti.close()
@@ -0,0 +1,88 @@
+from datetime import datetime, timezone
+from unittest.mock import MagicMock
+
+import pytest
+
+from graphiti_core.encryption import ENCRYPTED_PREFIX, GroupEncryption
+from graphiti_core.errors import EmbeddingsUnchangedError
+from graphiti_core.models.nodes.node_db_queries import ENTITY_NODE_SAVE
+from graphiti_core.nodes import EntityEmbeddingText
+from graphiti_core.testing import MockEmbedderClient
+
+NOW = datetime(2024, 6, 1, tzinfo=timezone.utc)
+
+NODE_RECORD = {
+    'uuid': 'alice',
+    'name': 'Alice',
+    'group_id': 'group_1',
+    'labels': ['Entity'],
+    'created_at': NOW.isoformat(),
+    'summary': '',
+    'attributes': {},
+}
+
+
+def _store(
+    driver: MagicMock, stored_embedding: list[float], node_record: dict = NODE_RECORD
+) -> None:
+    async def execute_query(query, **kwargs):
+        if 'n.name_embedding IS NOT NULL' in query:
+            return [{'uuid': 'alice', 'name_embedding': stored_embedding}], None, None
+        if 'MATCH (n:Entity {uuid: $uuid})' in query:
+            return [node_record], None, None
+        if 'MATCH (n:Entity) WHERE n.group_id IN $group_ids' in query:
+            return [node_record], None, None
+        return [], None, None
+
+    driver.execute_query.side_effect = execute_query
+
+
+@pytest.mark.asyncio
+async def test_reembed_replaces_embeddings_with_the_new_embedders(make_graphiti, mock_driver):
+    old_embedder = MockEmbedderClient(embedding_dim=4)
+    new_embedder = MockEmbedderClient(embedding_dim=8)
+    _store(mock_driver, old_embedder.embed('Alice'))
+    graphiti = make_graphiti(embedder=new_embedder)
+
+    reembedded = await graphiti.reembed('group_1')
+
+    saves = {call.args[0]: call.kwargs for call in mock_driver.execute_query.await_args_list}
+    assert reembedded == 1
+    assert saves[ENTITY_NODE_SAVE]['entity_data']['name_embedding'] == new_embedder.embed('Alice')
+
+
+@pytest.mark.asyncio
+async def test_reembed_with_an_unchanged_embedder_is_refused(make_graphiti, mock_driver):
+    embedder = MockEmbedderClient(embedding_dim=8)
+    _store(mock_driver, embedder.embed('Alice'))
+    graphiti = make_graphiti(embedder=embedder)
+
+    with pytest.raises(EmbeddingsUnchangedError):
+        await graphiti.reembed('group_1')
+
+    queries = [call.args[0] for call in mock_driver.execute_query.await_args_list]
+    assert ENTITY_NODE_SAVE not in queries
+
+
+@pytest.mark.asyncio
+async def test_reembed_of_an_encrypted_group_embeds_the_plaintext(make_graphiti, mock_driver):
+    encryption = GroupEncryption({'group_1': GroupEncryption.generate_key()})
+    summary = encryption.encrypt('group_1', 'Alice has asthma')
+    new_embedder = MockEmbedderClient(embedding_dim=8)
+    _store(
+        mock_driver,
+        MockEmbedderClient(embedding_dim=4).embed('Alice'),
+        {**NODE_RECORD, 'summary': summary},
+    )
+    graphiti = make_graphiti(
+        embedder=new_embedder,
+        encryption=encryption,
+        entity_embedding_text=EntityEmbeddingText.name_and_summary,
+    )
+
+    await graphiti.reembed('group_1')
+
+    saves = {call.args[0]: call.kwargs for call in mock_driver.execute_query.await_args_list}
+    saved = saves[ENTITY_NODE_SAVE]['entity_data']
+    assert saved['name_embedding'] == new_embedder.embed('Alice Alice has asthma')
+    assert saved['summary'].startswith(ENCRYPTED_PREFIX)
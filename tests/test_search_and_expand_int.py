@@ -0,0 +1,105 @@
+"""
+Copyright 2024, Zep Software, Inc.
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+"""
+
+import os
+from datetime import datetime, timezone
+from unittest.mock import AsyncMock, MagicMock
+from uuid import uuid4
+
+import pytest
+
+from graphiti_core.cross_encoder.client import CrossEncoderClient
+from graphiti_core.edges import EntityEdge
+from graphiti_core.embedder.client import EmbedderClient
+from graphiti_core.graphiti import Graphiti
+from graphiti_core.llm_client import LLMClient
+from graphiti_core.nodes import EntityNode
+from graphiti_core.search.search_config import NodeSearchConfig, NodeSearchMethod, SearchConfig
+from graphiti_core.utils.maintenance.graph_data_operations import clear_data
+
+pytestmark = pytest.mark.integration
+
+NEO4J_URI = os.getenv('NEO4J_URI', 'bolt://localhost:7687')
+NEO4J_USER = os.getenv('NEO4J_USER', 'neo4j')
+NEO4J_PASSWORD = os.getenv('NEO4J_PASSWORD', 'test')
+
+# Only Alice is similar to the query
+SEED_CONFIG = SearchConfig(
+    node_config=NodeSearchConfig(
+        search_methods=[NodeSearchMethod.cosine_similarity], sim_min_score=0.9
+    )
+)
+
+
+@pytest.mark.asyncio
+async def test_search_and_expand_reaches_nodes_that_do_not_match_the_query():
+    graphiti = Graphiti(
+        NEO4J_URI,
+        NEO4J_USER,
+        NEO4J_PASSWORD,
+        llm_client=MagicMock(spec=LLMClient),
+        embedder=MagicMock(spec=EmbedderClient, create=AsyncMock(return_value=[1.0, 0.0, 0.0])),
+        cross_encoder=MagicMock(spec=CrossEncoderClient),
+    )
+    group_id = f'expand_test_{uuid4().hex}'
+    now = datetime.now(timezone.utc)
+
+    try:
+        alice, bob, carol, dave = [
+            EntityNode(name=name, group_id=group_id, labels=['Entity'], name_embedding=embedding)
+            for name, embedding in [
+                ('Alice', [1.0, 0.0, 0.0]),
+                ('Bob', [0.0, 1.0, 0.0]),
+                ('Carol', [0.0, 0.0, 1.0]),
+                ('Dave', [0.0, 1.0, 0.0]),
+            ]
+        ]
+        for node in [alice, bob, carol, dave]:
+            await node.save(graphiti.driver)
+
+        # Alice knows Bob, who knows Carol; Dave is not connected
+        knows_bob, knows_carol = [
+            EntityEdge(
+                source_node_uuid=source.uuid,
+                target_node_uuid=target.uuid,
+                name='KNOWS',
+                fact=f'{source.name} knows {target.name}',
+                fact_embedding=[0.0, 1.0, 0.0],
+                group_id=group_id,
+                created_at=now,
+            )
+            for source, target in [(alice, bob), (bob, carol)]
+        ]
+        for edge in [knows_bob, knows_carol]:
+            await edge.save(graphiti.driver)
+
+        results = await graphiti.search_and_expand(
+            'Alice', expand_depth=2, config=SEED_CONFIG, group_ids=[group_id]
+        )
+
+        assert results.nodes[0].name == 'Alice'
+        assert {node.name for node in results.nodes} == {'Alice', 'Bob', 'Carol'}
+        assert {edge.uuid for edge in results.edges} == {knows_bob.uuid, knows_carol.uuid}
+
+        results = await graphiti.search_and_expand(
+            'Alice', expand_depth=1, config=SEED_CONFIG, group_ids=[group_id]
+        )
+
+        assert {node.name for node in results.nodes} == {'Alice', 'Bob'}
+        assert {edge.uuid for edge in results.edges} == {knows_bob.uuid}
+    finally:
+        await clear_data(graphiti.driver, [group_id])
+        await graphiti.close()
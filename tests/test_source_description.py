@@ -0,0 +1,73 @@
+from datetime import datetime, timezone
+from unittest.mock import AsyncMock, patch
+
+import pytest
+
+from graphiti_core.errors import MissingSourceDescriptionError
+from graphiti_core.graphiti import Graphiti
+from graphiti_core.nodes import EpisodeType
+from graphiti_core.utils.datetime_utils import FixedClock, use_clock
+
+NOW = datetime(2024, 6, 1, 12, 0, tzinfo=timezone.utc)
+
+
+async def _add_episode(graphiti: Graphiti, source_description: str):
+    with (
+        patch('graphiti_core.graphiti.retrieve_episodes', AsyncMock(return_value=[])),
+        patch('graphiti_core.graphiti.extract_nodes', AsyncMock(return_value=[])),
+        patch('graphiti_core.graphiti.extract_edges', AsyncMock(return_value=[])),
+        patch(
+            'graphiti_core.graphiti.resolve_extracted_nodes',
+            AsyncMock(return_value=([], {}, [])),
+        ),
+        patch('graphiti_core.graphiti.resolve_extracted_edges', AsyncMock(return_value=([], []))),
+        patch('graphiti_core.graphiti.extract_attributes_from_nodes', AsyncMock(return_value=[])),
+        patch('graphiti_core.graphiti.add_nodes_and_edges_bulk', AsyncMock()) as save_bulk,
+        use_clock(FixedClock(NOW)),
+    ):
+        await graphiti.add_episode(
+            name='episode',
+            episode_body='Alice knows Bob',
+            source_description=source_description,
+            reference_time=NOW,
+            source=EpisodeType.text,
+            group_id='group_1',
+        )
+
+    return save_bulk.call_args.args[1][0]
+
+
+@pytest.mark.asyncio
+async def test_missing_source_description_is_rejected_when_required(make_graphiti):
+    graphiti = make_graphiti(enable_embeddings=False, require_source_description=True)
+
+    with pytest.raises(MissingSourceDescriptionError):
+        await _add_episode(graphiti, '  ')
+
+    episode = await _add_episode(graphiti, 'crm export')
+    assert episode.source_description == 'crm export'
+
+
+@pytest.mark.asyncio
+async def test_default_template_fills_in_missing_source_description(make_graphiti):
+    graphiti = make_graphiti(
+        enable_embeddings=False,
+        require_source_description=True,
+        default_source_description='imported from {source} at {time}',
+    )
+
+    episode = await _add_episode(graphiti, '')
+
+    assert episode.source_description == 'imported from text at 2024-06-01T12:00:00+00:00'
+
+
+@pytest.mark.asyncio
+async def test_blank_source_description_is_kept_by_default(make_graphiti):
+    episode = await _add_episode(make_graphiti(enable_embeddings=False), '')
+
+    assert episode.source_description == ''
+
+
+def test_unknown_template_fields_are_rejected(make_graphiti):
+    with pytest.raises(ValueError):
+        make_graphiti(enable_embeddings=False, default_source_description='imported from {channel}')
@@ -0,0 +1,84 @@
+from datetime import datetime, timezone
+
+import pytest
+
+from graphiti_core.prompts import SystemInstructions, prompt_library, use_system_instructions
+from graphiti_core.prompts.extract_edges import ExtractedEdges
+from graphiti_core.prompts.extract_nodes import ExtractedEntities
+from graphiti_core.prompts.prompt_helpers import DO_NOT_ESCAPE_UNICODE
+from graphiti_core.testing import MockLLMClient
+
+MEDICAL = 'This is medical text; preserve drug dosages.'
+
+CONTEXT = {
+    'episode_content': 'Take 20mg of aspirin',
+    'entity_types': [],
+    'custom_prompt': '',
+}
+
+
+def _system_message(messages) -> str:
+    return next(message.content for message in messages if message.role == 'system')
+
+
+def test_custom_instructions_reach_system_message():
+    with use_system_instructions(SystemInstructions(default=MEDICAL)):
+        messages = prompt_library.extract_nodes.extract_text(CONTEXT)
+
+    system_message = _system_message(messages)
+    assert MEDICAL in system_message
+    # The unicode instruction is still appended last
+    assert system_message.endswith(DO_NOT_ESCAPE_UNICODE)
+    assert MEDICAL not in messages[-1].content
+
+
+def test_instructions_are_scoped_to_the_context():
+    with use_system_instructions(SystemInstructions(default=MEDICAL)):
+        pass
+
+    assert MEDICAL not in _system_message(prompt_library.extract_nodes.extract_text(CONTEXT))
+
+
+def test_per_step_overrides():
+    instructions = SystemInstructions(
+        default=MEDICAL,
+        overrides={'extract_nodes': 'Only extract drugs.', 'extract_nodes.extract_json': ''},
+    )
+
+    with use_system_instructions(instructions):
+        text_prompt = _system_message(prompt_library.extract_nodes.extract_text(CONTEXT))
+        json_prompt = _system_message(
+            prompt_library.extract_nodes.extract_json({**CONTEXT, 'source_description': 'test'})
+        )
+
+    assert 'Only extract drugs.' in text_prompt
+    assert MEDICAL not in text_prompt
+    assert MEDICAL not in json_prompt
+    assert 'Only extract drugs.' not in json_prompt
+
+
+@pytest.mark.asyncio
+async def test_add_episode_sends_custom_instructions(make_graphiti):
+    llm_client = (
+        MockLLMClient(default_response={'summary': 'summary'})
+        .add_response(ExtractedEntities, {'extracted_entities': []})
+        .add_response(ExtractedEdges, {'edges': []})
+    )
+    graphiti = make_graphiti(llm_client=llm_client, custom_system_instructions=MEDICAL)
+
+    await graphiti.add_episode(
+        name='episode',
+        episode_body='Take 20mg of aspirin',
+        source_description='test',
+        reference_time=datetime.now(timezone.utc),
+        source='text',
+        group_id='group_1',
+    )
+
+    extraction_calls = [
+        messages
+        for messages, response_model in llm_client.calls
+        if response_model is ExtractedEntities
+    ]
+    assert len(extraction_calls) == 1
+    assert MEDICAL in _system_message(extraction_calls[0])
@@ -4,7 +4,8 @@ from unittest.mock import MagicMock
 import pytest
 
 from graphiti_core.edges import EntityEdge
-from graphiti_core.nodes import EpisodicNode
+from graphiti_core.nodes import EntityNode, EpisodicNode
+from graphiti_core.utils.maintenance.edge_operations import build_episodic_edges
 
 
 @pytest.fixture
@@ -89,6 +90,20 @@ def mock_previous_episodes():
     ]
 
 
+def test_build_episodic_edges_links_each_entity_once(mock_current_episode):
+    alice = EntityNode(name='Alice', group_id='group_1')
+    bob = EntityNode(name='Bob', group_id='group_1')
+    now = datetime.now(timezone.utc)
+
+    # Alice was extracted twice from the same episode
+    edges = build_episodic_edges([alice, bob, alice.model_copy()], mock_current_episode, now)
+
+    assert [(edge.source_node_uuid, edge.target_node_uuid) for edge in edges] == [
+        ('episode_1', alice.uuid),
+        ('episode_1', bob.uuid),
+    ]
+
+
 # Run the tests
 if __name__ == '__main__':
     pytest.main([__file__])
@@ -0,0 +1,100 @@
+from datetime import datetime, timedelta, timezone
+from unittest.mock import AsyncMock, MagicMock, patch
+
+import pytest
+
+from graphiti_core.driver.driver import GraphDriver
+from graphiti_core.utils.maintenance.graph_data_operations import (
+    retrieve_episodes,
+    wait_for_database,
+)
+
+
+@pytest.mark.asyncio
+async def test_wait_for_database_retries_until_connected():
+    driver = MagicMock(spec=GraphDriver)
+    driver.execute_query = AsyncMock(
+        side_effect=[ConnectionError('refused'), ConnectionError('refused'), ([], None, None)]
+    )
+    mock_sleep = AsyncMock()
+
+    with patch('graphiti_core.utils.maintenance.graph_data_operations.asyncio.sleep', mock_sleep):
+        await wait_for_database(driver, retry_seconds=60, initial_delay=1)
+
+    assert driver.execute_query.await_count == 3
+    # Backoff doubles between attempts
+    assert [call.args[0] for call in mock_sleep.await_args_list] == [1, 2]
+
+
+@pytest.mark.asyncio
+async def test_wait_for_database_gives_up_after_retry_window():
+    driver = MagicMock(spec=GraphDriver)
+    driver.execute_query = AsyncMock(side_effect=ConnectionError('refused'))
+
+    with pytest.raises(ConnectionError):
+        await wait_for_database(driver, retry_seconds=0)
+
+    assert driver.execute_query.await_count == 1
+
+
+START = datetime(2024, 1, 1, tzinfo=timezone.utc)
+
+
+def _episode_store_driver(count: int) -> MagicMock:
+    # Answers episode lookups from episodes one second apart, like the Cypher query would
+    records = [
+        {
+            'content': f'Message {i}',
+            'created_at': (START + timedelta(seconds=i)).isoformat(),
+            'valid_at': (START + timedelta(seconds=i)).isoformat(),
+            'uuid': f'episode-{i}',
+            'group_id': 'group_1',
+            'name': f'Episode {i}',
+            'source_description': 'chat',
+            'source': 'message',
+        }
+        for i in range(count)
+    ]
+
+    async def execute_query(query, reference_time, num_episodes, **kwargs):
+        matching = [
+            record
+            for record in records
+            if datetime.fromisoformat(record['valid_at']) <= reference_time
+        ]
+        return list(reversed(matching))[:num_episodes], None, None
+
+    return MagicMock(spec=GraphDriver, execute_query=AsyncMock(side_effect=execute_query))
+
+
+@pytest.mark.asyncio
+async def test_retrieve_episodes_returns_the_latest_episodes():
+    driver = _episode_store_driver(10)
+
+    episodes = await retrieve_episodes(driver, START + timedelta(seconds=9), last_n=3)
+
+    assert [episode.name for episode in episodes] == ['Episode 7', 'Episode 8', 'Episode 9']
+
+
+@pytest.mark.asyncio
+async def test_retrieve_episodes_skips_episodes_closer_than_the_min_interval():
+    driver = _episode_store_driver(10)
+
+    episodes = await retrieve_episodes(
+        driver, START + timedelta(seconds=9), last_n=3, min_interval=timedelta(seconds=3)
+    )
+
+    assert [episode.name for episode in episodes] == ['Episode 3', 'Episode 6', 'Episode 9']
+    gaps = [later.valid_at - earlier.valid_at for earlier, later in zip(episodes, episodes[1:])]
+    assert all(gap >= timedelta(seconds=3) for gap in gaps)
+
+
+@pytest.mark.asyncio
+async def test_retrieve_episodes_with_min_interval_stops_at_the_oldest_episode():
+    driver = _episode_store_driver(10)
+
+    episodes = await retrieve_episodes(
+        driver, START + timedelta(seconds=9), last_n=5, min_interval=timedelta(seconds=4)
+    )
+
+    assert [episode.name for episode in episodes] == ['Episode 1', 'Episode 5', 'Episode 9']
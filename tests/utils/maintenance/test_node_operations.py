@@ -0,0 +1,540 @@
+import json
+from datetime import datetime, timezone
+from typing import Any
+from unittest.mock import AsyncMock, MagicMock, patch
+
+import pytest
+from pydantic import BaseModel, Field
+
+from graphiti_core.driver.driver import GraphDriver
+from graphiti_core.errors import ExtractionError
+from graphiti_core.graphiti_types import GraphitiClients
+from graphiti_core.helpers import AttributeConflictPolicy, AttributeValidationPolicy
+from graphiti_core.nodes import (
+    EntityEmbeddingText,
+    EntityNode,
+    EpisodeType,
+    EpisodicNode,
+    get_entity_node_from_record,
+)
+from graphiti_core.prompts.dedupe_nodes import NodeResolutions
+from graphiti_core.prompts.extract_edges import ExtractedEdges, MissingFacts
+from graphiti_core.prompts.extract_nodes import ExtractedEntities, MissedEntities
+from graphiti_core.prompts.summarize_nodes import Summary, SummaryDescription
+from graphiti_core.search.search_config import SearchResults
+from graphiti_core.testing import MockCrossEncoderClient, MockEmbedderClient, MockLLMClient
+from graphiti_core.utils.maintenance.community_operations import build_community
+from graphiti_core.utils.maintenance.edge_operations import extract_edges
+from graphiti_core.utils.maintenance.node_operations import (
+    ATTRIBUTE_HISTORY_KEY,
+    ATTRIBUTE_OBSERVED_AT_KEY,
+    extract_attributes_from_nodes,
+    extract_nodes,
+    merge_extracted_attributes,
+    resolve_extracted_nodes,
+)
+
+LONG_SUMMARY = ' '.join(f'word{i}' for i in range(2000))
+
+
+def _clients(llm_client: MockLLMClient, **settings: Any) -> GraphitiClients:
+    return GraphitiClients(
+        driver=MagicMock(spec=GraphDriver, execute_query=AsyncMock(return_value=([], None, None))),
+        llm_client=llm_client,
+        embedder=MockEmbedderClient(),
+        cross_encoder=MockCrossEncoderClient(),
+        **{'max_reflexion_rounds': 1, **settings},
+    )
+
+
+def _episode() -> EpisodicNode:
+    return EpisodicNode(
+        name='episode',
+        group_id='group',
+        source=EpisodeType.text,
+        source_description='test',
+        content='Alice met Bob in Paris.',
+        valid_at=datetime.now(timezone.utc),
+    )
+
+
+def _entity(name: str) -> dict:
+    return {'name': name, 'entity_type_id': 0}
+
+
+def _scripted_node_llm() -> MockLLMClient:
+    # The first extraction misses Bob; reflexion reports him once and is then satisfied
+    return (
+        MockLLMClient()
+        .add_response(ExtractedEntities, {'extracted_entities': [_entity('Alice')]}, times=1)
+        .add_response(ExtractedEntities, {'extracted_entities': [_entity('Bob')]})
+        .add_response(MissedEntities, {'missed_entities': ['Bob']}, times=1)
+        .add_response(MissedEntities, {'missed_entities': []})
+    )
+
+
+@pytest.mark.asyncio
+async def test_extract_nodes_reflexion_merges_missed_entities():
+    llm_client = _scripted_node_llm()
+
+    nodes = await extract_nodes(_clients(llm_client, max_reflexion_rounds=1), _episode(), [])
+
+    assert sorted(node.name for node in nodes) == ['Alice', 'Bob']
+    assert llm_client.count_calls(MissedEntities) == 1
+    # The re-extraction is told which entities were missed
+    assert llm_client.count_calls('Make sure that the following entities are extracted') == 1
+
+
+@pytest.mark.asyncio
+async def test_extract_nodes_reflexion_stops_when_nothing_is_missed():
+    llm_client = _scripted_node_llm()
+
+    nodes = await extract_nodes(_clients(llm_client, max_reflexion_rounds=3), _episode(), [])
+
+    assert sorted(node.name for node in nodes) == ['Alice', 'Bob']
+    assert llm_client.count_calls(MissedEntities) == 2
+    assert llm_client.count_calls(ExtractedEntities) == 2
+
+
+@pytest.mark.asyncio
+async def test_extract_nodes_without_reflexion():
+    llm_client = _scripted_node_llm()
+
+    nodes = await extract_nodes(_clients(llm_client, max_reflexion_rounds=0), _episode(), [])
+
+    assert [node.name for node in nodes] == ['Alice']
+    assert llm_client.count_calls(MissedEntities) == 0
+
+
+@pytest.mark.asyncio
+async def test_extract_edges_reflexion_merges_missed_facts():
+    alice_knows_bob = {
+        'relation_type': 'KNOWS',
+        'source_entity_id': 0,
+        'target_entity_id': 1,
+        'fact': 'Alice knows Bob',
+    }
+    alice_in_paris = {
+        'relation_type': 'LOCATED_IN',
+        'source_entity_id': 0,
+        'target_entity_id': 2,
+        'fact': 'Alice was in Paris',
+    }
+    llm_client = (
+        MockLLMClient()
+        .add_response(ExtractedEdges, {'edges': [alice_knows_bob]}, times=1)
+        .add_response(ExtractedEdges, {'edges': [alice_knows_bob, alice_in_paris]})
+        .add_response(MissingFacts, {'missing_facts': ['Alice was in Paris']}, times=1)
+        .add_response(MissingFacts, {'missing_facts': []})
+    )
+    clients = _clients(llm_client, max_reflexion_rounds=1)
+    nodes = [EntityNode(name=name, group_id='group') for name in ['Alice', 'Bob', 'Paris']]
+
+    edges = await extract_edges(clients, _episode(), nodes, [], {}, group_id='group')
+
+    assert sorted(edge.fact for edge in edges) == ['Alice knows Bob', 'Alice was in Paris']
+    assert llm_client.count_calls(MissingFacts) == 1
+
+
+@pytest.mark.asyncio
+async def test_extract_edges_keeps_reported_confidence():
+    hedged = {
+        'relation_type': 'KNOWS',
+        'source_entity_id': 0,
+        'target_entity_id': 1,
+        'fact': 'Alice might know Bob',
+        'confidence': 0.3,
+    }
+    overconfident = {
+        'relation_type': 'LOCATED_IN',
+        'source_entity_id': 0,
+        'target_entity_id': 2,
+        'fact': 'Alice was in Paris',
+        'confidence': 7,
+    }
+    unrated = {
+        'relation_type': 'LOCATED_IN',
+        'source_entity_id': 1,
+        'target_entity_id': 2,
+        'fact': 'Bob was in Paris',
+    }
+    llm_client = MockLLMClient().add_response(
+        ExtractedEdges, {'edges': [hedged, overconfident, unrated]}
+    )
+    nodes = [EntityNode(name=name, group_id='group') for name in ['Alice', 'Bob', 'Paris']]
+
+    edges = await extract_edges(
+        _clients(llm_client, max_reflexion_rounds=0), _episode(), nodes, [], {}, group_id='group'
+    )
+
+    assert {edge.fact: edge.confidence for edge in edges} == {
+        'Alice might know Bob': 0.3,
+        'Alice was in Paris': 1.0,
+        'Bob was in Paris': None,
+    }
+
+
+@pytest.mark.asyncio
+async def test_extracted_entities_are_capped_per_episode():
+    llm_client = MockLLMClient().add_response(
+        ExtractedEntities, {'extracted_entities': [_entity(f'Person {i}') for i in range(10)]}
+    )
+
+    nodes = await extract_nodes(
+        _clients(llm_client, max_reflexion_rounds=0, max_entities_per_episode=3), _episode(), []
+    )
+
+    assert [node.name for node in nodes] == ['Person 0', 'Person 1', 'Person 2']
+
+
+@pytest.mark.asyncio
+async def test_unparseable_extraction_is_empty_unless_strict():
+    llm_client = MockLLMClient().add_response(ExtractedEntities, {'entities': 'garbage'})
+
+    nodes = await extract_nodes(_clients(llm_client, max_reflexion_rounds=0), _episode(), [])
+    assert nodes == []
+
+    with pytest.raises(ExtractionError):
+        await extract_nodes(
+            _clients(llm_client, max_reflexion_rounds=0, strict_extraction=True), _episode(), []
+        )
+
+
+@pytest.mark.asyncio
+async def test_language_hint_keeps_native_script_names():
+    def echo_hint(messages, response_model):
+        # Romanizes the name unless the prompt says to keep it as written
+        prompt = messages[-1].content
+        keep_script = 'The content is written in Russian' in prompt and 'romanize' in prompt
+        name = 'Мария Иванова' if keep_script else 'Mariya Ivanova'
+        return {'extracted_entities': [_entity(name)]}
+
+    llm_client = MockLLMClient().add_response(ExtractedEntities, echo_hint)
+    episode = _episode()
+    episode.content = 'Мария Иванова переехала в Москву.'
+    clients = _clients(llm_client, max_reflexion_rounds=0)
+
+    assert [node.name for node in await extract_nodes(clients, episode, [])] == ['Mariya Ivanova']
+
+    episode.language = 'Russian'
+    assert [node.name for node in await extract_nodes(clients, episode, [])] == ['Мария Иванова']
+
+
+@pytest.mark.asyncio
+async def test_strict_extraction_fails_when_nothing_is_extracted():
+    llm_client = MockLLMClient().add_response(ExtractedEntities, {'extracted_entities': []})
+    clients = _clients(llm_client, max_reflexion_rounds=0, strict_extraction=True)
+
+    with pytest.raises(ExtractionError):
+        await extract_nodes(clients, _episode(), [])
+
+    empty_episode = _episode()
+    empty_episode.content = ''
+    assert await extract_nodes(clients, empty_episode, []) == []
+
+
+@pytest.mark.asyncio
+async def test_extracted_edges_are_capped_to_the_most_confident():
+    facts = [
+        {
+            'relation_type': 'KNOWS',
+            'source_entity_id': 0,
+            'target_entity_id': 1,
+            'fact': f'Fact {i}',
+            'confidence': confidence,
+        }
+        for i, confidence in enumerate([0.2, None, 0.9, 0.5, 0.9])
+    ]
+    llm_client = MockLLMClient().add_response(ExtractedEdges, {'edges': facts})
+    nodes = [EntityNode(name=name, group_id='group') for name in ['Alice', 'Bob']]
+
+    edges = await extract_edges(
+        _clients(llm_client, max_reflexion_rounds=0, max_edges_per_episode=3),
+        _episode(),
+        nodes,
+        [],
+        {},
+        group_id='group',
+    )
+
+    assert [edge.fact for edge in edges] == ['Fact 2', 'Fact 3', 'Fact 4']
+
+
+@pytest.mark.asyncio
+async def test_extracted_self_loops_and_duplicates_are_dropped():
+    alice_knows_bob = {
+        'relation_type': 'KNOWS',
+        'source_entity_id': 0,
+        'target_entity_id': 1,
+        'fact': 'Alice knows Bob',
+    }
+    repeated = {**alice_knows_bob, 'fact': ' alice knows Bob'}
+    self_loop = {
+        'relation_type': 'KNOWS',
+        'source_entity_id': 0,
+        'target_entity_id': 0,
+        'fact': 'Alice knows herself',
+    }
+    llm_client = MockLLMClient().add_response(
+        ExtractedEdges, {'edges': [alice_knows_bob, self_loop, repeated]}
+    )
+    nodes = [EntityNode(name=name, group_id='group') for name in ['Alice', 'Bob']]
+
+    edges = await extract_edges(
+        _clients(llm_client, max_reflexion_rounds=0), _episode(), nodes, [], {}, group_id='group'
+    )
+    assert [edge.fact for edge in edges] == ['Alice knows Bob']
+
+    edges = await extract_edges(
+        _clients(llm_client, max_reflexion_rounds=0, allow_self_loops=True),
+        _episode(),
+        nodes,
+        [],
+        {},
+        group_id='group',
+    )
+    assert [edge.fact for edge in edges] == ['Alice knows Bob', 'Alice knows herself']
+
+
+@pytest.mark.asyncio
+async def test_extract_attributes_truncates_long_summaries():
+    llm_client = MockLLMClient(default_response={'summary': LONG_SUMMARY})
+    node = EntityNode(name='Alice', group_id='group', labels=['Entity'])
+
+    [updated] = await extract_attributes_from_nodes(_clients(llm_client), [node], _episode(), [])
+
+    assert len(updated.summary.split()) == 250
+    assert updated.summary.endswith('word249...')
+
+
+@pytest.mark.parametrize(
+    'composition, expected',
+    [
+        (EntityEmbeddingText.name, 'Alice'),
+        (EntityEmbeddingText.name_and_summary, 'Alice A traveller'),
+        (
+            EntityEmbeddingText.name_summary_and_mentions,
+            'Alice A traveller Alice met Bob in Paris.',
+        ),
+    ],
+)
+@pytest.mark.asyncio
+async def test_entity_embedding_text_follows_the_configured_composition(composition, expected):
+    llm_client = MockLLMClient(default_response={'summary': 'A traveller'})
+    clients = _clients(llm_client, entity_embedding_text=composition)
+    node = EntityNode(name='Alice', group_id='group', labels=['Entity'])
+
+    [updated] = await extract_attributes_from_nodes(clients, [node], _episode(), [])
+
+    assert clients.embedder.inputs == [expected]
+    assert updated.name_embedding == clients.embedder.embed(expected)
+
+
+class Car(BaseModel):
+    """A car"""
+
+    color: str | None = Field(None, description='The color of the car')
+    doors: int | None = Field(None, description='The number of doors')
+
+
+@pytest.mark.parametrize(
+    'policy, expected',
+    [
+        (AttributeValidationPolicy.keep, {'color': 'red', 'doors': '4', 'colour': 'blue'}),
+        (AttributeValidationPolicy.coerce, {'color': 'red', 'doors': 4}),
+        (AttributeValidationPolicy.drop, {'color': 'red'}),
+    ],
+)
+@pytest.mark.asyncio
+async def test_extracted_attributes_are_validated_per_policy(policy, expected):
+    llm_client = MockLLMClient(
+        default_response={'summary': 'A red car', 'color': 'red', 'doors': '4', 'colour': 'blue'}
+    )
+    node = EntityNode(name='Herbie', group_id='group', labels=['Entity', 'Car'])
+
+    [updated] = await extract_attributes_from_nodes(
+        _clients(llm_client, attribute_validation=policy), [node], _episode(), [], {'Car': Car}
+    )
+
+    assert updated.attributes == expected
+
+
+JUNE = datetime(2024, 6, 1, tzinfo=timezone.utc)
+JANUARY = datetime(2024, 1, 1, tzinfo=timezone.utc)
+
+
+@pytest.mark.parametrize(
+    'policy, expected_color',
+    [
+        (AttributeConflictPolicy.overwrite, 'green'),
+        (AttributeConflictPolicy.keep_newest, 'red'),
+        (AttributeConflictPolicy.append_history, 'green'),
+    ],
+)
+@pytest.mark.asyncio
+async def test_attribute_updates_follow_conflict_policy(policy, expected_color):
+    node = EntityNode(
+        name='Herbie', group_id='group', labels=['Entity', 'Car'], attributes={'color': 'blue'}
+    )
+    clients = _clients(MockLLMClient(), attribute_conflict_policy=policy)
+
+    # A June episode repaints the car red, then an older January episode is ingested late
+    for color, valid_at in [('red', JUNE), ('green', JANUARY)]:
+        clients.llm_client = MockLLMClient(default_response={'summary': 'A car', 'color': color})
+        episode = _episode().model_copy(update={'valid_at': valid_at})
+        [node] = await extract_attributes_from_nodes(clients, [node], episode, [], {'Car': Car})
+
+    assert node.attributes['color'] == expected_color
+    # Bookkeeping is kept out of the user-facing attributes
+    assert node.attributes == {'color': expected_color}
+    if policy != AttributeConflictPolicy.overwrite:
+        assert ATTRIBUTE_OBSERVED_AT_KEY in node.attribute_metadata
+    if policy == AttributeConflictPolicy.append_history:
+        assert json.loads(node.attribute_metadata[ATTRIBUTE_HISTORY_KEY]) == {
+            'color': [
+                {'value': 'blue', 'observed_at': None, 'replaced_at': JUNE.isoformat()},
+                {
+                    'value': 'red',
+                    'observed_at': JUNE.isoformat(),
+                    'replaced_at': JANUARY.isoformat(),
+                },
+            ]
+        }
+    else:
+        assert ATTRIBUTE_HISTORY_KEY not in node.attribute_metadata
+
+
+def test_keep_newest_compares_naive_and_aware_observation_times():
+    node = EntityNode(name='Herbie', group_id='group', labels=['Entity', 'Car'])
+
+    merge_extracted_attributes(node, {'color': 'red'}, AttributeConflictPolicy.keep_newest, JUNE)
+    merge_extracted_attributes(
+        node,
+        {'color': 'green'},
+        AttributeConflictPolicy.keep_newest,
+        JANUARY.replace(tzinfo=None),
+    )
+
+    assert node.attributes == {'color': 'red'}
+
+
+def test_attribute_metadata_is_read_apart_from_the_attributes():
+    node = EntityNode(name='Herbie', group_id='group', labels=['Entity', 'Car'])
+    merge_extracted_attributes(node, {'color': 'red'}, AttributeConflictPolicy.keep_newest, JUNE)
+    record = {
+        'uuid': node.uuid,
+        'name': node.name,
+        'group_id': node.group_id,
+        'labels': node.labels,
+        'created_at': node.created_at,
+        'summary': '',
+        'attributes': {'uuid': node.uuid, **node.attributes, **node.attribute_metadata},
+    }
+
+    read_node = get_entity_node_from_record(record)
+
+    assert read_node.attributes == {'color': 'red'}
+    assert read_node.attribute_metadata == node.attribute_metadata
+
+
+@pytest.mark.asyncio
+async def test_community_summary_is_truncated_before_naming():
+    llm_client = (
+        MockLLMClient()
+        .add_response(Summary, {'summary': LONG_SUMMARY})
+        .add_response(SummaryDescription, {'description': 'Words'})
+    )
+    nodes = [
+        EntityNode(name=name, group_id='group', summary=f'{name} summary')
+        for name in ['Alice', 'Bob']
+    ]
+
+    community, _ = await build_community(llm_client, nodes, max_summary_words=10)
+
+    assert len(community.summary.split()) == 10
+    # The community name, and so its embedding, is generated from the truncated summary
+    [(messages, _)] = [call for call in llm_client.calls if call[1] is SummaryDescription]
+    assert 'word9...' in messages[-1].content
+    assert 'word10' not in messages[-1].content
+
+
+def _candidates(*nodes: EntityNode):
+    return patch(
+        'graphiti_core.utils.maintenance.node_operations.search',
+        AsyncMock(
+            return_value=SearchResults(edges=[], nodes=list(nodes), episodes=[], communities=[])
+        ),
+    )
+
+
+@pytest.mark.asyncio
+async def test_high_similarity_match_is_merged_without_llm():
+    existing = EntityNode(name='Alice', group_id='group', labels=['Entity'])
+    extracted = EntityNode(name='Alice', group_id='group', labels=['Entity'])
+    llm_client = MockLLMClient()
+
+    with _candidates(existing):
+        resolved, uuid_map, _ = await resolve_extracted_nodes(
+            _clients(llm_client, auto_merge_threshold=0.98), [extracted], _episode(), []
+        )
+
+    assert [node.uuid for node in resolved] == [existing.uuid]
+    assert uuid_map == {extracted.uuid: existing.uuid}
+    assert llm_client.call_count == 0
+
+
+@pytest.mark.asyncio
+async def test_only_ambiguous_nodes_go_to_the_llm():
+    existing = EntityNode(name='Alice', group_id='group', labels=['Entity'])
+    alice = EntityNode(name='Alice', group_id='group', labels=['Entity'])
+    bob = EntityNode(name='Bob', group_id='group', labels=['Entity'])
+    llm_client = MockLLMClient().add_response(
+        NodeResolutions,
+        {'entity_resolutions': [{'id': 0, 'name': 'Bob', 'duplicate_idx': -1}]},
+    )
+
+    with _candidates(existing):
+        resolved, uuid_map, _ = await resolve_extracted_nodes(
+            _clients(llm_client, auto_merge_threshold=0.98), [alice, bob], _episode(), []
+        )
+
+    assert {node.uuid for node in resolved} == {existing.uuid, bob.uuid}
+    assert uuid_map == {alice.uuid: existing.uuid, bob.uuid: bob.uuid}
+    [(messages, _)] = llm_client.calls
+    assert '"name": "Bob"' in messages[-1].content
+    # Alice is only listed as an existing node
+    assert messages[-1].content.count('"name": "Alice"') == 1
+
+
+@pytest.mark.asyncio
+async def test_default_threshold_always_asks_the_llm():
+    existing = EntityNode(name='Alice', group_id='group', labels=['Entity'])
+    extracted = EntityNode(name='Alice', group_id='group', labels=['Entity'])
+    llm_client = MockLLMClient().add_response(
+        NodeResolutions,
+        {'entity_resolutions': [{'id': 0, 'name': 'Alice', 'duplicate_idx': 0}]},
+    )
+
+    with _candidates(existing):
+        resolved, _, _ = await resolve_extracted_nodes(
+            _clients(llm_client), [extracted], _episode(), []
+        )
+
+    assert [node.uuid for node in resolved] == [existing.uuid]
+    assert llm_client.call_count == 1
+
+
+@pytest.mark.asyncio
+async def test_typo_in_name_is_merged_without_llm():
+    existing = EntityNode(name='Microsoft', group_id='group', labels=['Entity'])
+    extracted = EntityNode(name='Micorsoft', group_id='group', labels=['Entity'])
+    llm_client = MockLLMClient()
+
+    with _candidates(existing):
+        resolved, uuid_map, _ = await resolve_extracted_nodes(
+            _clients(llm_client, name_similarity_threshold=0.9), [extracted], _episode(), []
+        )
+
+    assert [node.uuid for node in resolved] == [existing.uuid]
+    assert uuid_map == {extracted.uuid: existing.uuid}
+    assert llm_client.call_count == 0
@@ -0,0 +1,157 @@
+from datetime import datetime, timezone
+from time import monotonic
+from unittest.mock import AsyncMock, patch
+
+import pytest
+
+from graphiti_core.edges import EntityEdge
+from graphiti_core.search.search_cache import DEFAULT_SEARCH_CACHE_TTL, SearchResultCache
+from graphiti_core.search.search_config import SearchResults
+from graphiti_core.search.search_config_recipes import EDGE_HYBRID_SEARCH_RRF
+from graphiti_core.search.search_filters import SearchFilters
+
+
+def _results(*facts: str) -> SearchResults:
+    edges = [
+        EntityEdge(
+            source_node_uuid='source',
+            target_node_uuid='target',
+            name='RELATES_TO',
+            fact=fact,
+            group_id='group_1',
+            created_at=datetime.now(timezone.utc),
+        )
+        for fact in facts
+    ]
+    return SearchResults(edges=edges, nodes=[], episodes=[], communities=[])
+
+
+def _key(cache: SearchResultCache, group_ids: list[str] | None) -> str:
+    return cache.key('query', EDGE_HYBRID_SEARCH_RRF, group_ids, SearchFilters())
+
+
+def test_cache_hit_and_group_invalidation():
+    cache = SearchResultCache(ttl=60)
+    results = _results('fact')
+
+    cache.set(_key(cache, ['group_1']), results)
+    cache.set(_key(cache, ['group_2']), results)
+    assert cache.get(_key(cache, ['group_1'])) == results
+
+    cache.invalidate(['group_1'])
+
+    assert cache.get(_key(cache, ['group_1'])) is None
+    assert cache.get(_key(cache, ['group_2'])) == results
+
+
+def test_cached_results_are_copies():
+    cache = SearchResultCache(ttl=60)
+    key = _key(cache, ['group_1'])
+    results = _results('fact')
+    cache.set(key, results)
+
+    results.edges[0].fact = 'changed before get'
+    cached = cache.get(key)
+    assert cached is not None
+    cached.edges[0].fact = 'changed after get'
+    cached.edges.clear()
+
+    assert [edge.fact for edge in cache.get(key).edges] == ['fact']
+
+
+def test_group_invalidation_invalidates_all_group_searches():
+    cache = SearchResultCache(ttl=60)
+    cache.set(_key(cache, None), _results('fact'))
+
+    cache.invalidate(['group_1'])
+
+    assert cache.get(_key(cache, None)) is None
+
+
+def test_cache_entries_expire():
+    cache = SearchResultCache(ttl=60)
+    key = _key(cache, ['group_1'])
+    cache.set(key, _results('fact'))
+
+    with patch('graphiti_core.search.search_cache.monotonic', return_value=float('inf')):
+        assert cache.get(key) is None
+
+
+@pytest.mark.asyncio
+async def test_graphiti_search_cache_is_short_lived_by_default(make_graphiti):
+    graphiti = make_graphiti()
+    mock_search = AsyncMock(return_value=_results('fact'))
+
+    with patch('graphiti_core.graphiti.search', mock_search):
+        await graphiti.search('fact', group_ids=['group_1'])
+        await graphiti.search('fact', group_ids=['group_1'])
+        assert mock_search.await_count == 1
+
+        expired = monotonic() + DEFAULT_SEARCH_CACHE_TTL + 1
+        with patch('graphiti_core.search.search_cache.monotonic', return_value=expired):
+            await graphiti.search('fact', group_ids=['group_1'])
+
+    assert mock_search.await_count == 2
+
+
+def test_zero_ttl_disables_cache():
+    cache = SearchResultCache(ttl=0)
+    key = _key(cache, ['group_1'])
+    cache.set(key, _results('fact'))
+
+    assert cache.get(key) is None
+
+
+@pytest.mark.asyncio
+async def test_search_after_ingest_is_not_served_from_cache(make_graphiti):
+    graphiti = make_graphiti(search_cache_ttl=60)
+
+    pre_ingest = _results('old fact')
+    post_ingest = _results('old fact', 'new fact')
+    mock_search = AsyncMock(side_effect=[pre_ingest, pre_ingest, post_ingest])
+
+    with (
+        patch('graphiti_core.graphiti.search', mock_search),
+        patch('graphiti_core.graphiti.retrieve_episodes', AsyncMock(return_value=[])),
+        patch('graphiti_core.graphiti.extract_nodes', AsyncMock(return_value=[])),
+        patch(
+            'graphiti_core.graphiti.resolve_extracted_nodes', AsyncMock(return_value=([], {}, []))
+        ),
+        patch('graphiti_core.graphiti.extract_edges', AsyncMock(return_value=[])),
+        patch(
+            'graphiti_core.graphiti.resolve_extracted_edges', AsyncMock(return_value=([], []))
+        ),
+        patch('graphiti_core.graphiti.extract_attributes_from_nodes', AsyncMock(return_value=[])),
+        patch('graphiti_core.graphiti.add_nodes_and_edges_bulk', AsyncMock()),
+    ):
+        first = await graphiti.search('fact', group_ids=['group_1'])
+        cached = await graphiti.search('fact', group_ids=['group_1'])
+        assert mock_search.await_count == 1
+        assert [edge.fact for edge in cached] == [edge.fact for edge in first]
+
+        await graphiti.add_episode(
+            name='episode',
+            episode_body='new fact',
+            source_description='test',
+            reference_time=datetime.now(timezone.utc),
+            group_id='group_1',
+        )
+
+        after_ingest = await graphiti.search('fact', group_ids=['group_1'])
+
+    assert mock_search.await_count == 2
+    assert [edge.fact for edge in after_ingest] == ['old fact', 'new fact']
+
+
+@pytest.mark.asyncio
+async def test_clear_invalidates_the_cleared_groups(make_graphiti, mock_driver):
+    graphiti = make_graphiti()
+    mock_search = AsyncMock(return_value=_results('fact'))
+
+    with patch('graphiti_core.graphiti.search', mock_search):
+        await graphiti.search('fact', group_ids=['group_1'])
+        await graphiti.clear(['group_1'])
+        await graphiti.search('fact', group_ids=['group_1'])
+
+    assert mock_search.await_count == 2
+    mock_driver.session.return_value.execute_write.assert_awaited_once()
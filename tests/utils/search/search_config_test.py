@@ -0,0 +1,66 @@
+from graphiti_core.search.search_config import (
+    EdgeReranker,
+    NodeReranker,
+    NodeSearchMethod,
+    SearchConfig,
+)
+from graphiti_core.search.search_config_recipes import (
+    COMBINED_HYBRID_SEARCH_RRF,
+    EDGE_HYBRID_SEARCH_RRF,
+    NODE_HYBRID_SEARCH_RRF,
+)
+
+
+def test_nodes_only_searches_nodes_only():
+    config = SearchConfig.nodes_only(limit=5)
+
+    assert config.node_config is not None
+    assert config.node_config.search_methods == [
+        NodeSearchMethod.bm25,
+        NodeSearchMethod.cosine_similarity,
+    ]
+    assert config.edge_config is None
+    assert config.episode_config is None
+    assert config.community_config is None
+    assert config.limit == 5
+
+
+def test_facts_only_searches_edges_only():
+    config = SearchConfig.facts_only()
+
+    assert config.edge_config is not None
+    assert config.node_config is None
+    assert config.episode_config is None
+    assert config.community_config is None
+
+
+def test_episodes_only_searches_episodes_only():
+    config = SearchConfig.episodes_only()
+
+    assert config.episode_config is not None
+    assert config.edge_config is None
+    assert config.node_config is None
+    assert config.community_config is None
+
+
+def test_presets_match_hybrid_rrf_recipes():
+    assert SearchConfig.nodes_only() == NODE_HYBRID_SEARCH_RRF
+    assert SearchConfig.facts_only() == EDGE_HYBRID_SEARCH_RRF
+    assert SearchConfig.comprehensive() == COMBINED_HYBRID_SEARCH_RRF
+
+
+def test_presets_take_a_reranker():
+    nodes = SearchConfig.nodes_only(reranker=NodeReranker.node_distance)
+    facts = SearchConfig.facts_only(reranker=EdgeReranker.node_distance)
+
+    assert nodes.node_config is not None
+    assert nodes.node_config.reranker == NodeReranker.node_distance
+    assert facts.edge_config is not None
+    assert facts.edge_config.reranker == EdgeReranker.node_distance
+
+
+def test_presets_are_independent_copies():
+    config = SearchConfig.facts_only()
+    config.limit = 1
+
+    assert SearchConfig.facts_only().limit != 1
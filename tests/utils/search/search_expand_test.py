@@ -0,0 +1,84 @@
+from unittest.mock import AsyncMock, MagicMock, patch
+
+import pytest
+
+from graphiti_core.cross_encoder.client import CrossEncoderClient
+from graphiti_core.driver.driver import GraphDriver
+from graphiti_core.embedder.client import EmbedderClient
+from graphiti_core.errors import SearchDepthExceededError
+from graphiti_core.graphiti_types import GraphitiClients
+from graphiti_core.llm_client import LLMClient
+from graphiti_core.nodes import EntityNode
+from graphiti_core.search.search import search_and_expand
+from graphiti_core.search.search_config import SearchConfig, SearchResults
+from graphiti_core.search.search_filters import SearchFilters
+from graphiti_core.search.search_utils import MAX_SEARCH_DEPTH, node_bfs_search
+
+
+def _clients(**settings) -> GraphitiClients:
+    return GraphitiClients(
+        driver=MagicMock(spec=GraphDriver, execute_query=AsyncMock(return_value=([], None, None))),
+        llm_client=MagicMock(spec=LLMClient),
+        embedder=MagicMock(spec=EmbedderClient),
+        cross_encoder=MagicMock(spec=CrossEncoderClient),
+        **settings,
+    )
+
+
+def _nodes(*names: str) -> list[EntityNode]:
+    return [EntityNode(uuid=name, name=name, labels=['Entity'], group_id='group') for name in names]
+
+
+@pytest.mark.asyncio
+async def test_expansion_past_the_maximum_depth_is_rejected():
+    clients = _clients()
+    seed_search = AsyncMock()
+
+    with patch('graphiti_core.search.search.search', seed_search):
+        with pytest.raises(SearchDepthExceededError):
+            await search_and_expand(
+                clients,
+                'Alice',
+                ['group'],
+                SearchConfig.nodes_only(),
+                SearchFilters(),
+                expand_depth=MAX_SEARCH_DEPTH + 1,
+            )
+
+    # Nothing is searched or traversed
+    seed_search.assert_not_called()
+    clients.driver.execute_query.assert_not_called()
+
+
+@pytest.mark.asyncio
+async def test_bfs_past_the_maximum_depth_is_rejected():
+    driver = MagicMock(spec=GraphDriver, execute_query=AsyncMock(return_value=([], None, None)))
+
+    with pytest.raises(SearchDepthExceededError):
+        await node_bfs_search(driver, ['alice'], SearchFilters(), MAX_SEARCH_DEPTH + 1, 10)
+
+    driver.execute_query.assert_not_called()
+
+
+@pytest.mark.asyncio
+async def test_expansion_is_truncated_at_the_node_budget():
+    seeds = SearchResults(edges=[], nodes=_nodes('alice'), episodes=[], communities=[])
+    bfs_search = AsyncMock(return_value=_nodes('bob', 'carol', 'dave'))
+
+    with (
+        patch('graphiti_core.search.search.search', AsyncMock(return_value=seeds)),
+        patch('graphiti_core.search.search.node_bfs_search', bfs_search),
+        patch('graphiti_core.search.search.edge_bfs_search', AsyncMock(return_value=[])),
+    ):
+        results = await search_and_expand(
+            _clients(max_traversal_nodes=3),
+            'Alice',
+            ['group'],
+            SearchConfig.nodes_only(limit=10),
+            SearchFilters(),
+        )
+
+    assert [node.name for node in results.nodes] == ['alice', 'bob', 'carol']
+    assert results.truncated
+    # The traversal itself stops one node past the budget
+    assert bfs_search.call_args.args[4] == 4
@@ -0,0 +1,80 @@
+from datetime import datetime, timezone
+from unittest.mock import AsyncMock, MagicMock, patch
+
+import pytest
+
+from graphiti_core.cross_encoder.client import CrossEncoderClient
+from graphiti_core.driver.driver import GraphDriver
+from graphiti_core.edges import EntityEdge
+from graphiti_core.embedder.client import EmbedderClient
+from graphiti_core.graphiti_types import GraphitiClients
+from graphiti_core.llm_client import LLMClient
+from graphiti_core.search.search import search
+from graphiti_core.search.search_config import SearchConfig, SearchResults
+from graphiti_core.search.search_filters import SearchFilters
+
+NOW = datetime(2024, 6, 1, tzinfo=timezone.utc)
+
+
+def _edge(uuid: str) -> EntityEdge:
+    return EntityEdge(
+        uuid=uuid,
+        source_node_uuid='source',
+        target_node_uuid='target',
+        name='RELATES_TO',
+        fact=uuid,
+        group_id='group_1',
+        created_at=NOW,
+    )
+
+
+async def _search(config: SearchConfig) -> SearchResults:
+    clients = GraphitiClients(
+        driver=MagicMock(spec=GraphDriver),
+        llm_client=MagicMock(spec=LLMClient),
+        embedder=MagicMock(spec=EmbedderClient),
+        cross_encoder=MagicMock(spec=CrossEncoderClient),
+    )
+    keyword_match, semantic_match = _edge('keyword'), _edge('semantic')
+
+    async def fulltext_search(*args, scores: dict[str, float], **kwargs):
+        scores.update({'keyword': 7.5, 'semantic': 2.25})
+        return [keyword_match, semantic_match]
+
+    async def similarity_search(*args, scores: dict[str, float], **kwargs):
+        scores.update({'semantic': 0.875})
+        return [semantic_match]
+
+    with (
+        patch('graphiti_core.search.search.edge_fulltext_search', side_effect=fulltext_search),
+        patch('graphiti_core.search.search.edge_similarity_search', side_effect=similarity_search),
+        patch('graphiti_core.search.search.edge_bfs_search', AsyncMock(return_value=[])),
+        patch('graphiti_core.search.search.utc_now', return_value=NOW),
+    ):
+        return await search(clients, 'query', None, config, SearchFilters(), query_vector=[])
+
+
+@pytest.mark.asyncio
+async def test_explain_breaks_down_scores_by_method():
+    config = SearchConfig.facts_only().model_copy(update={'explain': True, 'recency_weight': 0.5})
+
+    results = await _search(config)
+
+    assert results.score_breakdowns is not None
+    # Search methods are recorded with the raw database scores
+    assert results.score_breakdowns['keyword'] == {'bm25': 7.5, 'rerank': 0.5, 'recency': 1.0}
+    assert results.score_breakdowns['semantic'] == {
+        'bm25': 2.25,
+        'cosine_similarity': 0.875,
+        'rerank': 1.0,
+        'recency': 1.0,
+    }
+
+
+@pytest.mark.asyncio
+async def test_breakdown_is_omitted_without_explain():
+    results = await _search(SearchConfig.facts_only())
+
+    assert results.score_breakdowns is None
+    assert 'score_breakdowns' not in results.model_dump()
+    assert 'score_breakdowns' not in results.model_dump_json()
@@ -0,0 +1,65 @@
+import pytest
+
+from graphiti_core.search.search_filters import (
+    AttributeFilter,
+    ComparisonOperator,
+    SearchFilters,
+    edge_search_filter_query_constructor,
+    node_search_filter_query_constructor,
+)
+
+
+def test_min_confidence_excludes_low_confidence_edges():
+    filter_query, filter_params = edge_search_filter_query_constructor(
+        SearchFilters(min_confidence=0.7)
+    )
+
+    assert 'r.confidence >= $min_confidence' in filter_query
+    assert filter_params['min_confidence'] == 0.7
+
+
+def test_no_confidence_filter_by_default():
+    filter_query, filter_params = edge_search_filter_query_constructor(SearchFilters())
+
+    assert 'confidence' not in filter_query
+    assert 'min_confidence' not in filter_params
+
+
+def test_invalidated_edges_are_excluded_by_default():
+    filter_query, filter_params = edge_search_filter_query_constructor(SearchFilters())
+
+    assert 'r.invalid_at IS NULL OR r.invalid_at > $now' in filter_query
+    assert 'r.expired_at IS NULL OR r.expired_at > $now' in filter_query
+    assert 'now' in filter_params
+
+
+def test_include_invalidated_drops_the_validity_filter():
+    filter_query, filter_params = edge_search_filter_query_constructor(
+        SearchFilters(include_invalidated=True)
+    )
+
+    assert filter_query == ''
+    assert filter_params == {}
+
+
+def test_node_attribute_filters_compare_attributes():
+    filter_query, filter_params = node_search_filter_query_constructor(
+        SearchFilters(
+            node_attributes=[
+                AttributeFilter(
+                    name='age', value=30, comparison_operator=ComparisonOperator.greater_than
+                ),
+                AttributeFilter(name='city', value='Paris'),
+            ]
+        )
+    )
+
+    assert filter_query == ' AND n.age > $node_attribute_0 AND n.city = $node_attribute_1'
+    assert filter_params == {'node_attribute_0': 30, 'node_attribute_1': 'Paris'}
+
+
+def test_node_attribute_filter_rejects_invalid_names():
+    filters = SearchFilters(node_attributes=[AttributeFilter(name='age) OR (true', value=1)])
+
+    with pytest.raises(ValueError):
+        node_search_filter_query_constructor(filters)
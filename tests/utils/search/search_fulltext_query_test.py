@@ -0,0 +1,107 @@
+from unittest.mock import AsyncMock, MagicMock, patch
+
+import pytest
+
+from graphiti_core.cross_encoder.client import CrossEncoderClient
+from graphiti_core.driver.driver import GraphDriver
+from graphiti_core.embedder.client import EmbedderClient
+from graphiti_core.graphiti_types import GraphitiClients
+from graphiti_core.llm_client import LLMClient
+from graphiti_core.search.search import search
+from graphiti_core.search.search_config import (
+    FulltextMatchMode,
+    NodeSearchConfig,
+    NodeSearchMethod,
+    SearchConfig,
+)
+from graphiti_core.search.search_filters import SearchFilters
+from graphiti_core.search.search_utils import fulltext_query
+
+
+def test_any_terms_is_the_default():
+    assert fulltext_query('machine learning') == '(machine learning)'
+    assert fulltext_query('machine learning', ['group_1']) == (
+        'group_id:"group_1" AND (machine learning)'
+    )
+
+
+def test_all_terms_joins_terms_with_and():
+    query = fulltext_query('machine  learning', match_mode=FulltextMatchMode.all_terms)
+
+    assert query == '(machine AND learning)'
+
+
+def test_phrase_quotes_the_terms():
+    query = fulltext_query('machine learning', ['group_1'], FulltextMatchMode.phrase)
+
+    assert query == 'group_id:"group_1" AND ("machine learning")'
+
+
+def test_quotes_in_a_phrase_stay_escaped():
+    query = fulltext_query('say "hi"', match_mode=FulltextMatchMode.phrase)
+
+    assert query == '("say \\"hi\\"")'
+
+
+def test_field_boosts_weight_each_field():
+    query = fulltext_query('acme', ['group_1'], field_boosts={'name': 3.0, 'summary': 1.0})
+
+    assert query == 'group_id:"group_1" AND ((name:acme^3 OR summary:acme^1))'
+
+
+def test_all_terms_with_field_boosts_can_match_across_fields():
+    query = fulltext_query(
+        'alice acme',
+        match_mode=FulltextMatchMode.all_terms,
+        field_boosts={'name': 3.0, 'summary': 1.0},
+    )
+
+    # alice may match the name while acme matches the summary
+    assert query == '((name:alice^3 OR summary:alice^1) AND (name:acme^3 OR summary:acme^1))'
+
+
+def test_phrase_with_field_boosts_is_boosted_whole():
+    query = fulltext_query(
+        'machine learning', match_mode=FulltextMatchMode.phrase, field_boosts={'name': 2.0}
+    )
+
+    assert query == '((name:"machine learning"^2))'
+
+
+@pytest.mark.asyncio
+async def test_match_mode_is_passed_from_the_search_config():
+    clients = GraphitiClients(
+        driver=MagicMock(spec=GraphDriver),
+        llm_client=MagicMock(spec=LLMClient),
+        embedder=MagicMock(spec=EmbedderClient),
+        cross_encoder=MagicMock(spec=CrossEncoderClient),
+    )
+    config = SearchConfig.episodes_only()
+    config.fulltext_match_mode = FulltextMatchMode.phrase
+    fulltext_search = AsyncMock(return_value=[])
+
+    with patch('graphiti_core.search.search.episode_fulltext_search', fulltext_search):
+        await search(clients, 'query', None, config, SearchFilters(), query_vector=[])
+
+    assert fulltext_search.call_args.args[-1] == FulltextMatchMode.phrase
+
+
+@pytest.mark.asyncio
+async def test_node_field_boosts_are_passed_from_the_search_config():
+    driver = MagicMock(spec=GraphDriver, execute_query=AsyncMock(return_value=([], None, None)))
+    driver.provider = 'neo4j'
+    clients = GraphitiClients(
+        driver=driver,
+        llm_client=MagicMock(spec=LLMClient),
+        embedder=MagicMock(spec=EmbedderClient),
+        cross_encoder=MagicMock(spec=CrossEncoderClient),
+    )
+    config = SearchConfig(
+        node_config=NodeSearchConfig(
+            search_methods=[NodeSearchMethod.bm25], name_boost=5, summary_boost=0.5
+        )
+    )
+
+    await search(clients, 'acme', None, config, SearchFilters(), query_vector=[])
+
+    assert driver.execute_query.call_args.kwargs['query'] == '((name:acme^5 OR summary:acme^0.5))'
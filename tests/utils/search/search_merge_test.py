@@ -0,0 +1,68 @@
+from datetime import datetime, timezone
+from unittest.mock import patch
+
+import pytest
+
+from graphiti_core.edges import EntityEdge
+from graphiti_core.nodes import EntityNode
+from graphiti_core.search.search_config import SearchResults
+from graphiti_core.search.search_config_recipes import EDGE_HYBRID_SEARCH_RRF
+
+
+def _edge(uuid: str, group_id: str = 'group_1') -> EntityEdge:
+    return EntityEdge(
+        uuid=uuid,
+        source_node_uuid='source',
+        target_node_uuid='target',
+        name='RELATES_TO',
+        fact=f'fact {uuid}',
+        group_id=group_id,
+        created_at=datetime.now(timezone.utc),
+    )
+
+
+def _results(*edges: EntityEdge, nodes: list[EntityNode] | None = None) -> SearchResults:
+    return SearchResults(edges=list(edges), nodes=nodes or [], episodes=[], communities=[])
+
+
+def test_merge_dedupes_overlapping_uuids():
+    shared = EntityNode(uuid='shared', name='Shared', group_id='group_1', labels=['Entity'])
+    first = _results(_edge('a'), _edge('shared'), nodes=[shared])
+    second = _results(_edge('shared'), _edge('b'), nodes=[shared.model_copy()])
+
+    merged = SearchResults.merge([first, second])
+
+    assert [edge.uuid for edge in merged.edges].count('shared') == 1
+    assert {edge.uuid for edge in merged.edges} == {'a', 'shared', 'b'}
+    # Found by both searches, so it ranks first
+    assert merged.edges[0].uuid == 'shared'
+    assert [node.uuid for node in merged.nodes] == ['shared']
+
+
+def test_merge_truncates_to_limit():
+    first = _results(_edge('a'), _edge('b'))
+    second = _results(_edge('c'), _edge('d'))
+
+    merged = SearchResults.merge([first, second], limit=2)
+
+    assert [edge.uuid for edge in merged.edges] == ['a', 'c']
+
+
+@pytest.mark.asyncio
+async def test_search_multi_group_searches_each_group(make_graphiti):
+    graphiti = make_graphiti()
+    results_by_group = {
+        'notes': _results(_edge('note', 'notes')),
+        'shared': _results(_edge('shared', 'shared')),
+    }
+
+    async def fake_search(clients, query, group_ids, *args, **kwargs):
+        return results_by_group[group_ids[0]]
+
+    with patch('graphiti_core.graphiti.search', side_effect=fake_search) as mock_search:
+        merged = await graphiti.search_multi_group(
+            'query', ['notes', 'shared'], EDGE_HYBRID_SEARCH_RRF
+        )
+
+    assert mock_search.call_count == 2
+    assert {edge.uuid for edge in merged.edges} == {'note', 'shared'}
@@ -0,0 +1,67 @@
+from unittest.mock import AsyncMock, MagicMock, patch
+
+import pytest
+
+from graphiti_core.cross_encoder.client import CrossEncoderClient
+from graphiti_core.driver.driver import GraphDriver
+from graphiti_core.embedder.client import EmbedderClient
+from graphiti_core.graphiti_types import GraphitiClients
+from graphiti_core.llm_client import LLMClient
+from graphiti_core.search.search import search
+from graphiti_core.search.search_config import (
+    DEFAULT_OVERFETCH_FACTOR,
+    EdgeReranker,
+    EdgeSearchConfig,
+    EdgeSearchMethod,
+    SearchConfig,
+)
+from graphiti_core.search.search_filters import SearchFilters
+
+
+async def _fetch_limit(config: SearchConfig) -> int:
+    """Runs a fact search and returns the limit passed to the BM25 search."""
+    clients = GraphitiClients(
+        driver=MagicMock(spec=GraphDriver),
+        llm_client=MagicMock(spec=LLMClient),
+        embedder=MagicMock(spec=EmbedderClient),
+        cross_encoder=MagicMock(spec=CrossEncoderClient),
+    )
+    fulltext_search = AsyncMock(return_value=[])
+
+    with (
+        patch('graphiti_core.search.search.edge_fulltext_search', fulltext_search),
+        patch('graphiti_core.search.search.edge_similarity_search', AsyncMock(return_value=[])),
+        patch('graphiti_core.search.search.edge_bfs_search', AsyncMock(return_value=[])),
+    ):
+        await search(clients, 'query', None, config, SearchFilters(), query_vector=[])
+
+    return fulltext_search.call_args.args[4]
+
+
+def _bm25_config(reranker: EdgeReranker = EdgeReranker.rrf, **kwargs) -> SearchConfig:
+    return SearchConfig(
+        edge_config=EdgeSearchConfig(search_methods=[EdgeSearchMethod.bm25], reranker=reranker),
+        limit=10,
+        **kwargs,
+    )
+
+
+@pytest.mark.asyncio
+async def test_single_method_without_reranking_fetches_the_limit():
+    assert await _fetch_limit(_bm25_config()) == 10
+
+
+@pytest.mark.asyncio
+async def test_reranked_results_are_overfetched():
+    expected = 10 * DEFAULT_OVERFETCH_FACTOR
+
+    assert await _fetch_limit(_bm25_config(EdgeReranker.episode_mentions)) == expected
+    assert await _fetch_limit(SearchConfig.facts_only(limit=10)) == expected
+
+
+@pytest.mark.asyncio
+async def test_overfetch_factor_overrides_the_default():
+    hybrid_config = SearchConfig.facts_only(limit=10).model_copy(update={'overfetch_factor': 1})
+
+    assert await _fetch_limit(_bm25_config(overfetch_factor=3)) == 30
+    assert await _fetch_limit(hybrid_config) == 10
@@ -0,0 +1,160 @@
+from datetime import datetime, timezone
+from unittest.mock import AsyncMock, MagicMock, patch
+
+import pytest
+
+from graphiti_core.cross_encoder.client import CrossEncoderClient
+from graphiti_core.driver.driver import GraphDriver
+from graphiti_core.edges import EntityEdge
+from graphiti_core.graphiti_types import GraphitiClients
+from graphiti_core.llm_client import LLMClient
+from graphiti_core.prompts.expand_query import ExpandedQuery
+from graphiti_core.search.search import search
+from graphiti_core.search.search_config import (
+    EdgeReranker,
+    EdgeSearchConfig,
+    EdgeSearchMethod,
+    SearchConfig,
+    SortBy,
+)
+from graphiti_core.search.search_filters import SearchFilters
+from graphiti_core.testing import MockEmbedderClient, MockLLMClient
+
+
+def _clients(llm_client: LLMClient) -> GraphitiClients:
+    return GraphitiClients(
+        driver=MagicMock(spec=GraphDriver, execute_query=AsyncMock(return_value=([], None, None))),
+        llm_client=llm_client,
+        embedder=MockEmbedderClient(),
+        cross_encoder=MagicMock(spec=CrossEncoderClient),
+    )
+
+
+def _edge(uuid: str = 'works_at', fact: str = 'Alice is an engineer at Acme') -> EntityEdge:
+    return EntityEdge(
+        uuid=uuid,
+        source_node_uuid='alice',
+        target_node_uuid='acme',
+        name='WORKS_AT',
+        fact=fact,
+        group_id='group',
+        created_at=datetime.now(timezone.utc),
+    )
+
+
+async def _fulltext_search(driver, query, *args, **kwargs):
+    # Only the expanded query shares a term with the fact
+    return [_edge()] if 'engineer' in query else []
+
+
+def _config(query_expansion: bool) -> SearchConfig:
+    return SearchConfig(
+        edge_config=EdgeSearchConfig(search_methods=[EdgeSearchMethod.bm25]),
+        query_expansion=query_expansion,
+    )
+
+
+@pytest.mark.asyncio
+@pytest.mark.parametrize('query_expansion', [True, False])
+async def test_query_expansion_recalls_facts_missed_by_the_original_query(query_expansion):
+    llm_client = MockLLMClient().add_response(
+        ExpandedQuery,
+        {'expanded_query': "Alice's job occupation engineer employer", 'sub_queries': []},
+    )
+
+    with patch(
+        'graphiti_core.search.search.edge_fulltext_search',
+        AsyncMock(side_effect=_fulltext_search),
+    ):
+        results = await search(
+            _clients(llm_client),
+            "Alice's job",
+            ['group'],
+            _config(query_expansion),
+            SearchFilters(),
+            query_vector=[0.0],
+        )
+
+    if query_expansion:
+        assert [edge.uuid for edge in results.edges] == ['works_at']
+        assert len(llm_client.calls) == 1
+    else:
+        assert results.edges == []
+        assert llm_client.calls == []
+
+
+@pytest.mark.asyncio
+async def test_failed_expansion_searches_the_original_query():
+    llm_client = MockLLMClient().add_response(ExpandedQuery, ValueError('bad response'))
+    fulltext_search = AsyncMock(return_value=[_edge()])
+
+    with patch('graphiti_core.search.search.edge_fulltext_search', fulltext_search):
+        results = await search(
+            _clients(llm_client),
+            'Alice',
+            ['group'],
+            _config(True),
+            SearchFilters(),
+            query_vector=[0.0],
+        )
+
+    assert [edge.uuid for edge in results.edges] == ['works_at']
+    assert [call.args[1] for call in fulltext_search.await_args_list] == ['Alice']
+
+
+@pytest.mark.asyncio
+async def test_expanded_candidates_are_reranked_once_against_the_original_query():
+    llm_client = MockLLMClient().add_response(
+        ExpandedQuery, {'expanded_query': 'alice employer', 'sub_queries': []}
+    )
+    edges = {
+        'alice': [_edge('a', 'Alice lives in Paris')],
+        'alice employer': [_edge('b', 'Alice left Initech'), _edge('c', 'Alice works at Acme')],
+    }
+    # The least relevant of the returned facts is the most recent
+    edges['alice'][0].created_at = datetime(2030, 1, 1, tzinfo=timezone.utc)
+    relevance = {'Alice works at Acme': 0.9, 'Alice lives in Paris': 0.5, 'Alice left Initech': 0.1}
+
+    async def fulltext_search(driver, query, *args, **kwargs):
+        return edges[query]
+
+    async def rank_batch(query, passages):
+        return sorted(
+            ((i, relevance[passage]) for i, passage in enumerate(passages)),
+            key=lambda ranked: ranked[1],
+            reverse=True,
+        )
+
+    clients = _clients(llm_client)
+    clients.cross_encoder.rank_batch = AsyncMock(side_effect=rank_batch)
+    config = SearchConfig(
+        edge_config=EdgeSearchConfig(
+            search_methods=[EdgeSearchMethod.bm25], reranker=EdgeReranker.cross_encoder
+        ),
+        query_expansion=True,
+        explain=True,
+        limit=2,
+    )
+
+    with patch('graphiti_core.search.search.edge_fulltext_search', side_effect=fulltext_search):
+        results = await search(
+            clients, 'alice', ['group'], config, SearchFilters(), query_vector=[0.0]
+        )
+        date_sorted = await search(
+            clients,
+            'alice',
+            ['group'],
+            config.model_copy(update={'sort_by': SortBy.created_at_desc}),
+            SearchFilters(),
+            query_vector=[0.0],
+        )
+
+    assert [edge.uuid for edge in results.edges] == ['c', 'a']
+    rank_batch_calls = clients.cross_encoder.rank_batch.await_args_list
+    assert len(rank_batch_calls) == 2
+    assert rank_batch_calls[0].args[0] == 'alice'
+    assert sorted(rank_batch_calls[0].args[1]) == sorted(relevance)
+    assert results.score_breakdowns is not None
+    assert results.score_breakdowns['c']['rerank'] == 1.0
+    # The date sort reorders the reranked page
+    assert [edge.uuid for edge in date_sorted.edges] == ['a', 'c']
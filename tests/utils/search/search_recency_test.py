@@ -0,0 +1,73 @@
+from datetime import datetime, timedelta, timezone
+from unittest.mock import AsyncMock, MagicMock, patch
+
+import pytest
+
+from graphiti_core.cross_encoder.client import CrossEncoderClient
+from graphiti_core.driver.driver import GraphDriver
+from graphiti_core.edges import EntityEdge
+from graphiti_core.embedder.client import EmbedderClient
+from graphiti_core.graphiti_types import GraphitiClients
+from graphiti_core.llm_client import LLMClient
+from graphiti_core.nodes import EntityNode
+from graphiti_core.search.search import rerank_by_recency, search
+from graphiti_core.search.search_config_recipes import EDGE_HYBRID_SEARCH_RRF
+from graphiti_core.search.search_filters import SearchFilters
+
+NOW = datetime(2024, 6, 1, tzinfo=timezone.utc)
+
+
+def _edge(uuid: str, valid_days_ago: int) -> EntityEdge:
+    return EntityEdge(
+        uuid=uuid,
+        source_node_uuid='source',
+        target_node_uuid='target',
+        name='RELATES_TO',
+        fact=uuid,
+        group_id='group_1',
+        valid_at=NOW - timedelta(days=valid_days_ago),
+        created_at=NOW,
+    )
+
+
+# Ordered by relevance; both facts match the query equally well
+EDGES = [_edge('old', 60), _edge('new', 0)]
+
+
+def test_rerank_by_recency():
+    assert [e.uuid for e in rerank_by_recency(EDGES, 0, 30, NOW)] == ['old', 'new']
+    assert [e.uuid for e in rerank_by_recency(EDGES, 0.5, 30, NOW)] == ['new', 'old']
+    # A long half-life makes the age difference negligible
+    assert [e.uuid for e in rerank_by_recency(EDGES, 0.5, 36500, NOW)] == ['old', 'new']
+
+
+def test_rerank_by_recency_uses_created_at_for_nodes():
+    nodes = [
+        EntityNode(uuid='old', name='Old', group_id='group_1', created_at=NOW - timedelta(days=90)),
+        EntityNode(uuid='new', name='New', group_id='group_1', created_at=NOW),
+    ]
+
+    assert [n.uuid for n in rerank_by_recency(nodes, 0.5, 30, NOW)] == ['new', 'old']
+
+
+@pytest.mark.asyncio
+async def test_search_applies_recency_weight():
+    clients = GraphitiClients(
+        driver=MagicMock(spec=GraphDriver),
+        llm_client=MagicMock(spec=LLMClient),
+        embedder=MagicMock(spec=EmbedderClient),
+        cross_encoder=MagicMock(spec=CrossEncoderClient),
+    )
+    config = EDGE_HYBRID_SEARCH_RRF.model_copy(update={'recency_weight': 0.5})
+
+    with (
+        patch('graphiti_core.search.search.edge_search', AsyncMock(return_value=EDGES)),
+        patch('graphiti_core.search.search.utc_now', return_value=NOW),
+    ):
+        weighted = await search(clients, 'query', None, config, SearchFilters(), query_vector=[])
+        unweighted = await search(
+            clients, 'query', None, EDGE_HYBRID_SEARCH_RRF, SearchFilters(), query_vector=[]
+        )
+
+    assert [edge.uuid for edge in weighted.edges] == ['new', 'old']
+    assert [edge.uuid for edge in unweighted.edges] == ['old', 'new']
@@ -0,0 +1,111 @@
+from datetime import datetime, timezone
+from unittest.mock import AsyncMock, MagicMock, patch
+
+import pytest
+
+from graphiti_core.cross_encoder.client import CrossEncoderClient
+from graphiti_core.driver.driver import GraphDriver
+from graphiti_core.edges import EntityEdge
+from graphiti_core.search.search import edge_search
+from graphiti_core.search.search_config import EdgeReranker, EdgeSearchConfig, EdgeSearchMethod
+from graphiti_core.search.search_filters import SearchFilters
+from graphiti_core.search.search_utils import rrf
+
+
+class CountingCrossEncoder(CrossEncoderClient):
+    def __init__(self):
+        self.passages_ranked = 0
+
+    async def rank(self, query: str, passages: list[str]) -> list[tuple[str, float]]:
+        self.passages_ranked += len(passages)
+        # Reverse the preliminary order so reranking is observable
+        return [(passage, 1 - i / len(passages)) for i, passage in enumerate(reversed(passages))]
+
+
+def _edges(count: int) -> list[EntityEdge]:
+    return [
+        EntityEdge(
+            uuid=f'edge_{i}',
+            source_node_uuid='source',
+            target_node_uuid='target',
+            name='RELATES_TO',
+            fact=f'fact {i}',
+            group_id='group_1',
+            created_at=datetime.now(timezone.utc),
+        )
+        for i in range(count)
+    ]
+
+
+@pytest.mark.asyncio
+async def test_cross_encoder_only_reranks_top_k():
+    cross_encoder = CountingCrossEncoder()
+    config = EdgeSearchConfig(
+        search_methods=[EdgeSearchMethod.bm25], reranker=EdgeReranker.cross_encoder
+    )
+
+    with (
+        patch(
+            'graphiti_core.search.search.edge_fulltext_search',
+            AsyncMock(return_value=_edges(30)),
+        ),
+        patch('graphiti_core.search.search.edge_similarity_search', AsyncMock(return_value=[])),
+        patch('graphiti_core.search.search.edge_bfs_search', AsyncMock(return_value=[])),
+    ):
+        results = await edge_search(
+            MagicMock(spec=GraphDriver),
+            cross_encoder,
+            'query',
+            [0.0],
+            None,
+            config,
+            SearchFilters(),
+            limit=30,
+            rerank_top_k=5,
+        )
+
+    assert cross_encoder.passages_ranked <= 5
+    # The reranked block comes first, the rest keep their preliminary order below it
+    assert [edge.uuid for edge in results[:5]] == [f'edge_{i}' for i in reversed(range(5))]
+    assert [edge.uuid for edge in results[5:]] == [f'edge_{i}' for i in range(5, 30)]
+
+
+def test_rrf_breaks_ties_by_uuid():
+    # b and a each come first in one ranking, as do d and c second, so their scores are equal
+    assert rrf([['b', 'd'], ['a', 'c']]) == ['a', 'b', 'c', 'd']
+    assert rrf([['a', 'c'], ['b', 'd']]) == ['a', 'b', 'c', 'd']
+
+
+@pytest.mark.asyncio
+async def test_equal_scores_are_ordered_the_same_across_runs():
+    edge_a, edge_b = _edges(2)
+    config = EdgeSearchConfig(
+        search_methods=[EdgeSearchMethod.bm25, EdgeSearchMethod.cosine_similarity],
+        reranker=EdgeReranker.rrf,
+    )
+
+    async def run(fulltext: list[EntityEdge], similarity: list[EntityEdge]) -> list[str]:
+        with (
+            patch(
+                'graphiti_core.search.search.edge_fulltext_search',
+                AsyncMock(return_value=fulltext),
+            ),
+            patch(
+                'graphiti_core.search.search.edge_similarity_search',
+                AsyncMock(return_value=similarity),
+            ),
+            patch('graphiti_core.search.search.edge_bfs_search', AsyncMock(return_value=[])),
+        ):
+            results = await edge_search(
+                MagicMock(spec=GraphDriver),
+                CountingCrossEncoder(),
+                'query',
+                [0.0],
+                None,
+                config,
+                SearchFilters(),
+            )
+        return [edge.uuid for edge in results]
+
+    assert await run([edge_a], [edge_b]) == ['edge_0', 'edge_1']
+    assert await run([edge_b], [edge_a]) == ['edge_0', 'edge_1']
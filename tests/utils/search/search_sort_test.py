@@ -0,0 +1,71 @@
+from datetime import datetime, timedelta, timezone
+from unittest.mock import AsyncMock, MagicMock, patch
+
+import pytest
+
+from graphiti_core.cross_encoder.client import CrossEncoderClient
+from graphiti_core.driver.driver import GraphDriver
+from graphiti_core.edges import EntityEdge
+from graphiti_core.embedder.client import EmbedderClient
+from graphiti_core.graphiti_types import GraphitiClients
+from graphiti_core.llm_client import LLMClient
+from graphiti_core.search.search import search, sort_results
+from graphiti_core.search.search_config import SortBy
+from graphiti_core.search.search_config_recipes import EDGE_HYBRID_SEARCH_RRF
+from graphiti_core.search.search_filters import SearchFilters
+
+NOW = datetime(2024, 6, 1, tzinfo=timezone.utc)
+
+
+def _edge(uuid: str, valid_days_ago: int | None, created_days_ago: int) -> EntityEdge:
+    return EntityEdge(
+        uuid=uuid,
+        source_node_uuid='source',
+        target_node_uuid='target',
+        name='RELATES_TO',
+        fact=uuid,
+        group_id='group_1',
+        valid_at=NOW - timedelta(days=valid_days_ago) if valid_days_ago is not None else None,
+        created_at=NOW - timedelta(days=created_days_ago),
+    )
+
+
+# Ordered by relevance
+EDGES = [_edge('old', 30, 1), _edge('unknown', None, 0), _edge('new', 1, 2), _edge('mid', 10, 3)]
+
+
+def test_sort_results():
+    assert [e.uuid for e in sort_results(EDGES, SortBy.relevance)] == [
+        'old',
+        'unknown',
+        'new',
+        'mid',
+    ]
+    assert [e.uuid for e in sort_results(EDGES, SortBy.valid_at_desc)] == [
+        'new',
+        'mid',
+        'old',
+        'unknown',
+    ]
+    assert [e.uuid for e in sort_results(EDGES, SortBy.created_at_desc)] == [
+        'unknown',
+        'old',
+        'new',
+        'mid',
+    ]
+
+
+@pytest.mark.asyncio
+async def test_search_applies_sort_by():
+    clients = GraphitiClients(
+        driver=MagicMock(spec=GraphDriver),
+        llm_client=MagicMock(spec=LLMClient),
+        embedder=MagicMock(spec=EmbedderClient),
+        cross_encoder=MagicMock(spec=CrossEncoderClient),
+    )
+    config = EDGE_HYBRID_SEARCH_RRF.model_copy(update={'sort_by': SortBy.valid_at_desc})
+
+    with patch('graphiti_core.search.search.edge_search', AsyncMock(return_value=EDGES)):
+        results = await search(clients, 'query', None, config, SearchFilters(), query_vector=[])
+
+    assert [edge.uuid for edge in results.edges] == ['new', 'mid', 'old', 'unknown']
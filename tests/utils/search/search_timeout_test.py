@@ -0,0 +1,114 @@
+import asyncio
+from datetime import datetime, timezone
+from unittest.mock import AsyncMock, MagicMock, patch
+
+import pytest
+
+from graphiti_core.cross_encoder.client import CrossEncoderClient
+from graphiti_core.driver.driver import GraphDriver
+from graphiti_core.edges import EntityEdge
+from graphiti_core.embedder.client import EmbedderClient
+from graphiti_core.graphiti_types import GraphitiClients
+from graphiti_core.llm_client import LLMClient
+from graphiti_core.nodes import EntityNode
+from graphiti_core.search.search import search
+from graphiti_core.search.search_config import (
+    EdgeSearchConfig,
+    EdgeSearchMethod,
+    NodeReranker,
+    NodeSearchConfig,
+    NodeSearchMethod,
+    SearchConfig,
+)
+from graphiti_core.search.search_filters import SearchFilters
+
+
+class SlowCrossEncoder(CrossEncoderClient):
+    def __init__(self):
+        self.cancelled = False
+
+    async def rank(self, query: str, passages: list[str]) -> list[tuple[str, float]]:
+        try:
+            await asyncio.sleep(10)
+        except asyncio.CancelledError:
+            self.cancelled = True
+            raise
+        return [(passage, 1.0) for passage in passages]
+
+
+def _clients(cross_encoder: CrossEncoderClient) -> GraphitiClients:
+    return GraphitiClients(
+        driver=MagicMock(spec=GraphDriver, execute_query=AsyncMock(return_value=([], None, None))),
+        llm_client=MagicMock(spec=LLMClient),
+        embedder=MagicMock(spec=EmbedderClient),
+        cross_encoder=cross_encoder,
+    )
+
+
+def _edge() -> EntityEdge:
+    return EntityEdge(
+        uuid='edge',
+        source_node_uuid='alice',
+        target_node_uuid='bob',
+        name='KNOWS',
+        fact='Alice knows Bob',
+        group_id='group',
+        created_at=datetime.now(timezone.utc),
+    )
+
+
+@pytest.mark.asyncio
+async def test_slow_reranker_times_out_with_partial_results():
+    cross_encoder = SlowCrossEncoder()
+    # Facts are ranked without the cross-encoder, so only the node search is slow
+    config = SearchConfig(
+        edge_config=EdgeSearchConfig(search_methods=[EdgeSearchMethod.bm25]),
+        node_config=NodeSearchConfig(
+            search_methods=[NodeSearchMethod.bm25], reranker=NodeReranker.cross_encoder
+        ),
+    )
+    node = EntityNode(uuid='alice', name='Alice', labels=['Entity'], group_id='group')
+
+    with (
+        patch(
+            'graphiti_core.search.search.edge_fulltext_search', AsyncMock(return_value=[_edge()])
+        ),
+        patch('graphiti_core.search.search.node_fulltext_search', AsyncMock(return_value=[node])),
+    ):
+        results = await asyncio.wait_for(
+            search(
+                _clients(cross_encoder),
+                'Alice',
+                ['group'],
+                config,
+                SearchFilters(),
+                query_vector=[0.0],
+                timeout=0.05,
+            ),
+            timeout=5,
+        )
+
+    assert results.timed_out
+    assert [edge.uuid for edge in results.edges] == ['edge']
+    assert results.nodes == []
+    assert cross_encoder.cancelled
+
+
+@pytest.mark.asyncio
+async def test_search_within_timeout_is_not_marked_timed_out():
+    with patch(
+        'graphiti_core.search.search.edge_fulltext_search', AsyncMock(return_value=[_edge()])
+    ):
+        results = await search(
+            _clients(SlowCrossEncoder()),
+            'Alice',
+            ['group'],
+            SearchConfig(edge_config=EdgeSearchConfig(search_methods=[EdgeSearchMethod.bm25])),
+            SearchFilters(),
+            query_vector=[0.0],
+            timeout=5,
+        )
+
+    assert not results.timed_out
+    assert 'timed_out' not in results.model_dump()
+    assert [edge.uuid for edge in results.edges] == ['edge']
@@ -0,0 +1,336 @@
+import asyncio
+from datetime import datetime, timezone
+from unittest.mock import AsyncMock, MagicMock, patch
+
+import pytest
+
+from graphiti_core.driver.driver import GraphDriver
+from graphiti_core.edges import EntityEdge
+from graphiti_core.embedder.client import EmbedderClient
+from graphiti_core.graphiti_types import GraphitiClients
+from graphiti_core.helpers import NameNormalization, StringSimilarity
+from graphiti_core.nodes import EntityNode, EpisodeType, EpisodicNode
+from graphiti_core.testing import MockCrossEncoderClient, MockEmbedderClient, MockLLMClient
+from graphiti_core.utils.bulk_utils import (
+    BatchWriter,
+    chunk_edges_by_nodes,
+    chunk_nodes_by_similarity,
+    compress_edges,
+    compress_nodes,
+    extract_nodes_and_edges_bulk,
+    group_duplicate_candidates,
+    node_name_match,
+)
+
+
+def _nodes(*names: str) -> list[EntityNode]:
+    return [EntityNode(name=name, group_id='group_1', labels=['Entity']) for name in names]
+
+
+def test_node_name_match_exact_by_default():
+    nodes = _nodes('OpenAI', 'openai', 'Open AI')
+
+    matched, uuid_map = node_name_match(nodes)
+
+    assert len(matched) == 3
+    assert uuid_map == {}
+
+
+def test_node_name_match_basic_normalization():
+    nodes = _nodes('OpenAI', ' openai ', 'Open AI')
+
+    matched, uuid_map = node_name_match(nodes, NameNormalization.basic)
+
+    assert [node.name for node in matched] == ['OpenAI', 'Open AI']
+    assert uuid_map == {nodes[1].uuid: nodes[0].uuid}
+
+
+def test_node_name_match_aggressive_normalization_collapses_variants():
+    nodes = _nodes('OpenAI', 'openai', 'Open AI')
+
+    matched, uuid_map = node_name_match(nodes, NameNormalization.aggressive)
+
+    assert len(matched) == 1
+    # The kept node preserves its original display name
+    assert matched[0].name == 'OpenAI'
+    assert uuid_map == {nodes[1].uuid: nodes[0].uuid, nodes[2].uuid: nodes[0].uuid}
+
+
+def test_node_name_match_merges_typos_above_similarity_threshold():
+    nodes = _nodes('Microsoft', 'Micorsoft', 'Apple')
+
+    matched, uuid_map = node_name_match(
+        nodes, NameNormalization.basic, name_similarity_threshold=0.9
+    )
+
+    assert [node.name for node in matched] == ['Microsoft', 'Apple']
+    assert uuid_map == {nodes[1].uuid: nodes[0].uuid}
+
+
+def test_node_name_match_similarity_metric_is_configurable():
+    nodes = _nodes('Microsoft', 'Micorsoft')
+
+    # Two transposed letters are two edits, so Levenshtein scores the pair lower
+    matched, _ = node_name_match(
+        nodes,
+        NameNormalization.basic,
+        name_similarity_threshold=0.9,
+        name_similarity=StringSimilarity.levenshtein,
+    )
+
+    assert len(matched) == 2
+    # Typos are kept apart by default
+    assert len(node_name_match(nodes, NameNormalization.basic)[0]) == 2
+
+
+def _one_hot(index: int, dim: int = 20) -> list[float]:
+    return [1.0 if i == index else 0.0 for i in range(dim)]
+
+
+def _edge(source: EntityNode, name: str, target: EntityNode) -> EntityEdge:
+    return EntityEdge(
+        source_node_uuid=source.uuid,
+        target_node_uuid=target.uuid,
+        name=name,
+        fact=f'{source.name} {name} {target.name}',
+        group_id='group_1',
+        created_at=datetime.now(timezone.utc),
+    )
+
+
+def test_chunk_edges_by_nodes_keeps_direction_of_asymmetric_relations():
+    alice, bob = _nodes('Alice', 'Bob')
+    manages = _edge(alice, 'MANAGES', bob)
+    managed_by = _edge(bob, 'MANAGES', alice)
+    married = _edge(alice, 'MARRIED_TO', bob)
+    married_reverse = _edge(bob, 'married_to', alice)
+
+    chunks = chunk_edges_by_nodes([manages, managed_by, married, married_reverse])
+
+    assert chunks == [[manages], [managed_by], [married, married_reverse]]
+
+
+@pytest.mark.asyncio
+async def test_opposite_direction_asymmetric_edges_are_not_merged():
+    alice, bob = _nodes('Alice', 'Bob')
+    manages = _edge(alice, 'MANAGES', bob)
+    managed_by = _edge(bob, 'MANAGES', alice)
+    married = _edge(alice, 'MARRIED_TO', bob)
+    married_reverse = _edge(bob, 'MARRIED_TO', alice)
+
+    # The LLM merges every chunk it is given into its first fact
+    async def keep_first(llm_client, edges):
+        return edges[:1]
+
+    with patch('graphiti_core.utils.bulk_utils.dedupe_edge_list', side_effect=keep_first):
+        edges = await compress_edges(
+            MockLLMClient(), [manages, managed_by, married, married_reverse]
+        )
+
+    assert edges == [manages, managed_by, married]
+
+
+def test_chunk_nodes_by_similarity_groups_similar_nodes():
+    nodes = _nodes(*[f'Node {i}' for i in range(20)])
+    embeddings = {node.uuid: _one_hot(i) for i, node in enumerate(nodes)}
+    # The first and last nodes are near-duplicates but would land in different size chunks
+    embeddings[nodes[-1].uuid] = [0.9 if i == 0 else 0.1 if i == 1 else 0.0 for i in range(20)]
+
+    chunks = chunk_nodes_by_similarity(nodes, embeddings, chunk_size=10)
+
+    assert len(chunks) == 2
+    assert sorted(len(chunk) for chunk in chunks) == [10, 10]
+    assert any(nodes[0] in chunk and nodes[-1] in chunk for chunk in chunks)
+
+
+@pytest.mark.asyncio
+async def test_compress_nodes_chunks_by_embedded_summaries():
+    nodes = _nodes(*[f'Node {i}' for i in range(20)])
+    nodes[0].summary = 'the first node'
+    nodes[-1].summary = 'the first node'
+    embedder = MagicMock(spec=EmbedderClient)
+    embedder.create_batch = AsyncMock(
+        side_effect=lambda texts: [
+            _one_hot(0) if text.endswith('the first node') else _one_hot(i)
+            for i, text in enumerate(texts)
+        ]
+    )
+    chunks: list[list[EntityNode]] = []
+
+    async def dedupe_node_list(llm_client, chunk):
+        chunks.append(chunk)
+        return chunk, {}
+
+    with patch('graphiti_core.utils.bulk_utils.dedupe_node_list', side_effect=dedupe_node_list):
+        await compress_nodes(MagicMock(), nodes, {}, embedder)
+
+    embedder.create_batch.assert_awaited_once()
+    assert embedder.create_batch.await_args.args[0][0] == 'Node 0: the first node'
+    assert any(nodes[0] in chunk and nodes[-1] in chunk for chunk in chunks)
+
+
+@pytest.mark.asyncio
+async def test_compress_nodes_falls_back_to_size_chunks_without_embeddings():
+    nodes = _nodes(*[f'Node {i}' for i in range(20)])
+    chunks: list[list[EntityNode]] = []
+
+    async def dedupe_node_list(llm_client, chunk):
+        chunks.append(chunk)
+        return chunk, {}
+
+    with patch('graphiti_core.utils.bulk_utils.dedupe_node_list', side_effect=dedupe_node_list):
+        await compress_nodes(MagicMock(), nodes, {})
+
+    assert chunks == [nodes[:10], nodes[10:]]
+
+
+@pytest.mark.asyncio
+async def test_group_duplicate_candidates_links_similar_nodes():
+    nodes = _nodes('Robert', 'Bob', 'Rob', 'Acme', 'Carol')
+    for i, node in enumerate(nodes[:4]):
+        node.name_embedding = _one_hot(i)
+    robert, bob, rob, acme, carol = nodes
+    # Each vector query returns the node itself and its most similar nodes
+    similar = {robert.uuid: [robert, bob], bob.uuid: [bob, rob], rob.uuid: [rob], acme.uuid: [acme]}
+
+    async def node_similarity_search(driver, search_vector, *args, **kwargs):
+        node = next(node for node in nodes if node.name_embedding == search_vector)
+        return similar[node.uuid]
+
+    with patch(
+        'graphiti_core.utils.bulk_utils.node_similarity_search',
+        side_effect=node_similarity_search,
+    ) as search:
+        groups = await group_duplicate_candidates(MagicMock(spec=GraphDriver), nodes)
+
+    assert groups == [[robert, bob, rob]]
+    # Carol has no stored embedding, so she is not queried
+    assert search.await_count == 4
+
+
+class SlowEmbedderClient(MockEmbedderClient):
+    async def create(self, input_data):
+        await asyncio.sleep(0.001)
+        return await super().create(input_data)
+
+
+@pytest.mark.asyncio
+async def test_pipelined_extraction_embeds_every_episode():
+    embedder = SlowEmbedderClient()
+    clients = GraphitiClients(
+        driver=MagicMock(spec=GraphDriver),
+        llm_client=MockLLMClient(),
+        embedder=embedder,
+        cross_encoder=MockCrossEncoderClient(),
+    )
+    episodes = [
+        EpisodicNode(
+            name=f'Episode {i}',
+            group_id='group_1',
+            source=EpisodeType.text,
+            source_description='test',
+            content=f'Person {2 * i} knows Person {2 * i + 1}',
+            valid_at=datetime.now(timezone.utc),
+        )
+        for i in range(50)
+    ]
+    # Episodes that are extracted but not embedded yet
+    pending: set[str] = set()
+    max_pending = 0
+
+    async def extract_nodes(clients, episode, *args, **kwargs):
+        nonlocal max_pending
+        await asyncio.sleep(0)
+        nodes = [
+            EntityNode(name=name, group_id=episode.group_id, labels=['Entity'])
+            for name in episode.content.split(' knows ')
+        ]
+        pending.add(nodes[0].uuid)
+        max_pending = max(max_pending, len(pending))
+        return nodes
+
+    async def extract_edges(clients, episode, nodes, *args, **kwargs):
+        return [
+            EntityEdge(
+                source_node_uuid=nodes[0].uuid,
+                target_node_uuid=nodes[1].uuid,
+                name='KNOWS',
+                fact=episode.content,
+                group_id=episode.group_id,
+                created_at=episode.created_at,
+            )
+        ]
+
+    async def generate_name_embedding(self, embedder):
+        self.name_embedding = await embedder.create(input_data=[self.name])
+        pending.discard(self.uuid)
+
+    with (
+        patch('graphiti_core.utils.bulk_utils.extract_nodes', side_effect=extract_nodes),
+        patch('graphiti_core.utils.bulk_utils.extract_edges', side_effect=extract_edges),
+        patch.object(EntityNode, 'generate_name_embedding', generate_name_embedding),
+    ):
+        nodes, edges, episodic_edges, failures = await extract_nodes_and_edges_bulk(
+            clients,
+            [(episode, []) for episode in episodes],
+            max_coroutines=5,
+            embedder=embedder,
+            embedding_channel_depth=2,
+        )
+
+    assert failures == {}
+    assert (len(nodes), len(edges), len(episodic_edges)) == (100, 50, 100)
+    assert all(node.name_embedding is not None for node in nodes)
+    assert all(edge.fact_embedding is not None for edge in edges)
+    assert len(embedder.inputs) == 150
+    # Queued episodes, extractions waiting to queue theirs and the one being embedded
+    assert max_pending <= 2 + 5 + 1
+
+
+def _counting_driver() -> tuple[MagicMock, MagicMock]:
+    # Bulk writes run in a transaction whose queries are counted
+    tx = MagicMock(run=AsyncMock())
+
+    async def execute_write(fn, *args, **kwargs):
+        return await fn(tx, *args, **kwargs)
+
+    driver = MagicMock(spec=GraphDriver)
+    driver.provider = 'neo4j'
+    driver.execute_query = AsyncMock(return_value=([], None, None))
+    driver.session.return_value = MagicMock(
+        execute_write=AsyncMock(side_effect=execute_write), close=AsyncMock()
+    )
+    return driver, tx
+
+
+@pytest.mark.asyncio
+async def test_batch_writer_writes_saves_in_bounded_queries():
+    driver, tx = _counting_driver()
+    nodes = _nodes(*[f'Person {i}' for i in range(150)])
+    edges = [_edge(nodes[i], 'KNOWS', nodes[i + 1]) for i in range(100)]
+
+    async with BatchWriter(driver, MockEmbedderClient(), batch_size=100) as writer:
+        await writer.add(*nodes)
+        await writer.add(*edges)
+
+    # 250 saves take three transactions of at most four queries each, and no single saves
+    assert driver.session.return_value.execute_write.await_count == 3
+    assert tx.run.await_count <= 12
+    driver.execute_query.assert_not_called()
+    saved_nodes = [
+        node['uuid'] for call in tx.run.await_args_list for node in call.kwargs.get('nodes', [])
+    ]
+    assert saved_nodes == [node.uuid for node in nodes]
+
+
+@pytest.mark.asyncio
+async def test_batch_writer_does_not_write_until_flushed():
+    driver, _ = _counting_driver()
+    writer = BatchWriter(driver, batch_size=100)
+
+    await writer.add(*_nodes('Alice', 'Bob'))
+    driver.session.assert_not_called()
+
+    await writer.flush()
+    await writer.flush()
+    assert driver.session.return_value.execute_write.await_count == 1
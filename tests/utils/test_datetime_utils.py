@@ -0,0 +1,41 @@
+from datetime import datetime, timedelta, timezone
+
+from graphiti_core.nodes import EntityNode, EpisodeType, EpisodicNode
+from graphiti_core.utils.datetime_utils import FixedClock, use_clock, utc_now
+from graphiti_core.utils.maintenance.edge_operations import build_episodic_edges
+
+NOW = datetime(2024, 6, 1, 12, 0, tzinfo=timezone.utc)
+
+
+def test_fixed_clock_sets_created_at():
+    with use_clock(FixedClock(NOW)) as clock:
+        node = EntityNode(name='Alice', group_id='group_1', labels=['Entity'])
+        assert utc_now() == NOW
+
+        clock.advance(timedelta(hours=1))
+        later = EntityNode(name='Bob', group_id='group_1', labels=['Entity'])
+        episode = EpisodicNode(
+            name='episode',
+            group_id='group_1',
+            source=EpisodeType.text,
+            source_description='test',
+            content='Alice met Bob',
+            valid_at=utc_now(),
+        )
+        edges = build_episodic_edges([node, later], episode, utc_now())
+
+    assert node.created_at == NOW
+    assert later.created_at == NOW + timedelta(hours=1)
+    assert episode.created_at == NOW + timedelta(hours=1)
+    assert [edge.created_at for edge in edges] == [NOW + timedelta(hours=1)] * 2
+
+
+def test_clock_is_restored_after_the_block():
+    with use_clock(FixedClock(NOW)):
+        pass
+
+    assert utc_now() > NOW + timedelta(days=365)
+
+
+def test_naive_fixed_time_is_utc():
+    assert FixedClock(datetime(2024, 6, 1, 12, 0)).now() == NOW